@@ -1,15 +1,134 @@
 use std::env;
+#[cfg(not(feature = "system-odgi"))]
+use std::fs;
+#[cfg(not(feature = "system-odgi"))]
+use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
+#[cfg(not(feature = "system-odgi"))]
 use fs_extra::dir::{copy, CopyOptions};
 
+/// Header directories of the vendored odgi tree that the FFI wrapper includes.
+const VENDORED_INCLUDE_DIRS: &[&str] = &[
+    "vendor/odgi/src",
+    "vendor/odgi/deps/libhandlegraph/src/include",
+    "vendor/odgi/deps/DYNAMIC/include",
+    "vendor/odgi/deps/hopscotch-map/include",
+    "vendor/odgi/deps/sparsepp/sparsepp",
+    "vendor/odgi/deps/flat_hash_map",
+    "vendor/odgi/deps/atomicbitvector/include",
+    "vendor/odgi/deps/IITree/src",
+    "vendor/odgi/deps/BBHash",
+    "vendor/odgi/deps/popv",
+    "vendor/odgi/deps/nameof/include",
+    "vendor/odgi/lib/sdsl-lite/include",
+];
+
+/// The static libraries of the vendored build, in link order.
+#[cfg(not(feature = "system-odgi"))]
+const STATIC_LIBS: &[&str] = &["odgi", "handlegraph", "sdsl"];
+
+/// The environment variable naming a directory of prebuilt odgi artifacts.
+#[cfg(not(feature = "system-odgi"))]
+const PREBUILT_DIR_VAR: &str = "ODGI_FFI_PREBUILT_DIR";
+/// The file in the prebuilt directory recording the build the artifacts came from.
+#[cfg(not(feature = "system-odgi"))]
+const PREBUILT_STAMP: &str = "build-stamp";
+
 fn main() {
     // If the `docs-only` feature is set, do nothing and exit early.
-    // This is the key to a successful docs.rs build.
+    // This is the key to a successful docs.rs build. The `wasm` feature enables
+    // `docs-only` too, since wasm builds cannot link the C++ library either.
     if cfg!(feature = "docs-only") {
         println!("cargo:warning=Skipping C++ build for docs.rs.");
         return;
     }
 
+    let target = Target::detect();
+    let openmp = OpenMp::for_target(&target);
+
+    // === Parts 0-4: Build and link odgi, or link an existing installation ===
+    #[cfg(feature = "system-odgi")]
+    let mut include_dirs = link_system_odgi();
+    #[cfg(not(feature = "system-odgi"))]
+    let mut include_dirs = build_vendored_odgi(openmp.as_ref());
+    link_runtime_libs(&target, openmp.as_ref());
+
+
+    // === Part 5: Build our C++ FFI wrapper code ===
+    let mut bridge = cxx_build::bridge("src/lib.rs");
+    if let Some(openmp) = &openmp {
+        for flag in openmp.flags {
+            bridge.flag(flag);
+        }
+        include_dirs.extend(openmp.prefix.as_ref().map(|prefix| prefix.join("include")));
+    }
+    bridge
+        .file("src/odgi.cpp")
+        .flag_if_supported("-std=c++17")
+        .includes(&include_dirs)
+        .compile("odgi_cxx_bridge");
+
+
+    // === Part 6: Tell Cargo to rerun this script if C++ sources change ===
+    println!("cargo:rerun-if-changed=src/odgi.cpp");
+    println!("cargo:rerun-if-changed=src/odgi_wrapper.hpp");
+    println!("cargo:rerun-if-changed=vendor/odgi");
+}
+
+/// Compiles the vendored odgi tree and links its static libraries.
+///
+/// If `ODGI_FFI_PREBUILT_DIR` names a directory with the static libraries in
+/// `lib` and the `odgi` binary in `bin`, those are linked instead and nothing is
+/// compiled. If the variable is set but the directory is not populated yet, or
+/// was populated by a build for another target, crate version, or OpenMP
+/// setting, the tree is compiled and its artifacts are copied there for the
+/// next build.
+///
+/// Returns the header directories for the FFI wrapper.
+#[cfg(not(feature = "system-odgi"))]
+fn build_vendored_odgi(openmp: Option<&OpenMp>) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-env-changed={}", PREBUILT_DIR_VAR);
+    let prebuilt_dir = env::var_os(PREBUILT_DIR_VAR).map(PathBuf::from);
+    let stamp = build_stamp(openmp);
+    let (odgi_exe_path, lib_dirs) = match &prebuilt_dir {
+        Some(dir) if is_prebuilt(dir, &stamp) => {
+            println!("cargo:rerun-if-changed={}", dir.display());
+            (dir.join("bin").join("odgi"), vec![dir.join("lib")])
+        }
+        _ => {
+            let (odgi_exe_path, lib_dirs) = compile_vendored_odgi(openmp);
+            if let Some(dir) = &prebuilt_dir {
+                export_prebuilt(dir, &odgi_exe_path, &lib_dirs, &stamp);
+            }
+            (odgi_exe_path, lib_dirs)
+        }
+    };
+
+    // === Part 2: Make the compiled odgi executable path available to our Rust code ===
+    println!("cargo:rustc-env=ODGI_EXE={}", odgi_exe_path.display());
+
+
+    // === Part 3: Tell Cargo where to find the compiled libraries ===
+    for lib_dir in &lib_dirs {
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    }
+
+
+    // === Part 4: Tell Cargo which libraries to link ===
+    // The runtime libraries they need are linked by `link_runtime_libs`.
+    for lib in STATIC_LIBS {
+        println!("cargo:rustc-link-lib=static={}", lib);
+    }
+
+    VENDORED_INCLUDE_DIRS.iter().map(PathBuf::from).collect()
+}
+
+/// Runs the CMake build of the vendored tree.
+///
+/// Returns the path of the `odgi` binary and the directories holding the static libraries.
+#[cfg(not(feature = "system-odgi"))]
+fn compile_vendored_odgi(openmp: Option<&OpenMp>) -> (PathBuf, Vec<PathBuf>) {
     // === Part 0: Copy C++ source to a temporary, writable directory ===
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
     let odgi_source_dir = PathBuf::from("vendor/odgi");
@@ -23,56 +142,237 @@ fn main() {
 
 
     // === Part 1: Build odgi from the COPIED source ===
-    let dst = cmake::Config::new(&odgi_build_source_dir)
+    let mut config = cmake::Config::new(&odgi_build_source_dir);
+    config
         .define("BUILD_TESTS", "OFF")
         .define("ODGI_BUILD_DOCS", "OFF")
         // DEFINITIVE FIX: Force a portable build profile and add the linker flag.
         .profile("Generic")
-        .define("CMAKE_EXE_LINKER_FLAGS", "-lpthread")
-        .build();
+        .define("CMAKE_EXE_LINKER_FLAGS", "-lpthread");
+    match openmp {
+        // Without OpenMP, the `#pragma omp` loops in odgi compile to serial loops.
+        None => {
+            config.define("CMAKE_DISABLE_FIND_PACKAGE_OpenMP", "ON");
+        }
+        // CMake cannot find a runtime outside the compiler's own search paths by itself.
+        Some(OpenMp { prefix: Some(prefix), flags, lib, .. }) => {
+            let cxx_flags = format!("{} -I{}", flags.join(" "), prefix.join("include").display());
+            let library = prefix.join("lib").join(format!("lib{}.dylib", lib));
+            for language in ["C", "CXX"] {
+                config
+                    .define(format!("OpenMP_{}_FLAGS", language), &cxx_flags)
+                    .define(format!("OpenMP_{}_LIB_NAMES", language), lib);
+            }
+            config.define(format!("OpenMP_{}_LIBRARY", lib), &library);
+        }
+        Some(_) => {}
+    }
+    let dst = config.build();
 
-    // === Part 2: Make the compiled odgi executable path available to our Rust code ===
-    let odgi_exe_path = dst.join("bin").join("odgi");
+    let lib_dirs = vec![
+        dst.join("lib"),
+        dst.join("lib64"),
+        dst.join("build/handlegraph-prefix/lib"),
+        dst.join("build/sdsl-lite-prefix/src/sdsl-lite-build/lib"),
+    ];
+    (dst.join("bin").join("odgi"), lib_dirs)
+}
+
+/// Describes the build whose artifacts may be shared through the prebuilt directory.
+#[cfg(not(feature = "system-odgi"))]
+fn build_stamp(openmp: Option<&OpenMp>) -> String {
+    format!(
+        "version={}\ntarget={}\nopenmp={}\n",
+        env::var("CARGO_PKG_VERSION").unwrap_or_default(),
+        env::var("TARGET").unwrap_or_default(),
+        openmp.map_or("off", |openmp| openmp.lib)
+    )
+}
+
+/// Returns `true` if `dir` holds every artifact that [`export_prebuilt`] writes,
+/// for the build described by `stamp`.
+#[cfg(not(feature = "system-odgi"))]
+fn is_prebuilt(dir: &Path, stamp: &str) -> bool {
+    let complete = dir.join("bin").join("odgi").is_file()
+        && STATIC_LIBS.iter().all(|lib| dir.join("lib").join(format!("lib{}.a", lib)).is_file());
+    if !complete {
+        return false;
+    }
+    let matches = fs::read_to_string(dir.join(PREBUILT_STAMP)).is_ok_and(|found| found == stamp);
+    if !matches {
+        println!(
+            "cargo:warning=The odgi build in {} is for another target or configuration; rebuilding it.",
+            dir.display()
+        );
+    }
+    matches
+}
+
+/// Copies the `odgi` binary and the static libraries into `dir` for later
+/// builds, and records `stamp` next to them.
+#[cfg(not(feature = "system-odgi"))]
+fn export_prebuilt(dir: &Path, odgi_exe_path: &Path, lib_dirs: &[PathBuf], stamp: &str) {
+    let _ = fs::remove_file(dir.join(PREBUILT_STAMP));
+    let export = |from: &Path, to: PathBuf| {
+        fs::create_dir_all(to.parent().unwrap())
+            .and_then(|_| fs::copy(from, &to))
+            .unwrap_or_else(|e| panic!("Failed to copy {} to {}: {}", from.display(), to.display(), e));
+    };
+    export(odgi_exe_path, dir.join("bin").join("odgi"));
+    for lib in STATIC_LIBS {
+        let file_name = format!("lib{}.a", lib);
+        let library = lib_dirs
+            .iter()
+            .map(|lib_dir| lib_dir.join(&file_name))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| panic!("The odgi build did not produce {}", file_name));
+        export(&library, dir.join("lib").join(&file_name));
+    }
+    // The stamp goes last, so that an interrupted copy is never mistaken for a complete one.
+    fs::write(dir.join(PREBUILT_STAMP), stamp)
+        .unwrap_or_else(|e| panic!("Failed to write {}: {}", dir.join(PREBUILT_STAMP).display(), e));
+    println!("cargo:warning=Saved the odgi build to {} for later builds.", dir.display());
+}
+
+/// Links dynamically against an installed libodgi instead of compiling the vendored tree.
+///
+/// The library is found through `ODGI_LIB_DIR` if it is set, and through
+/// pkg-config otherwise. Extra header directories can be listed in
+/// `ODGI_INCLUDE_DIR`; the vendored headers are searched after them, since odgi
+/// does not install the headers of its dependencies. The `odgi` binary is taken
+/// from `ODGI_EXE`, the installation's `bin` directory, or the `PATH`.
+///
+/// Returns the header directories for the FFI wrapper.
+#[cfg(feature = "system-odgi")]
+fn link_system_odgi() -> Vec<PathBuf> {
+    println!("cargo:rerun-if-env-changed=ODGI_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=ODGI_INCLUDE_DIR");
+    println!("cargo:rerun-if-env-changed=ODGI_EXE");
+    println!("cargo:rerun-if-env-changed=PATH");
+
+    let mut include_dirs: Vec<PathBuf> = env::var_os("ODGI_INCLUDE_DIR")
+        .map(|dirs| env::split_paths(&dirs).collect())
+        .unwrap_or_default();
+
+    // === Parts 3 and 4: Find and link the installed library ===
+    let bin_dir = match env::var_os("ODGI_LIB_DIR").map(PathBuf::from) {
+        Some(lib_dir) => {
+            println!("cargo:rustc-link-search=native={}", lib_dir.display());
+            println!("cargo:rustc-link-lib=dylib=odgi");
+            lib_dir.parent().map(|prefix| prefix.join("bin"))
+        }
+        None => {
+            let library = pkg_config::Config::new().probe("odgi").unwrap_or_else(|e| {
+                panic!("The system-odgi feature could not find odgi through pkg-config; set ODGI_LIB_DIR instead: {}", e)
+            });
+            include_dirs.extend(library.include_paths);
+            pkg_config::get_variable("odgi", "prefix").ok().map(|prefix| PathBuf::from(prefix).join("bin"))
+        }
+    };
+    include_dirs.extend(VENDORED_INCLUDE_DIRS.iter().map(PathBuf::from));
+
+    // === Part 2: Point the conversion functions at the installed odgi binary ===
+    let odgi_exe_path = env::var_os("ODGI_EXE")
+        .map(PathBuf::from)
+        .or_else(|| bin_dir.map(|dir| dir.join("odgi")).filter(|exe| exe.is_file()))
+        .or_else(|| {
+            env::split_paths(&env::var_os("PATH").unwrap_or_default())
+                .map(|dir| dir.join("odgi"))
+                .find(|exe| exe.is_file())
+        })
+        // Leave it to be resolved through the `PATH` at run time.
+        .unwrap_or_else(|| PathBuf::from("odgi"));
     println!("cargo:rustc-env=ODGI_EXE={}", odgi_exe_path.display());
 
+    include_dirs
+}
 
-    // === Part 3: Tell Cargo where to find the compiled libraries ===
-    println!("cargo:rustc-link-search=native={}/lib", dst.display());
-    println!("cargo:rustc-link-search=native={}/lib64", dst.display());
-    println!("cargo:rustc-link-search=native={}/build/handlegraph-prefix/lib", dst.display());
-    println!("cargo:rustc-link-search=native={}/build/sdsl-lite-prefix/src/sdsl-lite-build/lib", dst.display());
+/// The platform the crate is built for, which can differ from the build host.
+struct Target {
+    os: String,
+    env: String,
+}
 
+impl Target {
+    /// Reads the target from the variables Cargo sets for build scripts. The
+    /// `cfg!` macros in a build script describe the host instead.
+    fn detect() -> Self {
+        Target {
+            os: env::var("CARGO_CFG_TARGET_OS").unwrap_or_default(),
+            env: env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default(),
+        }
+    }
 
-    // === Part 4: Tell Cargo which libraries to link ===
-    println!("cargo:rustc-link-lib=static=odgi");
-    println!("cargo:rustc-link-lib=static=handlegraph");
-    println!("cargo:rustc-link-lib=static=sdsl");
-    println!("cargo:rustc-link-lib=dylib=gomp");
-    println!("cargo:rustc-link-lib=dylib=atomic");
+    fn is_macos(&self) -> bool {
+        self.os == "macos"
+    }
 
+    fn is_musl(&self) -> bool {
+        self.env == "musl"
+    }
+}
 
-    // === Part 5: Build our C++ FFI wrapper code ===
-    cxx_build::bridge("src/lib.rs")
-        .file("src/odgi.cpp")
-        .flag("-fopenmp")
-        .flag_if_supported("-std=c++17")
-        .include("vendor/odgi/src")
-        .include("vendor/odgi/deps/libhandlegraph/src/include")
-        .include("vendor/odgi/deps/DYNAMIC/include")
-        .include("vendor/odgi/deps/hopscotch-map/include")
-        .include("vendor/odgi/deps/sparsepp/sparsepp")
-        .include("vendor/odgi/deps/flat_hash_map")
-        .include("vendor/odgi/deps/atomicbitvector/include")
-        .include("vendor/odgi/deps/IITree/src")
-        .include("vendor/odgi/deps/BBHash")
-        .include("vendor/odgi/deps/popv")
-        .include("vendor/odgi/deps/nameof/include")
-        .include("vendor/odgi/lib/sdsl-lite/include")
-        .compile("odgi_cxx_bridge");
+/// How OpenMP is enabled and linked on the target.
+struct OpenMp {
+    /// The compiler flags that enable OpenMP.
+    flags: &'static [&'static str],
+    /// The name of the runtime library.
+    lib: &'static str,
+    /// How the runtime library is linked.
+    kind: &'static str,
+    /// The installation prefix of the runtime, if the compiler does not find it by itself.
+    prefix: Option<PathBuf>,
+}
 
+impl OpenMp {
+    /// Returns `None` if the `no-openmp` feature is enabled.
+    fn for_target(target: &Target) -> Option<Self> {
+        if cfg!(feature = "no-openmp") {
+            return None;
+        }
+        Some(if target.is_macos() {
+            // Apple Clang has no OpenMP runtime of its own; use Homebrew's libomp.
+            OpenMp { flags: &["-Xpreprocessor", "-fopenmp"], lib: "omp", kind: "dylib", prefix: Some(libomp_prefix()) }
+        } else if target.is_musl() {
+            // musl targets link statically by default.
+            OpenMp { flags: &["-fopenmp"], lib: "gomp", kind: "static", prefix: None }
+        } else {
+            OpenMp { flags: &["-fopenmp"], lib: "gomp", kind: "dylib", prefix: None }
+        })
+    }
+}
 
-    // === Part 6: Tell Cargo to rerun this script if C++ sources change ===
-    println!("cargo:rerun-if-changed=src/odgi.cpp");
-    println!("cargo:rerun-if-changed=src/odgi_wrapper.hpp");
-    println!("cargo:rerun-if-changed=vendor/odgi");
-}
\ No newline at end of file
+/// Finds the keg-only Homebrew libomp through `LIBOMP_PREFIX`, `brew --prefix
+/// libomp`, or Homebrew's default prefix on the build host.
+fn libomp_prefix() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=LIBOMP_PREFIX");
+    if let Some(prefix) = env::var_os("LIBOMP_PREFIX") {
+        return PathBuf::from(prefix);
+    }
+    let brew_prefix = Command::new("brew")
+        .args(["--prefix", "libomp"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()));
+    if let Some(prefix) = brew_prefix {
+        return prefix;
+    }
+    let default = if cfg!(target_arch = "aarch64") { "/opt/homebrew/opt/libomp" } else { "/usr/local/opt/libomp" };
+    PathBuf::from(default)
+}
+
+/// Links the OpenMP runtime, if any, and `libatomic`, which the C++ code needs
+/// for 128-bit atomics on Linux. macOS provides atomics in its C++ runtime.
+fn link_runtime_libs(target: &Target, openmp: Option<&OpenMp>) {
+    if let Some(openmp) = openmp {
+        if let Some(prefix) = &openmp.prefix {
+            println!("cargo:rustc-link-search=native={}", prefix.join("lib").display());
+        }
+        println!("cargo:rustc-link-lib={}={}", openmp.kind, openmp.lib);
+    }
+    if !target.is_macos() {
+        let kind = if target.is_musl() { "static" } else { "dylib" };
+        println!("cargo:rustc-link-lib={}=atomic", kind);
+    }
+}
@@ -0,0 +1,316 @@
+// src/align.rs
+
+//! Aligning sequences directly to the graph.
+//!
+//! [`poa_region`] extracts the local graph around a reference interval and
+//! aligns a query to it with partial-order alignment, i.e. dynamic
+//! programming over the graph's character DAG instead of a linear reference.
+//! Every base of the query is aligned, while the alignment may start and end
+//! anywhere in the region, which suits reads or contigs that span a locus
+//! being genotyped.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use super::graph::{Error, Graph};
+use super::path_alignment::{cigar, push_op};
+use super::region::GraphRegion;
+use super::seq::{bases_match, reverse_complement};
+
+/// The maximum number of oriented nodes in an extracted region.
+const MAX_REGION_NODES: usize = 100_000;
+/// The maximum number of cells in the dynamic programming matrix, i.e. the
+/// region's bases times the query's length plus one.
+const MAX_DP_CELLS: usize = 1 << 26;
+
+/// The alignment of a query to a walk through a graph region, returned by [`poa_region`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoaAlignment {
+    /// The oriented nodes the query aligns to, as `(node_id, is_forward)`.
+    pub walk: Vec<(u64, bool)>,
+    /// The offset of the first aligned base on the first node, in its orientation in the walk.
+    pub walk_start: u64,
+    /// The offset after the last aligned base on the last node, in its orientation in the walk.
+    pub walk_end: u64,
+    /// The alignment as run-length encoded operations: `=` (match), `X`
+    /// (mismatch), `I` (query base not in the walk), and `D` (walk base not in
+    /// the query).
+    pub operations: Vec<(char, u64)>,
+    /// The number of mismatched, inserted, and deleted bases.
+    pub edit_distance: u64,
+}
+
+impl PoaAlignment {
+    /// Returns the operations as a CIGAR string.
+    pub fn cigar(&self) -> String {
+        cigar(&self.operations)
+    }
+
+    /// Returns the fraction of alignment columns that are matches.
+    pub fn identity(&self) -> f64 {
+        let columns: u64 = self.operations.iter().map(|&(_, len)| len).sum();
+        let matches: u64 = self.operations.iter().filter(|&&(op, _)| op == '=').map(|&(_, len)| len).sum();
+        if columns == 0 { 0.0 } else { matches as f64 / columns as f64 }
+    }
+}
+
+/// Aligns `query` to the local graph around `region`.
+///
+/// The region's graph consists of the nodes of the region's path overlapping
+/// `region` plus every node on a walk between them, such as the alternative
+/// alleles of bubbles, as long as it lies within the length of the region and
+/// the query of the region's ends. Cycles are broken by ignoring edges that
+/// lead back against the order in which the region is explored. The query is
+/// then aligned end to end against the best-scoring walk through this graph,
+/// minimizing the edit distance; IUPAC ambiguity codes match every base they
+/// stand for.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the path does not exist, `region` is empty, or the
+/// region's graph or the alignment matrix for the query is too large. The end
+/// of the region is clamped to the end of the path.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{align, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let alignment = align::poa_region(&graph, &"grch38#chr6:31,000,001-31,002,000".parse().unwrap(), "ACGT...").unwrap();
+/// println!("{:?} {} ({:.1}% identity)", alignment.walk, alignment.cigar(), alignment.identity() * 100.0);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph, query), err))]
+pub fn poa_region(graph: &Graph, region: &GraphRegion, query: &str) -> Result<PoaAlignment, Error> {
+    let (index, start, end) = region.index(graph)?;
+    let first_step = index.step_at(start).expect("the region lies within the path");
+    let last_step = index.step_at(end - 1).expect("the region lies within the path");
+    let path_handles: Vec<(u64, bool)> = index.steps[first_step..=last_step]
+        .iter()
+        .map(|s| (s.node_id, s.is_forward))
+        .collect();
+
+    let handles = region_handles(graph, &path_handles, end - start + query.len() as u64)?;
+    let sequences = handles
+        .iter()
+        .map(|&(node_id, is_forward)| {
+            let forward = graph.get_node_sequence(node_id)?;
+            Ok(if is_forward { forward.into_bytes() } else { reverse_complement(&forward).into_bytes() })
+        })
+        .collect::<Result<Vec<Vec<u8>>, Error>>()?;
+
+    // Flatten the handles into a DAG of characters, in topological order.
+    let position: HashMap<(u64, bool), usize> = handles.iter().enumerate().map(|(i, &h)| (h, i)).collect();
+    let mut first_char = Vec::with_capacity(handles.len());
+    let mut chars: Vec<(u8, usize, u64)> = Vec::new(); // (base, handle index, offset)
+    for (i, sequence) in sequences.iter().enumerate() {
+        first_char.push(chars.len());
+        chars.extend(sequence.iter().enumerate().map(|(offset, &base)| (base, i, offset as u64)));
+    }
+    let cells = chars.len().checked_mul(query.len() + 1).filter(|&cells| cells <= MAX_DP_CELLS);
+    if cells.is_none() {
+        return Err(Error::invalid_argument(format!(
+            "Aligning {} bp to a region graph of {} bp exceeds {} alignment cells",
+            query.len(),
+            chars.len(),
+            MAX_DP_CELLS
+        )));
+    }
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); chars.len()];
+    for (c, &(_, _, offset)) in chars.iter().enumerate() {
+        if offset > 0 {
+            predecessors[c].push(c - 1);
+        }
+    }
+    for (i, &(node_id, is_forward)) in handles.iter().enumerate() {
+        if sequences[i].is_empty() {
+            continue;
+        }
+        let last = first_char[i] + sequences[i].len() - 1;
+        for edge in graph.get_successors(node_id)?.iter().filter(|e| e.from_orientation == is_forward) {
+            // Edges against the topological order close cycles and are ignored.
+            if let Some(&j) = position.get(&(edge.to_node, edge.to_orientation)).filter(|&&j| j > i)
+                && !sequences[j].is_empty()
+            {
+                predecessors[first_char[j]].push(last);
+            }
+        }
+    }
+
+    let (operations, consumed, edit_distance) = align_to_dag(query.as_bytes(), &chars, &predecessors);
+
+    // Recover the walk from the graph characters the alignment consumed.
+    let mut walk: Vec<(u64, bool)> = Vec::new();
+    let (mut walk_start, mut walk_end) = (0, 0);
+    for (k, &c) in consumed.iter().enumerate() {
+        let (_, handle, offset) = chars[c];
+        if k == 0 {
+            walk_start = offset;
+        }
+        if walk.last() != Some(&handles[handle]) {
+            walk.push(handles[handle]);
+        }
+        walk_end = offset + 1;
+    }
+    Ok(PoaAlignment { walk, walk_start, walk_end, operations, edit_distance })
+}
+
+/// Returns the oriented nodes between the first and last of `path_handles`,
+/// within `max_distance` bp of both, in topological order.
+fn region_handles(graph: &Graph, path_handles: &[(u64, bool)], max_distance: u64) -> Result<Vec<(u64, bool)>, Error> {
+    let first = path_handles[0];
+    let last = path_handles[path_handles.len() - 1];
+    let forward = reachable(graph, first, max_distance)?;
+    let backward: HashSet<(u64, bool)> = reachable(graph, (last.0, !last.1), max_distance)?
+        .into_iter()
+        .map(|(id, fwd)| (id, !fwd))
+        .collect();
+    let mut included: HashSet<(u64, bool)> = forward.intersection(&backward).copied().collect();
+    included.extend(path_handles.iter().copied());
+
+    // Depth-first reverse postorder from the first handle orders the region topologically.
+    let mut order = Vec::with_capacity(included.len());
+    let mut visited = HashSet::new();
+    let mut stack = vec![(first, false)];
+    while let Some((handle, expanded)) = stack.pop() {
+        if expanded {
+            order.push(handle);
+            continue;
+        }
+        if !visited.insert(handle) {
+            continue;
+        }
+        stack.push((handle, true));
+        for edge in graph.get_successors(handle.0)?.iter().filter(|e| e.from_orientation == handle.1) {
+            let next = (edge.to_node, edge.to_orientation);
+            if included.contains(&next) && !visited.contains(&next) {
+                stack.push((next, false));
+            }
+        }
+    }
+    order.reverse();
+    // Path steps not reachable from the first one (e.g. after a missing edge) follow in path order.
+    for &handle in path_handles {
+        if visited.insert(handle) {
+            order.push(handle);
+        }
+    }
+    Ok(order)
+}
+
+/// Returns the handles reachable from `start` within `max_distance` bp, including `start`.
+fn reachable(graph: &Graph, start: (u64, bool), max_distance: u64) -> Result<HashSet<(u64, bool)>, Error> {
+    let mut settled = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((0u64, start)));
+    while let Some(Reverse((distance, handle))) = queue.pop() {
+        if !settled.insert(handle) {
+            continue;
+        }
+        if settled.len() > MAX_REGION_NODES {
+            return Err(Error::invalid_argument(format!("Region graph exceeds {} nodes", MAX_REGION_NODES)));
+        }
+        let next_distance = distance + graph.get_node_len(handle.0)?;
+        if next_distance > max_distance {
+            continue;
+        }
+        for edge in graph.get_successors(handle.0)?.iter().filter(|e| e.from_orientation == handle.1) {
+            let next = (edge.to_node, edge.to_orientation);
+            if !settled.contains(&next) {
+                queue.push(Reverse((next_distance, next)));
+            }
+        }
+    }
+    Ok(settled)
+}
+
+/// Aligns `query` end to end against any walk through the character DAG.
+///
+/// Returns the operations, the consumed graph characters in order, and the edit distance.
+fn align_to_dag(query: &[u8], chars: &[(u8, usize, u64)], predecessors: &[Vec<usize>]) -> (Vec<(char, u64)>, Vec<usize>, u64) {
+    let m = query.len();
+    let width = m + 1;
+    // cost[c * width + j]: aligning query[..j] with the walk ending at graph character c.
+    let mut cost = vec![u32::MAX; chars.len() * width];
+    // The start state, before any graph character, only allows insertions.
+    let start_cost = |j: usize| j as u32;
+
+    for c in 0..chars.len() {
+        let base = chars[c].0;
+        // The walk may start at any character.
+        let sources: Vec<Option<usize>> = predecessors[c].iter().map(|&p| Some(p)).chain([None]).collect();
+        for j in 0..=m {
+            let mut best = u32::MAX;
+            for &source in &sources {
+                let before = |j: usize| match source {
+                    Some(p) => cost[p * width + j],
+                    None => start_cost(j),
+                };
+                best = best.min(before(j).saturating_add(1));
+                if j > 0 {
+                    best = best.min(before(j - 1).saturating_add(u32::from(!bases_match(base, query[j - 1]))));
+                }
+            }
+            if j > 0 {
+                best = best.min(cost[c * width + j - 1].saturating_add(1));
+            }
+            cost[c * width + j] = best;
+        }
+    }
+
+    // The walk may end at any character, or consume none at all.
+    let mut end: Option<usize> = None;
+    let mut best = start_cost(m);
+    for c in 0..chars.len() {
+        if cost[c * width + m] < best {
+            best = cost[c * width + m];
+            end = Some(c);
+        }
+    }
+
+    // Trace back from the end to recover the operations.
+    let mut ops_reversed: Vec<(char, u64)> = Vec::new();
+    let mut consumed = Vec::new();
+    let (mut state, mut j) = (end, m);
+    while let Some(c) = state {
+        let here = cost[c * width + j];
+        let base = chars[c].0;
+        let sources: Vec<Option<usize>> = predecessors[c].iter().map(|&p| Some(p)).chain([None]).collect();
+        let value = |source: Option<usize>, j: usize| match source {
+            Some(p) => cost[p * width + j],
+            None => start_cost(j),
+        };
+        // Prefer aligning the two bases, then an insertion, then a deletion.
+        let diagonal = (j > 0).then(|| {
+            let is_match = bases_match(base, query[j - 1]);
+            let op = if is_match { '=' } else { 'X' };
+            sources
+                .iter()
+                .find(|&&source| value(source, j - 1).saturating_add(u32::from(!is_match)) == here)
+                .map(|&source| (source, op))
+        });
+        if let Some(Some((source, op))) = diagonal {
+            push_op(&mut ops_reversed, op, 1);
+            consumed.push(c);
+            state = source;
+            j -= 1;
+        } else if j > 0 && cost[c * width + j - 1].saturating_add(1) == here {
+            push_op(&mut ops_reversed, 'I', 1);
+            j -= 1;
+        } else {
+            let source = *sources
+                .iter()
+                .find(|&&source| value(source, j).saturating_add(1) == here)
+                .expect("every cell has a predecessor");
+            push_op(&mut ops_reversed, 'D', 1);
+            consumed.push(c);
+            state = source;
+        }
+    }
+    if j > 0 {
+        push_op(&mut ops_reversed, 'I', j as u64);
+    }
+
+    ops_reversed.reverse();
+    consumed.reverse();
+    (ops_reversed, consumed, best as u64)
+}
@@ -0,0 +1,69 @@
+// src/alignment.rs
+
+//! Sequence-to-graph alignments, independent of the file format they were read from.
+
+/// An alignment of a query sequence to a walk through the graph.
+///
+/// This is the common representation of the records read by
+/// [`gaf::records`](crate::gaf::records) and [`gam::records`](crate::gam::records),
+/// so that they can be surjected, injected as paths, or otherwise processed regardless of the input format.
+/// Coordinates are 0-based and half-open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphAlignment {
+    /// The name of the query sequence.
+    pub query_name: String,
+    /// The length of the query sequence.
+    pub query_length: u64,
+    /// The start of the aligned part of the query.
+    pub query_start: u64,
+    /// The end of the aligned part of the query.
+    pub query_end: u64,
+    /// Whether the reverse complement of the query is aligned to the walk.
+    pub is_reverse: bool,
+    /// The oriented nodes of the walk, as `(node_id, is_forward)`.
+    pub walk: Vec<(u64, bool)>,
+    /// The total sequence length of the walk.
+    pub walk_length: u64,
+    /// The start of the alignment on the walk.
+    pub walk_start: u64,
+    /// The end of the alignment on the walk.
+    pub walk_end: u64,
+    /// The mapping quality, or `255` if unavailable.
+    pub mapping_quality: u8,
+    /// The alignment as run-length encoded CIGAR operations (`M`, `=`, `X`,
+    /// `I`, `D`), or `None` if the input did not specify one.
+    pub cigar: Option<Vec<(char, u64)>>,
+}
+
+impl GraphAlignment {
+    /// Returns the CIGAR operations, assuming a gapless alignment if none were given.
+    pub(crate) fn operations(&self) -> Vec<(char, u64)> {
+        match &self.cigar {
+            Some(ops) => ops.clone(),
+            None => vec![('M', self.walk_end - self.walk_start)],
+        }
+    }
+}
+
+/// Parses a CIGAR string into run-length encoded operations.
+pub(crate) fn parse_cigar(cigar: &str) -> Option<Vec<(char, u64)>> {
+    let mut ops = Vec::new();
+    let mut len = 0u64;
+    let mut has_digits = false;
+    for c in cigar.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            len = len.checked_mul(10)?.checked_add(digit as u64)?;
+            has_digits = true;
+        } else if matches!(c, 'M' | '=' | 'X' | 'I' | 'D') && has_digits {
+            ops.push((c, len));
+            len = 0;
+            has_digits = false;
+        } else {
+            return None;
+        }
+    }
+    if has_digits {
+        return None;
+    }
+    Some(ops)
+}
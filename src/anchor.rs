@@ -0,0 +1,117 @@
+// src/anchor.rs
+
+//! Anchoring off-reference nodes to the nearest reference coordinate.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// The reference coordinate closest to a node, found by
+/// [`Graph::nearest_reference_position`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceAnchor {
+    /// The reference path that was reached.
+    pub path_name: String,
+    /// The 0-based offset on the reference path where the graph walk joins it.
+    ///
+    /// For a node on the reference this is the start of its first step.
+    /// Otherwise it is the boundary of the reached reference node that faces
+    /// the query node, so insertions anchor between two reference bases.
+    pub position: u64,
+    /// The number of base pairs on the shortest walk between the query node and
+    /// the reference, excluding both end nodes. `0` for adjacent or reference nodes.
+    pub distance: u64,
+}
+
+impl Graph {
+    /// Finds the reference position closest to `node_id` in the graph.
+    ///
+    /// Starting from both sides of the node, the graph is searched outward in
+    /// order of increasing base-pair distance until a node covered by one of
+    /// `reference_paths` is reached. Ties are broken in favour of the reference
+    /// listed first. If a reached node occurs several times on a reference, its
+    /// first occurrence is used.
+    ///
+    /// Returns `None` if no reference node lies within `max_bp` base pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the node or one of the reference paths does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// if let Some(anchor) = graph.nearest_reference_position(42, &["grch38#chr1"], 100_000).unwrap() {
+    ///     println!("Node 42 is {} bp from {}:{}", anchor.distance, anchor.path_name, anchor.position);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn nearest_reference_position(
+        &self,
+        node_id: u64,
+        reference_paths: &[&str],
+        max_bp: u64,
+    ) -> Result<Option<ReferenceAnchor>, Error> {
+        self.check_node(node_id)?;
+
+        // The first step of every reference node: (reference, path offset, length, is_forward).
+        let mut reference_steps: HashMap<u64, (usize, u64, u64, bool)> = HashMap::new();
+        for (reference, &path_name) in reference_paths.iter().enumerate() {
+            let index = PathIndex::new(self, path_name)?
+                .ok_or_else(|| Error::path_not_found(path_name))?;
+            for (i, step) in index.steps.iter().enumerate() {
+                reference_steps
+                    .entry(step.node_id)
+                    .or_insert((reference, index.starts[i], index.lengths[i], step.is_forward));
+            }
+        }
+        let anchor = |reference: usize, position: u64, distance: u64| ReferenceAnchor {
+            path_name: reference_paths[reference].to_string(),
+            position,
+            distance,
+        };
+
+        if let Some(&(reference, start, _, _)) = reference_steps.get(&node_id) {
+            return Ok(Some(anchor(reference, start, 0)));
+        }
+
+        // Dijkstra over oriented nodes; the distance of (n, o) is the number of
+        // base pairs between the query node and the start of n in orientation o.
+        let mut queue = BinaryHeap::new();
+        let mut settled = HashSet::new();
+        for is_forward in [true, false] {
+            for edge in self.get_successors(node_id)?.iter().filter(|e| e.from_orientation == is_forward) {
+                queue.push(Reverse((0, edge.to_node, edge.to_orientation)));
+            }
+        }
+        let mut best: Option<(u64, usize, u64)> = None;
+        while let Some(Reverse((distance, current, is_forward))) = queue.pop() {
+            if distance > max_bp || best.is_some_and(|(d, _, _)| distance > d) {
+                break;
+            }
+            if current == node_id || !settled.insert((current, is_forward)) {
+                continue;
+            }
+            if let Some(&(reference, start, len, step_forward)) = reference_steps.get(&current) {
+                // Entering in the path's orientation reaches the start of the step, otherwise its end.
+                let position = if is_forward == step_forward { start } else { start + len };
+                let candidate = (distance, reference, position);
+                if best.is_none_or(|b| candidate < b) {
+                    best = Some(candidate);
+                }
+                continue;
+            }
+            let next_distance = distance + self.get_node_len(current)?;
+            for edge in self.get_successors(current)?.iter().filter(|e| e.from_orientation == is_forward) {
+                if !settled.contains(&(edge.to_node, edge.to_orientation)) {
+                    queue.push(Reverse((next_distance, edge.to_node, edge.to_orientation)));
+                }
+            }
+        }
+
+        Ok(best.map(|(distance, reference, position)| anchor(reference, position, distance)))
+    }
+}
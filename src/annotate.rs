@@ -0,0 +1,503 @@
+// src/annotate.rs
+
+//! GFF3 gene annotations placed on graph nodes.
+//!
+//! Classical annotations are given in the linear coordinates of a reference
+//! assembly. Loading them against the path that embeds that assembly places
+//! every feature on the nodes it covers, so that graph analyses can ask which
+//! genes a node falls into, and which nodes make up a gene. Complete gene
+//! models can also be lifted from the reference onto other haplotype paths
+//! with [`liftover_genes`].
+
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use super::graph::{Error, Graph};
+use super::pansn::DELIMITER;
+use super::path_alignment::PathAlignment;
+use super::path_index::PathIndex;
+
+/// A GFF3 feature on the reference path, with the nodes it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GffFeature {
+    /// The sequence the feature was annotated on (column 1).
+    pub seqid: String,
+    /// The program or database that produced the feature (column 2).
+    pub source: String,
+    /// The feature type, such as `gene`, `mRNA`, `exon`, or `CDS` (column 3).
+    pub feature_type: String,
+    /// The 0-based start on the reference path.
+    pub start: u64,
+    /// The 0-based, exclusive end on the reference path.
+    pub end: u64,
+    /// `Some(false)` for the reverse strand, `None` if the strand is unknown or irrelevant.
+    pub is_forward: Option<bool>,
+    /// The CDS phase (`0`, `1`, or `2`), or `None` if not given.
+    pub phase: Option<u8>,
+    /// The attributes (column 9) in file order, with percent-escapes decoded.
+    pub attributes: Vec<(String, String)>,
+    /// The nodes the feature covers, in the order the reference path visits them.
+    pub nodes: Vec<u64>,
+}
+
+impl GffFeature {
+    /// Returns the value of an attribute.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the `ID` attribute.
+    pub fn id(&self) -> Option<&str> {
+        self.attribute("ID")
+    }
+
+    /// Returns the `Name` attribute.
+    pub fn name(&self) -> Option<&str> {
+        self.attribute("Name")
+    }
+
+    /// Returns the IDs listed in the `Parent` attribute.
+    pub fn parents(&self) -> Vec<&str> {
+        self.attribute("Parent").map_or_else(Vec::new, |parents| parents.split(',').collect())
+    }
+
+    /// Returns whether the feature is a gene, including types such as
+    /// `pseudogene` and `ncRNA_gene`.
+    pub fn is_gene(&self) -> bool {
+        self.feature_type.ends_with("gene")
+    }
+
+    /// Returns whether the feature is known by `name`, as its `Name`, `ID`,
+    /// `gene_name`, or `gene` attribute.
+    fn is_named(&self, name: &str) -> bool {
+        ["Name", "ID", "gene_name", "gene"].iter().any(|key| self.attribute(key) == Some(name))
+    }
+}
+
+/// GFF3 features loaded against a reference path, created by [`load_gff3`].
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    reference_path: String,
+    features: Vec<GffFeature>,
+    /// The indices of the features covering each node.
+    by_node: HashMap<u64, Vec<usize>>,
+}
+
+/// How completely a gene model was lifted by [`liftover_genes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiftoverStatus {
+    /// Every feature was lifted, and all exons and CDS kept their length.
+    Intact,
+    /// Every feature was lifted, but some exons or CDS changed length without
+    /// shifting the reading frame, for example through in-frame indels.
+    LengthChanged,
+    /// Every feature was lifted, but the total CDS length of a transcript
+    /// changed by a number of bases that is not a multiple of 3.
+    Frameshifted,
+    /// Some features of the model could not be lifted.
+    Partial,
+    /// No feature of the model could be lifted.
+    Unmapped,
+}
+
+/// A gene model lifted onto another path, returned by [`liftover_genes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiftedGene {
+    /// The gene as annotated on the reference path.
+    pub gene: GffFeature,
+    /// How completely the model was lifted.
+    pub status: LiftoverStatus,
+    /// The lifted features of the model, the gene first and then its
+    /// descendants in file order, with coordinates, strands, CDS phases, and
+    /// nodes on the target path.
+    pub features: Vec<GffFeature>,
+    /// The features of the model that could not be lifted, as annotated on the reference path.
+    pub unmapped: Vec<GffFeature>,
+}
+
+/// Decodes the `%XX` escapes GFF3 uses for reserved characters in attributes.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses one GFF3 line into a feature without nodes.
+///
+/// Returns `Ok(None)` for blank lines, comments, and directives. `line_no` is
+/// 1-based and only used for error messages.
+fn parse_gff3_line(line: &str, line_no: usize) -> Result<Option<GffFeature>, Error> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    if line.trim().is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let invalid = |reason: &str| Error::invalid_format(format!("Invalid GFF3 line {}: {}", line_no, reason));
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 9 {
+        return Err(invalid("expected 9 columns"));
+    }
+    let coordinate = |field: &str| field.parse::<u64>().map_err(|_| invalid(&format!("bad coordinate '{}'", field)));
+    let (start, end) = (coordinate(fields[3])?, coordinate(fields[4])?);
+    if start == 0 || end < start {
+        return Err(invalid("coordinates must be 1-based with start <= end"));
+    }
+    let phase = match fields[7] {
+        "." => None,
+        phase => Some(
+            phase
+                .parse::<u8>()
+                .ok()
+                .filter(|&p| p < 3)
+                .ok_or_else(|| invalid(&format!("bad phase '{}'", phase)))?,
+        ),
+    };
+    let attributes = fields[8]
+        .split(';')
+        .filter(|pair| !pair.trim().is_empty() && *pair != ".")
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').ok_or_else(|| invalid(&format!("bad attribute '{}'", pair)))?;
+            Ok((percent_decode(key.trim()), percent_decode(value)))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Some(GffFeature {
+        seqid: percent_decode(fields[0]),
+        source: fields[1].to_string(),
+        feature_type: fields[2].to_string(),
+        start: start - 1,
+        end,
+        is_forward: match fields[6] {
+            "+" => Some(true),
+            "-" => Some(false),
+            _ => None,
+        },
+        phase,
+        attributes,
+        nodes: Vec::new(),
+    }))
+}
+
+/// Loads GFF3 features annotated on the assembly embedded as `reference_path`.
+///
+/// A feature belongs to the reference if its sequence ID is the path name or,
+/// for PanSN names such as `GRCh38#0#chr1`, the contig part of it (`chr1`).
+/// Features on other sequences are skipped, so a genome-wide GFF3 can be
+/// loaded against each chromosome path in turn. Reading stops at a `##FASTA`
+/// directive. Each feature is placed on the nodes the reference path visits
+/// within its coordinates.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the path does not exist, if the input cannot be
+/// read or is not valid GFF3, or if a feature extends past the end of the path.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{annotate, Graph};
+/// use std::io::BufReader;
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let gff = BufReader::new(std::fs::File::open("genes.gff3").unwrap());
+/// let annotation = annotate::load_gff3(&graph, gff, "GRCh38#0#chr17").unwrap();
+/// println!("BRCA1 spans {} nodes", annotation.nodes_for_gene("BRCA1").len());
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph, reader), err))]
+pub fn load_gff3<R: BufRead>(graph: &Graph, reader: R, reference_path: &str) -> Result<Annotation, Error> {
+    let index = PathIndex::new(graph, reference_path)?
+        .ok_or_else(|| Error::path_not_found(reference_path))?;
+    let contig = reference_path.rsplit(DELIMITER).next().unwrap_or(reference_path);
+
+    let mut features = Vec::new();
+    let mut by_node: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| Error::io("Failed to read GFF3 input", e))?;
+        if line.starts_with("##FASTA") {
+            break;
+        }
+        let Some(mut feature) = parse_gff3_line(&line, i + 1)? else { continue };
+        if feature.seqid != reference_path && feature.seqid != contig {
+            continue;
+        }
+        if feature.end > index.length {
+            return Err(Error::invalid_argument(format!(
+                "GFF3 line {} ends at {}, past the end of path '{}' ({} bp)",
+                i + 1,
+                feature.end,
+                reference_path,
+                index.length
+            )));
+        }
+
+        let first = index.step_at(feature.start).expect("the feature lies within the path");
+        let last = index.step_at(feature.end - 1).expect("the feature lies within the path");
+        let mut seen = HashSet::new();
+        for step in &index.steps[first..=last] {
+            if seen.insert(step.node_id) {
+                feature.nodes.push(step.node_id);
+                by_node.entry(step.node_id).or_default().push(features.len());
+            }
+        }
+        features.push(feature);
+    }
+
+    Ok(Annotation { reference_path: reference_path.to_string(), features, by_node })
+}
+
+impl Annotation {
+    /// Returns the path the features were loaded against.
+    pub fn reference_path(&self) -> &str {
+        &self.reference_path
+    }
+
+    /// Returns all loaded features, in file order.
+    pub fn features(&self) -> &[GffFeature] {
+        &self.features
+    }
+
+    /// Returns the features of any type that cover a node, in file order.
+    pub fn features_on_node(&self, node_id: u64) -> Vec<&GffFeature> {
+        self.by_node
+            .get(&node_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| &self.features[i]).collect())
+    }
+
+    /// Returns the genes that cover a node, in file order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::{annotate, Graph};
+    /// # let graph = Graph::load("pangenome.odgi").unwrap();
+    /// # let gff = std::io::BufReader::new(std::fs::File::open("genes.gff3").unwrap());
+    /// let annotation = annotate::load_gff3(&graph, gff, "GRCh38#0#chr17").unwrap();
+    /// for gene in annotation.genes_overlapping_node(1234) {
+    ///     println!("Node 1234 lies in {}", gene.name().or(gene.id()).unwrap_or("an unnamed gene"));
+    /// }
+    /// ```
+    pub fn genes_overlapping_node(&self, node_id: u64) -> Vec<&GffFeature> {
+        self.features_on_node(node_id).into_iter().filter(|feature| feature.is_gene()).collect()
+    }
+
+    /// Returns the nodes covered by the gene known as `gene`, in the order the
+    /// reference path visits them.
+    ///
+    /// Genes are matched by their `Name`, `ID`, `gene_name`, or `gene`
+    /// attribute. If several genes match, their nodes are combined. The result
+    /// is empty if no gene matches.
+    pub fn nodes_for_gene(&self, gene: &str) -> Vec<u64> {
+        let mut seen = HashSet::new();
+        self.features
+            .iter()
+            .filter(|feature| feature.is_gene() && feature.is_named(gene))
+            .flat_map(|feature| feature.nodes.iter().copied())
+            .filter(|&node_id| seen.insert(node_id))
+            .collect()
+    }
+}
+
+/// Translates reference positions to target positions through a path alignment.
+struct PositionMap<'a> {
+    alignment: &'a PathAlignment,
+    reference: &'a PathIndex,
+}
+
+impl PositionMap<'_> {
+    /// Maps a reference position to the forward coordinates of the target path.
+    ///
+    /// Positions on shared nodes map through the node, and positions between
+    /// two shared nodes map linearly if both paths have the same amount of
+    /// sequence there, as in a SNP bubble. Other positions do not map.
+    fn map(&self, pos: u64) -> Option<u64> {
+        let (alignment, reference) = (self.alignment, self.reference);
+        let k = alignment.anchors.partition_point(|&(_, j)| reference.starts[j] <= pos).checked_sub(1)?;
+        let (i, j) = alignment.anchors[k];
+        let query_pos = if pos < reference.starts[j] + reference.lengths[j] {
+            alignment.query.starts[i] + (pos - reference.starts[j])
+        } else {
+            if k + 1 == alignment.anchors.len() {
+                return None;
+            }
+            let ((query_start, query_end), (reference_start, reference_end)) = alignment.gap(reference, k + 1);
+            if query_end - query_start != reference_end - reference_start {
+                return None;
+            }
+            query_start + (pos - reference_start)
+        };
+        Some(if alignment.is_reverse { alignment.query.length - 1 - query_pos } else { query_pos })
+    }
+}
+
+/// Lifts complete gene models from the annotation's reference path onto `to_path`.
+///
+/// Each gene is lifted together with its descendants, found through their
+/// `Parent` attributes, such as transcripts, exons, and CDS. The reference and
+/// target paths are aligned through the nodes they share, as for
+/// [`liftover::write_chain`](crate::liftover::write_chain). A feature is lifted
+/// if both its first and last base map onto the target; bases in bubbles where
+/// the two paths carry the same amount of sequence map position by position.
+/// Lifted features take the target path as their sequence ID, switch strands
+/// if the target runs against the reference, and list the target nodes they
+/// cover. CDS phases are recomputed along each transcript from the lifted CDS
+/// lengths.
+///
+/// The [`LiftoverStatus`] of each model reports whether it was lifted intact,
+/// with changed exon or CDS lengths, with a shifted reading frame, only
+/// partially, or not at all. Genes are returned in file order.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the reference path or `to_path` does not exist in `graph`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::annotate::{self, LiftoverStatus};
+/// use odgi_ffi::Graph;
+/// use std::io::BufReader;
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let gff = BufReader::new(std::fs::File::open("genes.gff3").unwrap());
+/// let annotation = annotate::load_gff3(&graph, gff, "GRCh38#0#chr17").unwrap();
+/// for lifted in annotate::liftover_genes(&graph, &annotation, "HG002#1#chr17").unwrap() {
+///     if lifted.status != LiftoverStatus::Intact {
+///         println!("{}: {:?}", lifted.gene.id().unwrap_or("?"), lifted.status);
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph, annotation), err))]
+pub fn liftover_genes(graph: &Graph, annotation: &Annotation, to_path: &str) -> Result<Vec<LiftedGene>, Error> {
+    let reference = PathIndex::new(graph, &annotation.reference_path)?
+        .ok_or_else(|| Error::path_not_found(&annotation.reference_path))?;
+    let target = PathIndex::new(graph, to_path)?.ok_or_else(|| Error::path_not_found(to_path))?;
+    let alignment = PathAlignment::new(target.clone(), &reference);
+    let map = PositionMap { alignment: &alignment, reference: &reference };
+
+    let features = &annotation.features;
+    let mut children: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, feature) in features.iter().enumerate() {
+        for parent in feature.parents() {
+            children.entry(parent).or_default().push(i);
+        }
+    }
+
+    let mut genes = Vec::new();
+    for (g, gene) in features.iter().enumerate() {
+        if !gene.is_gene() {
+            continue;
+        }
+        // The gene and its descendants, in file order.
+        let mut model = vec![g];
+        let mut seen = HashSet::from([g]);
+        let mut k = 0;
+        while k < model.len() {
+            if let Some(ids) = features[model[k]].id().and_then(|id| children.get(id)) {
+                for &child in ids {
+                    if seen.insert(child) {
+                        model.push(child);
+                    }
+                }
+            }
+            k += 1;
+        }
+        model[1..].sort_unstable();
+
+        let mut lifted: HashMap<usize, GffFeature> = HashMap::new();
+        let mut unmapped = Vec::new();
+        for &f in &model {
+            let feature = &features[f];
+            match (map.map(feature.start), map.map(feature.end - 1)) {
+                (Some(a), Some(b)) => {
+                    let (start, end) = (a.min(b), a.max(b) + 1);
+                    let first = target.step_at(start).expect("lifted features lie within the target path");
+                    let last = target.step_at(end - 1).expect("lifted features lie within the target path");
+                    let mut seen_nodes = HashSet::new();
+                    let nodes = target.steps[first..=last]
+                        .iter()
+                        .map(|step| step.node_id)
+                        .filter(|&node_id| seen_nodes.insert(node_id))
+                        .collect();
+                    let is_forward = feature.is_forward.map(|is_forward| is_forward != alignment.is_reverse);
+                    lifted.insert(
+                        f,
+                        GffFeature { seqid: to_path.to_string(), start, end, is_forward, nodes, ..feature.clone() },
+                    );
+                }
+                _ => unmapped.push(feature.clone()),
+            }
+        }
+
+        let mut length_changed = false;
+        let mut frameshifted = false;
+        for (&f, feature) in &lifted {
+            if matches!(features[f].feature_type.as_str(), "exon" | "CDS")
+                && feature.end - feature.start != features[f].end - features[f].start
+            {
+                length_changed = true;
+            }
+        }
+        // Recompute CDS phases transcript by transcript, in the direction of transcription.
+        for &f in &model {
+            let Some(cds) = features[f].id().and_then(|id| children.get(id)).map(|ids| {
+                ids.iter().copied().filter(|c| features[*c].feature_type == "CDS").collect::<Vec<_>>()
+            }) else {
+                continue;
+            };
+            if cds.is_empty() || cds.iter().any(|c| !lifted.contains_key(c)) {
+                continue;
+            }
+            let reference_length: u64 = cds.iter().map(|&c| features[c].end - features[c].start).sum();
+            let lifted_length: u64 = cds.iter().map(|&c| lifted[&c].end - lifted[&c].start).sum();
+            if !reference_length.abs_diff(lifted_length).is_multiple_of(3) {
+                frameshifted = true;
+            }
+            let mut ordered = cds;
+            ordered.sort_by_key(|c| lifted[c].start);
+            if lifted[&ordered[0]].is_forward == Some(false) {
+                ordered.reverse();
+            }
+            let mut phase = lifted[&ordered[0]].phase.unwrap_or(0) as u64;
+            for c in ordered {
+                let feature = lifted.get_mut(&c).unwrap();
+                feature.phase = Some(phase as u8);
+                // The next CDS completes the codon that this one leaves unfinished.
+                phase = (phase + 3 - (feature.end - feature.start) % 3) % 3;
+            }
+        }
+
+        let status = if lifted.is_empty() {
+            LiftoverStatus::Unmapped
+        } else if !unmapped.is_empty() {
+            LiftoverStatus::Partial
+        } else if frameshifted {
+            LiftoverStatus::Frameshifted
+        } else if length_changed {
+            LiftoverStatus::LengthChanged
+        } else {
+            LiftoverStatus::Intact
+        };
+        genes.push(LiftedGene {
+            gene: gene.clone(),
+            status,
+            features: model.iter().filter_map(|f| lifted.remove(f)).collect(),
+            unmapped,
+        });
+    }
+    Ok(genes)
+}
@@ -0,0 +1,319 @@
+// src/assembly.rs
+
+//! Threading new assemblies into an existing graph.
+
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::ops::Range;
+use std::sync::Arc;
+use super::alignment::{parse_cigar, GraphAlignment};
+use super::gaf::{parse_gaf_line, slice_by_step};
+use super::graph::{Error, Graph};
+use super::pansn::DELIMITER;
+use super::path_index::PathIndex;
+use super::seq::reverse_complement;
+
+/// The result of [`Graph::add_assembly`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssemblyReport {
+    /// The names of the added paths, one per contig, in FASTA order.
+    pub paths: Vec<String>,
+    /// The number of nodes created for novel sequence.
+    pub new_nodes: u64,
+    /// The number of contig bases placed on new nodes.
+    pub novel_bp: u64,
+    /// The number of contig bases placed on existing nodes.
+    pub reused_bp: u64,
+}
+
+/// A stretch of a contig, either on an existing oriented node or novel.
+enum Piece {
+    Existing(u64, bool),
+    Novel(Range<usize>),
+}
+
+/// Reads the records of a FASTA file as `(name, sequence)` pairs.
+///
+/// Names are the first word of each header line.
+fn read_fasta<R: BufRead>(reader: R) -> Result<Vec<(String, String)>, Error> {
+    let mut records: Vec<(String, String)> = Vec::new();
+    for line in reader.lines() {
+        let line = line.map_err(|e| Error::io("Failed to read FASTA input", e))?;
+        let line = line.trim_end();
+        if let Some(header) = line.strip_prefix('>') {
+            let name = header.split_whitespace().next().unwrap_or_default();
+            records.push((name.to_string(), String::new()));
+        } else if !line.is_empty() {
+            let Some((name, sequence)) = records.last_mut() else {
+                return Err(Error::invalid_format("FASTA input does not start with a '>' header"));
+            };
+            if !line.is_ascii() {
+                return Err(Error::invalid_format(format!("FASTA record '{}' contains non-ASCII characters", name)));
+            }
+            sequence.push_str(line);
+        }
+    }
+    Ok(records)
+}
+
+/// Parses one PAF line whose target is an embedded path into an alignment to
+/// the walk of that path. `line_no` is 1-based and only used for error messages.
+fn parse_paf_line(
+    graph: &Graph,
+    indexes: &mut HashMap<String, Arc<PathIndex>>,
+    line: &str,
+    line_no: usize,
+) -> Result<Option<GraphAlignment>, Error> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let invalid = |reason: &str| Error::invalid_format(format!("Invalid PAF line {}: {}", line_no, reason));
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 12 {
+        return Err(invalid("expected at least 12 columns"));
+    }
+    let number = |index: usize| {
+        fields[index]
+            .parse::<u64>()
+            .map_err(|_| invalid(&format!("bad number '{}'", fields[index])))
+    };
+
+    let target = fields[5];
+    if !indexes.contains_key(target) {
+        let index = PathIndex::new(graph, target)?.ok_or_else(|| Error::path_not_found(target))?;
+        indexes.insert(target.to_string(), index);
+    }
+    let index = &indexes[target];
+    let (target_start, target_end) = (number(7)?, number(8)?);
+    if target_start >= target_end || target_end > index.length {
+        return Err(invalid(&format!("target range {}-{} is outside path '{}'", target_start, target_end, target)));
+    }
+    let first = index.step_at(target_start).expect("the range lies within the path");
+    let last = index.step_at(target_end - 1).expect("the range lies within the path");
+
+    let cigar = match fields[12..].iter().find_map(|tag| tag.strip_prefix("cg:Z:")) {
+        Some(cg) => Some(parse_cigar(cg).ok_or_else(|| invalid(&format!("bad CIGAR '{}'", cg)))?),
+        None => None,
+    };
+
+    Ok(Some(GraphAlignment {
+        query_name: fields[0].to_string(),
+        query_length: number(1)?,
+        query_start: number(2)?,
+        query_end: number(3)?,
+        is_reverse: fields[4] == "-",
+        walk: index.steps[first..=last].iter().map(|step| (step.node_id, step.is_forward)).collect(),
+        walk_length: index.starts[last] + index.lengths[last] - index.starts[first],
+        walk_start: target_start - index.starts[first],
+        walk_end: target_end - index.starts[first],
+        mapping_quality: fields[11].parse().unwrap_or(255),
+        cigar,
+    }))
+}
+
+impl Graph {
+    /// Threads the contigs of a new assembly into the graph as new paths.
+    ///
+    /// `fasta` holds the assembly, and `alignments` its contigs' alignments to
+    /// the graph, either as GAF with node walks or as PAF against paths already
+    /// embedded in the graph; both can be mixed in one input. Each contig
+    /// becomes a path named `<sample_name>#<contig>`, so passing a sample and
+    /// haplotype such as `HG002#1` yields PanSN names.
+    ///
+    /// Where a contig has several overlapping alignments, the longest ones are
+    /// kept. Along the kept alignments, the contig reuses every node that it
+    /// covers completely and spells exactly, in the orientation it is aligned
+    /// in. All other contig sequence, including unaligned contigs, the gaps
+    /// between alignments, and the parts of nodes covered only partially or
+    /// with differences, is placed on new nodes, one per stretch of novel
+    /// sequence, with IDs following the largest existing one. Missing edges
+    /// between consecutive steps are added. The new paths therefore spell
+    /// their contigs exactly, and the graph grows without being rebuilt.
+    ///
+    /// All inputs are read and validated before the graph is changed, so on
+    /// error the graph is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if an input cannot be read or parsed, if a path
+    /// name is already taken, or if an alignment refers to an unknown contig,
+    /// node, or path, or has coordinates inconsistent with the contig or graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    ///
+    /// let mut graph = Graph::load("cohort.odgi").unwrap();
+    /// let fasta = BufReader::new(File::open("HG002.hap1.fa").unwrap());
+    /// let gaf = BufReader::new(File::open("HG002.hap1.gaf").unwrap());
+    /// let report = graph.add_assembly(fasta, gaf, "HG002#1").unwrap();
+    /// println!("Added {} paths with {} bp of novel sequence", report.paths.len(), report.novel_bp);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, fasta, alignments), err))]
+    pub fn add_assembly<F: BufRead, A: BufRead>(
+        &mut self,
+        fasta: F,
+        alignments: A,
+        sample_name: &str,
+    ) -> Result<AssemblyReport, Error> {
+        let contigs = read_fasta(fasta)?;
+        let mut taken: HashSet<String> = self.get_path_names()?.into_iter().collect();
+        let mut names = Vec::with_capacity(contigs.len());
+        for (contig, _) in &contigs {
+            let name = format!("{}{}{}", sample_name, DELIMITER, contig);
+            if !taken.insert(name.clone()) {
+                return Err(Error::path_exists(name));
+            }
+            names.push(name);
+        }
+
+        let contig_lengths: HashMap<&str, u64> = contigs.iter().map(|(name, s)| (name.as_str(), s.len() as u64)).collect();
+        let mut by_contig: HashMap<String, Vec<GraphAlignment>> = HashMap::new();
+        let mut path_indexes = HashMap::new();
+        for (i, line) in alignments.lines().enumerate() {
+            let line = line.map_err(|e| Error::io("Failed to read alignment input", e))?;
+            let is_gaf = line.split('\t').nth(5).is_some_and(|target| target.starts_with(['>', '<']));
+            let alignment = if is_gaf {
+                parse_gaf_line(&line, i + 1)?
+            } else {
+                parse_paf_line(self, &mut path_indexes, &line, i + 1)?
+            };
+            let Some(alignment) = alignment else { continue };
+            self.validate_assembly_alignment(&alignment, &contig_lengths)?;
+            by_contig.entry(alignment.query_name.clone()).or_default().push(alignment);
+        }
+
+        let plans = contigs
+            .iter()
+            .map(|(contig, sequence)| self.thread_contig(sequence, by_contig.remove(contig).unwrap_or_default()))
+            .collect::<Result<Vec<Vec<Piece>>, Error>>()?;
+
+        let mut next_id = self.node_ids()?.into_iter().max().unwrap_or(0) + 1;
+        let mut report = AssemblyReport::default();
+        for (((_, sequence), name), pieces) in contigs.iter().zip(names).zip(plans) {
+            self.add_path(&name)?;
+            let mut prev: Option<(u64, bool)> = None;
+            for piece in pieces {
+                let (node_id, is_forward) = match piece {
+                    Piece::Existing(node_id, is_forward) => {
+                        report.reused_bp += self.get_node_len(node_id)?;
+                        (node_id, is_forward)
+                    }
+                    Piece::Novel(range) => {
+                        report.new_nodes += 1;
+                        report.novel_bp += range.len() as u64;
+                        self.add_node(next_id, &sequence[range])?;
+                        next_id += 1;
+                        (next_id - 1, true)
+                    }
+                };
+                if let Some((prev_id, prev_forward)) = prev {
+                    self.add_edge(prev_id, prev_forward, node_id, is_forward)?;
+                }
+                self.append_step(&name, node_id, is_forward)?;
+                prev = Some((node_id, is_forward));
+            }
+            report.paths.push(name);
+        }
+        Ok(report)
+    }
+
+    /// Checks that an alignment of a contig fits the contig and the graph.
+    fn validate_assembly_alignment(
+        &self,
+        alignment: &GraphAlignment,
+        contig_lengths: &HashMap<&str, u64>,
+    ) -> Result<(), Error> {
+        let query = &alignment.query_name;
+        let Some(&contig_length) = contig_lengths.get(query.as_str()) else {
+            return Err(Error::invalid_argument(format!("Alignment refers to contig '{}', which is not in the FASTA input", query)));
+        };
+        if alignment.query_length != contig_length
+            || alignment.query_start > alignment.query_end
+            || alignment.query_end > contig_length
+        {
+            return Err(Error::invalid_argument(format!("Alignment of '{}' has coordinates that do not match the contig", query)));
+        }
+
+        let mut walk_length = 0;
+        for &(node_id, _) in &alignment.walk {
+            walk_length += self
+                .get_node_len(node_id)
+                .map_err(|e| e.context(format!("Alignment of '{}'", query)))?;
+        }
+        let ops = alignment.operations();
+        let query_span: u64 = ops.iter().filter(|(op, _)| *op != 'D').map(|(_, len)| len).sum();
+        let walk_span: u64 = ops.iter().filter(|(op, _)| *op != 'I').map(|(_, len)| len).sum();
+        if walk_length != alignment.walk_length
+            || alignment.walk_start > alignment.walk_end
+            || alignment.walk_end > walk_length
+            || query_span != alignment.query_end - alignment.query_start
+            || walk_span != alignment.walk_end - alignment.walk_start
+        {
+            return Err(Error::invalid_argument(format!(
+                "Alignment of '{}' has walk coordinates that do not match the graph",
+                query
+            )));
+        }
+        Ok(())
+    }
+
+    /// Splits a contig into pieces on existing nodes and novel pieces, following its alignments.
+    fn thread_contig(&self, sequence: &str, mut alignments: Vec<GraphAlignment>) -> Result<Vec<Piece>, Error> {
+        // Keep the longest alignments that do not overlap on the contig.
+        alignments.sort_by_key(|a| std::cmp::Reverse(a.query_end - a.query_start));
+        let mut chain: Vec<GraphAlignment> = Vec::new();
+        for alignment in alignments {
+            if chain
+                .iter()
+                .all(|kept| alignment.query_end <= kept.query_start || alignment.query_start >= kept.query_end)
+            {
+                chain.push(alignment);
+            }
+        }
+        chain.sort_by_key(|a| a.query_start);
+
+        let mut pieces = Vec::new();
+        // The start of the contig bases not yet placed on a piece.
+        let mut placed = 0;
+        for alignment in &chain {
+            let mut reused: Vec<((u64, bool), Range<usize>)> = Vec::new();
+            for slice in slice_by_step(self, alignment)? {
+                let (node_id, walk_forward) = alignment.walk[slice.step];
+                if slice.node_range != (0, self.get_node_len(node_id)?) {
+                    continue;
+                }
+                // Reverse alignments read the walk against the reverse complement of the contig.
+                let (range, is_forward) = if alignment.is_reverse {
+                    (alignment.query_end - slice.query_range.1..alignment.query_end - slice.query_range.0, !walk_forward)
+                } else {
+                    (alignment.query_start + slice.query_range.0..alignment.query_start + slice.query_range.1, walk_forward)
+                };
+                let range = range.start as usize..range.end as usize;
+                let node_sequence = self.get_node_sequence(node_id)?;
+                let oriented = if is_forward { node_sequence } else { reverse_complement(&node_sequence) };
+                if sequence[range.clone()].eq_ignore_ascii_case(&oriented) {
+                    reused.push(((node_id, is_forward), range));
+                }
+            }
+            if alignment.is_reverse {
+                reused.reverse();
+            }
+            for ((node_id, is_forward), range) in reused {
+                if range.start > placed {
+                    pieces.push(Piece::Novel(placed..range.start));
+                }
+                pieces.push(Piece::Existing(node_id, is_forward));
+                placed = range.end;
+            }
+        }
+        if placed < sequence.len() {
+            pieces.push(Piece::Novel(placed..sequence.len()));
+        }
+        Ok(pieces)
+    }
+}
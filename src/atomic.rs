@@ -0,0 +1,85 @@
+// src/atomic.rs
+
+//! Atomic replacement of output files.
+//!
+//! Outputs are first written to a temporary file in the destination directory
+//! and only renamed into place once they are complete. A crashed or killed job
+//! therefore never leaves a truncated file behind under the final name.
+
+use super::graph::Error;
+use std::fs::File;
+#[cfg(not(feature = "docs-only"))]
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+
+/// A temporary file that replaces `target` when committed.
+///
+/// If the `AtomicFile` is dropped without being committed, the temporary file
+/// is removed and `target` is left untouched.
+pub(crate) struct AtomicFile {
+    temp: NamedTempFile,
+    target: PathBuf,
+}
+
+impl AtomicFile {
+    /// Creates an empty temporary file next to `target`.
+    pub(crate) fn new(target: &str) -> Result<Self, Error> {
+        let target = PathBuf::from(target);
+        // Renames are only atomic within a filesystem, so the temporary file
+        // must live in the same directory as the target.
+        let dir = match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let file_name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let prefix = format!(".{}.", file_name);
+        let mut builder = tempfile::Builder::new();
+        builder.prefix(&prefix).suffix(".tmp");
+        // Temporary files are private by default. Ask for 0666 instead, which
+        // the umask narrows just as it would for a file created in place.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o666));
+        }
+        let temp = builder
+            .tempfile_in(&dir)
+            .map_err(|e| Error::io(format!("Failed to create temporary file in '{}'", dir.display()), e))?;
+        Ok(AtomicFile { temp, target })
+    }
+
+    /// The path of the temporary file, for writers that need a path rather than a handle.
+    #[cfg(not(feature = "docs-only"))]
+    pub(crate) fn path(&self) -> &Path {
+        self.temp.path()
+    }
+
+    /// The open temporary file.
+    pub(crate) fn file(&mut self) -> &mut File {
+        self.temp.as_file_mut()
+    }
+
+    /// Flushes the temporary file to disk and renames it over the target.
+    ///
+    /// If the target already exists, its permissions are carried over to the
+    /// new file.
+    pub(crate) fn commit(self) -> Result<(), Error> {
+        self.temp
+            .as_file()
+            .sync_all()
+            .map_err(|e| Error::io(format!("Failed to flush '{}'", self.target.display()), e))?;
+        // A replaced file keeps the permissions it had before.
+        if let Ok(metadata) = std::fs::metadata(&self.target) {
+            std::fs::set_permissions(self.temp.path(), metadata.permissions())
+                .map_err(|e| Error::io(format!("Failed to copy permissions of '{}'", self.target.display()), e))?;
+        }
+        self.temp
+            .persist(&self.target)
+            .map_err(|e| Error::io(format!("Failed to move output into place at '{}'", self.target.display()), e.error))?;
+        Ok(())
+    }
+}
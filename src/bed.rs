@@ -0,0 +1,66 @@
+// src/bed.rs
+
+//! A minimal reader for BED intervals on embedded paths.
+
+use super::graph::Error;
+
+/// A single BED interval. The `chrom` column names an embedded path.
+#[derive(Debug, Clone)]
+pub(crate) struct BedRecord {
+    pub(crate) chrom: String,
+    pub(crate) start: u64,
+    pub(crate) end: u64,
+    pub(crate) name: Option<String>,
+    /// `Some(false)` for the reverse strand, `None` if no strand was given.
+    pub(crate) is_forward: Option<bool>,
+}
+
+/// Parses one BED line.
+///
+/// Returns `Ok(None)` for blank lines, comments, and `track`/`browser` headers.
+/// A header is the keyword followed by whitespace or the end of the line, so
+/// intervals on paths such as `track1` are read as intervals.
+/// `line_no` is 0-based and only used for error messages.
+pub(crate) fn parse_bed_line(line: &str, line_no: usize) -> Result<Option<BedRecord>, Error> {
+    let line = line.trim_end();
+    let is_header = |keyword: &str| {
+        line.strip_prefix(keyword)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    };
+    if line.is_empty() || line.starts_with('#') || is_header("track") || is_header("browser") {
+        return Ok(None);
+    }
+
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 3 {
+        return Err(Error::invalid_format(format!("Invalid BED line {}: expected at least 3 columns", line_no + 1)));
+    }
+    let coordinate = |field: &str| {
+        field
+            .parse::<u64>()
+            .map_err(|_| Error::invalid_format(format!("Invalid BED line {}: bad coordinate '{}'", line_no + 1, field)))
+    };
+    let start = coordinate(fields[1])?;
+    let end = coordinate(fields[2])?;
+    if end < start {
+        return Err(Error::invalid_format(format!("Invalid BED line {}: end is before start", line_no + 1)));
+    }
+
+    let name = fields
+        .get(3)
+        .filter(|n| !n.is_empty() && **n != ".")
+        .map(|n| n.to_string());
+    let is_forward = match fields.get(5) {
+        Some(&"+") => Some(true),
+        Some(&"-") => Some(false),
+        _ => None,
+    };
+
+    Ok(Some(BedRecord {
+        chrom: fields[0].to_string(),
+        start,
+        end,
+        name,
+        is_forward,
+    }))
+}
@@ -0,0 +1,162 @@
+// src/bed_batch.rs
+
+//! Running a query over every interval of a BED file.
+//!
+//! Each path named in the BED file is indexed once, when it first occurs, so a
+//! file of tens of thousands of intervals on a few paths costs about as much
+//! as indexing those paths plus the per-interval work.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Lines};
+use std::iter::Enumerate;
+use std::sync::Arc;
+use super::bed::{parse_bed_line, BedRecord};
+use super::depth::DepthOptions;
+use super::graph::{Error, Graph, PathPosition};
+use super::path_index::PathIndex;
+use super::region::GraphRegion;
+
+/// The query run on each interval: the graph, the BED record, and the index of its path.
+type Query<'g, T> = Box<dyn FnMut(&Graph, &BedRecord, &PathIndex) -> Result<T, Error> + 'g>;
+
+/// The results of a query over the intervals of a BED file, in the order of its lines.
+///
+/// Created by [`Graph::project_bed`], [`Graph::sequences_from_bed`],
+/// [`Graph::depths_from_bed`], and [`liftover::lift_bed`](crate::liftover::lift_bed),
+/// and used by [`Graph::extract_fasta_from_bed`] to write its records.
+/// Each item is an interval, as a [`GraphRegion`], with its result. Blank
+/// lines, comments, and `track`/`browser` headers are skipped. A line that is
+/// malformed, names a path that does not exist, or extends past the end of its
+/// path yields an error, and iteration continues with the next line.
+pub struct BedResults<'g, R, T> {
+    graph: &'g Graph,
+    lines: Enumerate<Lines<R>>,
+    indexes: HashMap<String, Arc<PathIndex>>,
+    query: Query<'g, T>,
+}
+
+impl<'g, R: BufRead, T> BedResults<'g, R, T> {
+    pub(crate) fn new(
+        graph: &'g Graph,
+        bed_reader: R,
+        query: impl FnMut(&Graph, &BedRecord, &PathIndex) -> Result<T, Error> + 'g,
+    ) -> Self {
+        BedResults { graph, lines: bed_reader.lines().enumerate(), indexes: HashMap::new(), query: Box::new(query) }
+    }
+
+    fn run(&mut self, record: BedRecord, line_no: usize) -> Result<(GraphRegion, T), Error> {
+        if !self.indexes.contains_key(&record.chrom) {
+            let index = PathIndex::new(self.graph, &record.chrom)?.ok_or_else(|| {
+                Error::path_not_found(&record.chrom).context(format!("BED line {}", line_no + 1))
+            })?;
+            self.indexes.insert(record.chrom.clone(), index);
+        }
+        let index = &self.indexes[&record.chrom];
+        if record.end > index.length {
+            return Err(Error::invalid_argument(format!(
+                "BED line {}: interval {}-{} extends past the end of path '{}' ({} bp)",
+                line_no + 1, record.start, record.end, record.chrom, index.length
+            )));
+        }
+        let result = (self.query)(self.graph, &record, index)
+            .map_err(|e| e.context(format!("BED line {}", line_no + 1)))?;
+        Ok((GraphRegion::new(&record.chrom, record.start, record.end), result))
+    }
+}
+
+impl<R: BufRead, T> Iterator for BedResults<'_, R, T> {
+    type Item = Result<(GraphRegion, T), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (line_no, line) in self.lines.by_ref() {
+            let record = match line {
+                Ok(line) => parse_bed_line(&line, line_no),
+                Err(e) => Err(Error::io("Failed to read BED input", e)),
+            };
+            match record {
+                Ok(Some(record)) => return Some(self.run(record, line_no)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        None
+    }
+}
+
+/// Rejects empty intervals, for queries that need at least one base.
+fn non_empty(start: u64, end: u64) -> Result<(), Error> {
+    if start == end {
+        return Err(Error::invalid_argument("interval is empty"));
+    }
+    Ok(())
+}
+
+impl Graph {
+    /// Projects the first and last base of every BED interval to graph coordinates.
+    ///
+    /// Positions are reported as by [`Graph::project`]. Empty intervals yield an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let bed = std::io::BufReader::new(std::fs::File::open("regions.bed").unwrap());
+    /// for result in graph.project_bed(bed) {
+    ///     let (region, (first, last)) = result.unwrap();
+    ///     println!("{}: nodes {} to {}", region, first.node_id, last.node_id);
+    /// }
+    /// ```
+    pub fn project_bed<R: BufRead>(&self, bed_reader: R) -> BedResults<'_, R, (PathPosition, PathPosition)> {
+        BedResults::new(self, bed_reader, |_, record, index| {
+            non_empty(record.start, record.end)?;
+            let first = index.project(record.start).expect("the interval lies within the path");
+            let last = index.project(record.end - 1).expect("the interval lies within the path");
+            Ok((first, last))
+        })
+    }
+
+    /// Returns the sequence of every BED interval on the forward strand of its path.
+    ///
+    /// This streams the sequences that [`Graph::extract_fasta_from_bed`] writes,
+    /// without the BED name and strand columns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let bed = std::io::BufReader::new(std::fs::File::open("regions.bed").unwrap());
+    /// let gc: usize = graph
+    ///     .sequences_from_bed(bed)
+    ///     .map(|result| result.unwrap().1.bytes().filter(|b| matches!(b, b'G' | b'C')).count())
+    ///     .sum();
+    /// ```
+    pub fn sequences_from_bed<R: BufRead>(&self, bed_reader: R) -> BedResults<'_, R, String> {
+        BedResults::new(self, bed_reader, |graph, record, index| index.sequence(graph, record.start, record.end))
+    }
+
+    /// Returns the mean depth of all paths over every BED interval, as [`Graph::region_depth`] does.
+    ///
+    /// Empty intervals yield an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{DepthOptions, Graph};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let bed = std::io::BufReader::new(std::fs::File::open("exons.bed").unwrap());
+    /// for result in graph.depths_from_bed(bed, &DepthOptions::default()) {
+    ///     let (region, depth) = result.unwrap();
+    ///     println!("{}\t{:.2}", region, depth);
+    /// }
+    /// ```
+    pub fn depths_from_bed<R: BufRead>(&self, bed_reader: R, options: &DepthOptions) -> BedResults<'_, R, f64> {
+        let options = options.clone();
+        BedResults::new(self, bed_reader, move |graph, record, index| {
+            non_empty(record.start, record.end)?;
+            graph.mean_depth(index, record.start, record.end, &options)
+        })
+    }
+}
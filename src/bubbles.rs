@@ -0,0 +1,107 @@
+// src/bubbles.rs
+
+//! Exporting the graph's bubbles as a vg snarl decomposition.
+//!
+//! vg describes the variation sites of a graph as snarls: subgraphs bounded by
+//! a start and an end node side. [`snarls`] lists the simple bubbles of the
+//! graph, where every allele is a single node or a deletion edge between the
+//! bounding nodes, and [`write_snarls`] writes them in the JSON form of vg's
+//! `Snarl` messages, one per line, as printed by `vg view -R -j`. Convert the
+//! output with `vg view -J -R -r - > graph.snarls` to obtain the protobuf file
+//! that vg's genotypers read.
+//!
+//! Simple bubbles cannot contain one another, so every snarl is a top-level
+//! snarl without a parent. Larger or nested sites, which need a full snarl
+//! decomposition, are not reported.
+
+use std::io::Write;
+use super::graph::{Error, Graph};
+
+/// A simple bubble, found by [`snarls`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snarl {
+    /// The node side the bubble opens from, as `(node_id, is_forward)`.
+    pub start: (u64, bool),
+    /// The node side the bubble closes into, as `(node_id, is_forward)`.
+    pub end: (u64, bool),
+    /// The allele nodes in their orientation from `start` to `end`, in ascending ID order.
+    pub alleles: Vec<(u64, bool)>,
+    /// Whether an edge leads directly from `start` to `end`.
+    pub has_deletion: bool,
+}
+
+/// Finds the simple bubbles of a graph.
+///
+/// Each bubble is reported once, from the end with the lower node ID. The
+/// snarls are ordered by the ID of that node, forward side first.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if odgi throws while reading the graph.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{bubbles, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// for snarl in bubbles::snarls(&graph).unwrap() {
+///     println!("{} -> {}: {} alleles", snarl.start.0, snarl.end.0, snarl.alleles.len());
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph), err))]
+pub fn snarls(graph: &Graph) -> Result<Vec<Snarl>, Error> {
+    Ok(graph
+        .small_bubbles(u64::MAX)?
+        .into_iter()
+        .map(|bubble| {
+            let mut alleles = bubble.alleles;
+            alleles.sort();
+            Snarl { start: bubble.source, end: bubble.sink, alleles, has_deletion: bubble.has_deletion }
+        })
+        .collect())
+}
+
+/// Writes the simple bubbles of a graph as vg `Snarl` messages in JSON, one per line.
+///
+/// Every snarl is an ultrabubble whose start reaches its end and whose net
+/// graph is acyclic. Fields that hold their default value are omitted, as vg
+/// does. Returns the number of snarls written.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if reading the graph or writing fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{bubbles, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let mut out = std::fs::File::create("graph.snarls.json").unwrap();
+/// bubbles::write_snarls(&graph, &mut out).unwrap();
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph, writer), err))]
+pub fn write_snarls<W: Write>(graph: &Graph, writer: &mut W) -> Result<usize, Error> {
+    let io_err = |e: std::io::Error| Error::io("Failed to write snarls", e);
+    let snarls = snarls(graph)?;
+    for snarl in &snarls {
+        writeln!(
+            writer,
+            "{{\"start\":{},\"end\":{},\"type\":\"ULTRABUBBLE\",\"start_end_reachable\":true,\"directed_acyclic_net_graph\":true}}",
+            visit(snarl.start),
+            visit(snarl.end)
+        )
+        .map_err(io_err)?;
+    }
+    Ok(snarls.len())
+}
+
+/// Formats a node side as a vg `Visit`, whose 64-bit node ID is a JSON string.
+fn visit((node_id, is_forward): (u64, bool)) -> String {
+    if is_forward {
+        format!("{{\"node_id\":\"{}\"}}", node_id)
+    } else {
+        format!("{{\"node_id\":\"{}\",\"backward\":true}}", node_id)
+    }
+}
@@ -0,0 +1,55 @@
+// src/cancel.rs
+
+//! Cooperative cancellation of long-running operations.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use super::graph::Error;
+
+/// A flag that can be used to abort long-running operations from another thread.
+///
+/// Tokens are cheap to clone, and all clones share the same flag: cancelling any
+/// of them cancels every operation the token was passed to. Operations check the
+/// flag periodically and return an [`Error`] for which [`Error::is_cancelled`] is
+/// `true` once it has been set.
+///
+/// # Examples
+///
+/// ```rust
+/// use odgi_ffi::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// assert!(!token.is_cancelled());
+///
+/// handle.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every operation using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if [`CancellationToken::cancel`] has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns a cancellation error if the token has been cancelled.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            Err(Error::cancelled())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,263 @@
+// src/command.rs
+
+//! A builder for running arbitrary subcommands of the bundled `odgi` executable.
+//!
+//! The crate only wraps a handful of `odgi` features directly. [`OdgiCommand`]
+//! gives access to everything else (`odgi sort`, `odgi unchop`, `odgi viz`, ...)
+//! while still providing captured output, cancellation, timeouts, and managed
+//! temporary files.
+
+use super::cancel::CancellationToken;
+use super::diagnostics::Diagnostic;
+use super::graph::Error;
+use std::ffi::{OsStr, OsString};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// The environment variable naming a directory of prebuilt odgi artifacts.
+const PREBUILT_DIR_VAR: &str = "ODGI_FFI_PREBUILT_DIR";
+
+/// How often a running `odgi` process is checked for completion or cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Returns the path of the `odgi` executable that was built together with this crate.
+///
+/// This is the exact binary used by the conversion functions and [`OdgiCommand`],
+/// compiled from the same vendored sources as the linked library. Pipelines that
+/// invoke it directly are therefore guaranteed to run the same `odgi` version
+/// that produced or will read the crate's graphs.
+///
+/// The executable lives in Cargo's build output directory, so it is only
+/// available as long as that directory is not cleaned.
+///
+/// If the `ODGI_FFI_PREBUILT_DIR` environment variable names a directory with
+/// an executable at `bin/odgi`, that executable is returned instead, so that
+/// deployments can share one cached build of `odgi` (see the build-time use of
+/// the same variable in the crate documentation).
+///
+/// With the `system-odgi` feature, this is instead the installed `odgi` found at
+/// build time: `ODGI_EXE` if it was set, the `bin` directory of the installation,
+/// or the first `odgi` on the `PATH`. If none was found, it is the bare name
+/// `odgi`, which is looked up on the `PATH` when the command runs.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::process::Command;
+///
+/// let status = Command::new(odgi_ffi::odgi_executable())
+///     .arg("version")
+///     .status()
+///     .expect("Failed to run odgi");
+/// assert!(status.success());
+/// ```
+pub fn odgi_executable() -> PathBuf {
+    if let Some(dir) = std::env::var_os(PREBUILT_DIR_VAR) {
+        let prebuilt = Path::new(&dir).join("bin").join("odgi");
+        if prebuilt.is_file() {
+            return prebuilt;
+        }
+    }
+    Path::new(env!("ODGI_EXE")).to_path_buf()
+}
+
+/// A builder for a single invocation of an `odgi` subcommand.
+///
+/// Like [`std::process::Command`], the builder methods take `&mut self` so that
+/// calls can be chained on a temporary. Output is always captured.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::OdgiCommand;
+///
+/// // Sort a graph into a temporary output file managed by the command.
+/// let mut sort = OdgiCommand::new("sort");
+/// sort.args(["-i", "unsorted.odgi", "-O"]).temp_output("-o", "sorted.odgi");
+/// let output = sort.run().unwrap();
+///
+/// if output.status.success() {
+///     let sorted = &output.temp_outputs[0];
+///     println!("Sorted graph written to {}", sorted.display());
+/// } else {
+///     eprintln!("odgi sort failed with {:?}", output.status.code());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OdgiCommand {
+    subcommand: String,
+    args: Vec<OsString>,
+    timeout: Option<Duration>,
+    token: Option<CancellationToken>,
+    temp_outputs: Vec<PathBuf>,
+    temp_dir: Option<std::io::Result<TempDir>>,
+}
+
+/// The captured result of running an [`OdgiCommand`].
+#[derive(Debug)]
+pub struct OdgiOutput {
+    /// The exit status of the `odgi` process.
+    pub status: ExitStatus,
+    /// Everything the process wrote to stdout.
+    pub stdout: Vec<u8>,
+    /// Everything the process wrote to stderr.
+    pub stderr: Vec<u8>,
+    /// The paths created by [`OdgiCommand::temp_output`], in the order they were requested.
+    ///
+    /// The files are deleted when this `OdgiOutput` is dropped.
+    pub temp_outputs: Vec<PathBuf>,
+    _temp_dir: Option<TempDir>,
+}
+
+impl OdgiCommand {
+    /// Creates a command that will run `odgi <subcommand>`.
+    pub fn new(subcommand: &str) -> Self {
+        OdgiCommand {
+            subcommand: subcommand.to_string(),
+            args: Vec::new(),
+            timeout: None,
+            token: None,
+            temp_outputs: Vec::new(),
+            temp_dir: None,
+        }
+    }
+
+    /// Adds a single argument.
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    /// Adds several arguments.
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        for arg in args {
+            self.arg(arg);
+        }
+        self
+    }
+
+    /// Kills the process and fails with an error for which [`Error::is_timeout`]
+    /// is `true` if it runs for longer than `timeout`.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Kills the process and fails with an error for which [`Error::is_cancelled`]
+    /// is `true` as soon as `token` is cancelled.
+    pub fn cancellation_token(&mut self, token: &CancellationToken) -> &mut Self {
+        self.token = Some(token.clone());
+        self
+    }
+
+    /// Adds `flag` followed by the path of a fresh temporary file named `file_name`.
+    ///
+    /// This is meant for output arguments such as `-o`. The path is reported in
+    /// [`OdgiOutput::temp_outputs`], and the file is removed when the output is
+    /// dropped, so callers never have to manage scratch files themselves.
+    pub fn temp_output(&mut self, flag: &str, file_name: &str) -> &mut Self {
+        let dir = self.temp_dir.get_or_insert_with(|| {
+            tempfile::Builder::new()
+                .prefix("odgi-ffi-")
+                .tempdir()
+        });
+        if let Ok(dir) = dir {
+            let path = dir.path().join(format!("{}-{}", self.temp_outputs.len(), file_name));
+            self.args.push(flag.into());
+            self.args.push(path.clone().into_os_string());
+            self.temp_outputs.push(path);
+        }
+        self
+    }
+
+    /// Runs the command to completion and captures its output.
+    ///
+    /// A non-zero exit status is not an error; check [`OdgiOutput::status`].
+    /// Files requested with [`OdgiCommand::temp_output`] are handed over to the
+    /// returned output, so they belong to the first run of the command only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the executable cannot be started, if a temporary
+    /// directory could not be created, or if the command was cancelled or timed out.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), fields(subcommand = %self.subcommand), err))]
+    pub fn run(&mut self) -> Result<OdgiOutput, Error> {
+        let temp_dir = match self.temp_dir.take() {
+            Some(Ok(dir)) => Some(dir),
+            Some(Err(e)) => return Err(Error::io("Failed to create temporary directory", e)),
+            None => None,
+        };
+
+        let started = Instant::now();
+        let mut child = Command::new(odgi_executable())
+            .arg(&self.subcommand)
+            .args(&self.args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::io("Failed to execute odgi command", e))?;
+
+        // Both pipes are drained on background threads so that a child producing
+        // a lot of output never blocks on a full pipe.
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let status = loop {
+            if self.token.as_ref().is_some_and(|t| t.is_cancelled()) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::cancelled());
+            }
+            if let Some(timeout) = self.timeout.filter(|&t| started.elapsed() >= t) {
+                let _ = child.kill();
+                let _ = child.wait();
+                // The pipe closes once the child is gone, so this returns promptly.
+                let partial_stderr = stderr.join().unwrap_or_default();
+                return Err(Error::timeout(
+                    &format!("odgi {}", self.subcommand),
+                    timeout,
+                    &String::from_utf8_lossy(&partial_stderr),
+                ));
+            }
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+                Err(e) => return Err(Error::io("Failed to wait for odgi command", e)),
+            }
+        };
+
+        Ok(OdgiOutput {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+            temp_outputs: std::mem::take(&mut self.temp_outputs),
+            _temp_dir: temp_dir,
+        })
+    }
+}
+
+impl OdgiOutput {
+    /// Returns stderr parsed into structured [`Diagnostic`]s.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        Diagnostic::parse_all(&String::from_utf8_lossy(&self.stderr))
+    }
+}
+
+/// Reads a child's pipe to the end on a background thread.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
@@ -0,0 +1,98 @@
+// src/compression.rs
+
+//! Transparent decompression of gzip- and zstd-compressed ODGI files.
+//!
+//! The format is detected from the leading bytes of the file rather than its
+//! extension, so `.og.gz`, `.og.zst`, and misnamed files all work.
+
+use super::graph::Error;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use tempfile::NamedTempFile;
+
+pub(crate) const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+pub(crate) const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// The compression format of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+/// Detects the compression format of the file at `path` from its leading bytes.
+pub(crate) fn detect(path: &str) -> Result<Compression, Error> {
+    let mut file = File::open(path).map_err(|e| Error::io(format!("Failed to open '{}'", path), e))?;
+    let mut magic = [0u8; 4];
+    let mut len = 0;
+    while len < magic.len() {
+        match file.read(&mut magic[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::io(format!("Failed to read '{}'", path), e)),
+        }
+    }
+    let magic = &magic[..len];
+    Ok(if magic.starts_with(&GZIP_MAGIC) {
+        Compression::Gzip
+    } else if magic.starts_with(&ZSTD_MAGIC) {
+        Compression::Zstd
+    } else {
+        Compression::None
+    })
+}
+
+/// Opens the file at `path` for reading, decompressing it on the fly if necessary.
+pub(crate) fn open(path: &str) -> Result<Box<dyn Read>, Error> {
+    let compression = detect(path)?;
+    let file = File::open(path).map_err(|e| Error::io(format!("Failed to open '{}'", path), e))?;
+    let reader = BufReader::new(file);
+    Ok(match compression {
+        Compression::None => Box::new(reader),
+        // Files written by `bgzip` or concatenated with `cat` consist of several gzip members.
+        Compression::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(
+            zstd::stream::read::Decoder::with_buffer(reader)
+                .map_err(|e| Error::io(format!("Failed to initialize zstd decoder for '{}'", path), e))?,
+        ),
+    })
+}
+
+/// Decompresses the file at `path` into a temporary file.
+///
+/// Returns `None` if the file is not compressed and can be read directly.
+#[cfg_attr(feature = "docs-only", allow(dead_code))]
+pub(crate) fn decompress_to_temp(path: &str) -> Result<Option<NamedTempFile>, Error> {
+    if detect(path)? == Compression::None {
+        return Ok(None);
+    }
+    let mut reader = open(path)?;
+    let mut temp = tempfile::Builder::new()
+        .prefix("odgi-ffi-")
+        .suffix(".og")
+        .tempfile()
+        .map_err(|e| Error::io("Failed to create temporary file", e))?;
+    io::copy(&mut reader, temp.as_file_mut())
+        .map_err(|e| Error::io(format!("Failed to decompress '{}'", path), e))?;
+    Ok(Some(temp))
+}
+
+/// Decompresses a gzip- or zstd-compressed buffer, detected from its leading bytes.
+///
+/// Returns `None` if the buffer is not compressed and can be read directly.
+#[cfg_attr(feature = "docs-only", allow(dead_code))]
+pub(crate) fn decompress_bytes(bytes: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+    let mut buffer = Vec::new();
+    if bytes.starts_with(&GZIP_MAGIC) {
+        flate2::read::MultiGzDecoder::new(bytes)
+            .read_to_end(&mut buffer)
+            .map_err(|e| Error::io("Failed to decompress gzip data", e))?;
+    } else if bytes.starts_with(&ZSTD_MAGIC) {
+        buffer = zstd::stream::decode_all(bytes).map_err(|e| Error::io("Failed to decompress zstd data", e))?;
+    } else {
+        return Ok(None);
+    }
+    Ok(Some(buffer))
+}
@@ -0,0 +1,137 @@
+// src/connectivity.rs
+
+//! Nodes and edges whose removal disconnects the graph.
+
+use std::collections::HashMap;
+use super::edge_key::EdgeKey;
+use super::graph::{Error, Graph};
+
+/// Marks a node that has not been visited by the search yet.
+const UNVISITED: usize = usize::MAX;
+
+impl Graph {
+    /// Returns the nodes whose removal splits a connected part of the graph into several.
+    ///
+    /// Connectivity ignores orientation: two nodes are adjacent if any edge
+    /// joins them. Every walk between the parts on either side of an
+    /// articulation point passes through it, so these nodes are the obligate
+    /// anchors of the graph, useful for scaffolding and for partitioning it
+    /// into independent pieces.
+    ///
+    /// The IDs are returned in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the nodes or edges.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// println!("{} articulation points", graph.articulation_points().unwrap().len());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn articulation_points(&self) -> Result<Vec<u64>, Error> {
+        Ok(self.cut_structure()?.0)
+    }
+
+    /// Returns the edges whose removal splits a connected part of the graph in two.
+    ///
+    /// As for [`Graph::articulation_points`], orientation is ignored, so an
+    /// edge is only a bridge if no other edge joins the same two nodes. Each
+    /// bridge is returned once, in its [canonical](EdgeKey::canonical) form,
+    /// and the edges are sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the nodes or edges.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for bridge in graph.bridges().unwrap() {
+    ///     println!("{} -> {}", bridge.from_node, bridge.to_node);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn bridges(&self) -> Result<Vec<EdgeKey>, Error> {
+        Ok(self.cut_structure()?.1)
+    }
+
+    /// Finds the articulation points and bridges with an iterative depth-first
+    /// search that tracks the earliest node reachable from each subtree.
+    fn cut_structure(&self) -> Result<(Vec<u64>, Vec<EdgeKey>), Error> {
+        let ids = self.node_ids()?;
+        let index: HashMap<u64, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let edges: Vec<EdgeKey> = self
+            .raw_edges()?
+            .into_iter()
+            .filter(|e| e.from_node != e.to_node)
+            .map(|e| EdgeKey::canonical(e.from_node, e.from_orientation, e.to_node, e.to_orientation))
+            .collect();
+        let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); ids.len()];
+        for (e, edge) in edges.iter().enumerate() {
+            let (from, to) = (index[&edge.from_node], index[&edge.to_node]);
+            adjacency[from].push((to, e));
+            adjacency[to].push((from, e));
+        }
+
+        let mut discovered = vec![UNVISITED; ids.len()];
+        let mut low = vec![0; ids.len()];
+        let mut is_cut = vec![false; ids.len()];
+        let mut bridges = Vec::new();
+        let mut time = 0;
+        // Each frame is a node, the edge it was entered through, and the next neighbour to visit.
+        let mut stack: Vec<(usize, usize, usize)> = Vec::new();
+        for root in 0..ids.len() {
+            if discovered[root] != UNVISITED {
+                continue;
+            }
+            discovered[root] = time;
+            low[root] = time;
+            time += 1;
+            let mut root_children = 0;
+            stack.push((root, UNVISITED, 0));
+            while let Some(&(v, entered_by, next)) = stack.last() {
+                if let Some(&(w, e)) = adjacency[v].get(next) {
+                    stack.last_mut().expect("the stack is not empty").2 += 1;
+                    if e == entered_by {
+                        continue;
+                    }
+                    if discovered[w] == UNVISITED {
+                        discovered[w] = time;
+                        low[w] = time;
+                        time += 1;
+                        if v == root {
+                            root_children += 1;
+                        }
+                        stack.push((w, e, 0));
+                    } else {
+                        low[v] = low[v].min(discovered[w]);
+                    }
+                    continue;
+                }
+                stack.pop();
+                if let Some(&(u, _, _)) = stack.last() {
+                    low[u] = low[u].min(low[v]);
+                    if low[v] > discovered[u] {
+                        bridges.push(edges[entered_by]);
+                    }
+                    if u != root && low[v] >= discovered[u] {
+                        is_cut[u] = true;
+                    }
+                }
+            }
+            if root_children > 1 {
+                is_cut[root] = true;
+            }
+        }
+
+        let points = ids.iter().zip(is_cut).filter(|&(_, cut)| cut).map(|(&id, _)| id).collect();
+        bridges.sort();
+        Ok((points, bridges))
+    }
+}
@@ -0,0 +1,199 @@
+// src/consensus.rs
+
+//! Consensus paths following the most-supported traversal of the graph.
+
+use std::collections::{HashMap, HashSet};
+use super::edge_key::EdgeKey;
+use super::graph::{Error, Graph};
+use super::region::GraphRegion;
+
+/// How [`Graph::consensus_path`] chooses between equally supported continuations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ConsensusTieBreak {
+    /// Take the node with the lowest ID, forward orientation first.
+    #[default]
+    LowestNodeId,
+    /// Take the longest node, then the one with the lowest ID.
+    LongestNode,
+    /// Take the node the named path continues to, and fall back to
+    /// [`ConsensusTieBreak::LowestNodeId`] where that path does not help.
+    FollowPath(String),
+}
+
+/// Options controlling [`Graph::consensus_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsensusOptions {
+    /// The minimum number of path traversals an edge needs to be followed.
+    /// The consensus ends where no continuation reaches it.
+    pub min_support: u64,
+    /// How to choose between continuations with the same support.
+    pub tie_break: ConsensusTieBreak,
+    /// Restricts the consensus to a locus: the walk starts at the node where
+    /// this region starts and ends at the node where it ends, if it gets there.
+    pub region: Option<GraphRegion>,
+}
+
+impl Default for ConsensusOptions {
+    /// Follow any edge used by at least one path, breaking ties by node ID.
+    fn default() -> Self {
+        ConsensusOptions { min_support: 1, tie_break: ConsensusTieBreak::LowestNodeId, region: None }
+    }
+}
+
+impl Graph {
+    /// Builds a consensus path by majority rule and embeds it as `name`.
+    ///
+    /// The walk starts at the oriented node where the most paths begin. From
+    /// there, it repeatedly takes the outgoing edge traversed by the most path
+    /// steps (in either direction), breaking ties as set by
+    /// [`ConsensusOptions::tie_break`]. Each node is visited at most once, so
+    /// cycles are not repeated. The walk ends when no edge to an unvisited node
+    /// has at least [`ConsensusOptions::min_support`] traversals.
+    ///
+    /// The choice is made locally at each branch, so the result is the
+    /// representative genome of the graph's majority alleles rather than a copy
+    /// of any input haplotype. For graphs with several components, such as one
+    /// per chromosome, only the component containing the start is covered.
+    /// With [`ConsensusOptions::region`] set, the walk instead starts at the
+    /// region's first node, oriented as its path visits it, and stops once it
+    /// reaches the region's last node.
+    ///
+    /// Returns the walk of the new path as `(node_id, is_forward)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a path named `name` already exists, if the graph
+    /// has no paths, if the path named by [`ConsensusTieBreak::FollowPath`] or
+    /// by the region does not exist, or if the region is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{ConsensusOptions, ConsensusTieBreak, Graph};
+    ///
+    /// let mut graph = Graph::load("pangenome.odgi").unwrap();
+    /// let options = ConsensusOptions {
+    ///     tie_break: ConsensusTieBreak::FollowPath("GRCh38#0#chr20".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let walk = graph.consensus_path("consensus#chr20", &options).unwrap();
+    /// println!("Consensus visits {} nodes", walk.len());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn consensus_path(&mut self, name: &str, options: &ConsensusOptions) -> Result<Vec<(u64, bool)>, Error> {
+        let path_names = self.get_path_names()?;
+        if path_names.iter().any(|p| p == name) {
+            return Err(Error::path_exists(name));
+        }
+
+        // The handle each step of the tie-breaking path continues to, and where it starts.
+        let mut followed: HashMap<(u64, bool), (u64, bool)> = HashMap::new();
+        let mut followed_start = None;
+        if let ConsensusTieBreak::FollowPath(path_name) = &options.tie_break {
+            if !path_names.contains(path_name) {
+                return Err(Error::path_not_found(path_name));
+            }
+            let steps = self.raw_path_steps(path_name)?;
+            followed_start = steps.first().map(|step| (step.node_id, step.is_forward));
+            for pair in steps.windows(2) {
+                followed
+                    .entry((pair[0].node_id, pair[0].is_forward))
+                    .or_insert((pair[1].node_id, pair[1].is_forward));
+            }
+        }
+
+        let mut support: HashMap<EdgeKey, u64> = HashMap::new();
+        let mut starts: HashMap<(u64, bool), u64> = HashMap::new();
+        for path_name in &path_names {
+            let steps = self.raw_path_steps(path_name)?;
+            if let Some(first) = steps.first() {
+                *starts.entry((first.node_id, first.is_forward)).or_default() += 1;
+            }
+            for pair in steps.windows(2) {
+                let key = EdgeKey::between(&pair[0], &pair[1]).canonicalized();
+                *support.entry(key).or_default() += 1;
+            }
+        }
+
+        // The region's first oriented node and the node that ends the walk.
+        let mut region_start = None;
+        let mut region_end = None;
+        if let Some(region) = &options.region {
+            let (index, start, end) = region.index(self)?;
+            let first = &index.steps[index.step_at(start).expect("the region lies within the path")];
+            let last = &index.steps[index.step_at(end - 1).expect("the region lies within the path")];
+            region_start = Some((first.node_id, first.is_forward));
+            region_end = Some(last.node_id);
+        }
+
+        let start = match region_start {
+            Some(start) => Some(start),
+            None => self.choose_consensus_step(starts.into_iter().collect(), followed_start, &options.tie_break)?,
+        };
+        let Some(mut current) = start else {
+            return Err(Error::invalid_argument("Graph has no paths to build a consensus from"));
+        };
+
+        let mut visited = HashSet::from([current.0]);
+        let mut walk = vec![current];
+        while region_end != Some(current.0) {
+            let candidates: Vec<((u64, bool), u64)> = self
+                .get_successors(current.0)?
+                .into_iter()
+                .filter(|edge| edge.from_orientation == current.1 && !visited.contains(&edge.to_node))
+                .map(|edge| {
+                    let count = support.get(&EdgeKey::from_successor(current.0, &edge).canonicalized()).copied();
+                    ((edge.to_node, edge.to_orientation), count.unwrap_or(0))
+                })
+                .filter(|&(_, count)| count >= options.min_support)
+                .collect();
+            let preferred = followed.get(&current).copied();
+            let Some(next) = self.choose_consensus_step(candidates, preferred, &options.tie_break)? else {
+                break;
+            };
+            visited.insert(next.0);
+            walk.push(next);
+            current = next;
+        }
+
+        self.add_path(name)?;
+        for &(node_id, is_forward) in &walk {
+            self.append_step(name, node_id, is_forward)?;
+        }
+        Ok(walk)
+    }
+
+    /// Picks the best supported candidate, breaking ties by `tie_break`.
+    fn choose_consensus_step(
+        &self,
+        candidates: Vec<((u64, bool), u64)>,
+        preferred: Option<(u64, bool)>,
+        tie_break: &ConsensusTieBreak,
+    ) -> Result<Option<(u64, bool)>, Error> {
+        let Some(max) = candidates.iter().map(|&(_, count)| count).max() else {
+            return Ok(None);
+        };
+        let tied = candidates.into_iter().filter(|&(_, count)| count == max).map(|(handle, _)| handle);
+        // Forward handles sort before reverse ones with the same ID.
+        let key = |&(node_id, is_forward): &(u64, bool)| (node_id, !is_forward);
+        Ok(match tie_break {
+            ConsensusTieBreak::LongestNode => {
+                let lengths = tied
+                    .map(|handle| Ok((self.get_node_len(handle.0)?, handle)))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                lengths
+                    .into_iter()
+                    .min_by_key(|&(len, handle)| (std::cmp::Reverse(len), key(&handle)))
+                    .map(|(_, handle)| handle)
+            }
+            ConsensusTieBreak::FollowPath(_) => {
+                let tied: Vec<(u64, bool)> = tied.collect();
+                match preferred {
+                    Some(handle) if tied.contains(&handle) => Some(handle),
+                    _ => tied.into_iter().min_by_key(key),
+                }
+            }
+            ConsensusTieBreak::LowestNodeId => tied.min_by_key(key),
+        })
+    }
+}
@@ -3,12 +3,18 @@
 //! Provides utilities to convert between GFA and ODGI file formats.
 //!
 //! The functions in this module shell out to the `odgi` command-line executable
-//! that is compiled as part of this crate's build process. This provides a stable
+//! that is compiled as part of this crate's build process (see
+//! [`odgi_executable`](crate::odgi_executable)). This provides a stable
 //! and robust way to perform complex file conversions without linking the entire
 //! `odgi build` and `odgi view` logic into the library binary.
-use super::graph::Error;
-use std::io::Write; // Needed for the updated examples
-use std::process::Command;
+use super::atomic::AtomicFile;
+use super::cancel::CancellationToken;
+use super::command::{OdgiCommand, OdgiOutput};
+use super::diagnostics::Diagnostic;
+use super::graph::{Error, Graph};
+use super::n_runs::NRunHandling;
+use std::io::Write;
+use std::time::Duration;
 use tempfile::NamedTempFile; // Needed for the updated examples
 
 /// Converts a GFA file to an ODGI file by calling `odgi build`.
@@ -16,6 +22,10 @@ use tempfile::NamedTempFile; // Needed for the updated examples
 /// This function is useful for preparing an ODGI graph from the more common
 /// GFA format, making it ready to be loaded by [`super::Graph::load`].
 ///
+/// The output is written to a temporary file in the same directory and only
+/// renamed to `odgi_path` once `odgi build` has succeeded, so a failed or
+/// killed conversion never leaves a truncated graph behind.
+///
 /// # Arguments
 ///
 /// * `gfa_path` - Path to the input GFA file.
@@ -48,31 +58,175 @@ use tempfile::NamedTempFile; // Needed for the updated examples
 ///
 /// assert!(odgi_path.exists());
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
 pub fn gfa_to_odgi(gfa_path: &str, odgi_path: &str) -> Result<(), Error> {
-    let odgi_exe = "odgi";
-    let output = Command::new(odgi_exe)
-        .arg("build")
-        .arg("-g")
-        .arg(gfa_path)
-        .arg("-o")
-        .arg(odgi_path)
-        .output()
-        .map_err(|e| Error(format!("Failed to execute odgi command: {}", e)))?;
+    gfa_to_odgi_cancellable(gfa_path, odgi_path, &CancellationToken::new())
+}
+
+/// Converts a GFA file to an ODGI file, aborting if `token` is cancelled.
+///
+/// This behaves like [`gfa_to_odgi`], but the `odgi build` process is killed as
+/// soon as the token is cancelled.
+///
+/// # Errors
+///
+/// In addition to the errors of [`gfa_to_odgi`], returns an [`Error`] for which
+/// [`Error::is_cancelled`] is `true` if the conversion was cancelled.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{gfa_to_odgi_cancellable, CancellationToken};
+///
+/// let token = CancellationToken::new();
+/// let worker_token = token.clone();
+/// let worker = std::thread::spawn(move || {
+///     gfa_to_odgi_cancellable("huge.gfa", "huge.odgi", &worker_token)
+/// });
+///
+/// // The client went away; stop the conversion.
+/// token.cancel();
+/// assert!(worker.join().unwrap().unwrap_err().is_cancelled());
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(token), err))]
+pub fn gfa_to_odgi_cancellable(gfa_path: &str, odgi_path: &str, token: &CancellationToken) -> Result<(), Error> {
+    run_gfa_to_odgi(gfa_path, odgi_path, token, None).map(|_| ())
+}
+
+/// Converts a GFA file to an ODGI file and returns the diagnostics `odgi build` reported.
+///
+/// This behaves like [`gfa_to_odgi`], but on success it also returns the
+/// warnings and notices that `odgi` printed, such as unknown GFA tags or
+/// skipped records, parsed into [`Diagnostic`]s.
+///
+/// # Errors
+///
+/// Returns the same errors as [`gfa_to_odgi`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{gfa_to_odgi_with_diagnostics, DiagnosticLevel};
+///
+/// let diagnostics = gfa_to_odgi_with_diagnostics("input.gfa", "output.odgi").unwrap();
+/// for d in diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Warning) {
+///     eprintln!("warning ({:?}): {}", d.kind, d.message);
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn gfa_to_odgi_with_diagnostics(gfa_path: &str, odgi_path: &str) -> Result<Vec<Diagnostic>, Error> {
+    run_gfa_to_odgi(gfa_path, odgi_path, &CancellationToken::new(), None)
+}
+
+/// Converts a GFA file to an ODGI file, giving up after `timeout`.
+///
+/// This behaves like [`gfa_to_odgi`], but the `odgi build` process is killed if
+/// it has not finished within `timeout`, so pathological inputs cannot hang the
+/// caller indefinitely.
+///
+/// # Errors
+///
+/// In addition to the errors of [`gfa_to_odgi`], returns an [`Error`] for which
+/// [`Error::is_timeout`] is `true` if the timeout elapsed. Its message includes
+/// whatever `odgi` had written to stderr before it was killed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::gfa_to_odgi_with_timeout;
+/// use std::time::Duration;
+///
+/// match gfa_to_odgi_with_timeout("input.gfa", "output.odgi", Duration::from_secs(600)) {
+///     Ok(()) => println!("Converted."),
+///     Err(e) if e.is_timeout() => eprintln!("Gave up: {}", e),
+///     Err(e) => eprintln!("Conversion failed: {}", e),
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn gfa_to_odgi_with_timeout(gfa_path: &str, odgi_path: &str, timeout: Duration) -> Result<(), Error> {
+    run_gfa_to_odgi(gfa_path, odgi_path, &CancellationToken::new(), Some(timeout)).map(|_| ())
+}
+
+/// Converts a GFA file to an ODGI file, splitting off or removing runs of ambiguous bases.
+///
+/// The graph is built with `odgi build` as in [`gfa_to_odgi`] and then passed
+/// through [`Graph::handle_n_runs`] with `handling` and `min_run_len` before it
+/// is written to `odgi_path`. As with the other conversions, the output file is
+/// only replaced once everything has succeeded.
+///
+/// # Errors
+///
+/// Returns the same errors as [`gfa_to_odgi`], and an [`Error`] if the built
+/// graph cannot be loaded or the processed graph cannot be written.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{gfa_to_odgi_with_n_runs, NRunHandling};
+///
+/// // Remove scaffold gaps of 10 or more Ns while building the graph.
+/// gfa_to_odgi_with_n_runs("scaffolds.gfa", "scaffolds.odgi", NRunHandling::Drop, 10).unwrap();
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn gfa_to_odgi_with_n_runs(
+    gfa_path: &str,
+    odgi_path: &str,
+    handling: NRunHandling,
+    min_run_len: u64,
+) -> Result<(), Error> {
+    let built = tempfile::Builder::new()
+        .suffix(".og")
+        .tempfile()
+        .map_err(|e| Error::io("Failed to create temporary file", e))?;
+    let built_path = built
+        .path()
+        .to_str()
+        .ok_or_else(|| Error::other("Temporary file has a non-UTF-8 path"))?;
+    gfa_to_odgi(gfa_path, built_path)?;
+    let graph = Graph::load(built_path)?.handle_n_runs(handling, min_run_len)?;
+
+    let target = AtomicFile::new(odgi_path)?;
+    let target_path = target
+        .path()
+        .to_str()
+        .ok_or_else(|| Error::other(format!("Temporary file for '{}' has a non-UTF-8 path", odgi_path)))?;
+    graph
+        .save_to(target_path)
+        .map_err(|e| e.context(format!("Failed to write ODGI graph to '{}'", odgi_path)))?;
+    target.commit()
+}
+
+fn run_gfa_to_odgi(
+    gfa_path: &str,
+    odgi_path: &str,
+    token: &CancellationToken,
+    timeout: Option<Duration>,
+) -> Result<Vec<Diagnostic>, Error> {
+    let target = AtomicFile::new(odgi_path)?;
+    let output = run(
+        OdgiCommand::new("build").args(["-g", gfa_path, "-o"]).arg(target.path()),
+        token,
+        timeout,
+    )?;
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(status = %output.status, "odgi build finished");
 
     if output.status.success() {
-        Ok(())
+        target.commit()?;
+        Ok(output.diagnostics())
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(Error(format!(
-            "odgi build command failed for '{}': {}",
-            gfa_path, stderr
-        )))
+        Err(Error::conversion_failed(
+            format!("odgi build command failed for '{}'", gfa_path),
+            String::from_utf8_lossy(&output.stderr),
+        ))
     }
 }
 
 /// Converts an ODGI file to a GFA file by calling `odgi view`.
 ///
-/// This is the reverse operation of [`gfa_to_odgi`].
+/// This is the reverse operation of [`gfa_to_odgi`]. Like it, the output file
+/// is replaced atomically and only once the conversion has succeeded.
 ///
 /// # Arguments
 ///
@@ -107,25 +261,86 @@ pub fn gfa_to_odgi(gfa_path: &str, odgi_path: &str) -> Result<(), Error> {
 ///
 /// assert!(gfa_out_path.exists());
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
 pub fn odgi_to_gfa(odgi_path: &str, gfa_path: &str) -> Result<(), Error> {
-    let odgi_exe = "odgi";
-    let output = Command::new(odgi_exe)
-        .arg("view")
-        .arg("-i")
-        .arg(odgi_path)
-        .arg("-g") // Output in GFA format
-        .output()
-        .map_err(|e| Error(format!("Failed to execute odgi command: {}", e)))?;
+    odgi_to_gfa_cancellable(odgi_path, gfa_path, &CancellationToken::new())
+}
+
+/// Converts an ODGI file to a GFA file, aborting if `token` is cancelled.
+///
+/// This behaves like [`odgi_to_gfa`], but the `odgi view` process is killed as
+/// soon as the token is cancelled, and no output file is written.
+///
+/// # Errors
+///
+/// In addition to the errors of [`odgi_to_gfa`], returns an [`Error`] for which
+/// [`Error::is_cancelled`] is `true` if the conversion was cancelled.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(token), err))]
+pub fn odgi_to_gfa_cancellable(odgi_path: &str, gfa_path: &str, token: &CancellationToken) -> Result<(), Error> {
+    run_odgi_to_gfa(odgi_path, gfa_path, token, None).map(|_| ())
+}
+
+/// Converts an ODGI file to a GFA file and returns the diagnostics `odgi view` reported.
+///
+/// This behaves like [`odgi_to_gfa`], but on success it also returns the
+/// messages that `odgi` printed, parsed into [`Diagnostic`]s.
+///
+/// # Errors
+///
+/// Returns the same errors as [`odgi_to_gfa`].
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn odgi_to_gfa_with_diagnostics(odgi_path: &str, gfa_path: &str) -> Result<Vec<Diagnostic>, Error> {
+    run_odgi_to_gfa(odgi_path, gfa_path, &CancellationToken::new(), None)
+}
+
+/// Converts an ODGI file to a GFA file, giving up after `timeout`.
+///
+/// This behaves like [`odgi_to_gfa`], but the `odgi view` process is killed if
+/// it has not finished within `timeout`, and no output file is written.
+///
+/// # Errors
+///
+/// In addition to the errors of [`odgi_to_gfa`], returns an [`Error`] for which
+/// [`Error::is_timeout`] is `true` if the timeout elapsed. Its message includes
+/// whatever `odgi` had written to stderr before it was killed.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn odgi_to_gfa_with_timeout(odgi_path: &str, gfa_path: &str, timeout: Duration) -> Result<(), Error> {
+    run_odgi_to_gfa(odgi_path, gfa_path, &CancellationToken::new(), Some(timeout)).map(|_| ())
+}
+
+fn run_odgi_to_gfa(
+    odgi_path: &str,
+    gfa_path: &str,
+    token: &CancellationToken,
+    timeout: Option<Duration>,
+) -> Result<Vec<Diagnostic>, Error> {
+    // `-g` selects GFA output.
+    let output = run(OdgiCommand::new("view").args(["-i", odgi_path, "-g"]), token, timeout)?;
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(status = %output.status, "odgi view finished");
 
     if output.status.success() {
-        std::fs::write(gfa_path, output.stdout)
-            .map_err(|e| Error(format!("Failed to write GFA output to file: {}", e)))?;
-        Ok(())
+        let mut target = AtomicFile::new(gfa_path)?;
+        target
+            .file()
+            .write_all(&output.stdout)
+            .map_err(|e| Error::io("Failed to write GFA output to file", e))?;
+        target.commit()?;
+        Ok(output.diagnostics())
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(Error(format!(
-            "odgi view command failed for '{}': {}",
-            odgi_path, stderr
-        )))
+        Err(Error::conversion_failed(
+            format!("odgi view command failed for '{}'", odgi_path),
+            String::from_utf8_lossy(&output.stderr),
+        ))
     }
-}
\ No newline at end of file
+}
+
+/// Applies the optional cancellation token and timeout, then runs the command.
+fn run(command: &mut OdgiCommand, token: &CancellationToken, timeout: Option<Duration>) -> Result<OdgiOutput, Error> {
+    command.cancellation_token(token);
+    if let Some(timeout) = timeout {
+        command.timeout(timeout);
+    }
+    command.run()
+}
@@ -0,0 +1,130 @@
+// src/cursor.rs
+
+//! A movable position on a path.
+
+use std::sync::Arc;
+use super::graph::{Error, Graph, PathPosition};
+use super::path_index::PathIndex;
+
+/// A position on a path that can be moved step by step in either direction or
+/// to any offset, created by [`Graph::path_cursor`].
+///
+/// The cursor indexes the path's steps once. Moving to a nearby position only
+/// looks at the neighbouring steps, and seeking further away is a binary
+/// search, so scanning a path incrementally (for example with sliding windows)
+/// never walks the path from the start the way [`Graph::project`] does.
+///
+/// A cursor on an empty path has no position, and every move fails.
+#[derive(Debug, Clone)]
+pub struct PathCursor {
+    index: Arc<PathIndex>,
+    step: usize,
+    offset: u64,
+}
+
+impl PathCursor {
+    /// Returns the length of the path in base pairs.
+    pub fn path_length(&self) -> u64 {
+        self.index.length
+    }
+
+    /// Returns the number of steps of the path.
+    pub fn step_count(&self) -> usize {
+        self.index.steps.len()
+    }
+
+    /// Returns the index of the current step.
+    pub fn step_index(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the current position along the path.
+    pub fn path_offset(&self) -> u64 {
+        self.index.starts.get(self.step).map_or(0, |start| start + self.offset)
+    }
+
+    /// Returns the node, offset within the node, and orientation of the current
+    /// position, exactly as [`Graph::project`] reports them for its path offset.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn position(&self) -> Option<PathPosition> {
+        let step = self.index.steps.get(self.step)?;
+        let offset = if step.is_forward { self.offset } else { self.index.lengths[self.step] - 1 - self.offset };
+        Some(PathPosition { node_id: step.node_id, offset, is_forward: step.is_forward })
+    }
+
+    /// Moves to position `pos` along the path.
+    ///
+    /// Returns `false` and leaves the cursor in place if `pos` is past the end of the path.
+    pub fn seek(&mut self, pos: u64) -> bool {
+        if pos >= self.index.length {
+            return false;
+        }
+        let covers = |step: usize| {
+            let start = self.index.starts[step];
+            pos >= start && pos < start + self.index.lengths[step]
+        };
+        // Try the current and adjacent steps before falling back to a binary search.
+        let step = [Some(self.step), self.step.checked_add(1), self.step.checked_sub(1)]
+            .into_iter()
+            .flatten()
+            .find(|&step| step < self.index.steps.len() && covers(step));
+        self.step = match step {
+            Some(step) => step,
+            None => self.index.step_at(pos).expect("position is within the path"),
+        };
+        self.offset = pos - self.index.starts[self.step];
+        true
+    }
+
+    /// Moves to the first base of the next step.
+    ///
+    /// Returns `false` and leaves the cursor in place on the last step.
+    pub fn next_step(&mut self) -> bool {
+        if self.step + 1 >= self.index.steps.len() {
+            return false;
+        }
+        self.step += 1;
+        self.offset = 0;
+        true
+    }
+
+    /// Moves to the first base of the previous step.
+    ///
+    /// Returns `false` and leaves the cursor in place on the first step.
+    pub fn prev_step(&mut self) -> bool {
+        if self.step == 0 {
+            return false;
+        }
+        self.step -= 1;
+        self.offset = 0;
+        true
+    }
+}
+
+impl Graph {
+    /// Creates a [`PathCursor`] at the start of a path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut cursor = graph.path_cursor("human_chr1").unwrap();
+    /// let mut pos = 0;
+    /// while cursor.seek(pos) {
+    ///     let position = cursor.position().unwrap();
+    ///     println!("{}\t{}\t{}", pos, position.node_id, position.offset);
+    ///     pos += 1000;
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn path_cursor(&self, path_name: &str) -> Result<PathCursor, Error> {
+        let index = PathIndex::new(self, path_name)?.ok_or_else(|| Error::path_not_found(path_name))?;
+        Ok(PathCursor { index, step: 0, offset: 0 })
+    }
+}
@@ -0,0 +1,197 @@
+// src/depth.rs
+
+//! Path depth (coverage) tracks along a reference path.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::Write;
+use super::cancel::CancellationToken;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+use super::region::GraphRegion;
+
+/// Options controlling [`Graph::write_depth_bedgraph`].
+#[derive(Debug, Clone, Default)]
+pub struct DepthOptions {
+    /// Report the mean depth over fixed-size windows of this many base pairs.
+    ///
+    /// When `None`, depth is reported per base, with adjacent bases of equal
+    /// depth merged into a single bedGraph record.
+    pub window: Option<u64>,
+    /// Count each path at most once per node instead of counting every step,
+    /// so repeated visits by the same path do not inflate the depth.
+    pub unique_paths: bool,
+}
+
+impl Graph {
+    /// Writes the depth of all paths along `path_name` as a bedGraph track.
+    ///
+    /// The depth at a position is the number of path steps (across all paths,
+    /// including the reference itself) on the node that the reference path
+    /// visits at that position. The output can be loaded directly into genome
+    /// browsers such as IGV or JBrowse using the reference path as the chromosome.
+    ///
+    /// # Arguments
+    ///
+    /// * `path_name` - The reference path to report depth along.
+    /// * `writer` - The destination for the bedGraph records.
+    /// * `options` - Resolution and counting options, see [`DepthOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist, if the window size is
+    /// `0`, or if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{DepthOptions, Graph};
+    ///
+    /// let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut out = std::fs::File::create("chr1.depth.bedgraph").unwrap();
+    /// let options = DepthOptions { window: Some(1000), ..Default::default() };
+    /// graph.write_depth_bedgraph("grch38#chr1", &mut out, &options).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer), err))]
+    pub fn write_depth_bedgraph<W: Write>(
+        &self,
+        path_name: &str,
+        writer: &mut W,
+        options: &DepthOptions,
+    ) -> Result<(), Error> {
+        self.write_depth_bedgraph_cancellable(path_name, writer, options, &CancellationToken::new())
+    }
+
+    /// Writes the depth of all paths along `path_name` as a bedGraph track, aborting if `token` is cancelled.
+    ///
+    /// This behaves like [`Graph::write_depth_bedgraph`], but checks the token
+    /// periodically while computing depths and writing records.
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of [`Graph::write_depth_bedgraph`], returns an
+    /// [`Error`] for which [`Error::is_cancelled`] is `true` if the export was cancelled.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer, token), err))]
+    pub fn write_depth_bedgraph_cancellable<W: Write>(
+        &self,
+        path_name: &str,
+        writer: &mut W,
+        options: &DepthOptions,
+        token: &CancellationToken,
+    ) -> Result<(), Error> {
+        if options.window == Some(0) {
+            return Err(Error::invalid_argument("Depth window size must be greater than 0"));
+        }
+        let index = PathIndex::new(self, path_name)?
+            .ok_or_else(|| Error::path_not_found(path_name))?;
+
+        // Depth of each step's node, looked up once per distinct node.
+        let mut cache: HashMap<u64, u64> = HashMap::new();
+        let mut depths = Vec::with_capacity(index.steps.len());
+        for step in &index.steps {
+            let depth = match cache.entry(step.node_id) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    token.check()?;
+                    *entry.insert(self.node_depth_for(step.node_id, options)?)
+                }
+            };
+            depths.push(depth);
+        }
+
+        let io_err = |e: std::io::Error| Error::io("Failed to write bedGraph output", e);
+        match options.window {
+            None => {
+                let mut run: Option<(u64, u64, u64)> = None;
+                for ((&start, &len), &depth) in index.starts.iter().zip(&index.lengths).zip(&depths) {
+                    if len == 0 {
+                        continue;
+                    }
+                    token.check()?;
+                    run = match run {
+                        Some((run_start, _, run_depth)) if run_depth == depth => Some((run_start, start + len, depth)),
+                        Some((run_start, run_end, run_depth)) => {
+                            writeln!(writer, "{}\t{}\t{}\t{}", path_name, run_start, run_end, run_depth).map_err(io_err)?;
+                            Some((start, start + len, depth))
+                        }
+                        None => Some((start, start + len, depth)),
+                    };
+                }
+                if let Some((run_start, run_end, run_depth)) = run {
+                    writeln!(writer, "{}\t{}\t{}\t{}", path_name, run_start, run_end, run_depth).map_err(io_err)?;
+                }
+            }
+            Some(window) => {
+                let mut step = 0;
+                let mut window_start = 0;
+                while window_start < index.length {
+                    token.check()?;
+                    let window_end = (window_start + window).min(index.length);
+                    while index.starts[step] + index.lengths[step] <= window_start {
+                        step += 1;
+                    }
+                    let mut total = 0;
+                    let mut i = step;
+                    while i < depths.len() && index.starts[i] < window_end {
+                        let overlap_start = index.starts[i].max(window_start);
+                        let overlap_end = (index.starts[i] + index.lengths[i]).min(window_end);
+                        total += depths[i] * (overlap_end - overlap_start);
+                        i += 1;
+                    }
+                    let mean = total as f64 / (window_end - window_start) as f64;
+                    writeln!(writer, "{}\t{}\t{}\t{}", path_name, window_start, window_end, mean).map_err(io_err)?;
+                    window_start = window_end;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the mean depth of all paths over a region of a reference path.
+    ///
+    /// Depth is counted as for [`Graph::write_depth_bedgraph`]; `options.window`
+    /// is ignored. The end of the region is clamped to the end of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist or the region is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{DepthOptions, Graph};
+    ///
+    /// let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let depth = graph.region_depth(&"grch38#chr1:10,001-20,000".parse().unwrap(), &DepthOptions::default()).unwrap();
+    /// println!("Mean depth: {:.1}", depth);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn region_depth(&self, region: &GraphRegion, options: &DepthOptions) -> Result<f64, Error> {
+        let (index, start, end) = region.index(self)?;
+        self.mean_depth(&index, start, end, options)
+    }
+
+    /// Returns the mean depth over `start..end` of an indexed path, which must be a non-empty range within it.
+    pub(crate) fn mean_depth(&self, index: &PathIndex, start: u64, end: u64, options: &DepthOptions) -> Result<f64, Error> {
+        let mut total = 0;
+        let mut step = index.step_at(start).expect("the range starts within the path");
+        while step < index.steps.len() && index.starts[step] < end {
+            let overlap_start = index.starts[step].max(start);
+            let overlap_end = (index.starts[step] + index.lengths[step]).min(end);
+            total += self.node_depth_for(index.steps[step].node_id, options)? * (overlap_end - overlap_start);
+            step += 1;
+        }
+        Ok(total as f64 / (end - start) as f64)
+    }
+
+    fn node_depth_for(&self, node_id: u64, options: &DepthOptions) -> Result<u64, Error> {
+        if options.unique_paths {
+            let mut paths = self.get_paths_on_node(node_id)?;
+            paths.sort();
+            paths.dedup();
+            Ok(paths.len() as u64)
+        } else {
+            self.node_depth(node_id)
+        }
+    }
+}
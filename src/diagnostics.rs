@@ -0,0 +1,101 @@
+// src/diagnostics.rs
+
+//! Structured diagnostics parsed from the stderr of the bundled `odgi` executable.
+
+/// How severe a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticLevel {
+    /// A progress or status message.
+    Info,
+    /// Something was ignored or adjusted, but the command carried on.
+    Warning,
+    /// Something went wrong; usually accompanied by a non-zero exit status.
+    Error,
+}
+
+/// What a [`Diagnostic`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// An optional GFA tag that `odgi` does not understand.
+    UnknownTag,
+    /// A GFA record or graph element that was skipped, dropped, or ignored.
+    DroppedElement,
+    /// A message about file format or program versions.
+    Version,
+    /// Anything else.
+    Other,
+}
+
+/// A single message reported by an `odgi` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How severe the message is.
+    pub level: DiagnosticLevel,
+    /// What the message is about.
+    pub kind: DiagnosticKind,
+    /// The component that emitted the message, such as `odgi::gfa_to_handle`,
+    /// taken from a leading `[...]` prefix if present.
+    pub source: Option<String>,
+    /// The message text, without the source prefix.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Parses every non-empty line of an `odgi` stderr stream into a diagnostic.
+    ///
+    /// `odgi` does not emit machine-readable diagnostics, so the level and kind
+    /// are inferred from keywords in each line. Lines that cannot be classified
+    /// are kept with [`DiagnosticKind::Other`] so nothing is lost.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use odgi_ffi::{Diagnostic, DiagnosticKind, DiagnosticLevel};
+    ///
+    /// let stderr = "[odgi::build] warning: unknown tag XY:Z:foo ignored\n";
+    /// let diagnostics = Diagnostic::parse_all(stderr);
+    /// assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+    /// assert_eq!(diagnostics[0].kind, DiagnosticKind::UnknownTag);
+    /// assert_eq!(diagnostics[0].source.as_deref(), Some("odgi::build"));
+    /// ```
+    pub fn parse_all(stderr: &str) -> Vec<Diagnostic> {
+        stderr
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(Diagnostic::parse_line)
+            .collect()
+    }
+
+    fn parse_line(line: &str) -> Diagnostic {
+        let (source, message) = match line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            Some((source, message)) => (Some(source.to_string()), message.trim()),
+            None => (None, line),
+        };
+
+        let lower = message.to_ascii_lowercase();
+        let level = if lower.contains("error") || lower.contains("fail") || lower.contains("abort") {
+            DiagnosticLevel::Error
+        } else if lower.contains("warn") || lower.contains("ignor") || lower.contains("skip") {
+            DiagnosticLevel::Warning
+        } else {
+            DiagnosticLevel::Info
+        };
+        let kind = if lower.contains("tag") && (lower.contains("unknown") || lower.contains("unsupported") || lower.contains("ignor")) {
+            DiagnosticKind::UnknownTag
+        } else if ["skip", "ignor", "drop", "discard", "remov"].iter().any(|k| lower.contains(k)) {
+            DiagnosticKind::DroppedElement
+        } else if lower.contains("version") {
+            DiagnosticKind::Version
+        } else {
+            DiagnosticKind::Other
+        };
+
+        Diagnostic {
+            level,
+            kind,
+            source,
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// src/dict.rs
+
+//! SAM sequence dictionaries for reference paths.
+
+use std::io::Write;
+use super::graph::{Error, Graph};
+use super::seq::reverse_complement;
+
+impl Graph {
+    /// Writes a SAM sequence dictionary with one `@SQ` line per reference path.
+    ///
+    /// The dictionary starts with an `@HD` header line, followed by the name
+    /// (`SN`), length (`LN`), and MD5 checksum (`M5`) of each path in
+    /// `reference_paths`, in the given order. The checksum is computed over the
+    /// upper-cased path sequence as the SAM specification requires, so the
+    /// output can be used as a `.dict` file or as the header of BAM and VCF
+    /// files derived from the graph's references.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if any of the paths does not exist or if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let mut out = std::fs::File::create("grch38.dict").unwrap();
+    /// graph.write_sequence_dictionary(&mut out, &["GRCh38#0#chr1", "GRCh38#0#chr2"]).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer), err))]
+    pub fn write_sequence_dictionary<W: Write>(&self, writer: &mut W, reference_paths: &[&str]) -> Result<(), Error> {
+        let path_names = self.get_path_names()?;
+        if let Some(missing) = reference_paths.iter().find(|&&p| !path_names.iter().any(|n| n == p)) {
+            return Err(Error::path_not_found(*missing));
+        }
+
+        let io_err = |e: std::io::Error| Error::io("Failed to write sequence dictionary", e);
+        writeln!(writer, "@HD\tVN:1.6").map_err(io_err)?;
+        for &path_name in reference_paths {
+            // The checksum is computed step by step, so the path is never held in memory as a whole.
+            let mut md5 = md5::Context::new();
+            let mut length = 0;
+            for step in self.raw_path_steps(path_name)? {
+                let mut sequence = self.get_node_sequence(step.node_id)?;
+                if !step.is_forward {
+                    sequence = reverse_complement(&sequence);
+                }
+                sequence.make_ascii_uppercase();
+                md5.consume(sequence.as_bytes());
+                length += sequence.len();
+            }
+            writeln!(writer, "@SQ\tSN:{}\tLN:{}\tM5:{:x}", path_name, length, md5.compute()).map_err(io_err)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+// src/edge_key.rs
+
+//! A hashable, canonicalizable key for bidirected edges.
+
+use super::edge_usage::EdgeUsage;
+use super::ffi;
+use super::graph::Edge;
+
+/// An edge between two oriented nodes, usable as a map key.
+///
+/// In a bidirected graph, the edge `A+ -> B+` read from the other strand is
+/// `B- -> A-`. Keys built with [`EdgeKey::new`] keep the direction they were
+/// given, so these two compare unequal. Keys built with
+/// [`EdgeKey::canonical`], or normalized with [`EdgeKey::canonicalized`], are
+/// the same for both readings of an edge, which is what counting or
+/// deduplicating edges usually needs.
+///
+/// Keys order by `from_node`, `from_orientation`, `to_node`, then
+/// `to_orientation`, and the canonical form is the smaller of the two readings.
+///
+/// # Examples
+///
+/// ```rust
+/// use odgi_ffi::EdgeKey;
+///
+/// let forward = EdgeKey::new(1, true, 2, true);
+/// let backward = EdgeKey::new(2, false, 1, false);
+/// assert_ne!(forward, backward);
+/// assert_eq!(forward.canonicalized(), backward.canonicalized());
+/// assert_eq!(backward.canonicalized(), EdgeKey::canonical(1, true, 2, true));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeKey {
+    /// The ID of the node where the edge begins.
+    pub from_node: u64,
+    /// The orientation of the "from" node's handle in this edge.
+    pub from_orientation: bool,
+    /// The ID of the node where the edge ends.
+    pub to_node: u64,
+    /// The orientation of the "to" node's handle in this edge.
+    pub to_orientation: bool,
+}
+
+impl EdgeKey {
+    /// Creates a key for the edge exactly as given.
+    pub fn new(from_node: u64, from_orientation: bool, to_node: u64, to_orientation: bool) -> Self {
+        EdgeKey { from_node, from_orientation, to_node, to_orientation }
+    }
+
+    /// Creates the canonical key for the edge, shared by both of its readings.
+    pub fn canonical(from_node: u64, from_orientation: bool, to_node: u64, to_orientation: bool) -> Self {
+        Self::new(from_node, from_orientation, to_node, to_orientation).canonicalized()
+    }
+
+    /// Creates a key from the edges returned by [`Graph::get_successors`](crate::Graph::get_successors)
+    /// for `from_node`.
+    pub fn from_successor(from_node: u64, edge: &Edge) -> Self {
+        Self::new(from_node, edge.from_orientation, edge.to_node, edge.to_orientation)
+    }
+
+    /// Returns the same edge read from the other strand.
+    pub fn flipped(&self) -> Self {
+        Self::new(self.to_node, !self.to_orientation, self.from_node, !self.from_orientation)
+    }
+
+    /// Returns the canonical form of the key.
+    pub fn canonicalized(self) -> Self {
+        self.min(self.flipped())
+    }
+
+    /// Returns whether the key is in canonical form.
+    pub fn is_canonical(&self) -> bool {
+        *self <= self.flipped()
+    }
+
+    /// Returns the key of the edge between two consecutive path steps.
+    pub(crate) fn between(from: &ffi::PathStep, to: &ffi::PathStep) -> Self {
+        Self::new(from.node_id, from.is_forward, to.node_id, to.is_forward)
+    }
+}
+
+impl From<&EdgeUsage> for EdgeKey {
+    fn from(edge: &EdgeUsage) -> Self {
+        Self::new(edge.from_node, edge.from_orientation, edge.to_node, edge.to_orientation)
+    }
+}
+
+impl From<&ffi::GraphEdge> for EdgeKey {
+    fn from(edge: &ffi::GraphEdge) -> Self {
+        Self::new(edge.from_node, edge.from_orientation, edge.to_node, edge.to_orientation)
+    }
+}
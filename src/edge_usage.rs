@@ -0,0 +1,76 @@
+// src/edge_usage.rs
+
+//! Counting how often paths traverse each edge.
+
+use std::collections::HashMap;
+use super::edge_key::EdgeKey;
+use super::graph::{Error, Graph};
+
+/// An edge of the graph together with the number of path traversals it supports.
+///
+/// Returned by [`Graph::edge_usage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeUsage {
+    /// The ID of the node where the edge begins.
+    pub from_node: u64,
+    /// The orientation of the "from" node's handle in this edge.
+    pub from_orientation: bool,
+    /// The ID of the node where the edge ends.
+    pub to_node: u64,
+    /// The orientation of the "to" node's handle in this edge.
+    pub to_orientation: bool,
+    /// The number of times any path steps across the edge, in either direction.
+    pub traversals: u64,
+}
+
+impl Graph {
+    /// Counts the path traversals of every edge in the graph.
+    ///
+    /// Each pair of consecutive steps on a path traverses one edge; traversals
+    /// in either direction count toward the same edge. Edges that no path uses
+    /// are included with a count of `0`, which makes them easy to find for
+    /// pruning. The edges are sorted by their endpoint node IDs.
+    ///
+    /// To find which paths use a particular edge, call [`Graph::get_paths_on_edge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the edges or paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let unsupported = graph.edge_usage().unwrap().into_iter().filter(|e| e.traversals == 0).count();
+    /// println!("{} edges are not used by any path", unsupported);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn edge_usage(&self) -> Result<Vec<EdgeUsage>, Error> {
+        let mut counts: HashMap<EdgeKey, u64> = HashMap::new();
+        for path_name in self.get_path_names()? {
+            let steps = self.raw_path_steps(&path_name)?;
+            for pair in steps.windows(2) {
+                let key = EdgeKey::between(&pair[0], &pair[1]).canonicalized();
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+
+        let mut usage: Vec<EdgeUsage> = self
+            .raw_edges()?
+            .into_iter()
+            .map(|edge| {
+                let key = EdgeKey::from(&edge).canonicalized();
+                EdgeUsage {
+                    from_node: edge.from_node,
+                    from_orientation: edge.from_orientation,
+                    to_node: edge.to_node,
+                    to_orientation: edge.to_orientation,
+                    traversals: counts.get(&key).copied().unwrap_or(0),
+                }
+            })
+            .collect();
+        usage.sort_by_key(|e| (e.from_node, e.to_node, e.from_orientation, e.to_orientation));
+        Ok(usage)
+    }
+}
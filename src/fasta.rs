@@ -0,0 +1,148 @@
+// src/fasta.rs
+
+//! FASTA export of path intervals and node sequences.
+
+use std::io::{BufRead, Write};
+use super::bed_batch::BedResults;
+use super::cancel::CancellationToken;
+use super::graph::{Error, Graph};
+use super::seq::reverse_complement;
+
+/// Options controlling [`Graph::write_nodes_fasta`].
+#[derive(Debug, Clone, Default)]
+pub struct NodeFastaOptions {
+    /// Add the node length to each record description as `len=<bp>`.
+    pub include_length: bool,
+    /// Add the number of path steps on the node to each record description as `depth=<steps>`.
+    pub include_depth: bool,
+}
+
+impl Graph {
+    /// Writes the sequence of every BED interval as a FASTA record.
+    ///
+    /// The first BED column must name an embedded path, and the interval
+    /// coordinates are 0-based, half-open offsets along that path. Records are
+    /// named after the BED name column when present, and `path:start-end`
+    /// otherwise. Intervals on the `-` strand are reverse-complemented.
+    ///
+    /// The intervals are read as by [`Graph::sequences_from_bed`], so each path
+    /// is indexed once and large BED files over the same few paths are
+    /// processed without rescanning the paths for every interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `bed_reader` - A reader over BED text (at least 3 columns; name in column 4, strand in column 6).
+    /// * `fasta_writer` - The destination for the FASTA records.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of FASTA records written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a BED line is malformed, names a path that does not
+    /// exist, or extends past the end of its path, or if reading or writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let bed = std::io::BufReader::new(std::fs::File::open("genes.bed").unwrap());
+    /// let mut fasta = std::fs::File::create("genes.fa").unwrap();
+    /// let written = graph.extract_fasta_from_bed(bed, &mut fasta).unwrap();
+    /// println!("Extracted {} sequences", written);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, bed_reader, fasta_writer), err))]
+    pub fn extract_fasta_from_bed<R: BufRead, W: Write>(
+        &self,
+        bed_reader: R,
+        fasta_writer: &mut W,
+    ) -> Result<usize, Error> {
+        self.extract_fasta_from_bed_cancellable(bed_reader, fasta_writer, &CancellationToken::new())
+    }
+
+    /// Writes the sequence of every BED interval as a FASTA record, aborting if `token` is cancelled.
+    ///
+    /// This behaves like [`Graph::extract_fasta_from_bed`], but checks the token
+    /// before each interval. Records written before cancellation are left in
+    /// `fasta_writer`.
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of [`Graph::extract_fasta_from_bed`], returns an
+    /// [`Error`] for which [`Error::is_cancelled`] is `true` if the extraction was cancelled.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, bed_reader, fasta_writer, token), err))]
+    pub fn extract_fasta_from_bed_cancellable<R: BufRead, W: Write>(
+        &self,
+        bed_reader: R,
+        fasta_writer: &mut W,
+        token: &CancellationToken,
+    ) -> Result<usize, Error> {
+        let mut records = BedResults::new(self, bed_reader, |graph, record, index| {
+            let mut sequence = index.sequence(graph, record.start, record.end)?;
+            if record.is_forward == Some(false) {
+                sequence = reverse_complement(&sequence);
+            }
+            let name = record
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{}:{}-{}", record.chrom, record.start, record.end));
+            Ok((name, sequence))
+        });
+        let mut written = 0;
+        loop {
+            token.check()?;
+            let Some(result) = records.next() else {
+                break;
+            };
+            let (_, (name, sequence)) = result?;
+            writeln!(fasta_writer, ">{}\n{}", name, sequence)
+                .map_err(|e| Error::io("Failed to write FASTA output", e))?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Writes the sequence of every node as a FASTA record named after its node ID.
+    ///
+    /// Records are written in ascending node ID order, with each node's forward
+    /// strand sequence on a single line. This makes the graph's segment set
+    /// directly usable by external k-mer counters and repeat annotators.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The destination for the FASTA records.
+    /// * `options` - What to include in the record descriptions, see [`NodeFastaOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Graph, NodeFastaOptions};
+    ///
+    /// let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut out = std::fs::File::create("nodes.fa").unwrap();
+    /// let options = NodeFastaOptions { include_length: true, include_depth: true };
+    /// graph.write_nodes_fasta(&mut out, &options).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer), err))]
+    pub fn write_nodes_fasta<W: Write>(&self, writer: &mut W, options: &NodeFastaOptions) -> Result<(), Error> {
+        let io_err = |e: std::io::Error| Error::io("Failed to write FASTA output", e);
+        for node_id in self.node_ids()? {
+            let sequence = self.get_node_sequence(node_id)?;
+            write!(writer, ">{}", node_id).map_err(io_err)?;
+            if options.include_length {
+                write!(writer, " len={}", sequence.len()).map_err(io_err)?;
+            }
+            if options.include_depth {
+                write!(writer, " depth={}", self.node_depth(node_id)?).map_err(io_err)?;
+            }
+            writeln!(writer, "\n{}", sequence).map_err(io_err)?;
+        }
+        Ok(())
+    }
+}
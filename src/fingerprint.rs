@@ -0,0 +1,138 @@
+// src/fingerprint.rs
+
+//! Content fingerprints that identify a graph independently of its node IDs.
+
+use std::collections::HashMap;
+use super::graph::{Error, Graph};
+use super::seq::reverse_complement;
+
+/// A 128-bit FNV-1a hasher.
+///
+/// The standard library's hashers are not guaranteed to be stable across Rust
+/// releases, but fingerprints are meant to be stored and compared across
+/// builds, so a fixed, well-known algorithm is used instead.
+struct Fnv128(u128);
+
+impl Fnv128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    fn new() -> Self {
+        Fnv128(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u128;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Writes a length-prefixed field, so that adjacent fields cannot run into each other.
+    fn write_field(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn finish(&self) -> u128 {
+        self.0
+    }
+}
+
+fn hash_of(fields: &[&[u8]]) -> u128 {
+    let mut hasher = Fnv128::new();
+    for field in fields {
+        hasher.write_field(field);
+    }
+    hasher.finish()
+}
+
+/// Hashes an unordered collection by hashing its sorted element hashes.
+fn hash_multiset(tag: &[u8], mut hashes: Vec<u128>) -> u128 {
+    hashes.sort_unstable();
+    let mut hasher = Fnv128::new();
+    hasher.write_field(tag);
+    for hash in hashes {
+        hasher.write(&hash.to_le_bytes());
+    }
+    hasher.finish()
+}
+
+impl Graph {
+    /// Computes a stable 128-bit fingerprint of the graph's content.
+    ///
+    /// The fingerprint covers node sequences, the edges between them, and the
+    /// name and spelling (step by step, including orientations) of every path.
+    /// Node IDs and the order of nodes, edges, and paths do not contribute, so
+    /// two files describing the same graph with different ID assignments or
+    /// sort orders get the same fingerprint. The hash algorithm is fixed, so
+    /// fingerprints can be stored and compared across runs, machines, and
+    /// versions of this crate.
+    ///
+    /// Nodes are identified by their sequence, so graphs that differ only in
+    /// how edges connect several nodes with identical sequences may collide.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let a = Graph::load("release_1.odgi").unwrap();
+    /// let b = Graph::load("release_1_resorted.odgi").unwrap();
+    /// if a.fingerprint().unwrap() == b.fingerprint().unwrap() {
+    ///     println!("Same graph: {:032x}", a.fingerprint().unwrap());
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn fingerprint(&self) -> Result<u128, Error> {
+        let sequences = self
+            .node_ids()?
+            .into_iter()
+            .map(|id| Ok((id, self.get_node_sequence(id)?)))
+            .collect::<Result<HashMap<u64, String>, Error>>()?;
+        let oriented = |node_id: u64, is_forward: bool| -> String {
+            let sequence = &sequences[&node_id];
+            if is_forward { sequence.clone() } else { reverse_complement(sequence) }
+        };
+
+        let nodes = hash_multiset(
+            b"nodes",
+            sequences.values().map(|s| hash_of(&[s.as_bytes()])).collect(),
+        );
+
+        let edges = hash_multiset(
+            b"edges",
+            self.raw_edges()?
+                .iter()
+                .map(|e| {
+                    let from = oriented(e.from_node, e.from_orientation);
+                    let to = oriented(e.to_node, e.to_orientation);
+                    // An edge reads the same as its reverse-strand equivalent.
+                    let forward = hash_of(&[from.as_bytes(), to.as_bytes()]);
+                    let reverse = hash_of(&[reverse_complement(&to).as_bytes(), reverse_complement(&from).as_bytes()]);
+                    forward.min(reverse)
+                })
+                .collect(),
+        );
+
+        let paths = hash_multiset(
+            b"paths",
+            self.get_path_names()?
+                .iter()
+                .map(|name| {
+                    let mut hasher = Fnv128::new();
+                    hasher.write_field(name.as_bytes());
+                    for step in self.raw_path_steps(name)? {
+                        hasher.write_field(oriented(step.node_id, step.is_forward).as_bytes());
+                    }
+                    Ok(hasher.finish())
+                })
+                .collect::<Result<_, Error>>()?,
+        );
+
+        Ok(hash_of(&[&nodes.to_le_bytes(), &edges.to_le_bytes(), &paths.to_le_bytes()]))
+    }
+}
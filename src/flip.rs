@@ -0,0 +1,109 @@
+// src/flip.rs
+
+//! Re-embedding paths that run against the graph's orientation.
+
+use std::collections::HashSet;
+use super::graph::{Error, Graph};
+
+/// Options controlling [`Graph::flip_paths`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlipOptions {
+    /// Flip a path when more than this fraction of its sequence traverses nodes in reverse.
+    pub min_reverse_fraction: f64,
+    /// Paths that are never flipped, such as the reference.
+    pub keep: Vec<String>,
+    /// Appended to the names of flipped paths, so that they stay
+    /// distinguishable from paths that were embedded forward.
+    pub suffix: Option<String>,
+}
+
+impl Default for FlipOptions {
+    /// Flip paths that are mostly reverse, as `odgi flip` does, adding its `_inv` suffix.
+    fn default() -> Self {
+        FlipOptions { min_reverse_fraction: 0.5, keep: Vec::new(), suffix: Some("_inv".to_string()) }
+    }
+}
+
+impl Graph {
+    /// Re-embeds paths that mostly traverse nodes in reverse on the opposite strand.
+    ///
+    /// For each path, the fraction of its sequence on reverse-oriented steps is
+    /// weighed against [`FlipOptions::min_reverse_fraction`]. Paths above it
+    /// are replaced by their reverse complement walk, which visits the same
+    /// nodes in reverse order and opposite orientation, so the path spells the
+    /// reverse complement of its former sequence through the same edges. This
+    /// matches `odgi flip` and makes visualizations and strand statistics
+    /// comparable across assemblies that were embedded on different strands.
+    ///
+    /// Flipped paths are renamed with [`FlipOptions::suffix`] and move to the
+    /// end of the path list. Returns the original names of the flipped paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a path in [`FlipOptions::keep`] does not exist,
+    /// or if the new name of a flipped path is already taken. The graph is left
+    /// untouched in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{FlipOptions, Graph};
+    ///
+    /// let mut graph = Graph::load("pangenome.odgi").unwrap();
+    /// let options = FlipOptions { keep: vec!["GRCh38#0#chr1".to_string()], ..Default::default() };
+    /// for name in graph.flip_paths(&options).unwrap() {
+    ///     println!("Flipped {}", name);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn flip_paths(&mut self, options: &FlipOptions) -> Result<Vec<String>, Error> {
+        let path_names = self.get_path_names()?;
+        for kept in &options.keep {
+            if !path_names.contains(kept) {
+                return Err(Error::path_not_found(kept));
+            }
+        }
+
+        let mut flipped = Vec::new();
+        for path_name in &path_names {
+            if options.keep.contains(path_name) {
+                continue;
+            }
+            let (mut total, mut reverse) = (0, 0);
+            for step in self.raw_path_steps(path_name)? {
+                let len = self.get_node_len(step.node_id)?;
+                total += len;
+                if !step.is_forward {
+                    reverse += len;
+                }
+            }
+            if total > 0 && reverse as f64 > options.min_reverse_fraction * total as f64 {
+                flipped.push(path_name.clone());
+            }
+        }
+
+        let suffix = options.suffix.as_deref().unwrap_or("");
+        let mut taken: HashSet<&String> = path_names.iter().collect();
+        for path_name in &flipped {
+            taken.remove(path_name);
+        }
+        let mut new_names = Vec::with_capacity(flipped.len());
+        for path_name in &flipped {
+            let new_name = format!("{}{}", path_name, suffix);
+            if taken.contains(&new_name) || new_names.contains(&new_name) {
+                return Err(Error::path_exists(new_name));
+            }
+            new_names.push(new_name);
+        }
+
+        for (path_name, new_name) in flipped.iter().zip(&new_names) {
+            let steps = self.raw_path_steps(path_name)?;
+            self.remove_path(path_name)?;
+            self.add_path(new_name)?;
+            for step in steps.iter().rev() {
+                self.append_step(new_name, step.node_id, !step.is_forward)?;
+            }
+        }
+        Ok(flipped)
+    }
+}
@@ -0,0 +1,312 @@
+// src/gaf.rs
+
+//! Reading GAF alignments and projecting them onto reference paths.
+//!
+//! GAF is the graph counterpart of PAF, written by graph mappers such as
+//! GraphAligner, minigraph, and `vg giraffe`. Only alignments to node walks
+//! (`>1<2>3`) are supported; walks given in stable coordinates are rejected.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use super::alignment::{parse_cigar, GraphAlignment};
+use super::graph::{Error, Graph};
+use super::path_alignment::{cigar, push_op};
+use super::path_index::PathIndex;
+
+/// An iterator over the records of a GAF file, created by [`records`].
+pub struct GafRecords<R> {
+    lines: std::io::Lines<R>,
+    line_no: usize,
+}
+
+/// Returns an iterator over the alignments in a GAF file.
+///
+/// Blank lines and lines starting with `#` are skipped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::gaf;
+/// use std::io::BufReader;
+///
+/// let file = std::fs::File::open("reads.gaf").unwrap();
+/// for record in gaf::records(BufReader::new(file)) {
+///     let alignment = record.unwrap();
+///     println!("{} aligns to {} nodes", alignment.query_name, alignment.walk.len());
+/// }
+/// ```
+pub fn records<R: BufRead>(reader: R) -> GafRecords<R> {
+    GafRecords { lines: reader.lines(), line_no: 0 }
+}
+
+impl<R: BufRead> Iterator for GafRecords<R> {
+    type Item = Result<GraphAlignment, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(Error::io("Failed to read GAF input", e))),
+            };
+            self.line_no += 1;
+            match parse_gaf_line(&line, self.line_no) {
+                Ok(Some(alignment)) => return Some(Ok(alignment)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Parses one GAF line. `line_no` is 1-based and only used for error messages.
+pub(crate) fn parse_gaf_line(line: &str, line_no: usize) -> Result<Option<GraphAlignment>, Error> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let invalid = |reason: &str| Error::invalid_format(format!("Invalid GAF line {}: {}", line_no, reason));
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 12 {
+        return Err(invalid("expected at least 12 columns"));
+    }
+    let number = |index: usize| {
+        fields[index]
+            .parse::<u64>()
+            .map_err(|_| invalid(&format!("bad number '{}'", fields[index])))
+    };
+
+    let mut walk = Vec::new();
+    let mut rest = fields[5];
+    while let Some(orientation) = rest.chars().next() {
+        if orientation != '>' && orientation != '<' {
+            return Err(invalid("only node walks such as '>1<2' are supported"));
+        }
+        let end = rest[1..].find(['>', '<']).map_or(rest.len(), |i| i + 1);
+        let node_id = rest[1..end]
+            .parse::<u64>()
+            .map_err(|_| invalid(&format!("bad node ID '{}'", &rest[1..end])))?;
+        walk.push((node_id, orientation == '>'));
+        rest = &rest[end..];
+    }
+    if walk.is_empty() {
+        return Err(invalid("empty walk"));
+    }
+
+    let cigar = match fields[12..].iter().find_map(|tag| tag.strip_prefix("cg:Z:")) {
+        Some(cg) => Some(parse_cigar(cg).ok_or_else(|| invalid(&format!("bad CIGAR '{}'", cg)))?),
+        None => None,
+    };
+
+    Ok(Some(GraphAlignment {
+        query_name: fields[0].to_string(),
+        query_length: number(1)?,
+        query_start: number(2)?,
+        query_end: number(3)?,
+        is_reverse: fields[4] == "-",
+        walk,
+        walk_length: number(6)?,
+        walk_start: number(7)?,
+        walk_end: number(8)?,
+        mapping_quality: fields[11].parse().unwrap_or(255),
+        cigar,
+    }))
+}
+
+/// The part of an alignment that falls on one step of its walk.
+pub(crate) struct StepSlice {
+    /// The index of the step in the walk.
+    pub(crate) step: usize,
+    /// The aligned range on the step's node, in the walk's orientation.
+    pub(crate) node_range: (u64, u64),
+    /// The aligned range of the query, relative to the start of the alignment.
+    pub(crate) query_range: (u64, u64),
+    ops: Vec<(char, u64)>,
+}
+
+/// Splits an alignment's operations at the node boundaries of its walk.
+///
+/// Insertions at a boundary are attributed to the preceding step.
+pub(crate) fn slice_by_step(graph: &Graph, alignment: &GraphAlignment) -> Result<Vec<StepSlice>, Error> {
+    if alignment.walk.is_empty() {
+        return Ok(Vec::new());
+    }
+    let node_len = |step: usize| graph.get_node_len(alignment.walk[step].0);
+    let (mut step, mut step_start, mut step_end) = (0, 0, node_len(0)?);
+    let (mut walk_pos, mut query_pos) = (alignment.walk_start, 0);
+    let mut slices: Vec<StepSlice> = Vec::new();
+
+    for (op, len) in alignment.operations() {
+        let mut remaining = len;
+        while remaining > 0 {
+            if op != 'I' {
+                while walk_pos >= step_end && step + 1 < alignment.walk.len() {
+                    step += 1;
+                    step_start = step_end;
+                    step_end += node_len(step)?;
+                }
+            }
+            if slices.last().is_none_or(|slice| slice.step != step) {
+                let offset = walk_pos - step_start;
+                slices.push(StepSlice {
+                    step,
+                    node_range: (offset, offset),
+                    query_range: (query_pos, query_pos),
+                    ops: Vec::new(),
+                });
+            }
+            // Operations consuming the walk stop at the end of the node, unless
+            // the alignment runs past the end of the walk.
+            let chunk = match op {
+                'I' => remaining,
+                _ => match step_end.saturating_sub(walk_pos) {
+                    0 => remaining,
+                    available => remaining.min(available),
+                },
+            };
+            let slice = slices.last_mut().unwrap();
+            push_op(&mut slice.ops, op, chunk);
+            if op != 'I' {
+                walk_pos += chunk;
+                slice.node_range.1 += chunk;
+            }
+            if op != 'D' {
+                query_pos += chunk;
+                slice.query_range.1 = query_pos;
+            }
+            remaining -= chunk;
+        }
+    }
+    Ok(slices)
+}
+
+/// A run of an alignment that is contiguous on the reference.
+struct Segment {
+    reference_range: (u64, u64),
+    query_range: (u64, u64),
+    /// Whether the walk runs in the direction of the reference.
+    same_direction: bool,
+    /// The operations in walk order.
+    ops: Vec<(char, u64)>,
+}
+
+/// Projects GAF alignments onto a reference path and writes them as PAF.
+///
+/// Each alignment is split at the boundaries of its walk's nodes. The parts on
+/// nodes that `reference_path` visits are placed at the corresponding
+/// reference coordinates, and consecutive parts that stay contiguous on the
+/// reference are joined into one PAF record. The alignment is split into
+/// several records wherever it leaves the reference (for example through an
+/// insertion bubble) or jumps to a distant part of it; parts on non-reference
+/// nodes are dropped. Records carry a `cg:Z:` CIGAR relative to the reference
+/// strand, and the mapping quality of the original alignment.
+///
+/// If the reference visits a node more than once, its first visit is used.
+/// Returns the number of PAF records written.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the reference path does not exist, if the GAF input
+/// cannot be read or parsed, if an alignment's walk visits a node that is not
+/// in the graph, or if writing fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{gaf, Graph};
+/// use std::io::BufReader;
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let gaf_file = BufReader::new(std::fs::File::open("reads.gaf").unwrap());
+/// let mut out = std::fs::File::create("reads.chr1.paf").unwrap();
+/// gaf::surject(&graph, gaf_file, "GRCh38#0#chr1", &mut out).unwrap();
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph, gaf_reader, writer), err))]
+pub fn surject<R: BufRead, W: Write>(
+    graph: &Graph,
+    gaf_reader: R,
+    reference_path: &str,
+    writer: &mut W,
+) -> Result<usize, Error> {
+    let reference = PathIndex::new(graph, reference_path)?
+        .ok_or_else(|| Error::path_not_found(reference_path))?;
+    let mut reference_steps: HashMap<u64, usize> = HashMap::new();
+    for (i, step) in reference.steps.iter().enumerate() {
+        reference_steps.entry(step.node_id).or_insert(i);
+    }
+    let io_err = |e: std::io::Error| Error::io("Failed to write PAF output", e);
+
+    let mut written = 0;
+    for record in records(gaf_reader) {
+        let alignment = record?;
+
+        let mut segments: Vec<Segment> = Vec::new();
+        for slice in slice_by_step(graph, &alignment)? {
+            let (node_id, walk_forward) = alignment.walk[slice.step];
+            let Some(&i) = reference_steps.get(&node_id) else {
+                continue;
+            };
+            let same_direction = walk_forward == reference.steps[i].is_forward;
+            let (from, to) = slice.node_range;
+            let reference_range = if same_direction {
+                (reference.starts[i] + from, reference.starts[i] + to)
+            } else {
+                (reference.starts[i] + reference.lengths[i] - to, reference.starts[i] + reference.lengths[i] - from)
+            };
+            let query_range = if alignment.is_reverse {
+                (alignment.query_end - slice.query_range.1, alignment.query_end - slice.query_range.0)
+            } else {
+                (alignment.query_start + slice.query_range.0, alignment.query_start + slice.query_range.1)
+            };
+
+            if let Some(last) = segments.last_mut() {
+                let contiguous = last.same_direction == same_direction
+                    && if same_direction {
+                        last.reference_range.1 == reference_range.0
+                    } else {
+                        reference_range.1 == last.reference_range.0
+                    };
+                if contiguous {
+                    last.reference_range.0 = last.reference_range.0.min(reference_range.0);
+                    last.reference_range.1 = last.reference_range.1.max(reference_range.1);
+                    last.query_range.0 = last.query_range.0.min(query_range.0);
+                    last.query_range.1 = last.query_range.1.max(query_range.1);
+                    for &(op, len) in &slice.ops {
+                        push_op(&mut last.ops, op, len);
+                    }
+                    continue;
+                }
+            }
+            segments.push(Segment { reference_range, query_range, same_direction, ops: slice.ops });
+        }
+
+        for mut segment in segments {
+            // PAF CIGARs follow the forward strand of the target.
+            if !segment.same_direction {
+                segment.ops.reverse();
+            }
+            let is_forward = segment.same_direction != alignment.is_reverse;
+            let matches: u64 = segment.ops.iter().filter(|(op, _)| matches!(op, '=' | 'M')).map(|(_, len)| len).sum();
+            let block_length: u64 = segment.ops.iter().map(|(_, len)| len).sum();
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\tcg:Z:{}",
+                alignment.query_name,
+                alignment.query_length,
+                segment.query_range.0,
+                segment.query_range.1,
+                if is_forward { '+' } else { '-' },
+                reference_path,
+                reference.length,
+                segment.reference_range.0,
+                segment.reference_range.1,
+                matches,
+                block_length,
+                alignment.mapping_quality,
+                cigar(&segment.ops)
+            )
+            .map_err(io_err)?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,278 @@
+// src/gam.rs
+
+//! Reading vg GAM alignment files.
+//!
+//! GAM is the binary alignment format of `vg`: a (usually BGZF-compressed)
+//! stream of groups of length-prefixed protobuf `Alignment` messages. Records
+//! are resolved against a loaded [`Graph`] and converted to the same
+//! [`GraphAlignment`] type as the records of [`gaf::records`](crate::gaf::records).
+
+use std::io::{BufRead, BufReader, Read};
+use super::alignment::GraphAlignment;
+use super::graph::{Error, Graph};
+use super::path_alignment::push_op;
+use super::protobuf::{decode_varint, FieldValue, MessageReader};
+
+/// The type tag that starts every group in GAM files written by recent versions of vg.
+const TYPE_TAG: &[u8] = b"GAM";
+
+/// An iterator over the alignments in a GAM stream, created by [`records`].
+pub struct GamRecords<'g> {
+    graph: &'g Graph,
+    reader: Box<dyn BufRead + 'g>,
+    /// Messages left in the current group.
+    remaining_in_group: u64,
+}
+
+/// Returns an iterator over the alignments in a GAM stream.
+///
+/// Both BGZF-compressed GAM files, as written by vg, and uncompressed streams
+/// are accepted. The node IDs of each alignment are checked against `graph`,
+/// which must be the graph the reads were aligned to. Unmapped reads are
+/// skipped.
+///
+/// Leading and trailing insertions are treated as soft clips and reflected in
+/// [`GraphAlignment::query_start`] and [`GraphAlignment::query_end`]. Matches
+/// and substitutions become `=` and `X` operations.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{gam, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let file = std::fs::File::open("reads.gam").unwrap();
+/// for record in gam::records(file, &graph) {
+///     let alignment = record.unwrap();
+///     println!("{} aligns to {} nodes", alignment.query_name, alignment.walk.len());
+/// }
+/// ```
+pub fn records<'g, R: Read + 'g>(reader: R, graph: &'g Graph) -> GamRecords<'g> {
+    let mut buffered = BufReader::new(reader);
+    let is_gzip = buffered.fill_buf().map(|b| b.starts_with(&[0x1f, 0x8b])).unwrap_or(false);
+    let reader: Box<dyn BufRead + 'g> = if is_gzip {
+        Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(buffered)))
+    } else {
+        Box::new(buffered)
+    };
+    GamRecords { graph, reader, remaining_in_group: 0 }
+}
+
+impl GamRecords<'_> {
+    /// Reads a varint, returning `None` at a clean end of the stream.
+    fn read_varint(&mut self) -> Result<Option<u64>, Error> {
+        let mut bytes = Vec::with_capacity(10);
+        loop {
+            let mut byte = [0u8];
+            match self.reader.read(&mut byte) {
+                Ok(0) if bytes.is_empty() => return Ok(None),
+                Ok(0) => return Err(Error::invalid_format("Truncated GAM stream")),
+                Ok(_) => bytes.push(byte[0]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::io("Failed to read GAM input", e)),
+            }
+            if byte[0] & 0x80 == 0 || bytes.len() == 10 {
+                let (value, _) = decode_varint(&bytes).ok_or_else(|| Error::invalid_format("Invalid varint in GAM stream"))?;
+                return Ok(Some(value));
+            }
+        }
+    }
+
+    /// Reads the next message of the stream, returning `None` at its end.
+    fn read_message(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        while self.remaining_in_group == 0 {
+            match self.read_varint()? {
+                Some(count) => self.remaining_in_group = count,
+                None => return Ok(None),
+            }
+        }
+        let len = self.read_varint()?.ok_or_else(|| Error::invalid_format("Truncated GAM stream"))?;
+        // The length comes from the input, so the buffer only grows with the
+        // bytes that are actually there rather than being allocated up front.
+        let mut message = Vec::new();
+        (&mut self.reader)
+            .take(len)
+            .read_to_end(&mut message)
+            .map_err(|e| Error::io("Failed to read GAM input", e))?;
+        if (message.len() as u64) < len {
+            return Err(Error::invalid_format("Truncated GAM stream"));
+        }
+        self.remaining_in_group -= 1;
+        Ok(Some(message))
+    }
+}
+
+impl Iterator for GamRecords<'_> {
+    type Item = Result<GraphAlignment, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let message = match self.read_message() {
+                Ok(Some(message)) => message,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+            if message == TYPE_TAG {
+                continue;
+            }
+            match decode_alignment(&message).and_then(|a| resolve(self.graph, a)) {
+                Ok(Some(alignment)) => return Some(Ok(alignment)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// The parts of a vg `Mapping` that are needed here.
+struct Mapping {
+    node_id: u64,
+    offset: u64,
+    is_reverse: bool,
+    /// `(from_length, to_length, has_sequence)` of each edit.
+    edits: Vec<(u64, u64, bool)>,
+}
+
+/// The parts of a vg `Alignment` that are needed here.
+struct RawAlignment {
+    name: String,
+    sequence_length: u64,
+    mapping_quality: u8,
+    mappings: Vec<Mapping>,
+}
+
+fn decode_alignment(message: &[u8]) -> Result<RawAlignment, Error> {
+    let mut alignment = RawAlignment { name: String::new(), sequence_length: 0, mapping_quality: 0, mappings: Vec::new() };
+    let mut fields = MessageReader::new(message);
+    while let Some((field, value)) = fields.next_field()? {
+        match (field, value) {
+            (1, FieldValue::Bytes(sequence)) => alignment.sequence_length = sequence.len() as u64,
+            (2, FieldValue::Bytes(path)) => alignment.mappings = decode_path(path)?,
+            (3, FieldValue::Bytes(name)) => alignment.name = String::from_utf8_lossy(name).into_owned(),
+            (5, FieldValue::Varint(quality)) => alignment.mapping_quality = quality.min(255) as u8,
+            _ => {}
+        }
+    }
+    Ok(alignment)
+}
+
+fn decode_path(message: &[u8]) -> Result<Vec<Mapping>, Error> {
+    let mut mappings = Vec::new();
+    let mut fields = MessageReader::new(message);
+    while let Some((field, value)) = fields.next_field()? {
+        if let (2, FieldValue::Bytes(mapping)) = (field, value) {
+            mappings.push(decode_mapping(mapping)?);
+        }
+    }
+    Ok(mappings)
+}
+
+fn decode_mapping(message: &[u8]) -> Result<Mapping, Error> {
+    let mut mapping = Mapping { node_id: 0, offset: 0, is_reverse: false, edits: Vec::new() };
+    let mut fields = MessageReader::new(message);
+    while let Some((field, value)) = fields.next_field()? {
+        match (field, value) {
+            (1, FieldValue::Bytes(position)) => {
+                let mut position_fields = MessageReader::new(position);
+                while let Some((field, value)) = position_fields.next_field()? {
+                    match (field, value) {
+                        (1, FieldValue::Varint(node_id)) => mapping.node_id = node_id,
+                        (2, FieldValue::Varint(offset)) => mapping.offset = offset,
+                        (4, FieldValue::Varint(is_reverse)) => mapping.is_reverse = is_reverse != 0,
+                        _ => {}
+                    }
+                }
+            }
+            (2, FieldValue::Bytes(edit)) => {
+                let (mut from_length, mut to_length, mut has_sequence) = (0, 0, false);
+                let mut edit_fields = MessageReader::new(edit);
+                while let Some((field, value)) = edit_fields.next_field()? {
+                    match (field, value) {
+                        (1, FieldValue::Varint(len)) => from_length = len,
+                        (2, FieldValue::Varint(len)) => to_length = len,
+                        (3, FieldValue::Bytes(sequence)) => has_sequence = !sequence.is_empty(),
+                        _ => {}
+                    }
+                }
+                mapping.edits.push((from_length, to_length, has_sequence));
+            }
+            _ => {}
+        }
+    }
+    Ok(mapping)
+}
+
+/// Converts a decoded alignment into a [`GraphAlignment`], checking it against the graph.
+///
+/// Returns `None` for unmapped reads.
+fn resolve(graph: &Graph, raw: RawAlignment) -> Result<Option<GraphAlignment>, Error> {
+    if raw.mappings.is_empty() {
+        return Ok(None);
+    }
+
+    let mut walk: Vec<(u64, bool)> = Vec::new();
+    let mut ops: Vec<(char, u64)> = Vec::new();
+    let (mut walk_length, mut walk_start, mut walk_end) = (0, 0, 0);
+    for (k, mapping) in raw.mappings.iter().enumerate() {
+        let node_len = graph
+            .get_node_len(mapping.node_id)
+            .map_err(|e| e.context(format!("Alignment '{}'", raw.name)))?;
+        let from_total: u64 = mapping.edits.iter().map(|&(from, _, _)| from).sum();
+        if mapping.offset + from_total > node_len {
+            return Err(Error::invalid_format(format!(
+                "Alignment '{}' does not fit node {} of the graph",
+                raw.name, mapping.node_id
+            )));
+        }
+        // vg may split the alignment to one node over several consecutive mappings.
+        let step = (mapping.node_id, !mapping.is_reverse);
+        let continues_node = walk.last() == Some(&step) && mapping.offset == walk_end - (walk_length - node_len);
+        if !continues_node {
+            if k == 0 {
+                walk_start = mapping.offset;
+            }
+            walk.push(step);
+            walk_length += node_len;
+        }
+        walk_end = walk_length - node_len + mapping.offset + from_total;
+
+        for &(from, to, has_sequence) in &mapping.edits {
+            let op = match (from, to) {
+                (0, _) => 'I',
+                (_, 0) => 'D',
+                _ if from == to && !has_sequence => '=',
+                _ if from == to => 'X',
+                _ => {
+                    return Err(Error::invalid_format(format!("Alignment '{}' has an edit of unequal lengths", raw.name)));
+                }
+            };
+            push_op(&mut ops, op, from.max(to));
+        }
+    }
+
+    // Insertions at either end of the alignment are soft clips.
+    let mut query_start = 0;
+    let mut query_end = raw.sequence_length;
+    if let Some(&('I', len)) = ops.first() {
+        query_start = len;
+        ops.remove(0);
+    }
+    if let Some(&('I', len)) = ops.last() {
+        query_end -= len.min(query_end);
+        ops.pop();
+    }
+
+    Ok(Some(GraphAlignment {
+        query_name: raw.name,
+        query_length: raw.sequence_length,
+        query_start,
+        query_end,
+        is_reverse: false,
+        walk,
+        walk_length,
+        walk_start,
+        walk_end,
+        mapping_quality: raw.mapping_quality,
+        cigar: Some(ops),
+    }))
+}
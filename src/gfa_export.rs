@@ -0,0 +1,67 @@
+// src/gfa_export.rs
+
+//! Writing the in-memory graph as GFA text.
+
+use std::io::Write;
+use super::graph::{Error, Graph};
+use super::rgfa::orientation_symbol;
+
+impl Graph {
+    /// Writes the graph as GFA 1.0 text, with `S`, `L`, and `P` lines.
+    ///
+    /// The output is produced from the graph in memory, including any changes
+    /// made since it was loaded, without running the `odgi` executable, so it
+    /// also works where starting subprocesses is not allowed. It follows
+    /// `odgi view`: a `VN:Z:1.0` header, segments in the graph's current order,
+    /// one link per edge with a `0M` overlap, and paths in the order of
+    /// [`Graph::get_path_names`] with `*` for their overlaps. Segments are
+    /// named by their node IDs, so [`gfa_to_odgi`](crate::gfa_to_odgi) and
+    /// [`Graph::from_gfa_str`] read the output back to the same graph.
+    ///
+    /// The writer is not buffered here; wrap it in a [`std::io::BufWriter`]
+    /// when writing to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let mut out = std::io::BufWriter::new(std::fs::File::create("pangenome.gfa").unwrap());
+    /// graph.to_gfa(&mut out).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer), err))]
+    pub fn to_gfa<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let io_err = |e: std::io::Error| Error::io("Failed to write GFA output", e);
+
+        writeln!(writer, "H\tVN:Z:1.0").map_err(io_err)?;
+        let mut sequence = String::new();
+        for node_id in self.node_order()? {
+            self.get_node_sequence_into(node_id, &mut sequence)?;
+            writeln!(writer, "S\t{}\t{}", node_id, sequence).map_err(io_err)?;
+        }
+        for edge in self.raw_edges()? {
+            writeln!(
+                writer,
+                "L\t{}\t{}\t{}\t{}\t0M",
+                edge.from_node,
+                orientation_symbol(edge.from_orientation),
+                edge.to_node,
+                orientation_symbol(edge.to_orientation)
+            )
+            .map_err(io_err)?;
+        }
+        for path_name in self.get_path_names()? {
+            write!(writer, "P\t{}\t", path_name).map_err(io_err)?;
+            for (i, step) in self.raw_path_steps(&path_name)?.iter().enumerate() {
+                let separator = if i == 0 { "" } else { "," };
+                write!(writer, "{}{}{}", separator, step.node_id, orientation_symbol(step.is_forward)).map_err(io_err)?;
+            }
+            writeln!(writer, "\t*").map_err(io_err)?;
+        }
+        Ok(())
+    }
+}
@@ -4,30 +4,264 @@
 //!
 //! This module defines the central [`Graph`] object, which is the primary
 //! entry point for querying a loaded ODGI graph. It also defines the
-//! associated [`Error`] type for handling failures.
+//! associated [`Error`] and [`ErrorKind`] types for handling failures.
 
+#[cfg(not(feature = "docs-only"))]
 use cxx::UniquePtr;
+#[cfg(not(feature = "docs-only"))]
+use std::pin::Pin;
+#[cfg(not(feature = "docs-only"))]
+use std::sync::Arc;
+#[cfg(not(feature = "wasm"))]
+use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::fmt;
+#[cfg(not(feature = "wasm"))]
 use super::ffi;
+#[cfg(not(feature = "docs-only"))]
+use super::atomic::AtomicFile;
+#[cfg(not(feature = "docs-only"))]
+use super::journal::{self, Journal, Undo};
+#[cfg(not(feature = "docs-only"))]
+use super::path_index::PathIndex;
+#[cfg(not(feature = "docs-only"))]
+use super::query_metrics;
 
 // Re-export the FFI data structures so they are part of the public API
 // and can be used as return types from the Graph methods.
-pub use super::ffi::{Edge, PathPosition};
+pub use super::ffi::{Edge, GraphStats, NodeStep, PathPosition};
 
-/// A custom error type for operations within the `odgi-ffi` crate.
+/// The error type for operations within the `odgi-ffi` crate.
 ///
 /// This error is returned by functions that might fail, such as [`Graph::load`].
+/// Its [`kind`](Error::kind) tells failures apart by their cause, so callers
+/// can, for example, treat a missing path differently from a corrupt file.
+/// Errors raised while processing a record of a larger input carry the
+/// location as a prefix of their message, such as `BED line 3: ...`, without
+/// changing their kind.
 #[derive(Debug)]
-pub struct Error(pub String);
+pub struct Error {
+    kind: ErrorKind,
+    context: Option<String>,
+}
+
+/// The cause of an [`Error`].
+///
+/// New kinds may be added in minor releases, so matches on `ErrorKind` need a
+/// wildcard arm.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Reading or writing a file or stream failed.
+    #[error("{context}: {source}")]
+    Io {
+        /// What was being done, such as `Failed to open 'graph.og'`.
+        context: String,
+        /// The underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// An input file, stream, or string is not in the expected format.
+    #[error("{0}")]
+    InvalidFormat(String),
+    /// An argument is outside the range the operation accepts.
+    #[error("{0}")]
+    InvalidArgument(String),
+    /// A path with the given name does not exist in the graph.
+    #[error("Path '{0}' not found in graph")]
+    PathNotFound(String),
+    /// A node with the given ID does not exist in the graph.
+    #[error("Node {0} not found in graph")]
+    NodeNotFound(u64),
+    /// A path with the given name already exists in the graph.
+    #[error("Path '{0}' already exists in graph")]
+    PathExists(String),
+    /// The `odgi` executable ran but failed.
+    #[error("{message}: {stderr}")]
+    ConversionFailed {
+        /// Which command failed, and on which input.
+        message: String,
+        /// What the command wrote to stderr.
+        stderr: String,
+    },
+    /// A file is not in an ODGI format version the linked `odgi` library can read.
+    #[error(
+        "Unsupported ODGI format in '{path}': found {}, but this build supports format versions {} to {}; \
+         the file may not be an ODGI graph or may have been written by an incompatible odgi release",
+        found_format(.magic, .version),
+        super::inspect::SUPPORTED_FORMAT_VERSIONS.start(),
+        super::inspect::SUPPORTED_FORMAT_VERSIONS.end()
+    )]
+    VersionMismatch {
+        /// The file, or a description of the source for graphs read from memory.
+        path: String,
+        /// The magic number at the start of the file.
+        magic: u32,
+        /// The format version, or `None` if the magic number does not identify one.
+        version: Option<u32>,
+    },
+    /// The operation's [`CancellationToken`](crate::CancellationToken) was cancelled.
+    #[error("Operation cancelled")]
+    Cancelled,
+    /// The operation did not finish within its timeout.
+    #[error("Timed out after {timeout:?} waiting for {operation}; stderr so far: {stderr}")]
+    TimedOut {
+        /// The operation that was waited for.
+        operation: String,
+        /// How long it was given.
+        timeout: std::time::Duration,
+        /// What it had written to stderr when it was stopped.
+        stderr: String,
+    },
+    /// The `odgi` library threw a C++ exception, such as while reading a corrupt file.
+    #[error("odgi failed: {0}")]
+    Exception(String),
+    /// The operation or input is not supported by this build.
+    #[error("{0}")]
+    Unsupported(String),
+    /// Any other failure, such as the `odgi` library failing without giving a cause.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Describes the format found in a file, for [`ErrorKind::VersionMismatch`].
+fn found_format(magic: &u32, version: &Option<u32>) -> String {
+    match version {
+        Some(version) => format!("format version {}", version),
+        None => format!("unrecognized magic number {:#010x}", magic),
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        match &self.context {
+            Some(context) => write!(f, "{}: {}", context, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.kind.source()
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error { kind, context: None }
+    }
+}
+
+#[cfg(not(feature = "docs-only"))]
+impl From<cxx::Exception> for Error {
+    fn from(exception: cxx::Exception) -> Self {
+        ErrorKind::Exception(exception.what().to_string()).into()
     }
 }
 
-impl StdError for Error {}
+impl Error {
+    /// Returns the cause of the error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Consumes the error, returning its cause.
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Prefixes the message with where the error happened, keeping its kind.
+    pub(crate) fn context(mut self, context: impl fmt::Display) -> Self {
+        self.context = Some(match self.context {
+            Some(inner) => format!("{}: {}", context, inner),
+            None => context.to_string(),
+        });
+        self
+    }
+
+    /// Creates an [`ErrorKind::Io`] error, described by what was being done.
+    pub(crate) fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        ErrorKind::Io { context: context.into(), source }.into()
+    }
+
+    /// Creates an [`ErrorKind::InvalidFormat`] error.
+    pub(crate) fn invalid_format(message: impl Into<String>) -> Self {
+        ErrorKind::InvalidFormat(message.into()).into()
+    }
+
+    /// Creates an [`ErrorKind::InvalidArgument`] error.
+    pub(crate) fn invalid_argument(message: impl Into<String>) -> Self {
+        ErrorKind::InvalidArgument(message.into()).into()
+    }
+
+    /// Creates an [`ErrorKind::PathNotFound`] error.
+    pub(crate) fn path_not_found(path_name: impl Into<String>) -> Self {
+        ErrorKind::PathNotFound(path_name.into()).into()
+    }
+
+    /// Creates an [`ErrorKind::NodeNotFound`] error.
+    pub(crate) fn node_not_found(node_id: u64) -> Self {
+        ErrorKind::NodeNotFound(node_id).into()
+    }
+
+    /// Creates an [`ErrorKind::PathExists`] error.
+    pub(crate) fn path_exists(path_name: impl Into<String>) -> Self {
+        ErrorKind::PathExists(path_name.into()).into()
+    }
+
+    /// Creates an [`ErrorKind::ConversionFailed`] error.
+    #[cfg_attr(feature = "docs-only", allow(dead_code))]
+    pub(crate) fn conversion_failed(message: impl Into<String>, stderr: impl Into<String>) -> Self {
+        ErrorKind::ConversionFailed { message: message.into(), stderr: stderr.into() }.into()
+    }
+
+    /// Creates an [`ErrorKind::Unsupported`] error.
+    #[cfg_attr(not(any(feature = "wasm", all(feature = "http", not(feature = "docs-only")))), allow(dead_code))]
+    pub(crate) fn unsupported(message: impl Into<String>) -> Self {
+        ErrorKind::Unsupported(message.into()).into()
+    }
+
+    /// Creates an [`ErrorKind::Other`] error.
+    pub(crate) fn other(message: impl Into<String>) -> Self {
+        ErrorKind::Other(message.into()).into()
+    }
+
+    /// Creates the error returned when a [`crate::CancellationToken`] is cancelled.
+    pub(crate) fn cancelled() -> Self {
+        ErrorKind::Cancelled.into()
+    }
+
+    /// Returns `true` if the operation failed because its
+    /// [`CancellationToken`](crate::CancellationToken) was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.kind, ErrorKind::Cancelled)
+    }
+
+    /// Creates the error returned when `operation` did not finish within `timeout`.
+    #[cfg_attr(feature = "docs-only", allow(dead_code))]
+    pub(crate) fn timeout(operation: &str, timeout: std::time::Duration, stderr: &str) -> Self {
+        ErrorKind::TimedOut { operation: operation.to_string(), timeout, stderr: stderr.to_string() }.into()
+    }
+
+    /// Returns `true` if the operation failed because it did not finish within its timeout.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.kind, ErrorKind::TimedOut { .. })
+    }
+
+    /// Creates the error returned for a file whose format the linked `odgi` cannot read.
+    ///
+    /// `version` is `None` if the magic number does not identify any known format version.
+    pub(crate) fn version_mismatch(path: &str, magic: u32, version: Option<u32>) -> Self {
+        ErrorKind::VersionMismatch { path: path.to_string(), magic, version }.into()
+    }
+
+    /// Returns `true` if a file could not be read because it is not in an ODGI
+    /// format version supported by the linked `odgi` library.
+    pub fn is_version_mismatch(&self) -> bool {
+        matches!(self.kind, ErrorKind::VersionMismatch { .. })
+    }
+}
 
 /// A safe, idiomatic Rust wrapper around a C++ `odgi::graph_t` object.
 ///
@@ -35,15 +269,33 @@ impl StdError for Error {}
 /// Once loaded, you can use its methods to perform various queries, such as
 /// retrieving node sequences, finding paths, and traversing the graph structure.
 ///
-/// The only way to create a `Graph` is by calling [`Graph::load`].
+/// A failure inside the C++ library never aborts or panics the process.
+/// Every method that calls into odgi, queries included, returns it as an
+/// [`Error`] of kind [`ErrorKind::Exception`]; an edit that fails is not
+/// recorded in an open [checkpoint](Graph::checkpoint).
+///
+/// Queries about a node or path that does not exist fail with
+/// [`ErrorKind::NodeNotFound`] or [`ErrorKind::PathNotFound`] rather than
+/// returning an empty string or `0`; use [`Graph::has_node`] and
+/// [`Graph::has_path`] to test for one first.
 pub struct Graph {
     // This field will only exist in real builds.
     #[cfg(not(feature = "docs-only"))]
     inner: UniquePtr<ffi::OpaqueGraph>,
+    // Undo records for open checkpoints.
+    #[cfg(not(feature = "docs-only"))]
+    pub(crate) journal: Journal,
+    // Path indexes built by `Graph::load_with`, dropped by the first edit.
+    #[cfg(not(feature = "docs-only"))]
+    pub(crate) path_indexes: HashMap<String, Arc<PathIndex>>,
 
     // For docs builds, add a dummy field to make the struct valid.
     #[cfg(feature = "docs-only")]
-    _inner: (),
+    pub(crate) _inner: (),
+
+    // In wasm builds, the graph is held by a pure-Rust store instead.
+    #[cfg(feature = "wasm")]
+    pub(crate) store: super::wasm::MemoryGraph,
 }
 
 // --- REAL IMPLEMENTATION (for normal builds) ---
@@ -55,9 +307,22 @@ impl Graph {
     ///
     /// * `path` - A string slice that holds the path to the ODGI file.
     ///
+    /// Files compressed with gzip (including BGZF) or zstd, such as `.og.gz` and
+    /// `.og.zst`, are detected from their contents and decompressed into a
+    /// temporary file that is removed once the graph has been loaded.
+    ///
+    /// odgi deserializes the whole graph into its own structures on the heap
+    /// and cannot answer queries from a memory-mapped file, so every process
+    /// that loads a graph holds a full copy of it, whichever way it is loaded.
+    ///
     /// # Errors
     ///
-    /// Returns an [`Error`] if the file does not exist or if the file format is invalid.
+    /// Returns an [`Error`] if the file does not exist or if the file format is
+    /// invalid, including files that are truncated or that odgi fails to read.
+    /// Files written in an ODGI format version that the linked `odgi` cannot
+    /// read are rejected before deserialization with an error for which
+    /// [`Error::is_version_mismatch`] is `true`, naming the file's format
+    /// version and the supported range.
     ///
     /// # Examples
     ///
@@ -66,48 +331,373 @@ impl Graph {
     ///
     /// match Graph::load("my_graph.odgi") {
     ///     Ok(graph) => println!("Graph loaded successfully!"),
+    ///     Err(e) if e.is_version_mismatch() => eprintln!("Incompatible graph: {}", e),
     ///     Err(e) => eprintln!("Failed to load graph: {}", e),
     /// }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
     pub fn load(path: &str) -> Result<Self, Error> {
-        let graph_ptr = ffi::load_graph(path);
-        if graph_ptr.is_null() {
-            Err(Error(format!("Failed to load ODGI graph from '{}'", path)))
-        } else {
-            Ok(Graph { inner: graph_ptr })
-        }
+        let _timer = query_metrics::query("load");
+        super::inspect::check_loadable(path)?;
+        let decompressed = super::compression::decompress_to_temp(path)?;
+        let load_path = match &decompressed {
+            Some(temp) => temp
+                .path()
+                .to_str()
+                .ok_or_else(|| Error::other(format!("Temporary file for '{}' has a non-UTF-8 path", path)))?,
+            None => path,
+        };
+        let graph_ptr = ffi::load_graph(load_path)
+            .map_err(|e| Error::from(e).context(format!("Failed to load ODGI graph from '{}'", path)))?;
+        Ok(Graph { inner: graph_ptr, journal: Journal::default(), path_indexes: HashMap::new() })
+    }
+
+    /// Loads an ODGI graph from a buffer in memory.
+    ///
+    /// This reads the same data as [`Graph::load`] reads from a file, for
+    /// graphs fetched from object storage or embedded in a binary. The buffer
+    /// is deserialized in place, without being written to a file or copied
+    /// first. gzip- and zstd-compressed buffers are detected from their
+    /// contents and decompressed in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the buffer cannot be decompressed or is not an
+    /// ODGI graph. As with [`Graph::load`], graphs written in an unsupported
+    /// format version are rejected with an error for which
+    /// [`Error::is_version_mismatch`] is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let bytes = std::fs::read("pangenome.odgi.zst").unwrap();
+    /// let graph = Graph::load_from_bytes(&bytes).unwrap();
+    /// println!("{} nodes", graph.node_count().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(bytes), fields(len = bytes.len()), err))]
+    pub fn load_from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let _timer = query_metrics::query("load_from_bytes");
+        let decompressed = super::compression::decompress_bytes(bytes)?;
+        let bytes = decompressed.as_deref().unwrap_or(bytes);
+        super::inspect::check_loadable_bytes("in-memory graph", bytes)?;
+        let graph_ptr = ffi::load_graph_from_bytes(bytes)
+            .map_err(|e| Error::from(e).context("Failed to load ODGI graph from memory"))?;
+        Ok(Graph { inner: graph_ptr, journal: Journal::default(), path_indexes: HashMap::new() })
+    }
+
+    /// Loads an ODGI graph from any reader, such as a network stream.
+    ///
+    /// odgi builds the whole graph in memory, so the reader is read to its end
+    /// into a buffer, which is then loaded as by [`Graph::load_from_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if reading fails, or if [`Graph::load_from_bytes`] would.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let stdin = std::io::stdin().lock();
+    /// let graph = Graph::load_from_reader(stdin).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(reader), err))]
+    pub fn load_from_reader<R: std::io::Read>(mut reader: R) -> Result<Self, Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| Error::io("Failed to read ODGI graph", e))?;
+        Graph::load_from_bytes(&bytes)
+    }
+
+    /// Writes the graph to a file in the ODGI format.
+    ///
+    /// The graph is serialized as it is in memory, including any changes made
+    /// since it was loaded, so an edited graph can be written without a round
+    /// trip through GFA. The file is written to a temporary file next to
+    /// `path` and renamed into place once complete, so an interrupted save
+    /// never leaves a truncated graph behind. The result can be read back with
+    /// [`Graph::load`] or by the `odgi` tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let mut graph = Graph::load("pangenome.odgi").unwrap();
+    /// graph.load_order_file("pangenome.order.txt").unwrap();
+    /// graph.save("pangenome.sorted.odgi").unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let _timer = query_metrics::query("save");
+        let target = AtomicFile::new(path)?;
+        let target_path = target
+            .path()
+            .to_str()
+            .ok_or_else(|| Error::other(format!("Temporary file for '{}' has a non-UTF-8 path", path)))?;
+        self.save_to(target_path)
+            .map_err(|e| e.context(format!("Failed to write ODGI graph to '{}'", path)))?;
+        target.commit()
+    }
+
+    /// Builds a graph from a GFA file in this process.
+    ///
+    /// The graph is built by the same odgi code that [`gfa_to_odgi`](crate::gfa_to_odgi)
+    /// and `odgi build` run, so it has the same nodes, edges, paths, and node
+    /// IDs, but without starting a subprocess or writing an ODGI file first.
+    /// Files compressed with gzip or zstd are detected from their contents
+    /// and decompressed into a temporary file first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let graph = Graph::from_gfa_file("pangenome.gfa").unwrap();
+    /// println!("{} nodes", graph.node_count().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+    pub fn from_gfa_file(path: &str) -> Result<Self, Error> {
+        let _timer = query_metrics::query("from_gfa_file");
+        let decompressed = super::compression::decompress_to_temp(path)?;
+        let gfa_path = match &decompressed {
+            Some(temp) => temp
+                .path()
+                .to_str()
+                .ok_or_else(|| Error::other(format!("Temporary file for '{}' has a non-UTF-8 path", path)))?,
+            None => path,
+        };
+        let graph_ptr = ffi::graph_from_gfa(gfa_path)
+            .map_err(|e| Error::from(e).context(format!("Failed to read GFA file '{}'", path)))?;
+        Ok(Graph { inner: graph_ptr, journal: Journal::default(), path_indexes: HashMap::new() })
+    }
+
+    /// Builds a graph from GFA text in this process.
+    ///
+    /// This behaves like [`Graph::from_gfa_file`]. odgi reads GFA from files,
+    /// so the text is written to a temporary file, which is removed once the
+    /// graph has been built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the temporary file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let graph = Graph::from_gfa_str("S\t1\tGATTACA\nS\t2\tT\nL\t1\t+\t2\t+\t0M\nP\tx\t1+,2+\t*\n").unwrap();
+    /// assert_eq!(graph.get_path_length("x").unwrap(), 8);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(gfa), err))]
+    pub fn from_gfa_str(gfa: &str) -> Result<Self, Error> {
+        let io_err = |e: std::io::Error| Error::io("Failed to write temporary GFA file", e);
+        let temp = tempfile::Builder::new().suffix(".gfa").tempfile().map_err(io_err)?;
+        std::fs::write(temp.path(), gfa).map_err(io_err)?;
+        let path = temp
+            .path()
+            .to_str()
+            .ok_or_else(|| Error::other("Temporary GFA file has a non-UTF-8 path"))?;
+        Graph::from_gfa_file(path)
     }
 
     /// Returns the total number of nodes in the graph.
     ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// # use odgi_ffi::Graph;
     /// # let graph = Graph::load("my_graph.odgi").unwrap();
-    /// let count = graph.node_count();
+    /// let count = graph.node_count().unwrap();
     /// println!("The graph has {} nodes.", count);
     /// ```
-    pub fn node_count(&self) -> u64 {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn node_count(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("node_count");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::get_node_count(graph_t_ref)?)
+    }
+
+    /// Returns the total number of edges in the graph.
+    ///
+    /// Each edge is counted once, whichever of its two readings the graph
+    /// stores, so this is the number of items [`Graph::edges`] yields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// println!("The graph has {} edges.", graph.edge_count().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn edge_count(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("edge_count");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_edge_count(graph_t_ref)?)
+    }
+
+    /// Returns the total number of paths in the graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// assert_eq!(graph.path_count().unwrap() as usize, graph.get_path_names().unwrap().len());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn path_count(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("path_count");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_path_count(graph_t_ref)?)
+    }
+
+    /// Returns the total length of all node sequences in base pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// println!("The graph holds {} bp of sequence.", graph.total_length().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn total_length(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("total_length");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_total_length(graph_t_ref)?)
+    }
+
+    /// Returns `true` if the graph has a node with the given ID.
+    ///
+    /// The node queries fail with [`ErrorKind::NodeNotFound`] for a missing
+    /// node; this tests for one without building an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// if graph.has_node(42).unwrap() {
+    ///     println!("Node 42 is {} bp long.", graph.get_node_len(42).unwrap());
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn has_node(&self, node_id: u64) -> Result<bool, Error> {
+        let _timer = query_metrics::query("has_node");
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::get_node_count(graph_t_ref)
+        Ok(ffi::raw_has_node(graph_t_ref, node_id)?)
+    }
+
+    /// Returns summary statistics of the whole graph.
+    ///
+    /// The counts are those of `odgi stats -S`, gathered in one pass over the
+    /// graph rather than one query each. Edges are counted once, whichever of
+    /// their two readings the graph stores.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws while walking the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let stats = graph.stats().unwrap();
+    /// println!(
+    ///     "{} nodes, {} edges, {} bp, {} paths",
+    ///     stats.node_count, stats.edge_count, stats.total_length, stats.path_count
+    /// );
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn stats(&self) -> Result<GraphStats, Error> {
+        let _timer = query_metrics::query("stats");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_stats(graph_t_ref)?)
     }
 
     /// Returns a list of all path names in the graph.
     ///
+    /// Paths are listed in the order they were added to the graph, which for
+    /// graphs built from GFA is the order of the `P` and `W` lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// # use odgi_ffi::Graph;
     /// # let graph = Graph::load("my_graph.odgi").unwrap();
-    /// let paths = graph.get_path_names();
+    /// let paths = graph.get_path_names().unwrap();
     /// for path_name in paths {
     ///     println!("Found path: {}", path_name);
     /// }
     /// ```
-    pub fn get_path_names(&self) -> Vec<String> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_path_names(&self) -> Result<Vec<String>, Error> {
+        let _timer = query_metrics::query("get_path_names");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_path_names(graph_t_ref)?)
+    }
+
+    /// Returns `true` if the graph has a path with the given name.
+    ///
+    /// This looks the name up directly, without listing every path as
+    /// [`Graph::get_path_names`] does, and is `true` for empty paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// assert!(!graph.has_path("no such path").unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn has_path(&self, path_name: &str) -> Result<bool, Error> {
+        let _timer = query_metrics::query("has_path");
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_path_names(graph_t_ref)
+        Ok(ffi::graph_has_path(graph_t_ref, path_name)?)
     }
 
     /// Projects a 0-based linear coordinate on a path to graph coordinates.
@@ -120,31 +710,34 @@ impl Graph {
     /// * `path_name` - The name of the path to project onto.
     /// * `pos` - The 0-based nucleotide position along the path.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Returns `Some(PathPosition)` if the path exists and the position is
-    /// within its bounds. Returns `None` otherwise.
+    /// Returns an [`Error`] of kind [`ErrorKind::PathNotFound`] if the path
+    /// does not exist, and of kind [`ErrorKind::InvalidArgument`] if `pos` is
+    /// not within its bounds.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// # use odgi_ffi::Graph;
     /// # let graph = Graph::load("my_graph.odgi").unwrap();
-    /// if let Some(position) = graph.project("human_chr1", 1_000_000) {
-    ///     println!("Position 1M on chr1 is at node {} offset {}",
-    ///              position.node_id, position.offset);
-    /// } else {
-    ///     println!("Position not found on path.");
+    /// match graph.project("human_chr1", 1_000_000) {
+    ///     Ok(position) => println!("Position 1M on chr1 is at node {} offset {}",
+    ///                              position.node_id, position.offset),
+    ///     Err(e) => println!("Position not found on path: {}", e),
     /// }
     /// ```
-    pub fn project(&self, path_name: &str, pos: u64) -> Option<PathPosition> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn project(&self, path_name: &str, pos: u64) -> Result<PathPosition, Error> {
+        let _timer = query_metrics::query("project");
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        let result_ptr = ffi::graph_project(graph_t_ref, path_name, pos);
-
-        if result_ptr.is_null() {
-            None
-        } else {
-            Some(result_ptr.as_ref().unwrap().clone())
+        let result_ptr = ffi::graph_project(graph_t_ref, path_name, pos)?;
+        match result_ptr.as_ref() {
+            Some(position) => Ok(position.clone()),
+            None => {
+                self.check_path(path_name)?;
+                Err(Error::invalid_argument(format!("Position {} is past the end of path '{}'", pos, path_name)))
+            }
         }
     }
 
@@ -156,11 +749,18 @@ impl Graph {
     ///
     /// # Returns
     ///
-    /// Returns the sequence as a `String`. If the `node_id` is invalid,
-    /// an empty string is returned.
-    pub fn get_node_sequence(&self, node_id: u64) -> String {
+    /// Returns the sequence as a `String`. Case is preserved, so soft-masked
+    /// (lowercase) bases of the source GFA stay lowercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_node_sequence(&self, node_id: u64) -> Result<String, Error> {
+        let _timer = query_metrics::query("get_node_sequence");
+        self.check_node(node_id)?;
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_node_sequence(graph_t_ref, node_id)
+        Ok(ffi::graph_get_node_sequence(graph_t_ref, node_id)?)
     }
 
     /// Gets the length of the sequence for a given node ID.
@@ -169,71 +769,319 @@ impl Graph {
     ///
     /// * `node_id` - The ID of the node to query.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Returns the sequence length. If the `node_id` is invalid, `0` is returned.
-    pub fn get_node_len(&self, node_id: u64) -> u64 {
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_node_len(&self, node_id: u64) -> Result<u64, Error> {
+        let _timer = query_metrics::query("get_node_len");
+        self.check_node(node_id)?;
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_node_len(graph_t_ref, node_id)
+        Ok(ffi::graph_get_node_len(graph_t_ref, node_id)?)
     }
 
     /// Gets all successor edges for a given node ID.
     ///
     /// Successors are the nodes immediately following this one in the graph topology.
-    pub fn get_successors(&self, node_id: u64) -> Vec<Edge> {
+    /// Edges leaving the forward strand come first, and each group is sorted by
+    /// the ID and then the orientation (forward first) of the node they lead to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_successors(&self, node_id: u64) -> Result<Vec<Edge>, Error> {
+        let _timer = query_metrics::query("get_successors");
+        self.check_node(node_id)?;
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_successors(graph_t_ref, node_id)
+        let mut edges = ffi::graph_get_successors(graph_t_ref, node_id)?;
+        edges.sort_by_key(|e| (!e.from_orientation, e.to_node, !e.to_orientation));
+        Ok(edges)
     }
 
     /// Gets all predecessor edges for a given node ID.
     ///
     /// Predecessors are the nodes immediately preceding this one in the graph topology.
-    pub fn get_predecessors(&self, node_id: u64) -> Vec<Edge> {
-        let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_predecessors(graph_t_ref, node_id)
-    }
-
-    /// Gets the names of all paths that step on a given node ID.
-    pub fn get_paths_on_node(&self, node_id: u64) -> Vec<String> {
+    /// Edges entering the forward strand come first, and each group is sorted by
+    /// the ID and then the orientation (forward first) of the node they come from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_predecessors(&self, node_id: u64) -> Result<Vec<Edge>, Error> {
+        let _timer = query_metrics::query("get_predecessors");
+        self.check_node(node_id)?;
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_paths_on_node(graph_t_ref, node_id)
+        let mut edges = ffi::graph_get_predecessors(graph_t_ref, node_id)?;
+        edges.sort_by_key(|e| (!e.to_orientation, e.to_node, !e.from_orientation));
+        Ok(edges)
     }
 
-    /// Gets the total length of a path in base pairs.
-    ///
-    /// # Arguments
+    /// Gets the DNA sequence for a given node ID into a reusable buffer.
     ///
-    /// * `path_name` - The name of the path to measure.
+    /// This behaves like [`Graph::get_node_sequence`], but replaces the contents
+    /// of `sequence` instead of allocating a new `String`, so that loops over
+    /// many nodes can reuse one buffer.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Returns `Some(u64)` with the path length if the path exists.
-    /// Returns `None` if no path with that name is found in the graph.
-    pub fn get_path_length(&self, path_name: &str) -> Option<u64> {
-        let graph_t_ref = ffi::get_graph_t(&self.inner);
-        // We can use the existing get_path_names to check for existence first,
-        // making our Rust API safer and more idiomatic than the C++ one.
-        if !self.get_path_names().iter().any(|p| p == path_name) {
-            return None;
-        }
-        let length = ffi::graph_get_path_length(graph_t_ref, path_name);
-        Some(length)
-    }
-    // ADD THIS NEW PUBLIC METHOD
-    /// Gets the next node ID on a given path from a specified node.
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node
+    /// does not exist, leaving `sequence` empty.
     ///
-    /// # Returns
+    /// # Examples
     ///
-    /// Returns `Some(u64)` with the next node ID if the current node is on the
-    /// path and is not the last node. Returns `None` otherwise.
-    pub fn get_next_node_on_path(&self, node_id: u64, path_name: &str) -> Option<u64> {
-        let graph_t_ref = ffi::get_graph_t(&self.inner);
-        let next_node_id = ffi::graph_get_next_node_on_path(graph_t_ref, path_name, node_id);
-        if next_node_id >= 0 {
-            Some(next_node_id as u64)
-        } else {
-            None
-        }
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut sequence = String::new();
+    /// let mut gc = 0;
+    /// for node_id in 1..=graph.node_count().unwrap() {
+    ///     graph.get_node_sequence_into(node_id, &mut sequence).unwrap();
+    ///     gc += sequence.bytes().filter(|b| matches!(b, b'G' | b'C')).count();
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, sequence), err))]
+    pub fn get_node_sequence_into(&self, node_id: u64, sequence: &mut String) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_node_sequence_into");
+        let mut bytes = std::mem::take(sequence).into_bytes();
+        let filled = self.fill_node_sequence(node_id, &mut bytes);
+        *sequence = String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+        filled
+    }
+
+    /// Gets the DNA sequence for a given node ID as bytes.
+    ///
+    /// This behaves like [`Graph::get_node_sequence`], but skips the UTF-8
+    /// check of a `String`, which suits code that works on bases as bytes.
+    /// odgi decodes sequences from its packed node storage on every call, so
+    /// there is no sequence to borrow from the graph; use
+    /// [`Graph::get_node_sequence_bytes_into`] to avoid allocating per node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_node_sequence_bytes(&self, node_id: u64) -> Result<Vec<u8>, Error> {
+        let _timer = query_metrics::query("get_node_sequence_bytes");
+        let mut bytes = Vec::new();
+        self.fill_node_sequence(node_id, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Gets the DNA sequence for a given node ID as bytes into a reusable buffer.
+    ///
+    /// This behaves like [`Graph::get_node_sequence_bytes`], but replaces the
+    /// contents of `sequence` instead of allocating a new `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node
+    /// does not exist, leaving `sequence` empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut sequence = Vec::new();
+    /// let mut n_bases = 0;
+    /// for node_id in 1..=graph.node_count().unwrap() {
+    ///     graph.get_node_sequence_bytes_into(node_id, &mut sequence).unwrap();
+    ///     n_bases += sequence.iter().filter(|&&b| b == b'N' || b == b'n').count();
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, sequence), err))]
+    pub fn get_node_sequence_bytes_into(&self, node_id: u64, sequence: &mut Vec<u8>) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_node_sequence_bytes_into");
+        self.fill_node_sequence(node_id, sequence)
+    }
+
+    /// Replaces the contents of `bytes` with the sequence of a node, leaving
+    /// it empty if the node does not exist.
+    fn fill_node_sequence(&self, node_id: u64, bytes: &mut Vec<u8>) -> Result<(), Error> {
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        bytes.clear();
+        // odgi reports a length of zero for a missing node, which spares a
+        // separate lookup to tell that the node is there.
+        let len = ffi::graph_get_node_len(graph_t_ref, node_id)?;
+        if len == 0 {
+            return Err(Error::node_not_found(node_id));
+        }
+        bytes.resize(len as usize, 0);
+        Ok(ffi::graph_get_node_sequence_into(graph_t_ref, node_id, bytes)?)
+    }
+
+    /// Gets all successor edges for a given node ID into a reusable buffer.
+    ///
+    /// This behaves like [`Graph::get_successors`], including the order of the
+    /// edges, but replaces the contents of `edges` instead of allocating a new `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node
+    /// does not exist, leaving `edges` empty.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, edges), err))]
+    pub fn get_successors_into(&self, node_id: u64, edges: &mut Vec<Edge>) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_successors_into");
+        edges.clear();
+        self.check_node(node_id)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        ffi::graph_get_successors_into(graph_t_ref, node_id, edges)?;
+        // Edges are unique, so the unstable sort, which does not allocate, gives the same order.
+        edges.sort_unstable_by_key(|e| (!e.from_orientation, e.to_node, !e.to_orientation));
+        Ok(())
+    }
+
+    /// Gets all predecessor edges for a given node ID into a reusable buffer.
+    ///
+    /// This behaves like [`Graph::get_predecessors`], including the order of the
+    /// edges, but replaces the contents of `edges` instead of allocating a new `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node
+    /// does not exist, leaving `edges` empty.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, edges), err))]
+    pub fn get_predecessors_into(&self, node_id: u64, edges: &mut Vec<Edge>) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_predecessors_into");
+        edges.clear();
+        self.check_node(node_id)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        ffi::graph_get_predecessors_into(graph_t_ref, node_id, edges)?;
+        edges.sort_unstable_by_key(|e| (!e.to_orientation, e.to_node, !e.from_orientation));
+        Ok(())
+    }
+
+    /// Gets the names of all paths that step on a given node ID.
+    ///
+    /// A path is listed once per visit, in the order the graph stores the
+    /// steps on the node, which is the same on every run and platform for a
+    /// given graph file. On hub nodes of large graphs the
+    /// result can be very large; use [`Graph::get_steps_on_node`] to fetch it
+    /// in pages, or [`Graph::steps_on_node`] to stream it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_paths_on_node(&self, node_id: u64) -> Result<Vec<String>, Error> {
+        let _timer = query_metrics::query("get_paths_on_node");
+        self.check_node(node_id)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_paths_on_node(graph_t_ref, node_id)?)
+    }
+
+    /// Returns the depth of a node: the number of path steps on it.
+    ///
+    /// Unlike counting the distinct names from [`Graph::get_paths_on_node`],
+    /// every visit is counted, so a path that loops through the node twice
+    /// adds two. The depth is `0` if no path visits the node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::{Error, Graph};
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut covered = 0;
+    /// for node_id in 1..=graph.node_count()? {
+    ///     if graph.node_depth(node_id)? >= 2 {
+    ///         covered += 1;
+    ///     }
+    /// }
+    /// println!("{} nodes are covered at least twice.", covered);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn node_depth(&self, node_id: u64) -> Result<u64, Error> {
+        let _timer = query_metrics::query("node_depth");
+        self.check_node(node_id)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_node_step_count(graph_t_ref, node_id)?)
+    }
+
+
+    /// Gets one page of the steps on a node.
+    ///
+    /// The steps are listed in the same order as by [`Graph::get_paths_on_node`],
+    /// skipping the first `offset` and returning at most `limit`. Servers can
+    /// use this to paginate high-fanout queries without materializing all steps.
+    /// A page takes time proportional to `limit`, not to `offset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node to query.
+    /// * `offset` - The number of steps to skip.
+    /// * `limit` - The maximum number of steps to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns the steps in the requested range, which is empty if `offset`
+    /// is past the last step.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let second_page = graph.get_steps_on_node(42, 100, 100).unwrap();
+    /// for step in &second_page {
+    ///     println!("{} ({})", step.path_name, if step.is_forward { '+' } else { '-' });
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_steps_on_node(&self, node_id: u64, offset: u64, limit: u64) -> Result<Vec<NodeStep>, Error> {
+        let _timer = query_metrics::query("get_steps_on_node");
+        self.check_node(node_id)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_steps_on_node(graph_t_ref, node_id, offset, limit)?)
+    }
+
+    /// Gets the total length of a path in base pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `path_name` - The name of the path to measure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::PathNotFound`] if no path
+    /// with that name is found in the graph.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_path_length(&self, path_name: &str) -> Result<u64, Error> {
+        let _timer = query_metrics::query("get_path_length");
+        self.check_path(path_name)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_path_length(graph_t_ref, path_name)?)
+    }
+
+    /// Gets the next node ID on a given path from a specified node.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(u64)` with the next node ID if the node is on the path
+    /// and its first visit is not the last step. Returns `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::PathNotFound`] or
+    /// [`ErrorKind::NodeNotFound`] if the path or the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_next_node_on_path(&self, node_id: u64, path_name: &str) -> Result<Option<u64>, Error> {
+        let _timer = query_metrics::query("get_next_node_on_path");
+        self.check_path(path_name)?;
+        self.check_node(node_id)?;
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        let next_node_id = ffi::graph_get_next_node_on_path(graph_t_ref, path_name, node_id)?;
+        Ok(u64::try_from(next_node_id).ok())
     }
 
     /// Gets the names of all paths that traverse a specific directed edge.
@@ -255,89 +1103,674 @@ impl Graph {
     /// Returns a `Vec<String>` containing the names of all paths on the given edge.
     /// If the edge does not exist on any path, an empty vector is returned.
     ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if either node does not exist.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// # use odgi_ffi::Graph;
     /// # let graph = Graph::load("my_graph.odgi").unwrap();
     /// // Find paths going from the forward orientation of node 1 to the forward orientation of node 2.
-    /// let paths = graph.get_paths_on_edge(1, true, 2, true);
+    /// let paths = graph.get_paths_on_edge(1, true, 2, true).unwrap();
     /// for path_name in paths {
     ///     println!("Found path on edge 1+ -> 2+: {}", path_name);
     /// }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
     pub fn get_paths_on_edge(
         &self,
         from_node: u64,
         from_orientation: bool,
         to_node: u64,
         to_orientation: bool,
-    ) -> Vec<String> {
+    ) -> Result<Vec<String>, Error> {
+        let _timer = query_metrics::query("get_paths_on_edge");
+        self.check_node(from_node)?;
+        self.check_node(to_node)?;
         let graph_t_ref = ffi::get_graph_t(&self.inner);
-        ffi::graph_get_paths_on_edge(
+        Ok(ffi::graph_get_paths_on_edge(
             graph_t_ref,
             from_node,
             from_orientation,
             to_node,
-            to_orientation
-        )
+            to_orientation,
+        )?)
+    }
+
+    /// Gets the offset of a node's first base in the graph's current 1D sort order.
+    ///
+    /// The 1D order is the order in which the graph stores its nodes, as
+    /// established by `odgi sort`. Concatenating all node sequences in that
+    /// order yields the "pangenome coordinate" system used by binning and
+    /// visualization tools such as `odgi bin` and `odgi viz`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node to query.
+    ///
+    /// # Returns
+    ///
+    /// Returns the 0-based offset of the node's first base.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`] if the node does not exist.
+    ///
+    /// # Performance
+    ///
+    /// Each call walks the 1D order from the start, so it is linear in the
+    /// number of nodes. Use [`Graph::node_sort_offsets`] to look up many nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let offset = graph.node_sort_offset(42).unwrap();
+    /// println!("Node 42 starts at pangenome position {}", offset);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn node_sort_offset(&self, node_id: u64) -> Result<u64, Error> {
+        let _timer = query_metrics::query("node_sort_offset");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        let offset = ffi::graph_get_node_sort_offset(graph_t_ref, node_id)?;
+        u64::try_from(offset).map_err(|_| Error::node_not_found(node_id))
+    }
+
+    /// Gets the 1D sort offset of every node in a single pass.
+    ///
+    /// This is the batch form of [`Graph::node_sort_offset`]. The map is a
+    /// snapshot: it goes stale once the graph's node order changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws while walking the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let offsets = graph.node_sort_offsets().unwrap();
+    /// for node_id in [1, 2, 3] {
+    ///     println!("Node {} starts at {:?}", node_id, offsets.get(&node_id));
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn node_sort_offsets(&self) -> Result<HashMap<u64, u64>, Error> {
+        let _timer = query_metrics::query("node_sort_offsets");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        let offsets = ffi::graph_get_node_sort_offsets(graph_t_ref)?;
+        Ok(self.node_order()?.into_iter().zip(offsets).collect())
+    }
+
+    /// Finds the node covering a given offset in the graph's current 1D sort order.
+    ///
+    /// This is the inverse of [`Graph::node_sort_offset`].
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The 0-based offset in the concatenation of all node sequences.
+    ///
+    /// # Returns
+    ///
+    /// Returns `(node_id, offset_in_node)` for the node covering `offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::InvalidArgument`] if the
+    /// offset is not within the graph's total sequence length.
+    ///
+    /// # Performance
+    ///
+    /// Like [`Graph::node_sort_offset`], this walks the 1D order and is linear
+    /// in the number of nodes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn node_at_sort_offset(&self, offset: u64) -> Result<(u64, u64), Error> {
+        let _timer = query_metrics::query("node_at_sort_offset");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        let node_id = ffi::graph_get_node_at_sort_offset(graph_t_ref, offset)?;
+        if node_id == 0 {
+            return Err(Error::invalid_argument(format!(
+                "Offset {} is past the end of the graph's sort order",
+                offset
+            )));
+        }
+        let node_start = self.node_sort_offset(node_id)?;
+        Ok((node_id, offset - node_start))
+    }
+
+    /// Reads through every node, edge, and path step of the graph once.
+    ///
+    /// Queries on a freshly loaded graph are slower at first, because the
+    /// deserialized structures are paged in and cached on demand. Latency-sensitive
+    /// services can call this after [`Graph::load`] during deployment or
+    /// start-up, so that the cost is paid before the first user query rather than by it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::Exception`] if odgi throws while walking the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// graph.prefault().unwrap();
+    /// // Ready to serve queries.
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn prefault(&self) -> Result<(), Error> {
+        let _timer = query_metrics::query("prefault");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        std::hint::black_box(ffi::graph_prefault(graph_t_ref)?);
+        Ok(())
+    }
+
+    /// Returns the underlying C++ graph, for the [`raw`](crate::raw) module.
+    pub(crate) fn graph_t(&self) -> &ffi::graph_t {
+        ffi::get_graph_t(&self.inner)
+    }
+
+    /// Returns the IDs of all nodes in the graph, in ascending order.
+    pub(crate) fn node_ids(&self) -> Result<Vec<u64>, Error> {
+        query_metrics::ffi_call("node_ids");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_node_ids(graph_t_ref)?)
+    }
+
+    /// Returns the IDs of all nodes in the graph's 1D order.
+    pub(crate) fn node_order(&self) -> Result<Vec<u64>, Error> {
+        query_metrics::ffi_call("node_order");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_node_order(graph_t_ref)?)
+    }
+
+    /// Returns every step of a path in path order.
+    ///
+    /// An empty vector is returned if the path does not exist.
+    pub(crate) fn raw_path_steps(&self, path_name: &str) -> Result<Vec<ffi::PathStep>, Error> {
+        query_metrics::ffi_call("raw_path_steps");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_path_steps(graph_t_ref, path_name)?)
+    }
+
+    /// Returns every edge of the graph exactly once, sorted by their endpoints.
+    pub(crate) fn raw_edges(&self) -> Result<Vec<ffi::GraphEdge>, Error> {
+        query_metrics::ffi_call("raw_edges");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        let mut edges = ffi::graph_get_edges(graph_t_ref)?;
+        edges.sort_by_key(|e| (e.from_node, !e.from_orientation, e.to_node, !e.to_orientation));
+        Ok(edges)
+    }
+
+    /// Returns the IDs of the nodes at the given ranks in ascending ID order.
+    ///
+    /// Ranks past the last node are skipped.
+    pub(crate) fn node_ids_at_ranks(&self, ranks: &[u64]) -> Result<Vec<u64>, Error> {
+        query_metrics::ffi_call("node_ids_at_ranks");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_node_ids_at_ranks(graph_t_ref, ranks)?)
+    }
+
+    /// Returns the names of the paths at the given ranks in the order of [`Graph::get_path_names`].
+    ///
+    /// Ranks past the last path are skipped.
+    pub(crate) fn path_names_at_ranks(&self, ranks: &[u64]) -> Result<Vec<String>, Error> {
+        query_metrics::ffi_call("path_names_at_ranks");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_path_names_at_ranks(graph_t_ref, ranks)?)
+    }
+
+    /// Projects ascending path offsets in a single pass over the path.
+    ///
+    /// Offsets past the end of the path are skipped, as are all offsets if the path does not exist.
+    pub(crate) fn project_sorted(&self, path_name: &str, positions: &[u64]) -> Result<Vec<PathPosition>, Error> {
+        query_metrics::ffi_call("project_sorted");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_project_sorted(graph_t_ref, path_name, positions)?)
+    }
+
+    /// Returns every step on a node with the path offset where it starts.
+    ///
+    /// Steps on paths indexed at load time are looked up in their index; odgi
+    /// locates the others by walking back to the start of their path.
+    pub(crate) fn node_path_positions(&self, node_id: u64) -> Result<Vec<ffi::StepPosition>, Error> {
+        query_metrics::ffi_call("node_path_positions");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        let indexed: Vec<String> = self.path_indexes.keys().cloned().collect();
+        let mut positions = ffi::graph_get_node_path_positions(graph_t_ref, node_id, &indexed)?;
+        for (path_name, index) in &self.path_indexes {
+            positions.extend(index.steps_on_node(node_id).iter().map(|&i| ffi::StepPosition {
+                path_name: path_name.clone(),
+                start: index.starts[i],
+                is_forward: index.steps[i].is_forward,
+            }));
+        }
+        Ok(positions)
+    }
+
+    /// Returns the length of each of `node_ids`, or `0` for a node that does not exist.
+    pub(crate) fn node_lens(&self, node_ids: &[u64]) -> Result<Vec<u64>, Error> {
+        query_metrics::ffi_call("node_lens");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_node_lens(graph_t_ref, node_ids)?)
+    }
+
+    /// Returns an [`ErrorKind::NodeNotFound`] error if the graph has no node with this ID.
+    pub(crate) fn check_node(&self, node_id: u64) -> Result<(), Error> {
+        if self.has_node(node_id)? { Ok(()) } else { Err(Error::node_not_found(node_id)) }
+    }
+
+    /// Returns an [`ErrorKind::PathNotFound`] error if the graph has no path with this name.
+    pub(crate) fn check_path(&self, path_name: &str) -> Result<(), Error> {
+        if self.has_path(path_name)? { Ok(()) } else { Err(Error::path_not_found(path_name)) }
+    }
+    /// Returns `true` if an edge leads from one oriented node to another.
+    ///
+    /// Both readings of an edge are found: `a -> b` exists if `b.flip() -> a.flip()` does.
+    pub(crate) fn edge_exists(&self, from: u64, from_forward: bool, to: u64, to_forward: bool) -> Result<bool, Error> {
+        query_metrics::ffi_call("edge_exists");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_has_edge(graph_t_ref, from, from_forward, to, to_forward)?)
+    }
+
+    /// Creates a new graph without any nodes, edges, or paths.
+    pub(crate) fn empty() -> Result<Self, Error> {
+        query_metrics::ffi_call("empty");
+        Ok(Graph { inner: ffi::new_graph()?, journal: Journal::default(), path_indexes: HashMap::new() })
+    }
+
+    /// Returns odgi's graph for an edit, dropping the path indexes it may invalidate.
+    fn graph_t_mut(&mut self) -> Pin<&mut ffi::graph_t> {
+        self.path_indexes.clear();
+        ffi::get_graph_t_mut(self.inner.pin_mut())
+    }
+
+    // The mutators return odgi's exceptions as errors instead of panicking, and
+    // record how to undo an edit only once odgi has made it, so a failed edit
+    // never leaves the journal out of step with the graph.
+
+    /// Adds a node with the given ID and sequence.
+    ///
+    /// Returns `false` if the ID is `0` or already in use.
+    pub(crate) fn add_node(&mut self, node_id: u64, sequence: &str) -> Result<bool, Error> {
+        query_metrics::ffi_call("add_node");
+        let graph_t_mut = self.graph_t_mut();
+        let added = ffi::graph_create_node(graph_t_mut, node_id, sequence)?;
+        if added {
+            self.journal.record(Undo::RemoveNode(node_id));
+        }
+        Ok(added)
+    }
+
+    /// Adds an edge between two oriented nodes, unless it already exists.
+    ///
+    /// Returns `false` if either node does not exist.
+    pub(crate) fn add_edge(
+        &mut self,
+        from_node: u64,
+        from_orientation: bool,
+        to_node: u64,
+        to_orientation: bool,
+    ) -> Result<bool, Error> {
+        query_metrics::ffi_call("add_edge");
+        let existed = self.journal.is_recording()
+            && self.has_node(from_node)?
+            && self
+                .get_successors(from_node)?
+                .iter()
+                .any(|e| e.from_orientation == from_orientation && e.to_node == to_node && e.to_orientation == to_orientation);
+        let graph_t_mut = self.graph_t_mut();
+        let added = ffi::graph_create_edge(graph_t_mut, from_node, from_orientation, to_node, to_orientation)?;
+        if added && !existed {
+            self.journal.record(Undo::RemoveEdge(ffi::GraphEdge { from_node, from_orientation, to_node, to_orientation }));
+        }
+        Ok(added)
+    }
+
+    /// Creates a new, empty path.
+    ///
+    /// Returns `false` if a path with this name already exists.
+    pub(crate) fn add_path(&mut self, path_name: &str) -> Result<bool, Error> {
+        query_metrics::ffi_call("add_path");
+        let graph_t_mut = self.graph_t_mut();
+        let added = ffi::graph_create_path(graph_t_mut, path_name)?;
+        if added {
+            self.journal.record(Undo::RemovePath(path_name.to_string()));
+        }
+        Ok(added)
+    }
+
+    /// Appends a step visiting `node_id` in the given orientation to the end of a path.
+    ///
+    /// Returns `false` if the path or the node does not exist.
+    pub(crate) fn append_step(&mut self, path_name: &str, node_id: u64, is_forward: bool) -> Result<bool, Error> {
+        query_metrics::ffi_call("append_step");
+        let length = if self.journal.is_recording() { self.path_step_count(path_name)? } else { 0 };
+        let graph_t_mut = self.graph_t_mut();
+        let appended = ffi::graph_append_step(graph_t_mut, path_name, node_id, is_forward)?;
+        if appended {
+            self.journal.record(Undo::TruncatePath(path_name.to_string(), length));
+        }
+        Ok(appended)
+    }
+
+    /// Removes a path and all of its steps.
+    ///
+    /// Returns `false` if the path does not exist.
+    pub(crate) fn remove_path(&mut self, path_name: &str) -> Result<bool, Error> {
+        query_metrics::ffi_call("remove_path");
+        let steps = if self.journal.is_recording() { self.raw_path_steps(path_name)? } else { Vec::new() };
+        let graph_t_mut = self.graph_t_mut();
+        let removed = ffi::graph_destroy_path(graph_t_mut, path_name)?;
+        if removed {
+            self.journal.record(Undo::RestorePath(path_name.to_string(), steps));
+        }
+        Ok(removed)
+    }
+
+    /// Removes every step of a path after the first `length`.
+    ///
+    /// Returns `false` if the path does not exist.
+    pub(crate) fn truncate_path(&mut self, path_name: &str, length: u64) -> Result<bool, Error> {
+        query_metrics::ffi_call("truncate_path");
+        let steps = if self.journal.is_recording() { self.raw_path_steps(path_name)? } else { Vec::new() };
+        let graph_t_mut = self.graph_t_mut();
+        let truncated = ffi::graph_truncate_path(graph_t_mut, path_name, length)?;
+        if truncated {
+            self.journal.record(Undo::RestorePath(path_name.to_string(), steps));
+        }
+        Ok(truncated)
+    }
+
+    /// Removes a node together with its edges and the path steps on it.
+    ///
+    /// Returns `false` if the node does not exist.
+    pub(crate) fn remove_node(&mut self, node_id: u64) -> Result<bool, Error> {
+        query_metrics::ffi_call("remove_node");
+        let undo = if self.journal.is_recording() { journal::node_undo(self, node_id)? } else { Vec::new() };
+        let graph_t_mut = self.graph_t_mut();
+        let removed = ffi::graph_destroy_node(graph_t_mut, node_id)?;
+        if removed {
+            undo.into_iter().for_each(|u| self.journal.record(u));
+        }
+        Ok(removed)
+    }
+
+    /// Removes the edge between two oriented nodes.
+    ///
+    /// Returns `false` if the edge does not exist.
+    pub(crate) fn remove_edge(
+        &mut self,
+        from_node: u64,
+        from_orientation: bool,
+        to_node: u64,
+        to_orientation: bool,
+    ) -> Result<bool, Error> {
+        query_metrics::ffi_call("remove_edge");
+        let graph_t_mut = self.graph_t_mut();
+        let removed = ffi::graph_destroy_edge(graph_t_mut, from_node, from_orientation, to_node, to_orientation)?;
+        if removed {
+            self.journal.record(Undo::AddEdge(ffi::GraphEdge { from_node, from_orientation, to_node, to_orientation }));
+        }
+        Ok(removed)
+    }
+
+    /// Splits a node at the given offsets along its forward strand.
+    ///
+    /// Paths over the node are rewritten to visit the pieces. Returns the IDs of
+    /// the pieces in forward order, or an empty vector if the node does not exist.
+    pub(crate) fn divide_node(&mut self, node_id: u64, offsets: &[u64]) -> Result<Vec<u64>, Error> {
+        query_metrics::ffi_call("divide_node");
+        let undo = if self.journal.is_recording() { journal::node_undo(self, node_id)? } else { Vec::new() };
+        let graph_t_mut = self.graph_t_mut();
+        let pieces = ffi::graph_divide_node(graph_t_mut, node_id, offsets)?;
+        if !pieces.is_empty() {
+            undo.into_iter().for_each(|u| self.journal.record(u));
+            self.journal.record(Undo::RemoveNodes(pieces.clone()));
+        }
+        Ok(pieces)
+    }
+
+    /// Returns the number of steps in a path, or `0` if the path does not exist.
+    pub(crate) fn path_step_count(&self, path_name: &str) -> Result<u64, Error> {
+        query_metrics::ffi_call("path_step_count");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::graph_get_path_step_count(graph_t_ref, path_name)?)
+    }
+
+    /// Rearranges the nodes into the given 1D order, keeping their IDs.
+    ///
+    /// `node_ids` must list every node exactly once. Returns `false` if it
+    /// names a node that does not exist.
+    pub(crate) fn apply_node_order(&mut self, node_ids: &[u64]) -> Result<bool, Error> {
+        query_metrics::ffi_call("apply_node_order");
+        let previous = if self.journal.is_recording() { self.node_order()? } else { Vec::new() };
+        let graph_t_mut = self.graph_t_mut();
+        let applied = ffi::graph_apply_node_order(graph_t_mut, node_ids)?;
+        if applied {
+            self.journal.record(Undo::ApplyNodeOrder(previous));
+        }
+        Ok(applied)
+    }
+
+    /// Writes the graph to `path` in the ODGI format.
+    pub(crate) fn save_to(&self, path: &str) -> Result<(), Error> {
+        query_metrics::ffi_call("save_to");
+        let graph_t_ref = ffi::get_graph_t(&self.inner);
+        Ok(ffi::save_graph(graph_t_ref, path)?)
     }
 }
 
 // --- MOCK IMPLEMENTATION (for docs.rs) ---
-#[cfg(feature = "docs-only")]
+// The `wasm` feature enables `docs-only` and replaces these stubs with `wasm.rs`.
+#[cfg(all(feature = "docs-only", not(feature = "wasm")))]
 impl Graph {
     /// Loads an ODGI graph from a file into memory.
     pub fn load(_path: &str) -> Result<Self, Error> { Ok(Graph { _inner: () }) }
 
+    /// Loads an ODGI graph from a buffer in memory.
+    pub fn load_from_bytes(_bytes: &[u8]) -> Result<Self, Error> { Ok(Graph { _inner: () }) }
+
+    /// Loads an ODGI graph from any reader, such as a network stream.
+    pub fn load_from_reader<R: std::io::Read>(_reader: R) -> Result<Self, Error> { Ok(Graph { _inner: () }) }
+
+    /// Writes the graph to a file in the ODGI format.
+    pub fn save(&self, _path: &str) -> Result<(), Error> { Ok(()) }
+
+    /// Builds a graph from a GFA file in this process.
+    pub fn from_gfa_file(_path: &str) -> Result<Self, Error> { Ok(Graph { _inner: () }) }
+
+    /// Builds a graph from GFA text in this process.
+    pub fn from_gfa_str(_gfa: &str) -> Result<Self, Error> { Ok(Graph { _inner: () }) }
+
     /// Returns the total number of nodes in the graph.
-    pub fn node_count(&self) -> u64 { 0 }
+    pub fn node_count(&self) -> Result<u64, Error> { Ok(0) }
+
+    /// Returns the total number of edges in the graph.
+    pub fn edge_count(&self) -> Result<u64, Error> { Ok(0) }
+
+    /// Returns the total number of paths in the graph.
+    pub fn path_count(&self) -> Result<u64, Error> { Ok(0) }
+
+    /// Returns the total length of all node sequences in base pairs.
+    pub fn total_length(&self) -> Result<u64, Error> { Ok(0) }
+
+    /// Returns `true` if the graph has a node with the given ID.
+    pub fn has_node(&self, _node_id: u64) -> Result<bool, Error> { Ok(false) }
+
+    /// Returns summary statistics of the whole graph.
+    pub fn stats(&self) -> Result<GraphStats, Error> { Ok(GraphStats::default()) }
 
     /// Returns a list of all path names in the graph.
-    pub fn get_path_names(&self) -> Vec<String> { vec![] }
+    pub fn get_path_names(&self) -> Result<Vec<String>, Error> { Ok(vec![]) }
+
+    /// Returns `true` if the graph has a path with the given name.
+    pub fn has_path(&self, _path_name: &str) -> Result<bool, Error> { Ok(false) }
 
     /// Projects a 0-based linear coordinate on a path to graph coordinates.
-    pub fn project(&self, _path_name: &str, _pos: u64) -> Option<PathPosition> { None }
+    pub fn project(&self, path_name: &str, _pos: u64) -> Result<PathPosition, Error> { Err(Error::path_not_found(path_name)) }
 
     /// Gets the DNA sequence for a given node ID.
-    pub fn get_node_sequence(&self, _node_id: u64) -> String { String::new() }
+    pub fn get_node_sequence(&self, node_id: u64) -> Result<String, Error> { Err(Error::node_not_found(node_id)) }
 
     /// Gets the length of the sequence for a given node ID.
-    pub fn get_node_len(&self, _node_id: u64) -> u64 { 0 }
+    pub fn get_node_len(&self, node_id: u64) -> Result<u64, Error> { Err(Error::node_not_found(node_id)) }
 
     /// Gets all successor edges for a given node ID.
-    pub fn get_successors(&self, _node_id: u64) -> Vec<Edge> { vec![] }
+    pub fn get_successors(&self, node_id: u64) -> Result<Vec<Edge>, Error> { Err(Error::node_not_found(node_id)) }
+
+    /// Gets the DNA sequence for a given node ID into a reusable buffer.
+    pub fn get_node_sequence_into(&self, node_id: u64, sequence: &mut String) -> Result<(), Error> {
+        sequence.clear();
+        Err(Error::node_not_found(node_id))
+    }
+
+    /// Gets the DNA sequence for a given node ID as bytes.
+    pub fn get_node_sequence_bytes(&self, node_id: u64) -> Result<Vec<u8>, Error> { Err(Error::node_not_found(node_id)) }
+
+    /// Gets the DNA sequence for a given node ID as bytes into a reusable buffer.
+    pub fn get_node_sequence_bytes_into(&self, node_id: u64, sequence: &mut Vec<u8>) -> Result<(), Error> {
+        sequence.clear();
+        Err(Error::node_not_found(node_id))
+    }
+
+    /// Gets all successor edges for a given node ID into a reusable buffer.
+    pub fn get_successors_into(&self, node_id: u64, edges: &mut Vec<Edge>) -> Result<(), Error> {
+        edges.clear();
+        Err(Error::node_not_found(node_id))
+    }
+
+    /// Gets all predecessor edges for a given node ID into a reusable buffer.
+    pub fn get_predecessors_into(&self, node_id: u64, edges: &mut Vec<Edge>) -> Result<(), Error> {
+        edges.clear();
+        Err(Error::node_not_found(node_id))
+    }
 
     /// Gets all predecessor edges for a given node ID.
-    pub fn get_predecessors(&self, _node_id: u64) -> Vec<Edge> { vec![] }
+    pub fn get_predecessors(&self, node_id: u64) -> Result<Vec<Edge>, Error> { Err(Error::node_not_found(node_id)) }
 
     /// Gets the names of all paths that step on a given node ID.
-    pub fn get_paths_on_node(&self, _node_id: u64) -> Vec<String> { vec![] }
+    pub fn get_paths_on_node(&self, node_id: u64) -> Result<Vec<String>, Error> { Err(Error::node_not_found(node_id)) }
+
+    /// Returns the depth of a node: the number of path steps on it.
+    pub fn node_depth(&self, node_id: u64) -> Result<u64, Error> { Err(Error::node_not_found(node_id)) }
+
+    /// Gets one page of the steps on a node.
+    pub fn get_steps_on_node(&self, node_id: u64, _offset: u64, _limit: u64) -> Result<Vec<NodeStep>, Error> {
+        Err(Error::node_not_found(node_id))
+    }
 
     /// Gets the total length of a path in base pairs.
-    pub fn get_path_length(&self, _path_name: &str) -> Option<u64> { None }
+    pub fn get_path_length(&self, path_name: &str) -> Result<u64, Error> { Err(Error::path_not_found(path_name)) }
+
+    /// Gets the next node ID on a given path from a specified node.
+    pub fn get_next_node_on_path(&self, _node_id: u64, path_name: &str) -> Result<Option<u64>, Error> {
+        Err(Error::path_not_found(path_name))
+    }
 
     /// Gets the names of all paths that traverse a specific directed edge.
     pub fn get_paths_on_edge(
         &self,
+        from_node: u64,
+        _from_orientation: bool,
+        _to_node: u64,
+        _to_orientation: bool,
+    ) -> Result<Vec<String>, Error> {
+        Err(Error::node_not_found(from_node))
+    }
+
+    /// Gets the offset of a node's first base in the graph's current 1D sort order.
+    pub fn node_sort_offset(&self, node_id: u64) -> Result<u64, Error> { Err(Error::node_not_found(node_id)) }
+
+    /// Finds the node covering a given offset in the graph's current 1D sort order.
+    pub fn node_at_sort_offset(&self, offset: u64) -> Result<(u64, u64), Error> {
+        Err(Error::invalid_argument(format!("Offset {} is past the end of the graph's sort order", offset)))
+    }
+
+    /// Gets the 1D sort offset of every node in a single pass.
+    pub fn node_sort_offsets(&self) -> Result<HashMap<u64, u64>, Error> { Ok(HashMap::new()) }
+
+    /// Reads through every node, edge, and path step of the graph once.
+    pub fn prefault(&self) -> Result<(), Error> { Ok(()) }
+
+    pub(crate) fn node_ids(&self) -> Result<Vec<u64>, Error> { Ok(vec![]) }
+
+    pub(crate) fn raw_path_steps(&self, _path_name: &str) -> Result<Vec<ffi::PathStep>, Error> { Ok(vec![]) }
+
+    pub(crate) fn raw_edges(&self) -> Result<Vec<ffi::GraphEdge>, Error> { Ok(vec![]) }
+
+    pub(crate) fn node_order(&self) -> Result<Vec<u64>, Error> { Ok(vec![]) }
+
+    pub(crate) fn node_ids_at_ranks(&self, _ranks: &[u64]) -> Result<Vec<u64>, Error> { Ok(vec![]) }
+
+    pub(crate) fn path_names_at_ranks(&self, _ranks: &[u64]) -> Result<Vec<String>, Error> { Ok(vec![]) }
+
+    pub(crate) fn project_sorted(&self, _path_name: &str, _positions: &[u64]) -> Result<Vec<PathPosition>, Error> { Ok(vec![]) }
+
+    pub(crate) fn check_node(&self, node_id: u64) -> Result<(), Error> { Err(Error::node_not_found(node_id)) }
+
+    pub(crate) fn check_path(&self, path_name: &str) -> Result<(), Error> { Err(Error::path_not_found(path_name)) }
+
+    pub(crate) fn edge_exists(&self, _from: u64, _from_forward: bool, _to: u64, _to_forward: bool) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn node_path_positions(&self, _node_id: u64) -> Result<Vec<ffi::StepPosition>, Error> { Ok(vec![]) }
+
+    pub(crate) fn node_lens(&self, node_ids: &[u64]) -> Result<Vec<u64>, Error> { Ok(vec![0; node_ids.len()]) }
+
+    pub(crate) fn empty() -> Result<Self, Error> { Ok(Graph { _inner: () }) }
+
+    pub(crate) fn add_node(&mut self, _node_id: u64, _sequence: &str) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn add_edge(
+        &mut self,
         _from_node: u64,
         _from_orientation: bool,
         _to_node: u64,
         _to_orientation: bool,
-    ) -> Vec<String> {
-        vec![]
+    ) -> Result<bool, Error> {
+        Ok(false)
     }
+
+    pub(crate) fn add_path(&mut self, _path_name: &str) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn append_step(&mut self, _path_name: &str, _node_id: u64, _is_forward: bool) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn remove_path(&mut self, _path_name: &str) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn truncate_path(&mut self, _path_name: &str, _length: u64) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn remove_node(&mut self, _node_id: u64) -> Result<bool, Error> { Ok(false) }
+
+    pub(crate) fn remove_edge(
+        &mut self,
+        _from_node: u64,
+        _from_orientation: bool,
+        _to_node: u64,
+        _to_orientation: bool,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    pub(crate) fn divide_node(&mut self, _node_id: u64, _offsets: &[u64]) -> Result<Vec<u64>, Error> { Ok(Vec::new()) }
+
+    pub(crate) fn apply_node_order(&mut self, _node_ids: &[u64]) -> Result<bool, Error> { Ok(false) }
 }
 
 
 /// Marks the `Graph` struct as safe to send between threads.
-// The `unsafe` keyword is our guarantee to the compiler that we've ensured
-// the underlying C++ object is safe to be sent and accessed across threads,
-// which is true in our read-only use case.
+// SAFETY: the `graph_t` is a heap object owned solely by this `Graph`, with no
+// thread-local state, so it can be dropped or used on any thread.
 unsafe impl Send for Graph {}
 
 /// Marks the `Graph` struct as safe to share between threads.
-// The `unsafe` keyword is our guarantee to the compiler that we've ensured
-// the underlying C++ object is safe to be sent and accessed across threads,
-// which is true in our read-only use case.
+// SAFETY: through `&Graph`, odgi is only reached via `get_graph_t`, whose
+// `const graph_t&` allows only const queries, and odgi's const queries are
+// safe to run concurrently. Every mutation goes through `Graph::graph_t_mut`,
+// which takes `&mut self`, so no query runs at the same time as an edit. The
+// `path_indexes` map is likewise only read through `&self`, and filled and
+// cleared only through `&mut self`.
 unsafe impl Sync for Graph {}
\ No newline at end of file
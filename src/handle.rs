@@ -0,0 +1,230 @@
+// src/handle.rs
+
+//! Oriented nodes, for walking the bidirected graph.
+
+use std::fmt;
+use std::vec::IntoIter;
+use super::ffi::GraphEdge;
+use super::graph::{Error, Graph};
+use super::seq::reverse_complement;
+
+/// A node in one orientation.
+///
+/// In a bidirected graph every node can be read on its forward or its reverse
+/// strand, and which edges lead on from it depends on the strand. A `Handle`
+/// keeps the node ID and the orientation together, packed into one integer
+/// the way odgi packs its handles, so walks can be written without tracking
+/// orientation separately.
+///
+/// Unlike a [`RawHandle`](crate::raw::RawHandle), a `Handle` holds the node ID
+/// itself rather than the graph's internal node number, so it can be built
+/// and read without the graph, and is safe to pass to any graph.
+///
+/// # Examples
+///
+/// ```rust
+/// use odgi_ffi::Handle;
+///
+/// let handle = Handle::forward(12);
+/// assert_eq!(handle.flip(), Handle::new(12, false));
+/// assert_eq!(handle.flip().node_id(), 12);
+/// assert_eq!(handle.flip().to_string(), "12-");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(u64);
+
+impl Handle {
+    /// Creates the handle of a node in the given orientation.
+    pub fn new(node_id: u64, is_forward: bool) -> Self {
+        Handle((node_id << 1) | u64::from(!is_forward))
+    }
+
+    /// Creates the handle of a node's forward strand.
+    pub fn forward(node_id: u64) -> Self {
+        Self::new(node_id, true)
+    }
+
+    /// Returns the ID of the node.
+    pub fn node_id(self) -> u64 {
+        self.0 >> 1
+    }
+
+    /// Returns `true` if the handle is the forward strand of its node.
+    pub fn is_forward(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    /// Returns the handle of the same node in the other orientation.
+    pub fn flip(self) -> Self {
+        Handle(self.0 ^ 1)
+    }
+}
+
+impl fmt::Display for Handle {
+    /// Formats the handle as its node ID followed by `+` or `-`, as in GFA walks.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.node_id(), if self.is_forward() { '+' } else { '-' })
+    }
+}
+
+/// Which side of a handle to follow edges from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The end of the handle, leading to the handles that can follow it.
+    Right,
+    /// The start of the handle, leading to the handles that can precede it.
+    Left,
+}
+
+/// An iterator over every edge of a graph, created by [`Graph::edges`].
+///
+/// The edges are read from the graph in a single call when the iterator is created.
+#[derive(Debug, Clone)]
+pub struct Edges {
+    edges: IntoIter<GraphEdge>,
+}
+
+fn edge_handles(edge: GraphEdge) -> (Handle, Handle) {
+    (Handle::new(edge.from_node, edge.from_orientation), Handle::new(edge.to_node, edge.to_orientation))
+}
+
+impl Iterator for Edges {
+    type Item = (Handle, Handle);
+
+    fn next(&mut self) -> Option<(Handle, Handle)> {
+        self.edges.next().map(edge_handles)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Edges {
+    fn next_back(&mut self) -> Option<(Handle, Handle)> {
+        self.edges.next_back().map(edge_handles)
+    }
+}
+
+impl ExactSizeIterator for Edges {}
+
+impl Graph {
+    /// Iterates over every edge of the graph exactly once, as `(from, to)` handles.
+    ///
+    /// An edge of a bidirected graph can be read in two directions: `a -> b`
+    /// is the same edge as `b.flip() -> a.flip()`. Each edge is yielded in one
+    /// of its two readings, and the edges are sorted by their endpoints. To
+    /// compare edges regardless of reading, convert them to
+    /// [canonical](crate::EdgeKey::canonical) [`EdgeKey`](crate::EdgeKey)s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the edges.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let edges = graph.edges().unwrap();
+    /// let total = edges.len();
+    /// let inverting = edges.filter(|(from, to)| from.is_forward() != to.is_forward()).count();
+    /// println!("{} of {} edges change strand", inverting, total);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn edges(&self) -> Result<Edges, Error> {
+        Ok(Edges { edges: self.raw_edges()?.into_iter() })
+    }
+
+    /// Returns the handles joined by an edge to one side of a handle.
+    ///
+    /// Going [`Direction::Right`] yields every handle that can be read after
+    /// `handle`; going [`Direction::Left`] yields every handle that can be
+    /// read before it. Both are in the orientation in which they are read, so
+    /// a walk continues by calling `neighbors` on the result in the same
+    /// direction. The handles are sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Direction, Graph, Handle};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// // Follow the first branch from node 1 for a few steps.
+    /// let mut handle = Handle::forward(1);
+    /// for _ in 0..10 {
+    ///     match graph.neighbors(handle, Direction::Right).unwrap().first() {
+    ///         Some(&next) => handle = next,
+    ///         None => break,
+    ///     }
+    ///     println!("{} {}", handle, graph.sequence(handle).unwrap());
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn neighbors(&self, handle: Handle, direction: Direction) -> Result<Vec<Handle>, Error> {
+        let mut handles: Vec<Handle> = match direction {
+            Direction::Right => self
+                .get_successors(handle.node_id())?
+                .into_iter()
+                .filter(|edge| edge.from_orientation == handle.is_forward())
+                .map(|edge| Handle::new(edge.to_node, edge.to_orientation))
+                .collect(),
+            // What precedes a handle is what follows its other strand, read on the other strand.
+            Direction::Left => self
+                .neighbors(handle.flip(), Direction::Right)?
+                .into_iter()
+                .map(Handle::flip)
+                .collect(),
+        };
+        handles.sort();
+        Ok(handles)
+    }
+
+    /// Returns `true` if the graph has an edge that leads from `from` to `to`.
+    ///
+    /// An edge can be read in either direction, so `has_edge(a, b)` and
+    /// `has_edge(b.flip(), a.flip())` always agree. Returns `false` if either
+    /// node does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while looking up the edge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Graph, Handle};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let (a, b) = (Handle::forward(1), Handle::forward(2));
+    /// assert_eq!(graph.has_edge(a, b).unwrap(), graph.has_edge(b.flip(), a.flip()).unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn has_edge(&self, from: Handle, to: Handle) -> Result<bool, Error> {
+        self.edge_exists(from.node_id(), from.is_forward(), to.node_id(), to.is_forward())
+    }
+
+    /// Returns the sequence of a node read in the orientation of a handle.
+    ///
+    /// The sequence of a reverse handle is the reverse complement of the
+    /// node's sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn sequence(&self, handle: Handle) -> Result<String, Error> {
+        let sequence = self.get_node_sequence(handle.node_id())?;
+        Ok(if handle.is_forward() {
+            sequence
+        } else {
+            reverse_complement(&sequence)
+        })
+    }
+}
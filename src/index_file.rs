@@ -0,0 +1,113 @@
+// src/index_file.rs
+
+//! Persisting minimizer indexes next to the graph they were built from.
+//!
+//! Minimizer indexes are the expensive indexes to build, since every path is
+//! hashed k-mer by k-mer. Path step and position lookups are not persisted:
+//! they are rebuilt from the graph with one walk of each path, which
+//! [`LoadOptions::index_paths`](crate::LoadOptions::index_paths) can do at load time.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use super::atomic::AtomicFile;
+use super::graph::{Error, Graph};
+use super::minimizer::MinimizerIndex;
+
+/// Identifies index files written by [`Graph::save_minimizer_indexes`].
+const MAGIC: &[u8; 4] = b"OFIX";
+/// The version of the index file format.
+const VERSION: u32 = 1;
+
+impl Graph {
+    /// Saves minimizer indexes built from this graph to a file.
+    ///
+    /// Several indexes, for example with different `(k, w)` parameters, can
+    /// be saved together. The file records the format version and the graph's
+    /// [`Graph::fingerprint`], so that [`Graph::load_minimizer_indexes`] can
+    /// reject files that were written by an incompatible version or for a
+    /// different graph. The file is replaced atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let index = graph.build_minimizer_index(15, 10).unwrap();
+    /// graph.save_minimizer_indexes("pangenome.idx", &[index]).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, indexes), err))]
+    pub fn save_minimizer_indexes(&self, path: &str, indexes: &[MinimizerIndex]) -> Result<(), Error> {
+        let io_err = |e: std::io::Error| Error::io(format!("Failed to write index file '{}'", path), e);
+        let mut target = AtomicFile::new(path)?;
+        let mut writer = BufWriter::new(target.file());
+        writer.write_all(MAGIC).map_err(io_err)?;
+        writer.write_all(&VERSION.to_le_bytes()).map_err(io_err)?;
+        writer.write_all(&self.fingerprint()?.to_le_bytes()).map_err(io_err)?;
+        writer.write_all(&(indexes.len() as u64).to_le_bytes()).map_err(io_err)?;
+        for index in indexes {
+            index.write_to(&mut writer)?;
+        }
+        writer.flush().map_err(io_err)?;
+        drop(writer);
+        target.commit()
+    }
+
+    /// Loads minimizer indexes saved by [`Graph::save_minimizer_indexes`] for this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read, is not an index file,
+    /// was written in an unsupported format version, or was saved for a graph
+    /// with a different [`Graph::fingerprint`]. Stale indexes are rejected
+    /// rather than returned, since their positions would not match the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let index = match graph.load_minimizer_indexes("pangenome.idx") {
+    ///     Ok(mut indexes) => indexes.remove(0),
+    ///     Err(_) => graph.build_minimizer_index(15, 10).unwrap(),
+    /// };
+    /// println!("{} minimizers", index.len());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn load_minimizer_indexes(&self, path: &str) -> Result<Vec<MinimizerIndex>, Error> {
+        let io_err = |e: std::io::Error| Error::io(format!("Failed to read index file '{}'", path), e);
+        let mut reader = BufReader::new(File::open(path).map_err(io_err)?);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic).map_err(io_err)?;
+        if &magic != MAGIC {
+            return Err(Error::invalid_format(format!("'{}' is not an index file", path)));
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version).map_err(io_err)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(Error::invalid_format(format!(
+                "Unsupported index file version {} in '{}' (supported: {})",
+                version, path, VERSION
+            )));
+        }
+        let mut fingerprint = [0u8; 16];
+        reader.read_exact(&mut fingerprint).map_err(io_err)?;
+        let fingerprint = u128::from_le_bytes(fingerprint);
+        let expected = self.fingerprint()?;
+        if fingerprint != expected {
+            return Err(Error::invalid_format(format!(
+                "Index file '{}' was built for a different graph (fingerprint {:032x}, expected {:032x})",
+                path, fingerprint, expected
+            )));
+        }
+        let mut count = [0u8; 8];
+        reader.read_exact(&mut count).map_err(io_err)?;
+        (0..u64::from_le_bytes(count))
+            .map(|_| MinimizerIndex::read_from(&mut reader))
+            .collect()
+    }
+}
@@ -0,0 +1,167 @@
+// src/inject.rs
+
+//! Embedding sequence-to-graph alignments as paths.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use super::alignment::GraphAlignment;
+use super::graph::{Error, Graph};
+
+impl Graph {
+    /// Adds one embedded path per alignment, following the alignment's walk.
+    ///
+    /// Paths are named `<name_prefix><query_name>`. If that name is already
+    /// taken, for example by a supplementary alignment of the same read, the
+    /// aligned query range is appended as `:<query_start>-<query_end>`.
+    ///
+    /// Without `split_nodes`, paths visit the first and last node of each walk in
+    /// full, even where the alignment only covers part of them. With
+    /// `split_nodes`, those nodes are first divided where the alignments start
+    /// and end, so that each path spells exactly the aligned part of the walk.
+    /// Divided nodes are replaced by new nodes with fresh IDs, and existing paths
+    /// are rewritten to visit the pieces.
+    ///
+    /// All alignments are validated before anything is changed, so on error the
+    /// graph is left untouched. Returns the number of added paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a walk visits a node that does not exist, takes an
+    /// edge that is not in the graph, has a length or coordinates inconsistent
+    /// with the graph, or if no unique path name can be derived for it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{gaf, Graph};
+    ///
+    /// let mut graph = Graph::load("pangenome.odgi").unwrap();
+    /// let file = std::fs::File::open("transcripts.gaf").unwrap();
+    /// let alignments: Vec<_> = gaf::records(std::io::BufReader::new(file))
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// let added = graph.inject_alignments(&alignments, "tx#", true).unwrap();
+    /// println!("Added {} transcript paths", added);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, alignments), err))]
+    pub fn inject_alignments(
+        &mut self,
+        alignments: &[GraphAlignment],
+        name_prefix: &str,
+        split_nodes: bool,
+    ) -> Result<usize, Error> {
+        let mut taken: HashSet<String> = self.get_path_names()?.into_iter().collect();
+        let mut names = Vec::with_capacity(alignments.len());
+        // Offsets along the forward strand at which each node has to be divided.
+        let mut splits: HashMap<u64, BTreeSet<u64>> = HashMap::new();
+
+        for alignment in alignments {
+            let name = self.validate_injected(alignment, name_prefix, &taken)?;
+            taken.insert(name.clone());
+            names.push(name);
+
+            if split_nodes {
+                let (first, last) = (alignment.walk[0], alignment.walk[alignment.walk.len() - 1]);
+                let first_len = self.get_node_len(first.0)?;
+                let last_len = self.get_node_len(last.0)?;
+                let end_in_last = alignment.walk_end - (alignment.walk_length - last_len);
+                for ((node_id, is_forward), len, offset) in
+                    [(first, first_len, alignment.walk_start), (last, last_len, end_in_last)]
+                {
+                    let forward_offset = if is_forward { offset } else { len - offset };
+                    if forward_offset > 0 && forward_offset < len {
+                        splits.entry(node_id).or_default().insert(forward_offset);
+                    }
+                }
+            }
+        }
+
+        // Each divided node maps to its pieces as (node_id, length), in forward order.
+        let mut pieces: HashMap<u64, Vec<(u64, u64)>> = HashMap::new();
+        for (node_id, offsets) in splits {
+            let offsets: Vec<u64> = offsets.into_iter().collect();
+            let len = self.get_node_len(node_id)?;
+            let ids = self.divide_node(node_id, &offsets)?;
+            let bounds: Vec<u64> = std::iter::once(0).chain(offsets).chain(std::iter::once(len)).collect();
+            let lengths = bounds.windows(2).map(|w| w[1] - w[0]);
+            pieces.insert(node_id, ids.into_iter().zip(lengths).collect());
+        }
+
+        for (alignment, name) in alignments.iter().zip(&names) {
+            self.add_path(name)?;
+            let last_index = alignment.walk.len() - 1;
+            let mut walk_offset = 0;
+            for (i, &(node_id, is_forward)) in alignment.walk.iter().enumerate() {
+                let node_pieces = match pieces.get(&node_id) {
+                    Some(node_pieces) => node_pieces.clone(),
+                    None => vec![(node_id, self.get_node_len(node_id)?)],
+                };
+                let oriented: Vec<(u64, u64)> = if is_forward {
+                    node_pieces
+                } else {
+                    node_pieces.into_iter().rev().collect()
+                };
+                for (piece_id, piece_len) in oriented {
+                    let (piece_start, piece_end) = (walk_offset, walk_offset + piece_len);
+                    walk_offset = piece_end;
+                    // Pieces outside the aligned range only exist at the ends of a divided walk.
+                    if split_nodes
+                        && ((i == 0 && piece_end <= alignment.walk_start)
+                            || (i == last_index && piece_start >= alignment.walk_end))
+                    {
+                        continue;
+                    }
+                    self.append_step(name, piece_id, is_forward)?;
+                }
+            }
+        }
+
+        Ok(names.len())
+    }
+
+    /// Checks that an alignment fits the graph and returns the name of its path.
+    fn validate_injected(
+        &self,
+        alignment: &GraphAlignment,
+        name_prefix: &str,
+        taken: &HashSet<String>,
+    ) -> Result<String, Error> {
+        let query = &alignment.query_name;
+        if alignment.walk.is_empty() {
+            return Err(Error::invalid_argument(format!("Alignment of '{}' has an empty walk", query)));
+        }
+
+        let mut walk_length = 0;
+        for (i, &(node_id, is_forward)) in alignment.walk.iter().enumerate() {
+            walk_length += self
+                .get_node_len(node_id)
+                .map_err(|e| e.context(format!("Alignment of '{}'", query)))?;
+            if let Some(&(prev_id, prev_forward)) = i.checked_sub(1).map(|p| &alignment.walk[p])
+                && !self.edge_exists(prev_id, prev_forward, node_id, is_forward)?
+            {
+                return Err(Error::invalid_argument(format!(
+                    "Alignment of '{}' follows a missing edge from node {} to node {}",
+                    query, prev_id, node_id
+                )));
+            }
+        }
+        if walk_length != alignment.walk_length
+            || alignment.walk_start > alignment.walk_end
+            || alignment.walk_end > walk_length
+        {
+            return Err(Error::invalid_argument(format!(
+                "Alignment of '{}' has walk coordinates that do not match the graph",
+                query
+            )));
+        }
+
+        let name = format!("{}{}", name_prefix, query);
+        if !taken.contains(&name) {
+            return Ok(name);
+        }
+        let name = format!("{}:{}-{}", name, alignment.query_start, alignment.query_end);
+        if !taken.contains(&name) {
+            return Ok(name);
+        }
+        Err(Error::path_exists(name))
+    }
+}
@@ -0,0 +1,136 @@
+// src/inspect.rs
+
+//! Reads the header of an ODGI file without loading the graph.
+
+use std::fs;
+use std::io::Read;
+use std::ops::RangeInclusive;
+use super::compression;
+use super::graph::Error;
+
+/// The magic numbers written at the start of ODGI files, and the format version
+/// each one identifies.
+///
+/// ODGI files do not carry an explicit version field. Like every
+/// `libhandlegraph` serializable graph, they start with a magic number that
+/// identifies the serialization layout, so the format version is derived from it.
+const FORMAT_VERSIONS: &[(u32, u32)] = &[(0x7680_BDBA, 1)];
+
+/// The format versions that the linked `odgi` library can deserialize.
+pub(crate) const SUPPORTED_FORMAT_VERSIONS: RangeInclusive<u32> = 1..=1;
+
+/// Summary information read from the header of an ODGI file.
+///
+/// Returned by [`inspect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileInfo {
+    /// The magic number at the start of the file.
+    pub magic: u32,
+    /// The serialization format version identified by the magic number.
+    pub format_version: u32,
+    /// The size of the file in bytes, as stored on disk.
+    pub file_size: u64,
+    /// The number of nodes in the graph.
+    pub node_count: u64,
+    /// The number of edges in the graph.
+    pub edge_count: u64,
+    /// The number of embedded paths.
+    pub path_count: u64,
+    /// The smallest node ID in the graph.
+    pub min_node_id: u64,
+    /// The largest node ID in the graph.
+    pub max_node_id: u64,
+}
+
+/// Reads the header of an ODGI file without deserializing the graph.
+///
+/// Only the first few dozen bytes of the file are read, so this is cheap enough
+/// to validate or catalog large collections of graphs before deciding which of
+/// them to [`load`](crate::Graph::load). Compressed files are supported; only
+/// the start of the stream is decompressed.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the file cannot be read or is too short to contain a
+/// header. If the file does not start with a known ODGI magic number, the error
+/// is one for which [`Error::is_version_mismatch`] is `true`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let info = odgi_ffi::inspect("my_graph.odgi").unwrap();
+/// println!(
+///     "format v{}: {} nodes, {} edges, {} paths",
+///     info.format_version, info.node_count, info.edge_count, info.path_count
+/// );
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn inspect(path: &str) -> Result<FileInfo, Error> {
+    let file_size = fs::metadata(path)
+        .map_err(|e| Error::io(format!("Failed to open '{}'", path), e))?
+        .len();
+
+    let mut header = [0u8; HEADER_LEN];
+    compression::open(path)?
+        .read_exact(&mut header)
+        .map_err(|_| Error::invalid_format(format!("'{}' is too short to be an ODGI file", path)))?;
+    parse_header(path, &header, file_size)
+}
+
+/// The length of the header that [`parse_header`] reads: the magic number and five counts.
+const HEADER_LEN: usize = 4 + 5 * 8;
+
+/// Reads the header at the start of an (uncompressed) ODGI file named `source`.
+fn parse_header(source: &str, header: &[u8], file_size: u64) -> Result<FileInfo, Error> {
+    if header.len() < HEADER_LEN {
+        return Err(Error::invalid_format(format!("'{}' is too short to be an ODGI file", source)));
+    }
+    // The magic number is written in network byte order, the counts that
+    // follow in the native (little-endian) order of the writing machine.
+    let magic = u32::from_be_bytes(header[0..4].try_into().unwrap());
+    let format_version = FORMAT_VERSIONS
+        .iter()
+        .find(|&&(known, _)| known == magic)
+        .map(|&(_, version)| version)
+        .ok_or_else(|| Error::version_mismatch(source, magic, None))?;
+
+    let field = |index: usize| {
+        let start = 4 + index * 8;
+        u64::from_le_bytes(header[start..start + 8].try_into().unwrap())
+    };
+
+    Ok(FileInfo {
+        magic,
+        format_version,
+        file_size,
+        max_node_id: field(0),
+        min_node_id: field(1),
+        node_count: field(2),
+        edge_count: field(3),
+        path_count: field(4),
+    })
+}
+
+/// Checks that the file at `path` can be deserialized by the linked `odgi` library.
+///
+/// `odgi` aborts the whole process when it is handed a file it cannot parse, so
+/// this runs before every load.
+#[cfg_attr(feature = "docs-only", allow(dead_code))]
+pub(crate) fn check_loadable(path: &str) -> Result<FileInfo, Error> {
+    check_supported(path, inspect(path)?)
+}
+
+/// Checks that an uncompressed ODGI file held in memory, described as `source`
+/// in errors, can be deserialized by the linked `odgi` library.
+#[cfg_attr(feature = "docs-only", allow(dead_code))]
+pub(crate) fn check_loadable_bytes(source: &str, bytes: &[u8]) -> Result<FileInfo, Error> {
+    check_supported(source, parse_header(source, bytes, bytes.len() as u64)?)
+}
+
+/// Rejects files whose format version the linked `odgi` cannot read.
+fn check_supported(source: &str, info: FileInfo) -> Result<FileInfo, Error> {
+    if !SUPPORTED_FORMAT_VERSIONS.contains(&info.format_version) {
+        return Err(Error::version_mismatch(source, info.magic, Some(info.format_version)));
+    }
+    Ok(info)
+}
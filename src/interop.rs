@@ -0,0 +1,203 @@
+// src/interop.rs
+
+//! Operations that relate two separately built graphs of the same genomes.
+//!
+//! Node IDs are only meaningful within a single graph. When a graph is rebuilt,
+//! for example with more samples or a newer version of the pipeline, the same
+//! sequence usually ends up under a different ID. The functions in this module
+//! recover the correspondence so that data attached to one graph can be
+//! migrated to the other.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use super::graph::{Error, Graph};
+use super::seq::reverse_complement;
+
+/// A node of one graph matched to a node of another.
+///
+/// Returned by [`match_nodes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMatch {
+    /// The ID of the node in the first graph.
+    pub a_node: u64,
+    /// The ID of the matching node in the second graph.
+    pub b_node: u64,
+    /// How certain the match is, between `0.0` (exclusive) and `1.0`.
+    ///
+    /// Identical sequences contribute half of the confidence, and the other
+    /// half measures how similar the nodes' neighbourhoods are. For sequences
+    /// that occur on several nodes, only the margin by which the chosen pair
+    /// beats the next best candidate counts.
+    pub confidence: f64,
+}
+
+/// The oriented sequences adjacent to a node, as hashes.
+///
+/// Each entry records whether the neighbour is attached to the start (`false`)
+/// or end (`true`) of the node's forward strand, and the neighbour's sequence
+/// as read from that side.
+type Neighbourhood = HashSet<(bool, u64)>;
+
+/// Nodes of graph `a` and of graph `b` sharing one neighbourhood.
+type NeighbourhoodGroup = (Vec<u64>, Vec<u64>);
+
+fn neighbourhood(graph: &Graph, node_id: u64) -> Result<Neighbourhood, Error> {
+    let hash = |sequence: &str| {
+        let mut hasher = DefaultHasher::new();
+        sequence.hash(&mut hasher);
+        hasher.finish()
+    };
+    let oriented = |id: u64, is_forward: bool| {
+        let sequence = graph.get_node_sequence(id)?;
+        Ok::<_, Error>(if is_forward { sequence } else { reverse_complement(&sequence) })
+    };
+    // The successors of both strands cover the edges on both sides of the node.
+    let mut neighbours = Neighbourhood::new();
+    for edge in graph.get_successors(node_id)? {
+        // Leaving the reverse strand means leaving the start of the forward strand,
+        // where the neighbour is read in the opposite orientation.
+        let at_end = edge.from_orientation;
+        let to_forward = if at_end { edge.to_orientation } else { !edge.to_orientation };
+        neighbours.insert((at_end, hash(&oriented(edge.to_node, to_forward)?)));
+    }
+    Ok(neighbours)
+}
+
+fn similarity(a: &Neighbourhood, b: &Neighbourhood) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / a.union(b).count() as f64
+}
+
+/// The largest number of candidate pairs for one sequence that are scored individually.
+const MAX_CANDIDATE_PAIRS: usize = 1024;
+
+/// Matches nodes sharing one sequence by scoring every candidate pair and
+/// assigning them greedily, most similar first.
+fn match_candidates(
+    a_nodes: &[u64],
+    a_neighbourhoods: &[Neighbourhood],
+    b_nodes: &[u64],
+    b_neighbourhoods: &[Neighbourhood],
+    matches: &mut Vec<NodeMatch>,
+) {
+    let scores: Vec<Vec<f64>> = a_neighbourhoods
+        .iter()
+        .map(|a_hood| b_neighbourhoods.iter().map(|b_hood| similarity(a_hood, b_hood)).collect())
+        .collect();
+    let mut pairs: Vec<(usize, usize)> =
+        (0..a_nodes.len()).flat_map(|i| (0..b_nodes.len()).map(move |j| (i, j))).collect();
+    pairs.sort_by(|&(i, j), &(x, y)| scores[x][y].total_cmp(&scores[i][j]));
+
+    let mut used_a = vec![false; a_nodes.len()];
+    let mut used_b = vec![false; b_nodes.len()];
+    for (i, j) in pairs {
+        if used_a[i] || used_b[j] {
+            continue;
+        }
+        // The best alternative either node would have had instead.
+        let row = (0..b_nodes.len()).filter(|&y| y != j).map(|y| scores[i][y]);
+        let column = (0..a_nodes.len()).filter(|&x| x != i).map(|x| scores[x][j]);
+        let runner_up = row.chain(column).reduce(f64::max);
+        let confidence = match runner_up {
+            None => 0.5 + 0.5 * scores[i][j],
+            Some(runner_up) => 0.5 * (scores[i][j] - runner_up),
+        };
+        if confidence <= 0.0 {
+            continue;
+        }
+        used_a[i] = true;
+        used_b[j] = true;
+        matches.push(NodeMatch { a_node: a_nodes[i], b_node: b_nodes[j], confidence });
+    }
+}
+
+/// Matches nodes sharing one sequence whose exact neighbourhood occurs only
+/// once among them in each graph.
+fn match_unique_neighbourhoods(
+    a_nodes: &[u64],
+    a_neighbourhoods: &[Neighbourhood],
+    b_nodes: &[u64],
+    b_neighbourhoods: &[Neighbourhood],
+    matches: &mut Vec<NodeMatch>,
+) {
+    let key = |hood: &Neighbourhood| {
+        let mut key: Vec<(bool, u64)> = hood.iter().copied().collect();
+        key.sort_unstable();
+        key
+    };
+    let mut groups: HashMap<Vec<(bool, u64)>, NeighbourhoodGroup> = HashMap::new();
+    for (&id, hood) in a_nodes.iter().zip(a_neighbourhoods) {
+        groups.entry(key(hood)).or_default().0.push(id);
+    }
+    for (&id, hood) in b_nodes.iter().zip(b_neighbourhoods) {
+        groups.entry(key(hood)).or_default().1.push(id);
+    }
+    for (a_ids, b_ids) in groups.into_values() {
+        if let ([a_node], [b_node]) = (a_ids.as_slice(), b_ids.as_slice()) {
+            matches.push(NodeMatch { a_node: *a_node, b_node: *b_node, confidence: 0.5 });
+        }
+    }
+}
+
+fn nodes_by_sequence(graph: &Graph) -> Result<HashMap<String, Vec<u64>>, Error> {
+    let mut by_sequence: HashMap<String, Vec<u64>> = HashMap::new();
+    for node_id in graph.node_ids()? {
+        by_sequence.entry(graph.get_node_sequence(node_id)?).or_default().push(node_id);
+    }
+    Ok(by_sequence)
+}
+
+/// Matches the nodes of graph `a` to the nodes of graph `b`.
+///
+/// Nodes are matched by identical sequence. When a sequence occurs on more than
+/// one node, the candidates are told apart by their local topology: the
+/// oriented sequences of their neighbours on either side. Each node is matched
+/// at most once, and nodes whose sequence does not occur in the other graph, or
+/// whose candidates cannot be distinguished, are left out. The result is sorted
+/// by [`NodeMatch::a_node`].
+///
+/// Sequences that occur on very many nodes, typically single bases, are only
+/// matched where a node's neighbourhood is unique among them in both graphs.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if odgi throws while reading either graph.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{interop, Graph};
+///
+/// let old = Graph::load("release_1.odgi").unwrap();
+/// let new = Graph::load("release_2.odgi").unwrap();
+/// for m in interop::match_nodes(&old, &new).unwrap() {
+///     if m.confidence > 0.9 {
+///         println!("{} -> {}", m.a_node, m.b_node);
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(a, b), err))]
+pub fn match_nodes(a: &Graph, b: &Graph) -> Result<Vec<NodeMatch>, Error> {
+    let b_by_sequence = nodes_by_sequence(b)?;
+    let mut matches = Vec::new();
+
+    for (sequence, a_nodes) in nodes_by_sequence(a)? {
+        let Some(b_nodes) = b_by_sequence.get(&sequence) else {
+            continue;
+        };
+        let a_neighbourhoods = a_nodes.iter().map(|&id| neighbourhood(a, id)).collect::<Result<Vec<_>, Error>>()?;
+        let b_neighbourhoods = b_nodes.iter().map(|&id| neighbourhood(b, id)).collect::<Result<Vec<_>, Error>>()?;
+
+        if a_nodes.len() * b_nodes.len() <= MAX_CANDIDATE_PAIRS {
+            match_candidates(&a_nodes, &a_neighbourhoods, b_nodes, &b_neighbourhoods, &mut matches);
+        } else {
+            match_unique_neighbourhoods(&a_nodes, &a_neighbourhoods, b_nodes, &b_neighbourhoods, &mut matches);
+        }
+    }
+
+    matches.sort_by_key(|m| m.a_node);
+    Ok(matches)
+}
@@ -0,0 +1,75 @@
+// src/inversions.rs
+
+//! Runs of reverse-oriented steps along a path.
+
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// A maximal run of steps that traverse their nodes in reverse, returned by [`Graph::inversions`].
+///
+/// Ranges are 0-based and half-open, in the forward coordinates of the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathInversion {
+    /// The path offset where the run starts.
+    pub start: u64,
+    /// The path offset where the run ends.
+    pub end: u64,
+    /// The index of the first step of the run.
+    pub first_step: usize,
+    /// The number of steps in the run.
+    pub step_count: usize,
+}
+
+impl PathInversion {
+    /// Returns the length of the run in base pairs.
+    pub fn length(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+impl Graph {
+    /// Finds the maximal runs of steps on which a path traverses nodes in reverse orientation.
+    ///
+    /// Graph builders orient nodes along the sequences they were built from,
+    /// so in a graph whose paths mostly run forward, a reverse run marks a
+    /// segment that is inverted relative to the graph's dominant orientation.
+    /// For a path that runs mostly in reverse, such as an assembly contig
+    /// embedded on the other strand, the runs cover most of the path; see
+    /// [`Graph::flip_paths`] to normalize such paths first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for inversion in graph.inversions("HG002#1#chr8").unwrap() {
+    ///     if inversion.length() >= 10_000 {
+    ///         println!("Inverted segment at {}-{}", inversion.start, inversion.end);
+    ///     }
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn inversions(&self, path_name: &str) -> Result<Vec<PathInversion>, Error> {
+        let index = PathIndex::new(self, path_name)?.ok_or_else(|| Error::path_not_found(path_name))?;
+
+        let mut inversions: Vec<PathInversion> = Vec::new();
+        for (i, step) in index.steps.iter().enumerate() {
+            if step.is_forward {
+                continue;
+            }
+            let end = index.starts[i] + index.lengths[i];
+            match inversions.last_mut() {
+                Some(run) if run.first_step + run.step_count == i => {
+                    run.end = end;
+                    run.step_count += 1;
+                }
+                _ => inversions.push(PathInversion { start: index.starts[i], end, first_step: i, step_count: 1 }),
+            }
+        }
+        Ok(inversions)
+    }
+}
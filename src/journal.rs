@@ -0,0 +1,251 @@
+// src/journal.rs
+
+//! Checkpoints that let edits to a loaded graph be rolled back in memory.
+//!
+//! While a checkpoint is open, every mutation records how to undo itself.
+//! Rolling back replays those records in reverse, which costs time in
+//! proportion to the edits made rather than to the size of the graph.
+
+use super::ffi;
+use super::graph::{Error, Graph};
+
+/// A point in a graph's edit history, created by [`Graph::checkpoint`].
+///
+/// Checkpoints nest: rolling back to or committing a checkpoint also ends every
+/// checkpoint created after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use = "a checkpoint keeps recording edits until it is rolled back or committed"]
+pub struct Checkpoint {
+    serial: u64,
+}
+
+/// How to undo one mutation.
+#[derive(Debug)]
+pub(crate) enum Undo {
+    RemoveNode(u64),
+    RemoveNodes(Vec<u64>),
+    RestoreNode { node_id: u64, sequence: String, edges: Vec<ffi::GraphEdge> },
+    AddEdge(ffi::GraphEdge),
+    RemoveEdge(ffi::GraphEdge),
+    RemovePath(String),
+    TruncatePath(String, u64),
+    RestorePath(String, Vec<ffi::PathStep>),
+    ApplyNodeOrder(Vec<u64>),
+}
+
+/// The undo records of a graph and the open checkpoints into them.
+#[derive(Debug, Default)]
+pub(crate) struct Journal {
+    undo: Vec<Undo>,
+    /// The serial of each open checkpoint and the number of records before it, oldest first.
+    checkpoints: Vec<(u64, usize)>,
+    next_serial: u64,
+}
+
+impl Journal {
+    /// Returns `true` if mutations should be recorded.
+    pub(crate) fn is_recording(&self) -> bool {
+        !self.checkpoints.is_empty()
+    }
+
+    /// Records how to undo a mutation, if a checkpoint is open.
+    pub(crate) fn record(&mut self, undo: Undo) {
+        if !self.is_recording() {
+            return;
+        }
+        // Appending to a path that was already being appended to needs no new
+        // record: truncating to the earlier length undoes both. This only holds
+        // within the newest checkpoint, since rolling back to it must not undo
+        // the appends made before it was opened.
+        let newest_start = self.checkpoints.last().map_or(0, |&(_, start)| start);
+        if let (Undo::TruncatePath(name, _), Some(Undo::TruncatePath(last, _))) = (&undo, self.undo.last())
+            && name == last
+            && self.undo.len() > newest_start
+        {
+            return;
+        }
+        self.undo.push(undo);
+    }
+
+    fn position(&self, checkpoint: Checkpoint) -> Result<usize, Error> {
+        self.checkpoints
+            .iter()
+            .position(|&(serial, _)| serial == checkpoint.serial)
+            .ok_or_else(|| Error::invalid_argument("Checkpoint was already rolled back or committed"))
+    }
+}
+
+/// Returns the records that undo removing or dividing a node: its paths, then the node itself.
+///
+/// A node that does not exist is left alone by both edits, so it needs no records.
+pub(crate) fn node_undo(graph: &Graph, node_id: u64) -> Result<Vec<Undo>, Error> {
+    if !graph.has_node(node_id)? {
+        return Ok(Vec::new());
+    }
+    let mut paths = graph.get_paths_on_node(node_id)?;
+    paths.sort();
+    paths.dedup();
+    let mut undo = paths
+        .into_iter()
+        .map(|name| {
+            let steps = graph.raw_path_steps(&name)?;
+            Ok(Undo::RestorePath(name, steps))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let outgoing = graph.get_successors(node_id)?.into_iter().map(|e| ffi::GraphEdge {
+        from_node: node_id,
+        from_orientation: e.from_orientation,
+        to_node: e.to_node,
+        to_orientation: e.to_orientation,
+    });
+    let incoming = graph.get_predecessors(node_id)?.into_iter().map(|e| ffi::GraphEdge {
+        from_node: e.to_node,
+        from_orientation: e.from_orientation,
+        to_node: node_id,
+        to_orientation: e.to_orientation,
+    });
+    let edges = outgoing.chain(incoming).collect();
+    undo.push(Undo::RestoreNode { node_id, sequence: graph.get_node_sequence(node_id)?, edges });
+    Ok(undo)
+}
+
+impl Graph {
+    /// Opens a checkpoint that the graph can later be rolled back to.
+    ///
+    /// Until the checkpoint is rolled back or committed, every edit to the
+    /// graph records how to undo itself, so that [`Graph::rollback`] can revert
+    /// a bad edit without reloading the graph from disk. Checkpoints can be
+    /// nested, for example one per step of an interactive curation session.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let mut graph = Graph::load("pangenome.odgi").unwrap();
+    /// let checkpoint = graph.checkpoint();
+    /// graph.remove_orphaned_nodes().unwrap();
+    /// if graph.node_count().unwrap() < 1000 {
+    ///     // Too much was removed; restore the graph as it was.
+    ///     graph.rollback(checkpoint).unwrap();
+    /// } else {
+    ///     graph.commit(checkpoint).unwrap();
+    /// }
+    /// ```
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        let journal = &mut self.journal;
+        let serial = journal.next_serial;
+        journal.next_serial += 1;
+        journal.checkpoints.push((serial, journal.undo.len()));
+        Checkpoint { serial }
+    }
+
+    /// Reverts every edit made since `checkpoint` was opened.
+    ///
+    /// The nodes, edges, path steps, and sequences are restored exactly. Nodes
+    /// and paths that are restored after being removed are placed after the
+    /// others in the graph's 1D order and in [`Graph::get_path_names`].
+    ///
+    /// The checkpoint and any checkpoints opened after it are closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the checkpoint was already rolled back or
+    /// committed, or of kind [`ErrorKind::Exception`](crate::ErrorKind::Exception)
+    /// if odgi throws while undoing an edit. The checkpoint is closed either way,
+    /// and the graph may then be only partly restored.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn rollback(&mut self, checkpoint: Checkpoint) -> Result<(), Error> {
+        let index = self.journal.position(checkpoint)?;
+        let (_, start) = self.journal.checkpoints[index];
+        // Undoing must not record anything, so detach the journal while it runs.
+        let mut journal = std::mem::take(&mut self.journal);
+        let undone = journal.undo.drain(start..).rev().try_for_each(|undo| self.apply_undo(undo));
+        journal.checkpoints.truncate(index);
+        self.journal = journal;
+        undone.map_err(|e| e.context("Failed to roll back to checkpoint"))
+    }
+
+    /// Keeps every edit made since `checkpoint` was opened and closes it.
+    ///
+    /// Checkpoints opened after it are closed as well. Edits stay revertible by
+    /// rolling back to an enclosing checkpoint; once no checkpoint is open, the
+    /// undo records are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the checkpoint was already rolled back or committed.
+    pub fn commit(&mut self, checkpoint: Checkpoint) -> Result<(), Error> {
+        let index = self.journal.position(checkpoint)?;
+        self.journal.checkpoints.truncate(index);
+        if !self.journal.is_recording() {
+            self.journal.undo.clear();
+        }
+        Ok(())
+    }
+
+    fn apply_undo(&mut self, undo: Undo) -> Result<(), Error> {
+        match undo {
+            Undo::RemoveNode(node_id) => {
+                self.remove_node(node_id)?;
+            }
+            Undo::RemoveNodes(node_ids) => {
+                for node_id in node_ids {
+                    self.remove_node(node_id)?;
+                }
+            }
+            Undo::RestoreNode { node_id, sequence, edges } => {
+                self.add_node(node_id, &sequence)?;
+                for e in edges {
+                    self.add_edge(e.from_node, e.from_orientation, e.to_node, e.to_orientation)?;
+                }
+            }
+            Undo::AddEdge(e) => {
+                self.add_edge(e.from_node, e.from_orientation, e.to_node, e.to_orientation)?;
+            }
+            Undo::RemoveEdge(e) => {
+                self.remove_edge(e.from_node, e.from_orientation, e.to_node, e.to_orientation)?;
+            }
+            Undo::RemovePath(name) => {
+                self.remove_path(&name)?;
+            }
+            Undo::TruncatePath(name, length) => {
+                self.truncate_path(&name, length)?;
+            }
+            Undo::RestorePath(name, steps) => {
+                if !self.add_path(&name)? {
+                    self.truncate_path(&name, 0)?;
+                }
+                for step in steps {
+                    self.append_step(&name, step.node_id, step.is_forward)?;
+                }
+            }
+            Undo::ApplyNodeOrder(node_ids) => {
+                self.apply_node_order(&node_ids)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Journal, Undo};
+
+    #[test]
+    fn test_appends_coalesce_within_a_checkpoint_only() {
+        let mut journal = Journal::default();
+        journal.checkpoints.push((0, journal.undo.len()));
+        journal.record(Undo::TruncatePath("x".to_string(), 1));
+        journal.record(Undo::TruncatePath("x".to_string(), 2));
+        assert_eq!(journal.undo.len(), 1);
+
+        // Appends after a nested checkpoint need their own record, or rolling
+        // back to it would leave them in place.
+        journal.checkpoints.push((1, journal.undo.len()));
+        journal.record(Undo::TruncatePath("x".to_string(), 3));
+        assert_eq!(journal.undo.len(), 2);
+        assert!(matches!(&journal.undo[1], Undo::TruncatePath(name, 3) if name == "x"));
+        journal.record(Undo::TruncatePath("x".to_string(), 4));
+        assert_eq!(journal.undo.len(), 2);
+    }
+}
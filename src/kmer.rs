@@ -0,0 +1,129 @@
+// src/kmer.rs
+
+//! Rolling 2-bit encoding of the k-mers of a sequence, and minimizer selection.
+
+use std::collections::VecDeque;
+
+/// The largest supported k-mer length, so that a k-mer fits into a `u64`.
+pub(crate) const MAX_K: usize = 32;
+
+/// Returns the 2-bit code of a base, or `None` for anything but `ACGT` in either case.
+fn code(base: u8) -> Option<u64> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+/// An iterator over the k-mers of a sequence, skipping those with non-`ACGT` bases.
+///
+/// Yields `(offset, forward, reverse)`, where `forward` is the encoding of the
+/// k-mer at `offset` and `reverse` that of its reverse complement.
+pub(crate) struct Kmers<'a> {
+    sequence: &'a [u8],
+    k: usize,
+    pos: usize,
+    /// The number of valid bases ending at `pos`, capped at `k`.
+    valid: usize,
+    forward: u64,
+    reverse: u64,
+}
+
+impl<'a> Kmers<'a> {
+    /// `k` must be between 1 and [`MAX_K`].
+    pub(crate) fn new(sequence: &'a [u8], k: usize) -> Self {
+        debug_assert!((1..=MAX_K).contains(&k));
+        Kmers { sequence, k, pos: 0, valid: 0, forward: 0, reverse: 0 }
+    }
+
+    fn mask(&self) -> u64 {
+        if self.k == MAX_K { u64::MAX } else { (1 << (2 * self.k)) - 1 }
+    }
+}
+
+impl Iterator for Kmers<'_> {
+    type Item = (usize, u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.sequence.len() {
+            let base = self.sequence[self.pos];
+            self.pos += 1;
+            match code(base) {
+                Some(c) => {
+                    self.forward = ((self.forward << 2) | c) & self.mask();
+                    self.reverse = (self.reverse >> 2) | ((3 - c) << (2 * (self.k - 1)));
+                    self.valid = (self.valid + 1).min(self.k);
+                    if self.valid == self.k {
+                        return Some((self.pos - self.k, self.forward, self.reverse));
+                    }
+                }
+                None => self.valid = 0,
+            }
+        }
+        None
+    }
+}
+
+/// Scrambles a k-mer encoding so that minimizers are not biased towards poly-A.
+pub(crate) fn hash(kmer: u64) -> u64 {
+    let mut x = kmer.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Returns the `(w, k)` minimizers of a sequence as `(offset, canonical k-mer, is_forward)`.
+///
+/// A minimizer is the canonical k-mer with the smallest hash among `w`
+/// consecutive k-mers, taking the leftmost on ties. `is_forward` is `true` if
+/// the canonical k-mer is the one read at `offset`, rather than its reverse
+/// complement. Runs of k-mers interrupted by non-`ACGT` bases are treated
+/// separately; a run of fewer than `w` k-mers contributes its smallest one.
+/// Each minimizer is reported once, in order of offset.
+pub(crate) fn minimizers(sequence: &[u8], k: usize, w: usize) -> Vec<(usize, u64, bool)> {
+    let mut result: Vec<(usize, u64, bool)> = Vec::new();
+    // Candidates of the current window as (offset, hash, canonical, is_forward), with increasing hashes.
+    let mut window: VecDeque<(usize, u64, u64, bool)> = VecDeque::new();
+    let mut run_start = 0;
+    let mut last_offset: Option<usize> = None;
+    let select = |window: &VecDeque<(usize, u64, u64, bool)>, result: &mut Vec<(usize, u64, bool)>| {
+        if let Some(&(offset, _, canonical, is_forward)) = window.front()
+            && result.last().is_none_or(|&(last, _, _)| last != offset)
+        {
+            result.push((offset, canonical, is_forward));
+        }
+    };
+    for (offset, forward, reverse) in Kmers::new(sequence, k) {
+        if let Some(last) = last_offset.filter(|&last| offset != last + 1) {
+            if last + 1 < run_start + w {
+                select(&window, &mut result);
+            }
+            window.clear();
+            run_start = offset;
+        }
+        if last_offset.is_none() {
+            run_start = offset;
+        }
+        last_offset = Some(offset);
+
+        let canonical = forward.min(reverse);
+        let h = hash(canonical);
+        while window.back().is_some_and(|&(_, back_hash, _, _)| back_hash > h) {
+            window.pop_back();
+        }
+        window.push_back((offset, h, canonical, canonical == forward));
+        while window.front().is_some_and(|&(front, _, _, _)| front + w <= offset) {
+            window.pop_front();
+        }
+        if offset + 1 >= run_start + w {
+            select(&window, &mut result);
+        }
+    }
+    if last_offset.is_some_and(|last| last + 1 < run_start + w) {
+        select(&window, &mut result);
+    }
+    result
+}
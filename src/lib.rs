@@ -13,14 +13,183 @@
 //!
 //! - [`graph`]: Contains the main [`Graph`] struct for querying graph data.
 //! - [`conversion`]: Provides functions like [`gfa_to_odgi`] for format conversion.
+//! - [`align`]: Aligns sequences to local regions of the graph.
+//! - [`annotate`]: Places GFF3 gene annotations on graph nodes and lifts gene models between paths.
+//! - [`bubbles`]: Lists simple bubbles and exports them as vg snarls.
+//! - [`gaf`]: Reads GAF alignments and surjects them onto reference paths.
+//! - [`gam`]: Reads vg GAM alignments against a loaded graph.
+//! - [`interop`]: Relates nodes of two separately built graphs of the same genomes.
+//! - [`liftover`]: Translates coordinates between graphs and writes chain files between paths.
+//! - [`raw`]: Low-level, `unsafe` access to odgi's node, path, and step handles.
+//! - [`seq`]: IUPAC-aware sequence comparison, reverse complementing, and normalization.
+//! - [`testing`]: Generates random pangenome-like graphs for tests and benchmarks.
+//! - [`viz`]: Renders graph regions as self-contained SVG or HTML.
 //!
 //! # Features
 //!
-//! - Load ODGI graphs from disk into a safe Rust wrapper.
-//! - Query graph properties, such as node count, path names, and node sequences.
+//! - Load ODGI graphs from disk into a safe Rust wrapper, including gzip- and
+//!   zstd-compressed files, or from memory and readers with [`Graph::load_from_bytes`] and
+//!   [`Graph::load_from_reader`].
+//! - Build graphs from GFA files or text in-process with [`Graph::from_gfa_file`] and
+//!   [`Graph::from_gfa_str`].
+//! - Save loaded or edited graphs back to ODGI files with [`Graph::save`], or write them
+//!   as GFA without the `odgi` executable with [`Graph::to_gfa`].
+//! - Load many graphs concurrently, with per-file errors and aggregated progress.
+//! - Warm up a loaded graph before serving queries with [`Graph::prefault`].
+//! - Walk the bidirected graph through oriented [`Handle`]s with [`Graph::neighbors`], and list
+//!   every edge once with [`Graph::edges`].
+//! - Walk the steps of a path, with their ranks, using [`Graph::path_steps`].
+//! - Validate, index, and warm up paths at load time with [`Graph::load_with`] and [`LoadOptions`].
+//! - Draw reproducible random samples of nodes, paths, and path positions.
+//! - Generate synthetic graphs with bubbles and haplotypes for tests and benchmarks.
+//! - Query graph properties, such as node, edge, and path counts, path names, and node sequences.
+//! - Check whether nodes, paths, and edges exist with [`Graph::has_node`], [`Graph::has_path`],
+//!   and [`Graph::has_edge`].
+//! - Summarize a graph's size, paths, and self-loops in one call with [`Graph::stats`].
 //! - Perform topological queries, such as finding node successors and predecessors.
+//! - Page through or stream the steps on high-fanout nodes without buffering them all.
+//! - Count the path steps on a node, including repeated visits, with [`Graph::node_depth`].
 //! - Project path coordinates to their corresponding nodes and offsets.
+//! - Scan paths incrementally with a seekable, bidirectional [`PathCursor`].
 //! - Convert between GFA and ODGI formats using the bundled `odgi` executable.
+//! - Abort long-running extractions and conversions with a [`CancellationToken`].
+//! - Tell failures apart by cause, such as a missing path or a malformed file, with [`ErrorKind`].
+//! - Run any other `odgi` subcommand through the `OdgiCommand` builder.
+//! - Export graphs as rGFA relative to a chosen reference path, and import rGFA files.
+//! - Extract FASTA sequences for BED intervals on embedded paths, or for every node.
+//! - Write SAM sequence dictionaries (`@SQ` lines with MD5 checksums) for reference paths.
+//! - Export path depth along a reference path as a bedGraph track.
+//! - Reconstruct the alignment between two paths as typed blocks with CIGAR strings.
+//! - Measure edit distance and identity between two haplotype paths.
+//! - Surject GAF read alignments onto a reference path as PAF.
+//! - Read vg GAM alignments into the same records as GAF alignments.
+//! - Embed read or transcript alignments as paths, optionally dividing nodes
+//!   where they start and end.
+//! - Thread new assemblies into the graph from their GAF or PAF alignments, adding
+//!   nodes only for novel sequence.
+//! - Anchor novel nodes to the nearest reference coordinate.
+//! - Load GFF3 annotations against a reference path and query genes by node, or nodes by gene.
+//! - Lift complete gene models onto other haplotype paths, reporting broken or partially mapped models.
+//! - Catalog the regions of the graph not covered by a reference path.
+//! - Collapse SNP- and indel-scale bubbles into simplified backbones.
+//! - Build a consensus path that follows the most-supported traversal of the graph.
+//! - Locate runs of `N` and other ambiguous bases, and split them off or drop
+//!   them when building graphs.
+//! - Query the soft-masked (lowercase) fraction of nodes and path intervals.
+//! - Retrieve node sequences in a compact 2-bit encoding.
+//! - Compute k-mer spectra over node or path sequences.
+//! - Build persistent minimizer indexes for seeding read mapping and sequence search.
+//! - Save built indexes alongside a graph and reload them, rejecting stale or incompatible index files.
+//! - Find maximal exact matches between query sequences and the graph.
+//! - Align reads to the local graph of a region with partial-order alignment.
+//! - Export the alignments implied between haplotype paths and a reference as PAF.
+//! - Scan paths with sliding windows summarizing depth, haplotypes, and GC content.
+//! - Render the local graph of a path interval as SVG or HTML with haplotype ribbons.
+//! - Inspect ODGI file headers (format version, counts) without loading the graph.
+//! - Insert, delete, and replace runs of path steps with connectivity checks.
+//! - Import paths from another graph that shares the same nodes.
+//! - Mask nodes outside a depth range, splitting the affected paths into fragments.
+//! - Remove nodes and edges that no path traverses anymore.
+//! - Split whole-genome graphs into per-contig subgraphs by path name.
+//! - Report nodes visited repeatedly by a path, such as collapsed tandem duplications.
+//! - Locate inverted segments as runs of reverse-oriented path steps.
+//! - Flip paths that mostly run against the graph's orientation, like `odgi flip`.
+//! - Count path traversals of every edge to find unsupported links.
+//! - Key edges with [`EdgeKey`], which can identify both strands of an edge.
+//! - Classify nodes as core, shell, or cloud by the samples traversing them, and
+//!   count per-sample node copy numbers.
+//! - Match nodes between two builds of a graph to migrate node-level annotations.
+//! - Lift graph positions over between graph releases through a shared reference.
+//! - Write UCSC chain files between embedded paths for liftOver and CrossMap.
+//! - Fingerprint graph content independently of node IDs for caching and comparison.
+//!
+//! # Iteration Order
+//!
+//! Results that enumerate parts of the graph come back in the same order on
+//! every run and platform, so that pipelines are reproducible and output can
+//! be compared in snapshot tests:
+//!
+//! - [`Graph::get_path_names`] lists paths in the order they were added to the graph.
+//! - [`Graph::get_successors`], [`Graph::get_predecessors`], and
+//!   [`Graph::edge_usage`] are sorted by node ID and orientation.
+//! - [`Graph::get_paths_on_node`] and [`Graph::steps_on_node`] follow the
+//!   order in which the graph file stores the steps on each node.
+//!
+//! # Cargo Features
+//!
+//! - `tracing`: Instruments [`Graph`] methods and the conversion functions with
+//!   [`tracing`](https://docs.rs/tracing) spans at the `DEBUG` level. Each span
+//!   records the call's arguments, and subscribers configured to report span
+//!   closes (e.g. `FmtSpan::CLOSE` in `tracing-subscriber`) also report how long
+//!   each call took.
+//! - `metrics`: Records counters and histograms through the
+//!   [`metrics`](https://docs.rs/metrics) facade, for whichever exporter the
+//!   application installs: `odgi_ffi_queries_total` and
+//!   `odgi_ffi_query_duration_seconds` (labeled by `query`) for [`Graph`]
+//!   queries, `odgi_ffi_ffi_calls_total` (labeled by `function`) for calls into
+//!   the C++ layer, and `odgi_ffi_cache_hits_total` and
+//!   `odgi_ffi_cache_misses_total` (labeled by `cache`) for internal caches.
+//! - `system-odgi`: Links dynamically against an installed libodgi instead of
+//!   compiling the vendored C++ tree, which takes the bulk of a cold build. The
+//!   library is found in `ODGI_LIB_DIR` if it is set, or through pkg-config
+//!   otherwise; `ODGI_INCLUDE_DIR` can list extra header directories. The
+//!   conversion functions and `OdgiCommand` then run the installed `odgi`
+//!   binary (see `odgi_executable`), whose version should match the library.
+//! - `no-openmp`: Builds the vendored odgi and the FFI wrapper without
+//!   `-fopenmp` and does not link an OpenMP runtime, for toolchains where none
+//!   is available or it fails to link (see [Platform Support](#platform-support)). The C++ loops
+//!   that OpenMP would parallelize then run on one thread; otherwise their
+//!   thread count can be changed at run time with [`set_odgi_threads`]. The
+//!   crate's own parallelism, such as [`Graph::load_many`], uses Rust threads
+//!   and is not affected. Prebuilt artifacts should come from a build with the
+//!   same setting.
+//! - `python`: Builds an `odgi_ffi` Python extension module with
+//!   [`pyo3`](https://docs.rs/pyo3), exposing [`Graph`] with its queries and
+//!   the GFA conversion functions. Build it with `maturin develop --release` or
+//!   `maturin build --release`, which pick up the settings in `pyproject.toml`.
+//! - `http`: Adds `Graph::load_url`, which streams an ODGI file from an HTTP(S)
+//!   URL into a temporary file and loads it, for services that keep their
+//!   graphs in object storage.
+//! - `s3`: Builds on `http` to accept `s3://bucket/key` URLs. Requests are not
+//!   signed, so objects must be publicly readable; `AWS_ENDPOINT_URL` selects
+//!   an S3-compatible store instead of AWS.
+//! - `docs-only`: Skips the C++ build and replaces the FFI layer with stubs. Only
+//!   intended for building documentation on docs.rs.
+//! - `wasm`: Builds on `docs-only` to compile for `wasm32-unknown-unknown`, for
+//!   browser-based viewers. Instead of stubs, [`Graph`] is backed by a pure-Rust
+//!   store that is built from GFA text in memory with `Graph::from_gfa_bytes`,
+//!   and the read-only queries work as in native builds. ODGI files cannot be
+//!   loaded, and the conversion functions and `OdgiCommand` are unavailable,
+//!   as with `docs-only`. Decompressing zstd buffers needs a C compiler that
+//!   targets wasm, such as `clang`.
+//!
+//! # Platform Support
+//!
+//! The build script links the C++ runtime libraries that the target needs,
+//! looking at the target rather than the build host:
+//!
+//! - Linux with glibc: `libgomp` and `libatomic`, dynamically.
+//! - Linux with musl: `libgomp` and `libatomic`, statically.
+//! - macOS, including Apple Silicon: Apple Clang has no OpenMP runtime, so
+//!   Homebrew's `libomp` is used (`brew install libomp`). It is found through
+//!   `LIBOMP_PREFIX`, `brew --prefix libomp`, or Homebrew's default prefix.
+//!
+//! If no OpenMP runtime can be installed, enable the `no-openmp` feature.
+//!
+//! # Prebuilt Artifacts
+//!
+//! Compiling the vendored C++ tree dominates a cold build. To reuse one build
+//! across many pipelines, set `ODGI_FFI_PREBUILT_DIR` to a cached directory:
+//!
+//! - If the directory holds `lib/libodgi.a`, `lib/libhandlegraph.a`,
+//!   `lib/libsdsl.a`, and `bin/odgi`, the build script links those instead of
+//!   compiling anything.
+//! - Otherwise, the tree is compiled as usual and the artifacts are copied into
+//!   the directory, ready for the next build.
+//!
+//! When the variable is set at run time, `odgi_executable`, the conversion
+//! functions, and `OdgiCommand` run `bin/odgi` from the directory as well, so
+//! the binary keeps working after Cargo's build directory has been cleaned.
 //!
 //! # Example
 //!
@@ -55,12 +224,12 @@
 //!      .expect("Failed to load ODGI graph");
 //!
 //! // 3. Query the graph.
-//! assert_eq!(graph.node_count(), 2);
+//! assert_eq!(graph.node_count().unwrap(), 2);
 //!
-//! let path_names = graph.get_path_names();
+//! let path_names = graph.get_path_names().unwrap();
 //! assert_eq!(path_names, vec!["x"]);
 //!
-//! let seq = graph.get_node_sequence(1);
+//! let seq = graph.get_node_sequence(1).unwrap();
 //! assert_eq!(seq, "GATTACA");
 //!
 //! // Get path length using the new method.
@@ -74,18 +243,151 @@
 //! ```
 
 mod graph;
+mod alignment;
+mod anchor;
+mod assembly;
+mod atomic;
+mod bed;
+mod bed_batch;
+mod cancel;
+mod compression;
+mod connectivity;
+mod consensus;
+mod cursor;
+mod depth;
+mod dict;
+mod diagnostics;
+mod edge_key;
+mod edge_usage;
+mod fasta;
+mod fingerprint;
+mod flip;
+mod gfa_export;
+mod handle;
+mod index_file;
+mod inject;
+mod inspect;
+mod inversions;
+#[cfg(not(feature = "docs-only"))]
+mod journal;
+mod kmer;
+mod load_many;
+mod load_options;
+mod mask;
+mod masking;
+mod mems;
+mod merge;
+mod minimizer;
+mod n_runs;
+mod node_steps;
+mod nonreference;
+mod order;
+mod orphans;
+mod packed;
+mod paf;
+mod pangenome_offset;
+mod pansn;
+mod path_alignment;
+mod path_edit;
+mod path_identity;
+mod path_index;
+mod path_steps;
+mod presence;
+mod projection;
+mod protobuf;
+#[cfg(all(feature = "python", not(feature = "docs-only")))]
+mod python;
+#[cfg_attr(feature = "docs-only", allow(dead_code))]
+mod query_metrics;
+mod region;
+#[cfg(all(feature = "http", not(feature = "docs-only")))]
+mod remote;
+mod repeats;
+mod rgfa;
+mod sampling;
+mod smoothing;
+mod spectrum;
+mod splice;
+mod split;
+mod synteny;
+mod threads;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod windows;
+
+pub mod align;
+pub mod annotate;
+pub mod bubbles;
+pub mod gaf;
+pub mod gam;
+pub mod interop;
+pub mod liftover;
+#[cfg(not(feature = "docs-only"))]
+pub mod raw;
+pub mod seq;
+pub mod testing;
+pub mod viz;
 
 // Conditionally compile the conversion module.
 // It will not exist for docs.rs builds.
 #[cfg(not(feature = "docs-only"))]
 mod conversion;
+#[cfg(not(feature = "docs-only"))]
+mod command;
 
 // Publicly re-export the core types for easy access.
-pub use graph::{Graph, Error, Edge, PathPosition};
+pub use graph::{Graph, Error, ErrorKind, Edge, GraphStats, NodeStep, PathPosition};
+pub use alignment::GraphAlignment;
+pub use anchor::ReferenceAnchor;
+pub use assembly::AssemblyReport;
+pub use bed_batch::BedResults;
+pub use cancel::CancellationToken;
+pub use consensus::{ConsensusOptions, ConsensusTieBreak};
+pub use cursor::PathCursor;
+pub use depth::DepthOptions;
+pub use diagnostics::{Diagnostic, DiagnosticKind, DiagnosticLevel};
+pub use edge_key::EdgeKey;
+pub use edge_usage::EdgeUsage;
+pub use fasta::NodeFastaOptions;
+pub use flip::FlipOptions;
+pub use handle::{Direction, Edges, Handle};
+pub use inspect::{inspect, FileInfo};
+pub use inversions::PathInversion;
+#[cfg(not(feature = "docs-only"))]
+pub use journal::Checkpoint;
+pub use load_many::LoadProgress;
+pub use load_options::{LoadOptions, PathIndexing};
+pub use mask::MaskReport;
+pub use mems::Mem;
+pub use minimizer::{MinimizerHit, MinimizerIndex};
+pub use n_runs::{NRun, NRunHandling};
+pub use node_steps::NodeSteps;
+pub use nonreference::NonReferenceRegion;
+pub use orphans::OrphanReport;
+pub use pangenome_offset::{PangenomeOffset, PathCrossing};
+pub use packed::PackedSequence;
+pub use path_alignment::{AlignmentBlock, AlignmentBlockKind, ImpliedAlignment};
+pub use path_identity::PathIdentity;
+pub use path_steps::{PathSteps, RankedStep};
+pub use presence::{PresenceClass, PresenceSummary, PresenceThresholds};
+pub use region::GraphRegion;
+pub use repeats::PathRepeat;
+pub use rgfa::RgfaSegment;
+pub use smoothing::{BubbleEdit, BubbleStrategy};
+pub use spectrum::KmerWeighting;
+pub use synteny::SharedSegment;
+pub use threads::{odgi_threads, set_odgi_threads};
+pub use windows::{PathWindows, WindowStats};
 
 // Conditionally re-export the conversion functions.
 #[cfg(not(feature = "docs-only"))]
-pub use conversion::{gfa_to_odgi, odgi_to_gfa};
+pub use command::{odgi_executable, OdgiCommand, OdgiOutput};
+#[cfg(not(feature = "docs-only"))]
+pub use conversion::{
+    gfa_to_odgi, gfa_to_odgi_cancellable, gfa_to_odgi_with_diagnostics, gfa_to_odgi_with_n_runs,
+    gfa_to_odgi_with_timeout,
+    odgi_to_gfa, odgi_to_gfa_cancellable, odgi_to_gfa_with_diagnostics, odgi_to_gfa_with_timeout,
+};
 
 
 // --- REAL FFI BRIDGE (for normal builds) ---
@@ -114,6 +416,75 @@ mod ffi {
         is_forward: bool,
     }
 
+    /// Represents a single step of a path: a node visited in a given orientation.
+    #[derive(Debug, Clone)]
+    struct PathStep {
+        /// The ID of the node visited by this step.
+        node_id: u64,
+        /// The orientation in which the path traverses the node.
+        is_forward: bool,
+    }
+
+    /// Represents a single visit of a path to a node.
+    #[derive(Debug, Clone)]
+    struct NodeStep {
+        /// The name of the path taking the step.
+        path_name: String,
+        /// The orientation in which the path traverses the node.
+        is_forward: bool,
+    }
+
+    /// Represents a complete directed edge, including both of its endpoints.
+    #[derive(Debug, Clone)]
+    struct GraphEdge {
+        /// The ID of the node where the edge begins.
+        from_node: u64,
+        /// The orientation of the "from" node's handle in this edge.
+        from_orientation: bool,
+        /// The ID of the node where the edge ends.
+        to_node: u64,
+        /// The orientation of the "to" node's handle in this edge.
+        to_orientation: bool,
+    }
+
+    /// A visit of a path to a node, with the path offset where the visit starts.
+    #[derive(Debug, Clone)]
+    struct StepPosition {
+        /// The name of the path taking the step.
+        path_name: String,
+        /// The 0-based path offset of the first base of the step.
+        start: u64,
+        /// The orientation in which the path traverses the node.
+        is_forward: bool,
+    }
+
+    /// Summary statistics of a whole graph, as reported by `odgi stats -S`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    struct GraphStats {
+        /// The number of nodes.
+        node_count: u64,
+        /// The number of edges, counting each edge once in either of its readings.
+        edge_count: u64,
+        /// The total length of all node sequences in base pairs.
+        total_length: u64,
+        /// The number of paths.
+        path_count: u64,
+        /// The total number of steps over all paths.
+        step_count: u64,
+        /// The number of edges that join a node to itself, in either orientation.
+        self_loop_count: u64,
+    }
+
+    /// A step handle, as the two integers that handlegraph packs it into.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct StepHandle {
+        first: i64,
+        second: i64,
+    }
+
+    // Every function that runs odgi code returns `Result`, so cxx catches any
+    // exception it throws instead of letting it abort the process. Only the
+    // accessors that dereference the wrapper cannot throw.
     unsafe extern "C++" {
         include!("odgi-ffi/src/odgi_wrapper.hpp");
         include!("odgi-ffi/src/lib.rs.h");
@@ -123,29 +494,55 @@ mod ffi {
         type OpaqueGraph;
 
         #[namespace = ""]
-        fn load_graph(path: &str) -> UniquePtr<OpaqueGraph>;
+        fn set_num_threads(threads: u64);
+        #[namespace = ""]
+        fn get_num_threads() -> u64;
+        #[namespace = ""]
+        fn load_graph(path: &str) -> Result<UniquePtr<OpaqueGraph>>;
+        #[namespace = ""]
+        fn load_graph_from_bytes(bytes: &[u8]) -> Result<UniquePtr<OpaqueGraph>>;
+        #[namespace = ""]
+        fn new_graph() -> Result<UniquePtr<OpaqueGraph>>;
+        #[namespace = ""]
+        fn graph_from_gfa(gfa_path: &str) -> Result<UniquePtr<OpaqueGraph>>;
+        #[namespace = ""]
+        fn save_graph(graph: &graph_t, path: &str) -> Result<()>;
         #[namespace = ""]
         fn get_graph_t<'a>(graph: &'a OpaqueGraph) -> &'a graph_t;
         #[namespace = ""]
-        fn get_node_count(graph: &graph_t) -> u64;
+        fn get_graph_t_mut<'a>(graph: Pin<&'a mut OpaqueGraph>) -> Pin<&'a mut graph_t>;
+        #[namespace = ""]
+        fn get_node_count(graph: &graph_t) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_edge_count(graph: &graph_t) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_total_length(graph: &graph_t) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_path_names(graph: &graph_t) -> Result<Vec<String>>;
         #[namespace = ""]
-        fn graph_get_path_names(graph: &graph_t) -> Vec<String>;
+        fn graph_project(graph: &graph_t, path_name: &str, pos: u64) -> Result<UniquePtr<PathPosition>>;
         #[namespace = ""]
-        fn graph_project(graph: &graph_t, path_name: &str, pos: u64) -> UniquePtr<PathPosition>;
+        fn graph_get_node_sequence(graph: &graph_t, node_id: u64) -> Result<String>;
         #[namespace = ""]
-        fn graph_get_node_sequence(graph: &graph_t, node_id: u64) -> String;
+        fn graph_get_node_len(graph: &graph_t, node_id: u64) -> Result<u64>;
         #[namespace = ""]
-        fn graph_get_node_len(graph: &graph_t, node_id: u64) -> u64;
+        fn graph_get_node_lens(graph: &graph_t, node_ids: &[u64]) -> Result<Vec<u64>>;
         #[namespace = ""]
-        fn graph_get_successors(graph: &graph_t, node_id: u64) -> Vec<Edge>;
+        fn graph_get_successors(graph: &graph_t, node_id: u64) -> Result<Vec<Edge>>;
         #[namespace = ""]
-        fn graph_get_predecessors(graph: &graph_t, node_id: u64) -> Vec<Edge>;
+        fn graph_get_predecessors(graph: &graph_t, node_id: u64) -> Result<Vec<Edge>>;
         #[namespace = ""]
-        fn graph_get_paths_on_node(graph: &graph_t, node_id: u64) -> Vec<String>;
+        fn graph_get_node_sequence_into(graph: &graph_t, node_id: u64, sequence: &mut [u8]) -> Result<()>;
         #[namespace = ""]
-        fn graph_get_path_length(graph: &graph_t, path_name: &str) -> u64;
+        fn graph_get_successors_into(graph: &graph_t, node_id: u64, edges: &mut Vec<Edge>) -> Result<()>;
         #[namespace = ""]
-        fn graph_get_next_node_on_path(graph: &graph_t, path_name: &str, node_id: u64) -> i64;
+        fn graph_get_predecessors_into(graph: &graph_t, node_id: u64, edges: &mut Vec<Edge>) -> Result<()>;
+        #[namespace = ""]
+        fn graph_get_paths_on_node(graph: &graph_t, node_id: u64) -> Result<Vec<String>>;
+        #[namespace = ""]
+        fn graph_get_path_length(graph: &graph_t, path_name: &str) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_next_node_on_path(graph: &graph_t, path_name: &str, node_id: u64) -> Result<i64>;
         #[namespace = ""]
         fn graph_get_paths_on_edge(
             graph: &graph_t,
@@ -153,7 +550,124 @@ mod ffi {
             from_orient: bool,
             to_node: u64,
             to_orient: bool
-        ) -> Vec<String>;
+        ) -> Result<Vec<String>>;
+        #[namespace = ""]
+        fn graph_get_node_ids(graph: &graph_t) -> Result<Vec<u64>>;
+        #[namespace = ""]
+        fn graph_get_path_steps(graph: &graph_t, path_name: &str) -> Result<Vec<PathStep>>;
+        #[namespace = ""]
+        fn graph_get_edges(graph: &graph_t) -> Result<Vec<GraphEdge>>;
+        #[namespace = ""]
+        fn graph_get_node_step_count(graph: &graph_t, node_id: u64) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_steps_on_node(graph: &graph_t, node_id: u64, offset: u64, limit: u64) -> Result<Vec<NodeStep>>;
+        #[namespace = ""]
+        fn graph_get_node_sort_offset(graph: &graph_t, node_id: u64) -> Result<i64>;
+        #[namespace = ""]
+        fn graph_get_node_sort_offsets(graph: &graph_t) -> Result<Vec<u64>>;
+        #[namespace = ""]
+        fn graph_get_node_at_sort_offset(graph: &graph_t, offset: u64) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_node_order(graph: &graph_t) -> Result<Vec<u64>>;
+        #[namespace = ""]
+        fn graph_prefault(graph: &graph_t) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_path_count(graph: &graph_t) -> Result<u64>;
+        #[namespace = ""]
+        fn graph_get_node_ids_at_ranks(graph: &graph_t, ranks: &[u64]) -> Result<Vec<u64>>;
+        #[namespace = ""]
+        fn graph_get_path_names_at_ranks(graph: &graph_t, ranks: &[u64]) -> Result<Vec<String>>;
+        #[namespace = ""]
+        fn graph_project_sorted(graph: &graph_t, path_name: &str, positions: &[u64]) -> Result<Vec<PathPosition>>;
+        #[namespace = ""]
+        fn graph_get_node_path_positions(graph: &graph_t, node_id: u64, skip_paths: &[String]) -> Result<Vec<StepPosition>>;
+        #[namespace = ""]
+        fn graph_has_path(graph: &graph_t, path_name: &str) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_get_stats(graph: &graph_t) -> Result<GraphStats>;
+        #[namespace = ""]
+        fn graph_has_edge(
+            graph: &graph_t,
+            from_node: u64,
+            from_orient: bool,
+            to_node: u64,
+            to_orient: bool
+        ) -> Result<bool>;
+
+        // --- Construction Functions ---
+        #[namespace = ""]
+        fn graph_create_node(graph: Pin<&mut graph_t>, node_id: u64, sequence: &str) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_create_edge(
+            graph: Pin<&mut graph_t>,
+            from_node: u64,
+            from_orient: bool,
+            to_node: u64,
+            to_orient: bool
+        ) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_create_path(graph: Pin<&mut graph_t>, path_name: &str) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_append_step(graph: Pin<&mut graph_t>, path_name: &str, node_id: u64, is_forward: bool) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_destroy_path(graph: Pin<&mut graph_t>, path_name: &str) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_truncate_path(graph: Pin<&mut graph_t>, path_name: &str, length: u64) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_destroy_node(graph: Pin<&mut graph_t>, node_id: u64) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_destroy_edge(
+            graph: Pin<&mut graph_t>,
+            from_node: u64,
+            from_orient: bool,
+            to_node: u64,
+            to_orient: bool
+        ) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_divide_node(graph: Pin<&mut graph_t>, node_id: u64, offsets: &[u64]) -> Result<Vec<u64>>;
+
+        #[namespace = ""]
+        fn graph_apply_node_order(graph: Pin<&mut graph_t>, node_ids: &[u64]) -> Result<bool>;
+        #[namespace = ""]
+        fn graph_get_path_step_count(graph: &graph_t, path_name: &str) -> Result<u64>;
+
+        // --- Raw Handle Functions ---
+        #[namespace = ""]
+        fn raw_has_node(graph: &graph_t, node_id: u64) -> Result<bool>;
+        #[namespace = ""]
+        fn raw_get_handle(graph: &graph_t, node_id: u64, is_reverse: bool) -> Result<u64>;
+        #[namespace = ""]
+        fn raw_get_id(graph: &graph_t, handle: u64) -> Result<u64>;
+        #[namespace = ""]
+        fn raw_get_length(graph: &graph_t, handle: u64) -> Result<u64>;
+        #[namespace = ""]
+        fn raw_get_sequence(graph: &graph_t, handle: u64) -> Result<String>;
+        #[namespace = ""]
+        fn raw_follow_edges(graph: &graph_t, handle: u64, go_left: bool) -> Result<Vec<u64>>;
+        #[namespace = ""]
+        fn raw_get_degree(graph: &graph_t, handle: u64, go_left: bool) -> Result<u64>;
+        #[namespace = ""]
+        fn raw_get_edges(graph: &graph_t) -> Result<Vec<u64>>;
+        #[namespace = ""]
+        fn raw_get_path_handle(graph: &graph_t, path_name: &str) -> Result<i64>;
+        #[namespace = ""]
+        fn raw_path_begin(graph: &graph_t, path: u64) -> Result<StepHandle>;
+        #[namespace = ""]
+        fn raw_path_end(graph: &graph_t, path: u64) -> Result<StepHandle>;
+        #[namespace = ""]
+        fn raw_has_next_step(graph: &graph_t, step: StepHandle) -> Result<bool>;
+        #[namespace = ""]
+        fn raw_has_previous_step(graph: &graph_t, step: StepHandle) -> Result<bool>;
+        #[namespace = ""]
+        fn raw_get_next_step(graph: &graph_t, step: StepHandle) -> Result<StepHandle>;
+        #[namespace = ""]
+        fn raw_get_previous_step(graph: &graph_t, step: StepHandle) -> Result<StepHandle>;
+        #[namespace = ""]
+        fn raw_get_handle_of_step(graph: &graph_t, step: StepHandle) -> Result<u64>;
+        #[namespace = ""]
+        fn raw_get_path_handle_of_step(graph: &graph_t, step: StepHandle) -> Result<u64>;
+        #[namespace = ""]
+        fn raw_get_steps_on_handle(graph: &graph_t, handle: u64) -> Result<Vec<StepHandle>>;
     }
 }
 
@@ -163,9 +677,6 @@ mod ffi {
     // This self-contained mock module provides all the types that `graph.rs` needs
     // to compile its public API for documentation purposes.
 
-    // Mock the opaque C++ types.
-    pub enum OpaqueGraph {}
-
     // Provide mock definitions for the shared structs.
     #[derive(Debug, Clone)]
     pub struct Edge {
@@ -180,4 +691,41 @@ mod ffi {
         pub offset: u64,
         pub is_forward: bool,
     }
+
+    #[derive(Debug, Clone)]
+    pub struct PathStep {
+        pub node_id: u64,
+        pub is_forward: bool,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct NodeStep {
+        pub path_name: String,
+        pub is_forward: bool,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct GraphEdge {
+        pub from_node: u64,
+        pub from_orientation: bool,
+        pub to_node: u64,
+        pub to_orientation: bool,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct StepPosition {
+        pub path_name: String,
+        pub start: u64,
+        pub is_forward: bool,
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct GraphStats {
+        pub node_count: u64,
+        pub edge_count: u64,
+        pub total_length: u64,
+        pub path_count: u64,
+        pub step_count: u64,
+        pub self_loop_count: u64,
+    }
 }
\ No newline at end of file
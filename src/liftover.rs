@@ -0,0 +1,294 @@
+// src/liftover.rs
+
+//! Coordinate liftover between graphs and between the paths of one graph.
+//!
+//! Two releases of a pangenome graph rarely share node IDs, but they usually
+//! embed the same reference assembly as a path. That path provides a common
+//! linear coordinate system through which positions can be translated.
+//! Within a single graph, the nodes shared by two paths relate their
+//! coordinates, which can be exported as a chain file.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use super::bed_batch::BedResults;
+use super::graph::{Error, Graph, PathPosition};
+use super::path_alignment::PathAlignment;
+use super::path_index::PathIndex;
+use super::region::GraphRegion;
+
+/// Maps graph positions in `a` to the corresponding positions in `b`.
+///
+/// Each position is first projected onto `reference_path` in `a`, and the
+/// resulting reference coordinate is then projected back into `b`. The strand
+/// of the position relative to the reference is preserved, so a position on
+/// the reverse strand of the reference in `a` ends up on the reverse strand of
+/// the reference in `b`.
+///
+/// The result has one entry per input position. It is `None` if the position's
+/// node does not exist or is not visited by the reference path in `a`. If the
+/// reference visits a node more than once, its first visit is used.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if either graph lacks `reference_path`, or if the path
+/// has a different length in the two graphs, which means the graphs were not
+/// built from the same reference.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{liftover, Graph, PathPosition};
+///
+/// let v1 = Graph::load("release_1.odgi").unwrap();
+/// let v2 = Graph::load("release_2.odgi").unwrap();
+/// let position = PathPosition { node_id: 42, offset: 3, is_forward: true };
+/// let lifted = liftover::across_graphs(&v1, &v2, "GRCh38#0#chr1", &[position]).unwrap();
+/// if let Some(p) = &lifted[0] {
+///     println!("Node 42+3 in v1 is node {}+{} in v2", p.node_id, p.offset);
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(a, b, positions), err))]
+pub fn across_graphs(
+    a: &Graph,
+    b: &Graph,
+    reference_path: &str,
+    positions: &[PathPosition],
+) -> Result<Vec<Option<PathPosition>>, Error> {
+    let missing = || Error::path_not_found(reference_path);
+    let a_index = PathIndex::new(a, reference_path)?.ok_or_else(missing)?;
+    let b_index = PathIndex::new(b, reference_path)?.ok_or_else(missing)?;
+    if a_index.length != b_index.length {
+        return Err(Error::invalid_argument(format!(
+            "Path '{}' is {} bp long in the first graph but {} bp in the second",
+            reference_path, a_index.length, b_index.length
+        )));
+    }
+
+    let mut first_step: HashMap<u64, usize> = HashMap::new();
+    for (i, step) in a_index.steps.iter().enumerate() {
+        first_step.entry(step.node_id).or_insert(i);
+    }
+
+    Ok(positions
+        .iter()
+        .map(|position| {
+            let &i = first_step.get(&position.node_id)?;
+            let a_step = &a_index.steps[i];
+            if position.offset >= a_index.lengths[i] {
+                return None;
+            }
+            let offset_in_step = if a_step.is_forward {
+                position.offset
+            } else {
+                a_index.lengths[i] - 1 - position.offset
+            };
+            let reference_pos = a_index.starts[i] + offset_in_step;
+
+            let j = b_index.step_at(reference_pos)?;
+            let b_step = &b_index.steps[j];
+            let offset_in_step = reference_pos - b_index.starts[j];
+            let same_strand = position.is_forward == a_step.is_forward;
+            Some(PathPosition {
+                node_id: b_step.node_id,
+                offset: if b_step.is_forward {
+                    offset_in_step
+                } else {
+                    b_index.lengths[j] - 1 - offset_in_step
+                },
+                is_forward: b_step.is_forward == same_strand,
+            })
+        })
+        .collect())
+}
+
+/// Writes a UCSC chain file lifting coordinates on `from_path` over to `to_path`.
+///
+/// Both paths must be embedded in `graph`. Their alignment is derived from the
+/// nodes they share, as described for [`Graph::paths_to_paf`], and written as
+/// a single chain with `from_path` as the target (`tName`) and `to_path` as the
+/// query (`qName`). If `to_path` runs through the shared nodes in the opposite
+/// direction, the chain is on the `-` query strand. The output can be used
+/// directly with `liftOver`, CrossMap, and other tools that read chain files.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if either path does not exist, if the paths share no
+/// node in a consistent orientation, or if writing fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{liftover, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let mut out = std::fs::File::create("grch38_to_chm13.chain").unwrap();
+/// liftover::write_chain(&graph, "GRCh38#0#chr1", "CHM13#0#chr1", &mut out).unwrap();
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph, writer), err))]
+pub fn write_chain<W: Write>(graph: &Graph, from_path: &str, to_path: &str, writer: &mut W) -> Result<(), Error> {
+    let from = PathIndex::new(graph, from_path)?
+        .ok_or_else(|| Error::path_not_found(from_path))?;
+    let to = PathIndex::new(graph, to_path)?
+        .ok_or_else(|| Error::path_not_found(to_path))?;
+    let to_length = to.length;
+
+    let alignment = PathAlignment::new(to, &from);
+    let (Some((t_start, t_end)), Some((q_start, q_end))) =
+        (alignment.reference_range(&from), alignment.query_range())
+    else {
+        return Err(Error::invalid_argument(format!("Paths '{}' and '{}' do not share any nodes", from_path, to_path)));
+    };
+    // Chain coordinates on the negative strand count from the end of the sequence.
+    let (q_start, q_end) = if alignment.is_reverse {
+        (to_length - q_end, to_length - q_start)
+    } else {
+        (q_start, q_end)
+    };
+
+    let ops = alignment.operations(graph, &from)?;
+    let score: u64 = ops.iter().filter(|(op, _)| *op == '=').map(|(_, len)| len).sum();
+    let mut blocks = Vec::new();
+    let (mut size, mut dt, mut dq) = (0, 0, 0);
+    for &(op, len) in &ops {
+        match op {
+            'D' => dt += len,
+            'I' => dq += len,
+            _ => {
+                if dt > 0 || dq > 0 {
+                    blocks.push((size, dt, dq));
+                    (size, dt, dq) = (0, 0, 0);
+                }
+                size += len;
+            }
+        }
+    }
+
+    let io_err = |e: std::io::Error| Error::io("Failed to write chain output", e);
+    writeln!(
+        writer,
+        "chain {} {} {} + {} {} {} {} {} {} {} 1",
+        score,
+        from_path,
+        from.length,
+        t_start,
+        t_end,
+        to_path,
+        to_length,
+        if alignment.is_reverse { '-' } else { '+' },
+        q_start,
+        q_end
+    )
+    .map_err(io_err)?;
+    for (size, dt, dq) in blocks {
+        writeln!(writer, "{}\t{}\t{}", size, dt, dq).map_err(io_err)?;
+    }
+    writeln!(writer, "{}\n", size).map_err(io_err)?;
+    Ok(())
+}
+
+/// Lifts a region of one path over to `to_path` through the nodes they share.
+///
+/// The paths are aligned as for [`write_chain`], and the region is mapped to
+/// the span of `to_path` between the first and the last aligned node that
+/// overlaps it, trimmed to the region's ends within those nodes. The result
+/// is on `to_path`'s own forward strand, also when the paths are aligned in
+/// opposite directions.
+///
+/// Returns `None` if no aligned node overlaps the region.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if either path does not exist or the region is empty.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{liftover, Graph, GraphRegion};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let region: GraphRegion = "GRCh38#0#chr1:1,000,001-1,010,000".parse().unwrap();
+/// if let Some(lifted) = liftover::lift_region(&graph, &region, "CHM13#0#chr1").unwrap() {
+///     println!("{} -> {}", region, lifted);
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph), err))]
+pub fn lift_region(graph: &Graph, region: &GraphRegion, to_path: &str) -> Result<Option<GraphRegion>, Error> {
+    let (from, start, end) = region.index(graph)?;
+    let to = PathIndex::new(graph, to_path)?
+        .ok_or_else(|| Error::path_not_found(to_path))?;
+    let to_length = to.length;
+
+    let alignment = PathAlignment::new(to, &from);
+    Ok(lift_range(&alignment, &from, to_length, to_path, start, end))
+}
+
+/// Lifts `start..end` of `from` to the query of `alignment`, a path of `to_length` bp named `to_path`.
+fn lift_range(
+    alignment: &PathAlignment,
+    from: &PathIndex,
+    to_length: u64,
+    to_path: &str,
+    start: u64,
+    end: u64,
+) -> Option<GraphRegion> {
+    let from_end = |j: usize| from.starts[j] + from.lengths[j];
+    let mut overlapping = alignment
+        .anchors
+        .iter()
+        .filter(|&&(_, j)| from.starts[j] < end && from_end(j) > start);
+    let &(first_i, first_j) = overlapping.next()?;
+    let (last_i, last_j) = overlapping.next_back().copied().unwrap_or((first_i, first_j));
+
+    let query = &alignment.query;
+    let q_start = query.starts[first_i] + (start.max(from.starts[first_j]) - from.starts[first_j]);
+    let q_end = query.starts[last_i] + (end.min(from_end(last_j)) - from.starts[last_j]);
+    let (q_start, q_end) = if alignment.is_reverse {
+        (to_length - q_end, to_length - q_start)
+    } else {
+        (q_start, q_end)
+    };
+    Some(GraphRegion::new(to_path, q_start, q_end))
+}
+
+/// Lifts every interval of a BED file over to `to_path`, as [`lift_region`] does for one region.
+///
+/// The results are streamed in the order of the BED lines. The alignment
+/// between `to_path` and each path named in the BED file is computed once, when
+/// the path first occurs, so large BED files cost little more than their paths' alignments.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if `to_path` does not exist. The iterator yields an
+/// error for each BED line that is malformed, names a path that does not
+/// exist, or extends past the end of its path, and continues with the next line.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{liftover, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let bed = std::io::BufReader::new(std::fs::File::open("grch38_peaks.bed").unwrap());
+/// for result in liftover::lift_bed(&graph, bed, "CHM13#0#chr1").unwrap() {
+///     let (region, lifted) = result.unwrap();
+///     match lifted {
+///         Some(lifted) => println!("{}\t{}\t{}", lifted.path, lifted.start, lifted.end.unwrap()),
+///         None => eprintln!("{} does not lift over", region),
+///     }
+/// }
+/// ```
+pub fn lift_bed<'g, R: BufRead>(
+    graph: &'g Graph,
+    bed_reader: R,
+    to_path: &str,
+) -> Result<BedResults<'g, R, Option<GraphRegion>>, Error> {
+    let to = PathIndex::new(graph, to_path)?.ok_or_else(|| Error::path_not_found(to_path))?;
+    let to_path = to_path.to_string();
+    let mut alignments: HashMap<String, PathAlignment> = HashMap::new();
+    Ok(BedResults::new(graph, bed_reader, move |_, record, from| {
+        let alignment = alignments
+            .entry(record.chrom.clone())
+            .or_insert_with(|| PathAlignment::new(to.clone(), from));
+        Ok(lift_range(alignment, from, to.length, &to_path, record.start, record.end))
+    }))
+}
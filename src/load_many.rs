@@ -0,0 +1,123 @@
+// src/load_many.rs
+
+//! Loading several graphs concurrently.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use super::graph::{Error, Graph};
+
+/// The state of a [`Graph::load_many_with_progress`] call after one more file has been loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The file that was just loaded or failed to load.
+    pub path: String,
+    /// Whether loading the file succeeded.
+    pub succeeded: bool,
+    /// The number of files finished so far, including this one.
+    pub completed: usize,
+    /// The number of files to load.
+    pub total: usize,
+    /// The on-disk size of the finished files.
+    pub bytes_completed: u64,
+    /// The on-disk size of all files. Files whose size cannot be read count as empty.
+    pub total_bytes: u64,
+}
+
+impl Graph {
+    /// Loads several ODGI files concurrently.
+    ///
+    /// At most `max_parallel` files are loaded at a time; `0` uses the number
+    /// of available CPUs. Every file is loaded as with [`Graph::load`], and one
+    /// file failing does not stop the others. The results are returned in the
+    /// order of `paths`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let paths: Vec<String> = (1..=22).map(|c| format!("graphs/chr{}.og", c)).collect();
+    /// let graphs: Vec<Graph> = Graph::load_many(&paths, 8).into_iter().collect::<Result<_, _>>().unwrap();
+    /// println!("Loaded {} chromosome graphs", graphs.len());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(paths)))]
+    pub fn load_many<S: AsRef<str> + Sync>(paths: &[S], max_parallel: usize) -> Vec<Result<Graph, Error>> {
+        Self::load_many_with_progress(paths, max_parallel, |_| {})
+    }
+
+    /// Loads several ODGI files concurrently, reporting progress after each file.
+    ///
+    /// This behaves like [`Graph::load_many`], but calls `progress` with the
+    /// aggregated [`LoadProgress`] whenever a file has finished loading. The
+    /// calls are made one at a time, from the loading threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let paths = ["chr1.og", "chr2.og", "chr3.og"];
+    /// let results = Graph::load_many_with_progress(&paths, 0, |p| {
+    ///     eprintln!("[{}/{}] {} ({} of {} bytes)", p.completed, p.total, p.path, p.bytes_completed, p.total_bytes);
+    /// });
+    /// for (path, result) in paths.iter().zip(&results) {
+    ///     if let Err(e) = result {
+    ///         eprintln!("{}: {}", path, e);
+    ///     }
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(paths, progress)))]
+    pub fn load_many_with_progress<S, F>(paths: &[S], max_parallel: usize, progress: F) -> Vec<Result<Graph, Error>>
+    where
+        S: AsRef<str> + Sync,
+        F: Fn(LoadProgress) + Sync,
+    {
+        let sizes: Vec<u64> = paths
+            .iter()
+            .map(|path| std::fs::metadata(path.as_ref()).map_or(0, |m| m.len()))
+            .collect();
+        let total_bytes = sizes.iter().sum();
+        let workers = match max_parallel {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        }
+        .min(paths.len());
+
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<Result<Graph, Error>>>> = Mutex::new((0..paths.len()).map(|_| None).collect());
+        // (completed, bytes_completed), updated and reported under one lock so reports are ordered.
+        let finished = Mutex::new((0, 0));
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = paths.get(i) else {
+                        break;
+                    };
+                    let result = Graph::load(path.as_ref());
+                    let succeeded = result.is_ok();
+                    results.lock().unwrap()[i] = Some(result);
+
+                    let mut finished = finished.lock().unwrap();
+                    finished.0 += 1;
+                    finished.1 += sizes[i];
+                    progress(LoadProgress {
+                        path: path.as_ref().to_string(),
+                        succeeded,
+                        completed: finished.0,
+                        total: paths.len(),
+                        bytes_completed: finished.1,
+                        total_bytes,
+                    });
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.expect("every file is loaded by a worker"))
+            .collect()
+    }
+}
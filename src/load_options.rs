@@ -0,0 +1,182 @@
+// src/load_options.rs
+
+//! Loading a graph with explicit start-up work.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use super::edge_key::EdgeKey;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// Which paths [`Graph::load_with`] indexes while loading.
+///
+/// An index holds the offset of every step of a path, so region extraction,
+/// liftover, depth, alignment, and [`Graph::node_to_path_positions`] on an
+/// indexed path look coordinates up instead of walking the path first. It
+/// takes about 32 bytes per step and is dropped by the first edit to the graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PathIndexing {
+    /// Index paths when a query needs them, as [`Graph::load`] does.
+    #[default]
+    OnDemand,
+    /// Index every path.
+    All,
+    /// Index the named paths.
+    Paths(Vec<String>),
+}
+
+/// Options controlling [`Graph::load_with`].
+///
+/// The defaults load a graph exactly as [`Graph::load`] does. The other
+/// settings move work from the first queries to load time, for services that
+/// would rather start slower than answer their first requests slowly.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Check that every pair of consecutive steps in each path is joined by
+    /// an edge of the graph, as `odgi validate` does, and fail to load a
+    /// graph where one is not.
+    pub validate: bool,
+    /// Read through every node, edge, and path step once after loading, as
+    /// [`Graph::prefault`] does, so that the first queries do not pay for it.
+    pub prefault: bool,
+    /// Index the steps and offsets of these paths and keep the indexes with
+    /// the graph.
+    pub index_paths: PathIndexing,
+    /// The number of path validation and indexing threads; `0` uses the
+    /// number of available CPUs. Deserialization itself runs on one thread.
+    pub threads: usize,
+}
+
+impl Graph {
+    /// Loads an ODGI graph from a file, doing the start-up work chosen in `options`.
+    ///
+    /// The file is read as by [`Graph::load`], including compressed files.
+    /// odgi cannot answer queries from a memory-mapped file, so there is no
+    /// option to map it: every loaded graph is a private copy on the heap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if [`Graph::load`] would, of kind
+    /// [`ErrorKind::InvalidFormat`](crate::ErrorKind::InvalidFormat) if
+    /// validation is requested and a path steps between two nodes that no edge
+    /// joins, or of kind [`ErrorKind::PathNotFound`](crate::ErrorKind::PathNotFound)
+    /// if a path named in [`LoadOptions::index_paths`] does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Graph, LoadOptions, PathIndexing};
+    ///
+    /// let options = LoadOptions {
+    ///     validate: true,
+    ///     prefault: true,
+    ///     index_paths: PathIndexing::Paths(vec!["GRCh38#0#chr6".to_string()]),
+    ///     threads: 8,
+    /// };
+    /// let graph = Graph::load_with("pangenome.odgi", &options).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+    pub fn load_with(path: &str, options: &LoadOptions) -> Result<Self, Error> {
+        let mut graph = Graph::load(path)?;
+        if options.validate {
+            graph
+                .validate_paths(options.threads)
+                .map_err(|e| e.context(format!("Invalid graph in '{}'", path)))?;
+        }
+        if options.prefault {
+            graph.prefault()?;
+        }
+        let path_names = match &options.index_paths {
+            PathIndexing::OnDemand => Vec::new(),
+            PathIndexing::All => graph.get_path_names()?,
+            PathIndexing::Paths(names) => names.clone(),
+        };
+        let indexes = graph.index_paths(&path_names, options.threads)?;
+        graph.keep_path_indexes(indexes);
+        Ok(graph)
+    }
+
+    /// Builds the index of each of `path_names`, splitting the paths across `threads` threads.
+    fn index_paths(&self, path_names: &[String], threads: usize) -> Result<HashMap<String, Arc<PathIndex>>, Error> {
+        let indexes = self.map_paths(path_names, threads, |path_name| {
+            let index = PathIndex::build(self, path_name)?.ok_or_else(|| Error::path_not_found(path_name))?;
+            Ok((path_name.to_string(), Arc::new(index)))
+        })?;
+        Ok(indexes.into_iter().collect())
+    }
+
+    /// Keeps path indexes for later queries, until the graph is next edited.
+    #[cfg(not(feature = "docs-only"))]
+    fn keep_path_indexes(&mut self, indexes: HashMap<String, Arc<PathIndex>>) {
+        self.path_indexes.extend(indexes);
+    }
+
+    /// Keeps path indexes for later queries, until the graph is next edited.
+    #[cfg(feature = "docs-only")]
+    fn keep_path_indexes(&mut self, _indexes: HashMap<String, Arc<PathIndex>>) {}
+
+    /// Checks that every path only follows edges of the graph, splitting the paths across `threads` threads.
+    fn validate_paths(&self, threads: usize) -> Result<(), Error> {
+        let edges: HashSet<EdgeKey> = self
+            .raw_edges()?
+            .into_iter()
+            .map(|e| EdgeKey::canonical(e.from_node, e.from_orientation, e.to_node, e.to_orientation))
+            .collect();
+        self.map_paths(&self.get_path_names()?, threads, |path_name| {
+            let steps = self.raw_path_steps(path_name)?;
+            for (rank, pair) in steps.windows(2).enumerate() {
+                let key = EdgeKey::canonical(pair[0].node_id, pair[0].is_forward, pair[1].node_id, pair[1].is_forward);
+                if !edges.contains(&key) {
+                    return Err(Error::invalid_format(format!(
+                        "path '{}' steps from node {} to node {} at rank {} without an edge",
+                        path_name,
+                        pair[0].node_id,
+                        pair[1].node_id,
+                        rank + 1
+                    )));
+                }
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Runs `f` on each of `path_names` across `threads` threads and collects
+    /// the results, stopping each thread at its first error.
+    fn map_paths<T, F>(&self, path_names: &[String], threads: usize, f: F) -> Result<Vec<T>, Error>
+    where
+        T: Send,
+        F: Fn(&str) -> Result<T, Error> + Sync,
+    {
+        let workers = match threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        }
+        .min(path_names.len());
+
+        let next = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| -> Result<Vec<T>, Error> {
+                        let mut results = Vec::new();
+                        while let Some(path_name) = path_names.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            results.push(f(path_name)?);
+                        }
+                        Ok(results)
+                    })
+                })
+                .collect();
+            let mut results = Vec::with_capacity(path_names.len());
+            for handle in handles {
+                let worker_results = handle
+                    .join()
+                    .map_err(|_| Error::other("A path validation or indexing thread panicked"))??;
+                results.extend(worker_results);
+            }
+            Ok(results)
+        })
+    }
+}
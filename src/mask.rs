@@ -0,0 +1,119 @@
+// src/mask.rs
+
+//! Removing nodes whose path depth falls outside a range.
+
+use std::collections::HashSet;
+use super::graph::{Error, Graph};
+
+/// What [`Graph::mask_by_depth`] removed from the graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaskReport {
+    /// The IDs of the removed nodes, in ascending order.
+    pub removed_nodes: Vec<u64>,
+    /// The total sequence length of the removed nodes.
+    pub removed_bp: u64,
+    /// The number of path steps that visited removed nodes.
+    pub removed_steps: u64,
+    /// Paths that lost steps, each with the names of the fragments that replace it.
+    ///
+    /// A path whose steps were all removed has no fragments.
+    pub split_paths: Vec<(String, Vec<String>)>,
+}
+
+impl Graph {
+    /// Returns a copy of the graph without the nodes whose depth is outside `min_depth..=max_depth`.
+    ///
+    /// The depth of a node is the number of path steps on it, counting repeated
+    /// visits. Removed nodes take their edges and path steps with them. A path
+    /// that loses steps is split into fragments at every removed run; each
+    /// fragment is named `path:start-end` after the 0-based, half-open range it
+    /// covers on the original path. Paths that keep all of their steps keep
+    /// their name. Node IDs are preserved.
+    ///
+    /// Use `min_depth` to drop nodes that too few haplotypes support and
+    /// `max_depth` to drop collapsed repeats that attract an unusual number of
+    /// steps. Everything that was removed is listed in the returned [`MaskReport`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi fails to build the masked graph, or one of
+    /// kind [`ErrorKind::PathExists`](crate::ErrorKind::PathExists) if a
+    /// fragment name is already taken by another path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let (masked, report) = graph.mask_by_depth(2, 500).unwrap();
+    /// println!(
+    ///     "Removed {} nodes ({} bp), split {} paths",
+    ///     report.removed_nodes.len(),
+    ///     report.removed_bp,
+    ///     report.split_paths.len()
+    /// );
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn mask_by_depth(&self, min_depth: u64, max_depth: u64) -> Result<(Graph, MaskReport), Error> {
+        let mut masked = Graph::empty()?;
+        let mut report = MaskReport::default();
+        let mut kept = HashSet::new();
+        for node_id in self.node_ids()? {
+            let depth = self.node_depth(node_id)?;
+            if (min_depth..=max_depth).contains(&depth) {
+                masked.add_node(node_id, &self.get_node_sequence(node_id)?)?;
+                kept.insert(node_id);
+            } else {
+                report.removed_nodes.push(node_id);
+                report.removed_bp += self.get_node_len(node_id)?;
+                report.removed_steps += depth;
+            }
+        }
+
+        for edge in self.raw_edges()? {
+            if kept.contains(&edge.from_node) && kept.contains(&edge.to_node) {
+                masked.add_edge(edge.from_node, edge.from_orientation, edge.to_node, edge.to_orientation)?;
+            }
+        }
+
+        for path_name in self.get_path_names()? {
+            let steps = self.raw_path_steps(&path_name)?;
+            if steps.iter().all(|step| kept.contains(&step.node_id)) {
+                masked.create_path(&path_name, steps.iter().map(|step| (step.node_id, step.is_forward)))?;
+                continue;
+            }
+
+            // Collect the runs of kept steps with their path coordinates.
+            let mut fragments = Vec::new();
+            let mut run: Vec<(u64, bool)> = Vec::new();
+            let (mut run_start, mut pos) = (0, 0);
+            for step in &steps {
+                let length = self.get_node_len(step.node_id)?;
+                if kept.contains(&step.node_id) {
+                    if run.is_empty() {
+                        run_start = pos;
+                    }
+                    run.push((step.node_id, step.is_forward));
+                } else if !run.is_empty() {
+                    fragments.push((run_start, pos, std::mem::take(&mut run)));
+                }
+                pos += length;
+            }
+            if !run.is_empty() {
+                fragments.push((run_start, pos, run));
+            }
+
+            let mut fragment_names = Vec::with_capacity(fragments.len());
+            for (start, end, run) in fragments {
+                let fragment_name = format!("{}:{}-{}", path_name, start, end);
+                // A fragment name can collide with an original path that
+                // happens to be named like a coordinate range.
+                masked.create_path(&fragment_name, run)?;
+                fragment_names.push(fragment_name);
+            }
+            report.split_paths.push((path_name, fragment_names));
+        }
+
+        Ok((masked, report))
+    }
+}
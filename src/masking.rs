@@ -0,0 +1,74 @@
+// src/masking.rs
+
+//! Queries over soft-masked (lowercase) sequence.
+
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// Returns the fraction of lowercase bases in a sequence.
+fn lowercase_fraction(sequence: &str) -> f64 {
+    let masked = sequence.bytes().filter(u8::is_ascii_lowercase).count();
+    masked as f64 / sequence.len() as f64
+}
+
+impl Graph {
+    /// Returns the fraction of a node's bases that are soft-masked.
+    ///
+    /// Repeat-masked assemblies mark repeats with lowercase bases, and graphs
+    /// built from them keep that case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// if graph.masked_fraction(42).unwrap() > 0.5 {
+    ///     println!("Node 42 is mostly repeat sequence");
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn masked_fraction(&self, node_id: u64) -> Result<f64, Error> {
+        let sequence = self.get_node_sequence(node_id)?;
+        if sequence.is_empty() {
+            return Ok(0.0);
+        }
+        Ok(lowercase_fraction(&sequence))
+    }
+
+    /// Returns the fraction of soft-masked bases in the interval `start..end` of a path.
+    ///
+    /// This is the masking state that matters for designing probes or primers
+    /// against a haplotype. The interval is 0-based and half-open, and is
+    /// clamped to the end of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist or the interval is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let fraction = graph.path_masked_fraction("HG002#1#chr1", 10_000, 10_120).unwrap();
+    /// println!("{:.0}% of the probe target is masked", fraction * 100.0);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn path_masked_fraction(&self, path_name: &str, start: u64, end: u64) -> Result<f64, Error> {
+        let index = PathIndex::new(self, path_name)?
+            .ok_or_else(|| Error::path_not_found(path_name))?;
+        let sequence = index.sequence(self, start, end)?;
+        if sequence.is_empty() {
+            return Err(Error::invalid_argument(format!(
+                "Interval {}-{} is empty on path '{}' of length {}",
+                start, end, path_name, index.length
+            )));
+        }
+        Ok(lowercase_fraction(&sequence))
+    }
+}
@@ -0,0 +1,277 @@
+// src/mems.rs
+
+//! Maximal exact matches between a query sequence and the graph.
+
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+use super::graph::{Error, Graph};
+use super::minimizer::{MinimizerHit, MinimizerIndex};
+use super::query_metrics;
+use super::seq::{bases_match, reverse_complement};
+
+/// The k-mer length used by [`Graph::find_mems`] for its own index.
+const SEED_K: usize = 15;
+/// The maximum number of nodes a single extension may visit, bounding the
+/// search in highly branching regions.
+const MAX_EXTENSION_NODES: usize = 4096;
+
+/// A maximal exact match between a query and a walk through the graph.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mem {
+    /// The start of the match on the query.
+    pub query_start: usize,
+    /// The end of the match on the query (exclusive).
+    pub query_end: usize,
+    /// The oriented nodes spelling the match, as `(node_id, is_forward)`.
+    ///
+    /// A node visited in reverse matches the reverse complement of its
+    /// sequence, so the strand of the match is given by the orientations.
+    pub walk: Vec<(u64, bool)>,
+    /// The offset of the first matched base on the first node, in its orientation in the walk.
+    pub walk_start: u64,
+    /// The offset after the last matched base on the last node, in its orientation in the walk.
+    pub walk_end: u64,
+}
+
+/// The result of extending a match to the right through the graph.
+struct Extension {
+    len: usize,
+    walk: Vec<(u64, bool)>,
+    end: u64,
+}
+
+/// A node whose match reached its end, waiting on the extensions through its successors.
+struct Frame {
+    /// The match on this node alone.
+    extension: Extension,
+    /// The target offset at which the successors continue the match.
+    t: usize,
+    successors: Vec<(u64, bool)>,
+    /// The index of the next successor to try.
+    next: usize,
+    /// The longest extension through a successor so far.
+    best: Option<Extension>,
+}
+
+/// The outcome of matching the target against a single node.
+enum NodeMatch {
+    /// The match ends on this node.
+    Done(Extension),
+    /// The match reached the end of the node and continues into its successors.
+    Branch(Frame),
+}
+
+/// Matches a query against oriented node sequences, caching the sequences.
+struct Extender<'g> {
+    graph: &'g Graph,
+    sequences: RefCell<HashMap<(u64, bool), Vec<u8>>>,
+}
+
+impl Extender<'_> {
+    fn node_len(&self, handle: (u64, bool)) -> Result<u64, Error> {
+        self.with_sequence(handle, |s| s.len() as u64)
+    }
+
+    fn with_sequence<T>(&self, handle: (u64, bool), f: impl FnOnce(&[u8]) -> T) -> Result<T, Error> {
+        let mut sequences = self.sequences.borrow_mut();
+        query_metrics::cache_lookup("mems_node_sequences", sequences.contains_key(&handle));
+        let sequence = match sequences.entry(handle) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let forward = self.graph.get_node_sequence(handle.0)?;
+                entry.insert(if handle.1 { forward.into_bytes() } else { reverse_complement(&forward).into_bytes() })
+            }
+        };
+        Ok(f(sequence))
+    }
+
+    /// Matches `target[t..]` from `offset` on the oriented node `handle`.
+    ///
+    /// Returns the finished extension if the match ends on this node, or a
+    /// frame to continue it through the node's successors otherwise.
+    fn match_node(&self, target: &[u8], t: usize, handle: (u64, bool), offset: u64) -> Result<NodeMatch, Error> {
+        let (matched, node_len) = self.with_sequence(handle, |sequence| {
+            let matched = sequence[offset as usize..]
+                .iter()
+                .zip(&target[t..])
+                .take_while(|&(&a, &b)| bases_match(a, b))
+                .count();
+            (matched, sequence.len() as u64)
+        })?;
+        let extension = Extension {
+            len: matched,
+            walk: if matched > 0 { vec![handle] } else { Vec::new() },
+            end: offset + matched as u64,
+        };
+        if extension.end < node_len || t + matched == target.len() {
+            return Ok(NodeMatch::Done(extension));
+        }
+        let successors = self
+            .graph
+            .get_successors(handle.0)?
+            .iter()
+            .filter(|e| e.from_orientation == handle.1)
+            .map(|e| (e.to_node, e.to_orientation))
+            .collect();
+        Ok(NodeMatch::Branch(Frame { extension, t: t + matched, successors, next: 0, best: None }))
+    }
+
+    /// Extends a match of `target[t..]` from `offset` on the oriented node
+    /// `handle`, following the longest matching branch at every node end.
+    ///
+    /// Branches are explored depth first on an explicit stack, as a walk may
+    /// run through up to `MAX_EXTENSION_NODES` nodes.
+    fn extend(&self, target: &[u8], t: usize, handle: (u64, bool), offset: u64, budget: &mut usize) -> Result<Extension, Error> {
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut step = self.match_node(target, t, handle, offset)?;
+        loop {
+            match step {
+                NodeMatch::Branch(frame) => stack.push(frame),
+                NodeMatch::Done(next) => {
+                    let Some(parent) = stack.last_mut() else {
+                        return Ok(next);
+                    };
+                    if next.len > 0 && parent.best.as_ref().is_none_or(|b| next.len > b.len) {
+                        parent.best = Some(next);
+                    }
+                }
+            }
+
+            // Descend into the next successor of the innermost node, or finish it.
+            let frame = stack.last_mut().expect("an unfinished node is on the stack");
+            step = if *budget > 0 && frame.next < frame.successors.len() {
+                *budget -= 1;
+                let successor = frame.successors[frame.next];
+                frame.next += 1;
+                self.match_node(target, frame.t, successor, 0)?
+            } else {
+                let mut frame = stack.pop().expect("an unfinished node is on the stack");
+                if let Some(next) = frame.best {
+                    frame.extension.len += next.len;
+                    frame.extension.walk.extend(next.walk);
+                    frame.extension.end = next.end;
+                }
+                NodeMatch::Done(frame.extension)
+            };
+        }
+    }
+
+    /// Returns the maximal match through the seed at query offset `q` and graph position `hit`.
+    fn mem_at(&self, query: &[u8], query_rc: &[u8], q: usize, hit: MinimizerHit) -> Result<Mem, Error> {
+        let n = query.len();
+        // Extend to the left by extending the reverse complement to the right
+        // from just past the seed's first base on the other strand.
+        let (node_id, is_forward) = (hit.node_id, hit.is_forward);
+        let len = self.node_len((node_id, is_forward))?;
+        let mut budget = MAX_EXTENSION_NODES;
+        let left = self.extend(query_rc, n - q, (node_id, !is_forward), len - hit.offset, &mut budget)?;
+        let (start_handle, start_offset) = match left.walk.last() {
+            Some(&(last_id, last_forward)) => {
+                ((last_id, !last_forward), self.node_len((last_id, last_forward))? - left.end)
+            }
+            None => ((node_id, is_forward), hit.offset),
+        };
+        let query_start = q - left.len;
+
+        let mut budget = MAX_EXTENSION_NODES;
+        let right = self.extend(query, query_start, start_handle, start_offset, &mut budget)?;
+        Ok(Mem {
+            query_start,
+            query_end: query_start + right.len,
+            walk: right.walk,
+            walk_start: start_offset,
+            walk_end: right.end,
+        })
+    }
+
+    /// Converts a match of the reverse-complemented query into one of the query.
+    fn flip(&self, mem: Mem, query_len: usize) -> Result<Mem, Error> {
+        let first_len = self.node_len(mem.walk[0])?;
+        let last_len = self.node_len(mem.walk[mem.walk.len() - 1])?;
+        Ok(Mem {
+            query_start: query_len - mem.query_end,
+            query_end: query_len - mem.query_start,
+            walk_start: last_len - mem.walk_end,
+            walk_end: first_len - mem.walk_start,
+            walk: mem.walk.iter().rev().map(|&(id, fwd)| (id, !fwd)).collect(),
+        })
+    }
+}
+
+impl Graph {
+    /// Finds the maximal exact matches of at least `min_len` bases between `query` and the graph.
+    ///
+    /// This builds a minimizer index of the graph for the search; to search
+    /// many queries, build one with [`Graph::build_minimizer_index`] and use
+    /// [`Graph::find_mems_with_index`] instead. Every exact match of at least
+    /// `min_len` bases along an embedded path is found; matches are then
+    /// extended in both directions along any edge of the graph. Matches on the
+    /// query's reverse complement are reported with reverse node orientations.
+    /// Bases are compared with [`bases_match`](crate::seq::bases_match), so
+    /// matching is case-insensitive and honours IUPAC ambiguity codes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `min_len` is `0`, or if odgi throws while the
+    /// index is built or the matches are extended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for mem in graph.find_mems("GATTACAGATTACAGATTACA", 12).unwrap() {
+    ///     println!("query {}..{} matches {:?} from offset {}", mem.query_start, mem.query_end, mem.walk, mem.walk_start);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, query), err))]
+    pub fn find_mems(&self, query: &str, min_len: usize) -> Result<Vec<Mem>, Error> {
+        if min_len == 0 {
+            return Err(Error::invalid_argument("Minimum MEM length must be at least 1"));
+        }
+        let k = min_len.min(SEED_K);
+        let index = self.build_minimizer_index(k, min_len - k + 1)?;
+        self.find_mems_with_index(&index, query, min_len)
+    }
+
+    /// Finds the maximal exact matches of at least `min_len` bases using a prebuilt minimizer index.
+    ///
+    /// Behaves like [`Graph::find_mems`]. All matches along embedded paths are
+    /// guaranteed to be found if `min_len` is at least `k + w - 1` of the
+    /// index; shorter matches are only found if they contain a minimizer.
+    /// The index must have been built from this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `min_len` is `0`, or if odgi throws while the
+    /// matches are extended.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, index, query), err))]
+    pub fn find_mems_with_index(&self, index: &MinimizerIndex, query: &str, min_len: usize) -> Result<Vec<Mem>, Error> {
+        if min_len == 0 {
+            return Err(Error::invalid_argument("Minimum MEM length must be at least 1"));
+        }
+        let extender = Extender { graph: self, sequences: RefCell::new(HashMap::new()) };
+        let query_bytes = query.as_bytes();
+        let query_rc = reverse_complement(query);
+        let query_rc = query_rc.as_bytes();
+        let n = query_bytes.len();
+
+        let mut mems = BTreeSet::new();
+        for (offset, is_forward, hits) in index.lookup(query) {
+            for &hit in hits {
+                // A non-canonical query k-mer matches the hit on the reverse complement.
+                let mem = if is_forward {
+                    extender.mem_at(query_bytes, query_rc, offset, hit)?
+                } else {
+                    let mem = extender.mem_at(query_rc, query_bytes, n - offset - index.k(), hit)?;
+                    if mem.walk.is_empty() { mem } else { extender.flip(mem, n)? }
+                };
+                if mem.query_end - mem.query_start >= min_len {
+                    mems.insert(mem);
+                }
+            }
+        }
+        Ok(mems.into_iter().collect())
+    }
+}
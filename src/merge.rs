@@ -0,0 +1,93 @@
+// src/merge.rs
+
+//! Importing paths from another graph that shares this graph's nodes.
+
+use std::collections::{HashMap, HashSet};
+use super::graph::{Error, Graph};
+
+impl Graph {
+    /// Copies every path of `other` into this graph.
+    ///
+    /// The nodes of `other` must correspond to nodes of this graph, for example
+    /// because both graphs were built from the same segments and only `other`
+    /// has the newly threaded haplotypes. `id_mapping` translates node IDs of
+    /// `other` to node IDs of this graph; with `None`, IDs are assumed to be
+    /// identical. Edges traversed by the imported paths are added if this graph
+    /// does not have them yet.
+    ///
+    /// All paths are validated before anything is changed, so on error the graph
+    /// is left untouched. Returns the number of imported paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a path of `other` already exists in this graph, if
+    /// a step visits a node that has no mapping or maps to a missing node, or if
+    /// a mapped node's sequence differs from the original.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let mut graph = Graph::load("base.odgi").unwrap();
+    /// let new_haplotypes = Graph::load("new_haplotypes.odgi").unwrap();
+    /// let imported = graph.merge_paths_from(&new_haplotypes, None).unwrap();
+    /// println!("Imported {} paths", imported);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, other, id_mapping), err))]
+    pub fn merge_paths_from(
+        &mut self,
+        other: &Graph,
+        id_mapping: Option<&HashMap<u64, u64>>,
+    ) -> Result<usize, Error> {
+        let existing_paths: HashSet<String> = self.get_path_names()?.into_iter().collect();
+        let existing_nodes: HashSet<u64> = self.node_ids()?.into_iter().collect();
+        let mut verified_nodes: HashMap<u64, u64> = HashMap::new();
+
+        let mut imports = Vec::new();
+        for path_name in other.get_path_names()? {
+            if existing_paths.contains(&path_name) {
+                return Err(Error::path_exists(path_name));
+            }
+            let mut steps = other.raw_path_steps(&path_name)?;
+            for step in &mut steps {
+                let source_id = step.node_id;
+                if let Some(&target_id) = verified_nodes.get(&source_id) {
+                    step.node_id = target_id;
+                    continue;
+                }
+                let target_id = match id_mapping {
+                    Some(mapping) => *mapping.get(&source_id).ok_or_else(|| {
+                        Error::invalid_argument(format!("Node {} on path '{}' has no ID mapping", source_id, path_name))
+                    })?,
+                    None => source_id,
+                };
+                if !existing_nodes.contains(&target_id) {
+                    return Err(Error::invalid_argument(format!(
+                        "Node {} on path '{}' maps to node {}, which does not exist in the target graph",
+                        source_id, path_name, target_id
+                    )));
+                }
+                if other.get_node_sequence(source_id)? != self.get_node_sequence(target_id)? {
+                    return Err(Error::invalid_argument(format!(
+                        "Node {} on path '{}' does not have the same sequence as node {} in the target graph",
+                        source_id, path_name, target_id
+                    )));
+                }
+                verified_nodes.insert(source_id, target_id);
+                step.node_id = target_id;
+            }
+            imports.push((path_name, steps));
+        }
+
+        for (path_name, steps) in &imports {
+            self.add_path(path_name)?;
+            for pair in steps.windows(2) {
+                self.add_edge(pair[0].node_id, pair[0].is_forward, pair[1].node_id, pair[1].is_forward)?;
+            }
+            for step in steps {
+                self.append_step(path_name, step.node_id, step.is_forward)?;
+            }
+        }
+        Ok(imports.len())
+    }
+}
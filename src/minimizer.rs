@@ -0,0 +1,230 @@
+// src/minimizer.rs
+
+//! An index from minimizers to the graph positions where they occur.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use super::graph::{Error, Graph};
+use super::kmer::{minimizers, MAX_K};
+use super::path_index::PathIndex;
+
+/// Identifies the index format in files written by [`MinimizerIndex::write_to`].
+const MAGIC: &[u8; 4] = b"OFMI";
+/// The version of the index format.
+const VERSION: u32 = 1;
+
+/// An oriented graph position at which a minimizer occurs.
+///
+/// Reading `k` bases from `offset` on the node in orientation `is_forward`,
+/// and continuing along the path the minimizer was found on, spells the
+/// minimizer's canonical k-mer. `offset` counts from the start of the node in
+/// that orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MinimizerHit {
+    /// The node containing the first base of the k-mer.
+    pub node_id: u64,
+    /// The offset of the first base on the oriented node.
+    pub offset: u64,
+    /// The orientation in which the node is read.
+    pub is_forward: bool,
+}
+
+/// A minimizer index over a graph, built by [`Graph::build_minimizer_index`].
+///
+/// The index is independent of the graph it was built from and can be written
+/// to disk with [`MinimizerIndex::write_to`] and read back with
+/// [`MinimizerIndex::read_from`], so it only needs to be built once per graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinimizerIndex {
+    k: usize,
+    w: usize,
+    /// Graph positions of each canonical minimizer, sorted.
+    hits: HashMap<u64, Vec<MinimizerHit>>,
+}
+
+impl Graph {
+    /// Builds an index of the `(w, k)` minimizers of the graph.
+    ///
+    /// Minimizers are taken from the sequence of every path, so k-mers spanning
+    /// node boundaries along haplotypes are indexed, and from the sequences of
+    /// nodes that no path visits. K-mers are canonical and case-insensitive,
+    /// and k-mers containing bases other than `A`, `C`, `G`, and `T` are
+    /// skipped. The index is the seeding layer for read mapping and sequence
+    /// search; see [`MinimizerIndex::lookup`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `k` is `0` or larger than 32, or if `w` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let index = graph.build_minimizer_index(15, 10).unwrap();
+    /// let mut file = std::fs::File::create("pangenome.min").unwrap();
+    /// index.write_to(&mut file).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn build_minimizer_index(&self, k: usize, w: usize) -> Result<MinimizerIndex, Error> {
+        if !(1..=MAX_K).contains(&k) {
+            return Err(Error::invalid_argument(format!("k-mer length must be between 1 and {}, got {}", MAX_K, k)));
+        }
+        if w == 0 {
+            return Err(Error::invalid_argument("Minimizer window must contain at least one k-mer"));
+        }
+
+        let mut hits: HashMap<u64, Vec<MinimizerHit>> = HashMap::new();
+        for path_name in self.get_path_names()? {
+            let Some(index) = PathIndex::new(self, &path_name)? else { continue };
+            let sequence = index.sequence(self, 0, index.length)?;
+            // Maps a path offset to the oriented node position it falls on.
+            let position = |pos: u64| {
+                let i = index.step_at(pos).expect("minimizers lie within the path");
+                (index.steps[i].node_id, pos - index.starts[i], index.steps[i].is_forward, index.lengths[i])
+            };
+            for (offset, canonical, is_forward) in minimizers(sequence.as_bytes(), k, w) {
+                let hit = if is_forward {
+                    let (node_id, node_offset, step_forward, _) = position(offset as u64);
+                    MinimizerHit { node_id, offset: node_offset, is_forward: step_forward }
+                } else {
+                    // The canonical k-mer starts at the last base, on the other strand.
+                    let (node_id, node_offset, step_forward, len) = position((offset + k - 1) as u64);
+                    MinimizerHit { node_id, offset: len - 1 - node_offset, is_forward: !step_forward }
+                };
+                hits.entry(canonical).or_default().push(hit);
+            }
+        }
+        for node_id in self.node_ids()? {
+            if self.node_depth(node_id)? > 0 {
+                continue;
+            }
+            let sequence = self.get_node_sequence(node_id)?;
+            for (offset, canonical, is_forward) in minimizers(sequence.as_bytes(), k, w) {
+                let hit = if is_forward {
+                    MinimizerHit { node_id, offset: offset as u64, is_forward: true }
+                } else {
+                    MinimizerHit { node_id, offset: (sequence.len() - offset - k) as u64, is_forward: false }
+                };
+                hits.entry(canonical).or_default().push(hit);
+            }
+        }
+        for node_hits in hits.values_mut() {
+            node_hits.sort();
+            node_hits.dedup();
+        }
+        Ok(MinimizerIndex { k, w, hits })
+    }
+}
+
+impl MinimizerIndex {
+    /// Returns the k-mer length.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the number of k-mers per minimizer window.
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// Returns the number of distinct minimizers.
+    pub fn len(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// Returns `true` if the index contains no minimizers.
+    pub fn is_empty(&self) -> bool {
+        self.hits.is_empty()
+    }
+
+    /// Returns the minimizers of `sequence` that occur in the graph, with their graph positions.
+    ///
+    /// Each entry is `(offset, is_forward, hits)`: the minimizer's k-mer starts
+    /// at `offset` in `sequence`, and `is_forward` tells whether that k-mer is
+    /// the canonical one spelled at the `hits`, or its reverse complement.
+    /// Minimizers that do not occur in the graph are left out.
+    pub fn lookup(&self, sequence: &str) -> Vec<(usize, bool, &[MinimizerHit])> {
+        minimizers(sequence.as_bytes(), self.k, self.w)
+            .into_iter()
+            .filter_map(|(offset, canonical, is_forward)| {
+                self.hits.get(&canonical).map(|hits| (offset, is_forward, hits.as_slice()))
+            })
+            .collect()
+    }
+
+    /// Writes the index in a compact binary format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if writing fails.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        let io_err = |e: std::io::Error| Error::io("Failed to write minimizer index", e);
+        writer.write_all(MAGIC).map_err(io_err)?;
+        writer.write_all(&VERSION.to_le_bytes()).map_err(io_err)?;
+        for value in [self.k as u64, self.w as u64, self.hits.len() as u64] {
+            writer.write_all(&value.to_le_bytes()).map_err(io_err)?;
+        }
+        let mut minimizers: Vec<&u64> = self.hits.keys().collect();
+        minimizers.sort();
+        for minimizer in minimizers {
+            let hits = &self.hits[minimizer];
+            writer.write_all(&minimizer.to_le_bytes()).map_err(io_err)?;
+            writer.write_all(&(hits.len() as u64).to_le_bytes()).map_err(io_err)?;
+            for hit in hits {
+                writer.write_all(&hit.node_id.to_le_bytes()).map_err(io_err)?;
+                // The orientation is stored in the lowest bit of the offset.
+                writer.write_all(&((hit.offset << 1) | hit.is_forward as u64).to_le_bytes()).map_err(io_err)?;
+            }
+        }
+        writer.flush().map_err(io_err)
+    }
+
+    /// Reads an index written by [`MinimizerIndex::write_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if reading fails, or if the input is not a
+    /// minimizer index or was written in an unsupported format version.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, Error> {
+        let io_err = |e: std::io::Error| Error::io("Failed to read minimizer index", e);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic).map_err(io_err)?;
+        if &magic != MAGIC {
+            return Err(Error::invalid_format("Input is not a minimizer index"));
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version).map_err(io_err)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(Error::invalid_format(format!(
+                "Unsupported minimizer index version {} (supported: {})",
+                version, VERSION
+            )));
+        }
+        let mut read_u64 = || -> Result<u64, Error> {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes).map_err(io_err)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+        let k = read_u64()? as usize;
+        let w = read_u64()? as usize;
+        if !(1..=MAX_K).contains(&k) || w == 0 {
+            return Err(Error::invalid_format(format!("Invalid minimizer index parameters k={}, w={}", k, w)));
+        }
+        let count = read_u64()?;
+        let mut hits = HashMap::new();
+        for _ in 0..count {
+            let minimizer = read_u64()?;
+            let hit_count = read_u64()?;
+            let mut minimizer_hits = Vec::new();
+            for _ in 0..hit_count {
+                let node_id = read_u64()?;
+                let packed = read_u64()?;
+                minimizer_hits.push(MinimizerHit { node_id, offset: packed >> 1, is_forward: packed & 1 == 1 });
+            }
+            hits.insert(minimizer, minimizer_hits);
+        }
+        Ok(MinimizerIndex { k, w, hits })
+    }
+}
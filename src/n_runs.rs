@@ -0,0 +1,235 @@
+// src/n_runs.rs
+
+//! Finding and removing runs of ambiguous bases.
+
+use std::collections::HashMap;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// What to do with runs of ambiguous bases, see [`Graph::handle_n_runs`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NRunHandling {
+    /// Leave the sequences unchanged.
+    #[default]
+    Keep,
+    /// Split nodes so that every run is a node of its own.
+    Split,
+    /// Remove the runs, splitting nodes and paths around them.
+    Drop,
+}
+
+/// A run of ambiguous bases in a node, returned by [`Graph::n_runs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NRun {
+    /// The node containing the run.
+    pub node_id: u64,
+    /// The 0-based offset of the run on the node's forward strand.
+    pub offset: u64,
+    /// The length of the run.
+    pub length: u64,
+    /// The start of the run on every path step visiting the node, as
+    /// `(path_name, offset)` in path coordinates, in path name order.
+    pub path_positions: Vec<(String, u64)>,
+}
+
+/// Returns whether a base is anything other than `A`, `C`, `G`, or `T`, in either case.
+fn is_ambiguous(base: u8) -> bool {
+    !matches!(base, b'A' | b'C' | b'G' | b'T' | b'a' | b'c' | b'g' | b't')
+}
+
+/// Splits a sequence into `(start, end, is_run)` pieces at runs of at least `min_run_len` ambiguous bases.
+fn pieces(sequence: &[u8], min_run_len: u64) -> Vec<(usize, usize, bool)> {
+    let mut pieces = Vec::new();
+    let mut piece_start = 0;
+    let mut i = 0;
+    while i < sequence.len() {
+        if !is_ambiguous(sequence[i]) {
+            i += 1;
+            continue;
+        }
+        let run_start = i;
+        while i < sequence.len() && is_ambiguous(sequence[i]) {
+            i += 1;
+        }
+        if (i - run_start) as u64 >= min_run_len.max(1) {
+            if run_start > piece_start {
+                pieces.push((piece_start, run_start, false));
+            }
+            pieces.push((run_start, i, true));
+            piece_start = i;
+        }
+    }
+    if piece_start < sequence.len() {
+        pieces.push((piece_start, sequence.len(), false));
+    }
+    pieces
+}
+
+/// A run of kept steps and the `start..end` range it covers on the original path.
+type PathFragment = (u64, u64, Vec<(u64, bool)>);
+
+impl Graph {
+    /// Returns every run of ambiguous bases in the graph.
+    ///
+    /// A run is a maximal stretch of bases other than `A`, `C`, `G`, and `T`
+    /// (case-insensitive) within a node, such as the `N` gaps of scaffolded
+    /// assemblies. Runs are ordered by node ID and offset, and each lists its
+    /// coordinates on every path that visits the node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the nodes or paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for run in graph.n_runs().unwrap() {
+    ///     println!("{} ambiguous bases in node {} at {:?}", run.length, run.node_id, run.path_positions);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn n_runs(&self) -> Result<Vec<NRun>, Error> {
+        let mut runs = Vec::new();
+        for node_id in self.node_ids()? {
+            let sequence = self.get_node_sequence(node_id)?;
+            for (start, end, is_run) in pieces(sequence.as_bytes(), 1) {
+                if is_run {
+                    runs.push(NRun { node_id, offset: start as u64, length: (end - start) as u64, path_positions: Vec::new() });
+                }
+            }
+        }
+        if runs.is_empty() {
+            return Ok(runs);
+        }
+
+        let mut runs_on_node: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (i, run) in runs.iter().enumerate() {
+            runs_on_node.entry(run.node_id).or_default().push(i);
+        }
+        let mut path_names = self.get_path_names()?;
+        path_names.sort();
+        for path_name in path_names {
+            let Some(index) = PathIndex::new(self, &path_name)? else { continue };
+            for (step, (&start, &node_len)) in index.steps.iter().zip(index.starts.iter().zip(&index.lengths)) {
+                for &i in runs_on_node.get(&step.node_id).into_iter().flatten() {
+                    let run = &mut runs[i];
+                    let offset_on_step = if step.is_forward { run.offset } else { node_len - run.offset - run.length };
+                    run.path_positions.push((path_name.clone(), start + offset_on_step));
+                }
+            }
+        }
+        Ok(runs)
+    }
+
+    /// Returns a copy of the graph with runs of ambiguous bases split off or removed.
+    ///
+    /// Runs shorter than `min_run_len` are left in place. With
+    /// [`NRunHandling::Split`], every remaining run becomes a node of its own,
+    /// so that it can be queried or skipped as a unit. With
+    /// [`NRunHandling::Drop`], runs are removed: nodes are split around them,
+    /// and a path that crossed a run is split into fragments named
+    /// `path:start-end` after the 0-based, half-open range each covers on the
+    /// original path. Nodes without runs keep their IDs; the pieces of split
+    /// nodes get new IDs above the largest existing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi fails to build the new graph, or one of
+    /// kind [`ErrorKind::PathExists`](crate::ErrorKind::PathExists) if a
+    /// fragment name is already taken by another path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Graph, NRunHandling};
+    ///
+    /// let graph = Graph::load("scaffolds.odgi").unwrap();
+    /// let cleaned = graph.handle_n_runs(NRunHandling::Drop, 10).unwrap();
+    /// assert!(cleaned.n_runs().unwrap().iter().all(|run| run.length < 10));
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn handle_n_runs(&self, handling: NRunHandling, min_run_len: u64) -> Result<Graph, Error> {
+        let node_ids = self.node_ids()?;
+        let mut next_id = node_ids.last().copied().unwrap_or(0) + 1;
+        let mut result = Graph::empty()?;
+        // The pieces of every node as (node_id, length, is_kept), in forward order.
+        let mut node_pieces: HashMap<u64, Vec<(u64, u64, bool)>> = HashMap::new();
+        for &node_id in &node_ids {
+            let sequence = self.get_node_sequence(node_id)?;
+            let node_split = match handling {
+                NRunHandling::Keep => Vec::new(),
+                NRunHandling::Split | NRunHandling::Drop => pieces(sequence.as_bytes(), min_run_len),
+            };
+            let is_dropped = |&(_, _, is_run): &(usize, usize, bool)| is_run && handling == NRunHandling::Drop;
+            if node_split.len() <= 1 && !node_split.first().is_some_and(is_dropped) {
+                result.add_node(node_id, &sequence)?;
+                node_pieces.insert(node_id, vec![(node_id, sequence.len() as u64, true)]);
+                continue;
+            }
+            let mut split = Vec::with_capacity(node_split.len());
+            for (start, end, is_run) in node_split {
+                let is_kept = !(is_run && handling == NRunHandling::Drop);
+                if is_kept {
+                    result.add_node(next_id, &sequence[start..end])?;
+                }
+                split.push((next_id, (end - start) as u64, is_kept));
+                next_id += 1;
+            }
+            for pair in split.windows(2) {
+                if pair[0].2 && pair[1].2 {
+                    result.add_edge(pair[0].0, true, pair[1].0, true)?;
+                }
+            }
+            node_pieces.insert(node_id, split);
+        }
+
+        for edge in self.raw_edges()? {
+            // Leaving a node forward starts from its last piece, entering it forward ends at its first.
+            let from_pieces = &node_pieces[&edge.from_node];
+            let to_pieces = &node_pieces[&edge.to_node];
+            let from = if edge.from_orientation { from_pieces.last() } else { from_pieces.first() };
+            let to = if edge.to_orientation { to_pieces.first() } else { to_pieces.last() };
+            if let (Some(&(from_id, _, true)), Some(&(to_id, _, true))) = (from, to) {
+                result.add_edge(from_id, edge.from_orientation, to_id, edge.to_orientation)?;
+            }
+        }
+
+        for path_name in self.get_path_names()? {
+            let mut fragments: Vec<PathFragment> = Vec::new();
+            let mut run: Vec<(u64, bool)> = Vec::new();
+            let (mut run_start, mut pos) = (0, 0);
+            let mut was_split = false;
+            for step in self.raw_path_steps(&path_name)? {
+                let split = &node_pieces[&step.node_id];
+                let oriented: Box<dyn Iterator<Item = &(u64, u64, bool)>> =
+                    if step.is_forward { Box::new(split.iter()) } else { Box::new(split.iter().rev()) };
+                for &(piece_id, length, is_kept) in oriented {
+                    if is_kept {
+                        if run.is_empty() {
+                            run_start = pos;
+                        }
+                        run.push((piece_id, step.is_forward));
+                    } else {
+                        was_split = true;
+                        if !run.is_empty() {
+                            fragments.push((run_start, pos, std::mem::take(&mut run)));
+                        }
+                    }
+                    pos += length;
+                }
+            }
+            if !run.is_empty() || !was_split {
+                fragments.push((run_start, pos, run));
+            }
+
+            for (start, end, steps) in fragments {
+                let fragment_name = if was_split { format!("{}:{}-{}", path_name, start, end) } else { path_name.clone() };
+                result.create_path(&fragment_name, steps)?;
+            }
+        }
+
+        Ok(result)
+    }
+}
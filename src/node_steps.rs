@@ -0,0 +1,99 @@
+// src/node_steps.rs
+
+//! Streaming the steps on a node page by page.
+
+use std::collections::VecDeque;
+use std::fmt;
+use super::graph::{Error, Graph, NodeStep};
+
+/// The number of steps fetched from the graph at a time.
+const PAGE_SIZE: u64 = 4096;
+
+/// An iterator over the steps on a node, created by [`Graph::steps_on_node`].
+///
+/// Steps are fetched in pages of a few thousand, so memory use stays bounded
+/// however many paths visit the node. Each page is read from its offset on
+/// the node directly, so streaming all steps costs time linear in their
+/// number. Skipping with [`Iterator::nth`] or [`Iterator::skip`] moves the
+/// offset past the skipped steps without fetching them, which together with
+/// [`Iterator::take`] serves offset and limit pagination.
+///
+/// If fetching a page fails, for example because the node does not exist, the
+/// error is yielded and the iteration ends.
+pub struct NodeSteps<'g> {
+    graph: &'g Graph,
+    node_id: u64,
+    /// The index of the first step not yet fetched.
+    next_offset: u64,
+    page: VecDeque<NodeStep>,
+    exhausted: bool,
+}
+
+impl fmt::Debug for NodeSteps<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeSteps")
+            .field("node_id", &self.node_id)
+            .field("next_offset", &self.next_offset)
+            .field("page", &self.page)
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for NodeSteps<'_> {
+    type Item = Result<NodeStep, Error>;
+
+    fn next(&mut self) -> Option<Result<NodeStep, Error>> {
+        if self.page.is_empty() && !self.exhausted {
+            let page = match self.graph.get_steps_on_node(self.node_id, self.next_offset, PAGE_SIZE) {
+                Ok(page) => page,
+                Err(e) => {
+                    self.exhausted = true;
+                    return Some(Err(e));
+                }
+            };
+            self.exhausted = (page.len() as u64) < PAGE_SIZE;
+            self.next_offset += page.len() as u64;
+            self.page = page.into();
+        }
+        self.page.pop_front().map(Ok)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Result<NodeStep, Error>> {
+        if n < self.page.len() {
+            self.page.drain(..n);
+        } else {
+            self.next_offset += (n - self.page.len()) as u64;
+            self.page.clear();
+        }
+        self.next()
+    }
+}
+
+impl Graph {
+    /// Streams the steps on a node.
+    ///
+    /// This yields the same steps as [`Graph::get_paths_on_node`], together
+    /// with their orientation, but fetches them lazily in pages instead of
+    /// collecting them all at once. This suits hub nodes of graphs with
+    /// hundreds of haplotypes, where the full list can be very large.
+    ///
+    /// Nothing is fetched until the iterator is advanced, so a node that does
+    /// not exist is reported by the first item as an error of kind
+    /// [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// // The third page of 50 steps.
+    /// for step in graph.steps_on_node(42).skip(100).take(50) {
+    ///     println!("{}", step.unwrap().path_name);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn steps_on_node(&self, node_id: u64) -> NodeSteps<'_> {
+        NodeSteps { graph: self, node_id, next_offset: 0, page: VecDeque::new(), exhausted: false }
+    }
+}
@@ -0,0 +1,121 @@
+// src/nonreference.rs
+
+//! Cataloguing the parts of the graph that a reference path does not cover.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// A maximal connected set of nodes that are not on the reference path,
+/// returned by [`Graph::nonreference_regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonReferenceRegion {
+    /// The IDs of the nodes in the region, sorted.
+    pub nodes: Vec<u64>,
+    /// The total sequence length of the nodes, in base pairs.
+    pub length: u64,
+    /// The span of the reference between the outermost points where the region
+    /// attaches to it, or `None` if the region is not connected to the reference.
+    ///
+    /// A pure insertion between two adjacent reference bases has an empty range.
+    pub reference_range: Option<Range<u64>>,
+}
+
+impl Graph {
+    /// Returns every maximal region of the graph not covered by `reference_path`.
+    ///
+    /// Nodes without a step on the reference are grouped into connected
+    /// components over the graph's edges. Each component is one region, i.e. one
+    /// entry of the catalog of insertions and novel sequence in the pangenome.
+    /// Its reference range is bounded by the reference junctions of all edges
+    /// between the region and reference nodes. Regions are sorted by the start of
+    /// their reference range, with unattached regions last.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the reference path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for region in graph.nonreference_regions("grch38#chr1").unwrap() {
+    ///     println!("{} novel bp in {} nodes at {:?}", region.length, region.nodes.len(), region.reference_range);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn nonreference_regions(&self, reference_path: &str) -> Result<Vec<NonReferenceRegion>, Error> {
+        let index = PathIndex::new(self, reference_path)?
+            .ok_or_else(|| Error::path_not_found(reference_path))?;
+        // Every step of each reference node: (path offset, length, is_forward).
+        let mut reference_steps: HashMap<u64, Vec<(u64, u64, bool)>> = HashMap::new();
+        for (i, step) in index.steps.iter().enumerate() {
+            reference_steps
+                .entry(step.node_id)
+                .or_default()
+                .push((index.starts[i], index.lengths[i], step.is_forward));
+        }
+
+        // Union-find over the non-reference nodes.
+        let nodes: Vec<u64> = self.node_ids()?.into_iter().filter(|id| !reference_steps.contains_key(id)).collect();
+        let slot: HashMap<u64, usize> = nodes.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let mut parent: Vec<usize> = (0..nodes.len()).collect();
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        // Reference junctions per non-reference node, as path offsets.
+        let mut junctions: Vec<(usize, u64)> = Vec::new();
+        for edge in self.raw_edges()? {
+            match (slot.get(&edge.from_node), slot.get(&edge.to_node)) {
+                (Some(&a), Some(&b)) => {
+                    let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+                    parent[root_a] = root_b;
+                }
+                // Leaving a reference node in its path orientation joins the end of its step.
+                (None, Some(&b)) => {
+                    for &(start, len, is_forward) in &reference_steps[&edge.from_node] {
+                        let at_end = edge.from_orientation == is_forward;
+                        junctions.push((b, if at_end { start + len } else { start }));
+                    }
+                }
+                // Entering a reference node in its path orientation joins the start of its step.
+                (Some(&a), None) => {
+                    for &(start, len, is_forward) in &reference_steps[&edge.to_node] {
+                        let at_start = edge.to_orientation == is_forward;
+                        junctions.push((a, if at_start { start } else { start + len }));
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        let mut components: BTreeMap<usize, NonReferenceRegion> = BTreeMap::new();
+        for (i, &node_id) in nodes.iter().enumerate() {
+            let region = components.entry(find(&mut parent, i)).or_insert_with(|| NonReferenceRegion {
+                nodes: Vec::new(),
+                length: 0,
+                reference_range: None,
+            });
+            region.nodes.push(node_id);
+            region.length += self.get_node_len(node_id)?;
+        }
+        for (i, position) in junctions {
+            let region = components.get_mut(&find(&mut parent, i)).expect("every node has a region");
+            region.reference_range = Some(match region.reference_range.take() {
+                Some(range) => range.start.min(position)..range.end.max(position),
+                None => position..position,
+            });
+        }
+
+        let mut regions: Vec<NonReferenceRegion> = components.into_values().collect();
+        regions.sort_by_key(|r| (r.reference_range.as_ref().map_or(u64::MAX, |range| range.start), r.nodes[0]));
+        Ok(regions)
+    }
+}
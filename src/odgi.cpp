@@ -1,35 +1,141 @@
 #include "odgi_wrapper.hpp"
+#include "gfa_to_handle.hpp"
+#include <cstring>
 #include <fstream>
+#include <istream>
+#include <stdexcept>
+#include <streambuf>
 #include <string>
+#include <unordered_set>
 #include <vector>
 #include <algorithm> // Required for std::sort and std::unique
 #include "odgi-ffi/src/lib.rs.h"
+#ifdef _OPENMP
+#include <omp.h>
+#endif
 // src/odgi.cpp
 // --- Core API ---
-std::unique_ptr<OpaqueGraph> load_graph(rust::Str path) {
+// Without -fopenmp (the no-openmp feature), odgi's loops are serial and these do nothing.
+void set_num_threads(uint64_t threads) {
+#ifdef _OPENMP
+    omp_set_num_threads(threads == 0 ? omp_get_num_procs() : static_cast<int>(threads));
+#else
+    (void)threads;
+#endif
+}
+
+uint64_t get_num_threads() {
+#ifdef _OPENMP
+    return omp_get_max_threads();
+#else
+    return 1;
+#endif
+}
+
+// Deserializes a graph, failing instead of returning a partial graph if the data ends early.
+static std::unique_ptr<odgi::graph_t> deserialize_graph(std::istream& in) {
     auto odgi_graph = std::make_unique<odgi::graph_t>();
-    std::ifstream in{std::string(path)};
-    if (!in) { return nullptr; }
     odgi_graph->deserialize(in);
+    if (in.fail()) {
+        throw std::runtime_error("ODGI data is truncated or corrupt");
+    }
+    return odgi_graph;
+}
+
+std::unique_ptr<OpaqueGraph> load_graph(rust::Str path) {
+    std::ifstream in{std::string(path)};
+    if (!in) {
+        throw std::runtime_error("cannot open '" + std::string(path) + "'");
+    }
+    auto odgi_graph = deserialize_graph(in);
+    auto wrapper = std::make_unique<OpaqueGraph>();
+    wrapper->graph = std::move(odgi_graph);
+    return wrapper;
+}
+
+// A read-only stream buffer over memory owned by Rust, so deserializing does not copy it first.
+struct SliceBuf : std::streambuf {
+    SliceBuf(const uint8_t* data, size_t size) {
+        char* begin = const_cast<char*>(reinterpret_cast<const char*>(data));
+        setg(begin, begin, begin + size);
+    }
+};
+
+std::unique_ptr<OpaqueGraph> load_graph_from_bytes(rust::Slice<const uint8_t> bytes) {
+    SliceBuf buffer(bytes.data(), bytes.size());
+    std::istream in(&buffer);
+    auto odgi_graph = deserialize_graph(in);
     auto wrapper = std::make_unique<OpaqueGraph>();
     wrapper->graph = std::move(odgi_graph);
     return wrapper;
 }
 
+std::unique_ptr<OpaqueGraph> new_graph() {
+    auto wrapper = std::make_unique<OpaqueGraph>();
+    wrapper->graph = std::make_unique<odgi::graph_t>();
+    return wrapper;
+}
+
+std::unique_ptr<OpaqueGraph> graph_from_gfa(rust::Str gfa_path) {
+    std::string path(gfa_path);
+    {
+        std::ifstream in{path};
+        if (!in) {
+            throw std::runtime_error("cannot open '" + path + "'");
+        }
+    }
+    auto wrapper = std::make_unique<OpaqueGraph>();
+    wrapper->graph = std::make_unique<odgi::graph_t>();
+    // The same construction as `odgi build`: numeric segment names are kept as node IDs.
+    odgi::gfa_to_handle(path, wrapper->graph.get(), false, 1, false);
+    return wrapper;
+}
+
+void save_graph(const odgi::graph_t& graph, rust::Str path) {
+    std::ofstream out{std::string(path)};
+    if (!out) {
+        throw std::runtime_error("cannot open '" + std::string(path) + "' for writing");
+    }
+    graph.serialize(out);
+    out.flush();
+    if (!out) {
+        throw std::runtime_error("failed to write '" + std::string(path) + "'");
+    }
+}
+
 const odgi::graph_t& get_graph_t(const OpaqueGraph& wrapper) {
     return *wrapper.graph.get();
 }
 
+odgi::graph_t& get_graph_t_mut(OpaqueGraph& wrapper) {
+    return *wrapper.graph.get();
+}
+
 uint64_t get_node_count(const odgi::graph_t& graph) {
     return graph.get_node_count();
 }
 
+uint64_t graph_get_edge_count(const odgi::graph_t& graph) {
+    return graph.get_edge_count();
+}
+
+uint64_t graph_get_total_length(const odgi::graph_t& graph) {
+    return graph.get_total_length();
+}
+
 // --- Query Functions ---
 rust::Vec<rust::String> graph_get_path_names(const odgi::graph_t& graph) {
-    rust::Vec<rust::String> names;
+    // Path handles are assigned in creation order; sorting by them makes the
+    // order independent of how the graph stores its path metadata.
+    std::vector<std::pair<uint64_t, std::string>> paths;
     graph.for_each_path_handle([&](const odgi::path_handle_t& path) {
-        names.push_back(graph.get_path_name(path));
+        paths.emplace_back(handlegraph::as_integer(path), graph.get_path_name(path));
     });
+    std::sort(paths.begin(), paths.end());
+    rust::Vec<rust::String> names;
+    for (const auto& path : paths) {
+        names.push_back(path.second);
+    }
     return names;
 }
 
@@ -76,6 +182,77 @@ std::unique_ptr<odgi::PathPosition> graph_project(const odgi::graph_t& graph, ru
     return found_pos;
 }
 
+rust::Vec<odgi::PathPosition> graph_project_sorted(const odgi::graph_t& graph, rust::Str path_name, rust::Slice<const uint64_t> positions) {
+    rust::Vec<odgi::PathPosition> result;
+    if (!graph.has_path(std::string(path_name))) {
+        return result;
+    }
+    odgi::path_handle_t path = graph.get_path_handle(std::string(path_name));
+    size_t next = 0;
+    uint64_t current_pos = 0;
+    graph.for_each_step_in_path(path, [&](const odgi::step_handle_t& step) {
+        odgi::handle_t handle = graph.get_handle_of_step(step);
+        uint64_t node_len = graph.get_length(handle);
+        while (next < positions.size() && positions[next] < current_pos + node_len) {
+            uint64_t offset_in_step = positions[next] - current_pos;
+            result.push_back({
+                (uint64_t)graph.get_id(handle),
+                graph.get_is_reverse(handle) ? (node_len - 1 - offset_in_step) : offset_in_step,
+                !graph.get_is_reverse(handle)
+            });
+            ++next;
+        }
+        current_pos += node_len;
+        return next < positions.size();
+    });
+    return result;
+}
+
+rust::Vec<odgi::StepPosition> graph_get_node_path_positions(const odgi::graph_t& graph, uint64_t node_id, rust::Slice<const rust::String> skip_paths) {
+    rust::Vec<odgi::StepPosition> positions;
+    if (!graph.has_node(node_id)) return positions;
+    std::unordered_set<std::string> skipped;
+    for (const rust::String& name : skip_paths) {
+        skipped.insert(std::string(name));
+    }
+    graph.for_each_step_on_handle(graph.get_handle(node_id, false), [&](const odgi::step_handle_t& step) {
+        odgi::path_handle_t path = graph.get_path_handle_of_step(step);
+        if (!skipped.empty() && skipped.count(graph.get_path_name(path))) return;
+        // Walk back to the start of the path, summing the lengths of the steps before this one.
+        // Stopping at path_begin also ends the walk on circular paths.
+        const odgi::step_handle_t begin = graph.path_begin(path);
+        uint64_t start = 0;
+        odgi::step_handle_t current = step;
+        while (current != begin) {
+            current = graph.get_previous_step(current);
+            start += graph.get_length(graph.get_handle_of_step(current));
+        }
+        positions.push_back({
+            rust::String(graph.get_path_name(path)),
+            start,
+            !graph.get_is_reverse(graph.get_handle_of_step(step))
+        });
+    });
+    return positions;
+}
+
+bool graph_has_path(const odgi::graph_t& graph, rust::Str path_name) {
+    return graph.has_path(std::string(path_name));
+}
+
+bool graph_has_edge(
+    const odgi::graph_t& graph,
+    uint64_t from_node, bool from_is_forward,
+    uint64_t to_node, bool to_is_forward
+) {
+    if (!graph.has_node(from_node) || !graph.has_node(to_node)) {
+        return false;
+    }
+    odgi::handle_t from_handle = graph.get_handle(from_node, !from_is_forward);
+    odgi::handle_t to_handle = graph.get_handle(to_node, !to_is_forward);
+    return graph.has_edge(from_handle, to_handle);
+}
+
 rust::String graph_get_node_sequence(const odgi::graph_t& graph, uint64_t node_id) {
     if (!graph.has_node(node_id)) return "";
     return graph.get_sequence(graph.get_handle(node_id, false));
@@ -86,9 +263,24 @@ uint64_t graph_get_node_len(const odgi::graph_t& graph, uint64_t node_id) {
     return graph.get_length(graph.get_handle(node_id, false));
 }
 
-rust::Vec<odgi::Edge> graph_get_successors(const odgi::graph_t& graph, uint64_t node_id) {
-    rust::Vec<odgi::Edge> edges;
-    if (!graph.has_node(node_id)) return edges;
+rust::Vec<uint64_t> graph_get_node_lens(const odgi::graph_t& graph, rust::Slice<const uint64_t> node_ids) {
+    rust::Vec<uint64_t> lengths;
+    lengths.reserve(node_ids.size());
+    for (const uint64_t node_id : node_ids) {
+        lengths.push_back(graph_get_node_len(graph, node_id));
+    }
+    return lengths;
+}
+
+void graph_get_node_sequence_into(const odgi::graph_t& graph, uint64_t node_id, rust::Slice<uint8_t> sequence) {
+    if (!graph.has_node(node_id)) return;
+    // The caller sizes the slice from graph_get_node_len, so the bases are copied in one go.
+    const std::string bases = graph.get_sequence(graph.get_handle(node_id, false));
+    std::memcpy(sequence.data(), bases.data(), std::min(bases.size(), sequence.size()));
+}
+
+void graph_get_successors_into(const odgi::graph_t& graph, uint64_t node_id, rust::Vec<odgi::Edge>& edges) {
+    if (!graph.has_node(node_id)) return;
 
     auto handle_fwd = graph.get_handle(node_id, false);
     graph.follow_edges(handle_fwd, false, [&](const odgi::handle_t& next) {
@@ -101,12 +293,16 @@ rust::Vec<odgi::Edge> graph_get_successors(const odgi::graph_t& graph, uint64_t
         edges.push_back({(uint64_t)graph.get_id(next), false, !graph.get_is_reverse(next)});
         return true;
     });
-    return edges;
 }
 
-rust::Vec<odgi::Edge> graph_get_predecessors(const odgi::graph_t& graph, uint64_t node_id) {
+rust::Vec<odgi::Edge> graph_get_successors(const odgi::graph_t& graph, uint64_t node_id) {
     rust::Vec<odgi::Edge> edges;
-    if (!graph.has_node(node_id)) return edges;
+    graph_get_successors_into(graph, node_id, edges);
+    return edges;
+}
+
+void graph_get_predecessors_into(const odgi::graph_t& graph, uint64_t node_id, rust::Vec<odgi::Edge>& edges) {
+    if (!graph.has_node(node_id)) return;
 
     auto handle_fwd = graph.get_handle(node_id, false);
     graph.follow_edges(handle_fwd, true, [&](const odgi::handle_t& prev) {
@@ -119,6 +315,11 @@ rust::Vec<odgi::Edge> graph_get_predecessors(const odgi::graph_t& graph, uint64_
         edges.push_back({(uint64_t)graph.get_id(prev), !graph.get_is_reverse(prev), false});
         return true;
     });
+}
+
+rust::Vec<odgi::Edge> graph_get_predecessors(const odgi::graph_t& graph, uint64_t node_id) {
+    rust::Vec<odgi::Edge> edges;
+    graph_get_predecessors_into(graph, node_id, edges);
     return edges;
 }
 
@@ -222,4 +423,445 @@ rust::Vec<rust::String> graph_get_paths_on_edge(
     }
 
     return final_paths;
-}
\ No newline at end of file
+}
+
+// --- Enumeration Functions ---
+rust::Vec<uint64_t> graph_get_node_ids(const odgi::graph_t& graph) {
+    std::vector<uint64_t> ids;
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        ids.push_back(graph.get_id(handle));
+    });
+    std::sort(ids.begin(), ids.end());
+
+    rust::Vec<uint64_t> result;
+    for (const auto& id : ids) {
+        result.push_back(id);
+    }
+    return result;
+}
+
+uint64_t graph_get_path_count(const odgi::graph_t& graph) {
+    return graph.get_path_count();
+}
+
+rust::Vec<uint64_t> graph_get_node_ids_at_ranks(const odgi::graph_t& graph, rust::Slice<const uint64_t> ranks) {
+    rust::Vec<uint64_t> result;
+    uint64_t count = graph.get_node_count();
+    uint64_t min_id = graph.min_node_id();
+    if (count > 0 && graph.max_node_id() - min_id + 1 == count) {
+        // Dense IDs map to ranks directly.
+        for (const auto& rank : ranks) {
+            if (rank < count) result.push_back(min_id + rank);
+        }
+        return result;
+    }
+    std::vector<uint64_t> ids;
+    ids.reserve(count);
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        ids.push_back(graph.get_id(handle));
+    });
+    std::sort(ids.begin(), ids.end());
+    for (const auto& rank : ranks) {
+        if (rank < ids.size()) result.push_back(ids[rank]);
+    }
+    return result;
+}
+
+rust::Vec<rust::String> graph_get_path_names_at_ranks(const odgi::graph_t& graph, rust::Slice<const uint64_t> ranks) {
+    std::vector<uint64_t> handles;
+    graph.for_each_path_handle([&](const odgi::path_handle_t& path) {
+        handles.push_back(handlegraph::as_integer(path));
+    });
+    std::sort(handles.begin(), handles.end());
+    rust::Vec<rust::String> names;
+    for (const auto& rank : ranks) {
+        if (rank < handles.size()) {
+            names.push_back(graph.get_path_name(handlegraph::as_path_handle(handles[rank])));
+        }
+    }
+    return names;
+}
+
+rust::Vec<odgi::PathStep> graph_get_path_steps(const odgi::graph_t& graph, rust::Str path_name) {
+    rust::Vec<odgi::PathStep> steps;
+    if (!graph.has_path(std::string(path_name))) {
+        return steps;
+    }
+    odgi::path_handle_t path = graph.get_path_handle(std::string(path_name));
+    graph.for_each_step_in_path(path, [&](const odgi::step_handle_t& step) {
+        odgi::handle_t handle = graph.get_handle_of_step(step);
+        steps.push_back({(uint64_t)graph.get_id(handle), !graph.get_is_reverse(handle)});
+        return true;
+    });
+    return steps;
+}
+
+rust::Vec<odgi::GraphEdge> graph_get_edges(const odgi::graph_t& graph) {
+    rust::Vec<odgi::GraphEdge> edges;
+    graph.for_each_edge([&](const odgi::edge_t& edge) {
+        edges.push_back({
+            (uint64_t)graph.get_id(edge.first), !graph.get_is_reverse(edge.first),
+            (uint64_t)graph.get_id(edge.second), !graph.get_is_reverse(edge.second)
+        });
+        return true;
+    });
+    return edges;
+}
+
+odgi::GraphStats graph_get_stats(const odgi::graph_t& graph) {
+    odgi::GraphStats stats{};
+    stats.node_count = graph.get_node_count();
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        stats.total_length += graph.get_length(handle);
+    });
+    graph.for_each_edge([&](const odgi::edge_t& edge) {
+        ++stats.edge_count;
+        if (graph.get_id(edge.first) == graph.get_id(edge.second)) {
+            ++stats.self_loop_count;
+        }
+        return true;
+    });
+    stats.path_count = graph.get_path_count();
+    graph.for_each_path_handle([&](const odgi::path_handle_t& path) {
+        stats.step_count += graph.get_step_count(path);
+    });
+    return stats;
+}
+
+uint64_t graph_get_node_step_count(const odgi::graph_t& graph, uint64_t node_id) {
+    if (!graph.has_node(node_id)) return 0;
+    uint64_t count = 0;
+    graph.for_each_step_on_handle(graph.get_handle(node_id, false), [&](const odgi::step_handle_t& step) {
+        ++count;
+        return true;
+    });
+    return count;
+}
+
+rust::Vec<odgi::NodeStep> graph_get_steps_on_node(const odgi::graph_t& graph, uint64_t node_id, uint64_t offset, uint64_t limit) {
+    rust::Vec<odgi::NodeStep> steps;
+    if (!graph.has_node(node_id) || limit == 0) return steps;
+    odgi::handle_t handle = graph.get_handle(node_id, false);
+    uint64_t count = graph.get_step_count(handle);
+    // odgi names a step on a node by the node's handle and the step's rank on
+    // the node, so a page starts at `offset` without walking the steps before it.
+    for (uint64_t rank = offset; rank < count && rank - offset < limit; ++rank) {
+        odgi::step_handle_t step;
+        handlegraph::as_integers(step)[0] = handlegraph::as_integer(handle);
+        handlegraph::as_integers(step)[1] = rank;
+        steps.push_back({
+            rust::String(graph.get_path_name(graph.get_path_handle_of_step(step))),
+            !graph.get_is_reverse(graph.get_handle_of_step(step))
+        });
+    }
+    return steps;
+}
+
+int64_t graph_get_node_sort_offset(const odgi::graph_t& graph, uint64_t node_id) {
+    if (!graph.has_node(node_id)) return -1;
+    int64_t offset = 0;
+    int64_t found = -1;
+    // Handles are visited in the graph's current 1D order.
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        if ((uint64_t)graph.get_id(handle) == node_id) {
+            found = offset;
+            return false; // Stop iterating
+        }
+        offset += graph.get_length(handle);
+        return true;
+    });
+    return found;
+}
+
+rust::Vec<uint64_t> graph_get_node_sort_offsets(const odgi::graph_t& graph) {
+    // Offsets are returned in the same 1D order as graph_get_node_order.
+    rust::Vec<uint64_t> offsets;
+    uint64_t offset = 0;
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        offsets.push_back(offset);
+        offset += graph.get_length(handle);
+    });
+    return offsets;
+}
+
+uint64_t graph_get_node_at_sort_offset(const odgi::graph_t& graph, uint64_t offset) {
+    uint64_t node_start = 0;
+    uint64_t found = 0;
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        uint64_t node_len = graph.get_length(handle);
+        if (offset < node_start + node_len) {
+            found = graph.get_id(handle);
+            return false; // Stop iterating
+        }
+        node_start += node_len;
+        return true;
+    });
+    return found;
+}
+
+rust::Vec<uint64_t> graph_get_node_order(const odgi::graph_t& graph) {
+    rust::Vec<uint64_t> ids;
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        ids.push_back(graph.get_id(handle));
+    });
+    return ids;
+}
+
+uint64_t graph_prefault(const odgi::graph_t& graph) {
+    // The checksum keeps the traversal from being optimized away.
+    uint64_t checksum = 0;
+    graph.for_each_handle([&](const odgi::handle_t& handle) {
+        std::string sequence = graph.get_sequence(handle);
+        checksum += sequence.size();
+        graph.follow_edges(handle, false, [&](const odgi::handle_t& next) {
+            checksum += graph.get_id(next);
+            return true;
+        });
+        graph.follow_edges(handle, true, [&](const odgi::handle_t& prev) {
+            checksum += graph.get_id(prev);
+            return true;
+        });
+        return true;
+    });
+    graph.for_each_path_handle([&](const odgi::path_handle_t& path) {
+        graph.for_each_step_in_path(path, [&](const odgi::step_handle_t& step) {
+            checksum += graph.get_id(graph.get_handle_of_step(step));
+            return true;
+        });
+        return true;
+    });
+    return checksum;
+}
+
+// --- Construction Functions ---
+bool graph_create_node(odgi::graph_t& graph, uint64_t node_id, rust::Str sequence) {
+    if (node_id == 0 || graph.has_node(node_id)) {
+        return false;
+    }
+    graph.create_handle(std::string(sequence), node_id);
+    return true;
+}
+
+bool graph_create_edge(
+    odgi::graph_t& graph,
+    uint64_t from_node, bool from_is_forward,
+    uint64_t to_node, bool to_is_forward
+) {
+    if (!graph.has_node(from_node) || !graph.has_node(to_node)) {
+        return false;
+    }
+    odgi::handle_t from_handle = graph.get_handle(from_node, !from_is_forward);
+    odgi::handle_t to_handle = graph.get_handle(to_node, !to_is_forward);
+    if (!graph.has_edge(from_handle, to_handle)) {
+        graph.create_edge(from_handle, to_handle);
+    }
+    return true;
+}
+
+bool graph_create_path(odgi::graph_t& graph, rust::Str path_name) {
+    std::string name(path_name);
+    if (graph.has_path(name)) {
+        return false;
+    }
+    graph.create_path_handle(name);
+    return true;
+}
+
+bool graph_append_step(odgi::graph_t& graph, rust::Str path_name, uint64_t node_id, bool is_forward) {
+    std::string name(path_name);
+    if (!graph.has_path(name) || !graph.has_node(node_id)) {
+        return false;
+    }
+    graph.append_step(graph.get_path_handle(name), graph.get_handle(node_id, !is_forward));
+    return true;
+}
+
+bool graph_destroy_path(odgi::graph_t& graph, rust::Str path_name) {
+    std::string name(path_name);
+    if (!graph.has_path(name)) {
+        return false;
+    }
+    graph.destroy_path(graph.get_path_handle(name));
+    return true;
+}
+
+bool graph_truncate_path(odgi::graph_t& graph, rust::Str path_name, uint64_t length) {
+    std::string name(path_name);
+    if (!graph.has_path(name)) {
+        return false;
+    }
+    odgi::path_handle_t path = graph.get_path_handle(name);
+    if (length >= graph.get_step_count(path)) {
+        return true;
+    }
+    odgi::step_handle_t first_removed = graph.path_begin(path);
+    for (uint64_t i = 0; i < length; ++i) {
+        first_removed = graph.get_next_step(first_removed);
+    }
+    // Replacing the range up to the past-the-end step with nothing drops the tail.
+    graph.rewrite_segment(first_removed, graph.path_end(path), std::vector<odgi::handle_t>());
+    return true;
+}
+
+bool graph_destroy_node(odgi::graph_t& graph, uint64_t node_id) {
+    if (!graph.has_node(node_id)) {
+        return false;
+    }
+    graph.destroy_handle(graph.get_handle(node_id));
+    return true;
+}
+
+bool graph_destroy_edge(
+    odgi::graph_t& graph,
+    uint64_t from_node, bool from_is_forward,
+    uint64_t to_node, bool to_is_forward
+) {
+    if (!graph.has_node(from_node) || !graph.has_node(to_node)) {
+        return false;
+    }
+    odgi::handle_t from_handle = graph.get_handle(from_node, !from_is_forward);
+    odgi::handle_t to_handle = graph.get_handle(to_node, !to_is_forward);
+    if (!graph.has_edge(from_handle, to_handle)) {
+        return false;
+    }
+    graph.destroy_edge(from_handle, to_handle);
+    return true;
+}
+
+rust::Vec<uint64_t> graph_divide_node(odgi::graph_t& graph, uint64_t node_id, rust::Slice<const uint64_t> offsets) {
+    rust::Vec<uint64_t> pieces;
+    if (!graph.has_node(node_id)) {
+        return pieces;
+    }
+    std::vector<size_t> split_offsets(offsets.begin(), offsets.end());
+    for (const auto& handle : graph.divide_handle(graph.get_handle(node_id), split_offsets)) {
+        pieces.push_back(graph.get_id(handle));
+    }
+    return pieces;
+}
+
+bool graph_apply_node_order(odgi::graph_t& graph, rust::Slice<const uint64_t> node_ids) {
+    std::vector<odgi::handle_t> order;
+    order.reserve(node_ids.size());
+    for (const auto& id : node_ids) {
+        if (!graph.has_node(id)) {
+            return false;
+        }
+        order.push_back(graph.get_handle(id));
+    }
+    graph.apply_ordering(order, false);
+    return true;
+}
+
+uint64_t graph_get_path_step_count(const odgi::graph_t& graph, rust::Str path_name) {
+    std::string name(path_name);
+    if (!graph.has_path(name)) {
+        return 0;
+    }
+    return graph.get_step_count(graph.get_path_handle(name));
+}
+
+// --- Raw Handle Functions ---
+static odgi::step_handle_t to_step(const odgi::StepHandle& step) {
+    odgi::step_handle_t result;
+    handlegraph::as_integers(result)[0] = step.first;
+    handlegraph::as_integers(result)[1] = step.second;
+    return result;
+}
+
+static odgi::StepHandle from_step(const odgi::step_handle_t& step) {
+    const int64_t* integers = handlegraph::as_integers(step);
+    return {integers[0], integers[1]};
+}
+
+bool raw_has_node(const odgi::graph_t& graph, uint64_t node_id) {
+    return node_id != 0 && graph.has_node(node_id);
+}
+
+uint64_t raw_get_handle(const odgi::graph_t& graph, uint64_t node_id, bool is_reverse) {
+    return handlegraph::as_integer(graph.get_handle(node_id, is_reverse));
+}
+
+uint64_t raw_get_id(const odgi::graph_t& graph, uint64_t handle) {
+    return graph.get_id(handlegraph::as_handle(handle));
+}
+
+uint64_t raw_get_length(const odgi::graph_t& graph, uint64_t handle) {
+    return graph.get_length(handlegraph::as_handle(handle));
+}
+
+rust::String raw_get_sequence(const odgi::graph_t& graph, uint64_t handle) {
+    return rust::String(graph.get_sequence(handlegraph::as_handle(handle)));
+}
+
+rust::Vec<uint64_t> raw_follow_edges(const odgi::graph_t& graph, uint64_t handle, bool go_left) {
+    rust::Vec<uint64_t> handles;
+    graph.follow_edges(handlegraph::as_handle(handle), go_left, [&](const odgi::handle_t& next) {
+        handles.push_back(handlegraph::as_integer(next));
+        return true;
+    });
+    return handles;
+}
+
+uint64_t raw_get_degree(const odgi::graph_t& graph, uint64_t handle, bool go_left) {
+    return graph.get_degree(handlegraph::as_handle(handle), go_left);
+}
+
+rust::Vec<uint64_t> raw_get_edges(const odgi::graph_t& graph) {
+    rust::Vec<uint64_t> handles;
+    graph.for_each_edge([&](const odgi::edge_t& edge) {
+        handles.push_back(handlegraph::as_integer(edge.first));
+        handles.push_back(handlegraph::as_integer(edge.second));
+        return true;
+    });
+    return handles;
+}
+
+int64_t raw_get_path_handle(const odgi::graph_t& graph, rust::Str path_name) {
+    std::string name(path_name);
+    if (!graph.has_path(name)) {
+        return -1;
+    }
+    return handlegraph::as_integer(graph.get_path_handle(name));
+}
+
+odgi::StepHandle raw_path_begin(const odgi::graph_t& graph, uint64_t path) {
+    return from_step(graph.path_begin(handlegraph::as_path_handle(path)));
+}
+
+odgi::StepHandle raw_path_end(const odgi::graph_t& graph, uint64_t path) {
+    return from_step(graph.path_end(handlegraph::as_path_handle(path)));
+}
+
+bool raw_has_next_step(const odgi::graph_t& graph, odgi::StepHandle step) {
+    return graph.has_next_step(to_step(step));
+}
+
+bool raw_has_previous_step(const odgi::graph_t& graph, odgi::StepHandle step) {
+    return graph.has_previous_step(to_step(step));
+}
+
+odgi::StepHandle raw_get_next_step(const odgi::graph_t& graph, odgi::StepHandle step) {
+    return from_step(graph.get_next_step(to_step(step)));
+}
+
+odgi::StepHandle raw_get_previous_step(const odgi::graph_t& graph, odgi::StepHandle step) {
+    return from_step(graph.get_previous_step(to_step(step)));
+}
+
+uint64_t raw_get_handle_of_step(const odgi::graph_t& graph, odgi::StepHandle step) {
+    return handlegraph::as_integer(graph.get_handle_of_step(to_step(step)));
+}
+
+uint64_t raw_get_path_handle_of_step(const odgi::graph_t& graph, odgi::StepHandle step) {
+    return handlegraph::as_integer(graph.get_path_handle_of_step(to_step(step)));
+}
+
+rust::Vec<odgi::StepHandle> raw_get_steps_on_handle(const odgi::graph_t& graph, uint64_t handle) {
+    rust::Vec<odgi::StepHandle> steps;
+    graph.for_each_step_on_handle(handlegraph::as_handle(handle), [&](const odgi::step_handle_t& step) {
+        steps.push_back(from_step(step));
+        return true;
+    });
+    return steps;
+}
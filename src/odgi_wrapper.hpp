@@ -3,8 +3,28 @@
 
 #include "odgi.hpp"
 #include "rust/cxx.h"
+#include <cstddef>
+#include <cstdint>
+#include <exception>
 #include <memory>
 
+// Catches every exception thrown across the bridge, not only those derived
+// from std::exception, and hands it to Rust as the `Err` of the call, so a
+// failure inside odgi never aborts the process. cxx uses this in place of
+// its default handler for every function that returns `Result`.
+namespace rust {
+namespace behavior {
+template <typename Try, typename Fail>
+static void trycatch(Try &&func, Fail &&fail) noexcept try {
+    func();
+} catch (const std::exception &e) {
+    fail(e.what());
+} catch (...) {
+    fail("unknown C++ exception");
+}
+} // namespace behavior
+} // namespace rust
+
 // OpaqueGraph is defined in the global namespace.
 struct OpaqueGraph {
     std::unique_ptr<odgi::graph_t> graph;
@@ -16,20 +36,39 @@ struct OpaqueGraph {
 namespace odgi {
 struct Edge;
 struct PathPosition;
+struct PathStep;
+struct GraphEdge;
+struct NodeStep;
+struct StepPosition;
+struct GraphStats;
+struct StepHandle;
 }
 
 
+void set_num_threads(uint64_t threads);
+uint64_t get_num_threads();
 std::unique_ptr<OpaqueGraph> load_graph(rust::Str path);
+std::unique_ptr<OpaqueGraph> load_graph_from_bytes(rust::Slice<const uint8_t> bytes);
+std::unique_ptr<OpaqueGraph> new_graph();
+std::unique_ptr<OpaqueGraph> graph_from_gfa(rust::Str gfa_path);
+void save_graph(const odgi::graph_t& graph, rust::Str path);
 const odgi::graph_t& get_graph_t(const OpaqueGraph& graph);
+odgi::graph_t& get_graph_t_mut(OpaqueGraph& graph);
 uint64_t get_node_count(const odgi::graph_t& graph);
+uint64_t graph_get_edge_count(const odgi::graph_t& graph);
+uint64_t graph_get_total_length(const odgi::graph_t& graph);
 
 rust::Vec<rust::String> graph_get_path_names(const odgi::graph_t& graph);
 // CORRECTED: Update signature to match the bridge
 std::unique_ptr<odgi::PathPosition> graph_project(const odgi::graph_t& graph, rust::Str path_name, uint64_t pos);
 rust::String graph_get_node_sequence(const odgi::graph_t& graph, uint64_t node_id);
 uint64_t graph_get_node_len(const odgi::graph_t& graph, uint64_t node_id);
+rust::Vec<uint64_t> graph_get_node_lens(const odgi::graph_t& graph, rust::Slice<const uint64_t> node_ids);
 rust::Vec<odgi::Edge> graph_get_successors(const odgi::graph_t& graph, uint64_t node_id);
 rust::Vec<odgi::Edge> graph_get_predecessors(const odgi::graph_t& graph, uint64_t node_id);
+void graph_get_node_sequence_into(const odgi::graph_t& graph, uint64_t node_id, rust::Slice<uint8_t> sequence);
+void graph_get_successors_into(const odgi::graph_t& graph, uint64_t node_id, rust::Vec<odgi::Edge>& edges);
+void graph_get_predecessors_into(const odgi::graph_t& graph, uint64_t node_id, rust::Vec<odgi::Edge>& edges);
 rust::Vec<rust::String> graph_get_paths_on_node(const odgi::graph_t& graph, uint64_t node_id);
 uint64_t graph_get_path_length(const odgi::graph_t& graph, rust::Str path_name);
 int64_t graph_get_next_node_on_path(const odgi::graph_t& graph, rust::Str path_name, uint64_t node_id);
@@ -38,4 +77,68 @@ rust::Vec<rust::String> graph_get_paths_on_edge(
     const odgi::graph_t& graph,
     uint64_t from_node, bool from_orient,
     uint64_t to_node, bool to_orient
-);
\ No newline at end of file
+);
+
+rust::Vec<uint64_t> graph_get_node_ids(const odgi::graph_t& graph);
+rust::Vec<odgi::PathStep> graph_get_path_steps(const odgi::graph_t& graph, rust::Str path_name);
+rust::Vec<odgi::GraphEdge> graph_get_edges(const odgi::graph_t& graph);
+uint64_t graph_get_node_step_count(const odgi::graph_t& graph, uint64_t node_id);
+rust::Vec<odgi::NodeStep> graph_get_steps_on_node(const odgi::graph_t& graph, uint64_t node_id, uint64_t offset, uint64_t limit);
+uint64_t graph_prefault(const odgi::graph_t& graph);
+uint64_t graph_get_path_count(const odgi::graph_t& graph);
+rust::Vec<uint64_t> graph_get_node_ids_at_ranks(const odgi::graph_t& graph, rust::Slice<const uint64_t> ranks);
+rust::Vec<rust::String> graph_get_path_names_at_ranks(const odgi::graph_t& graph, rust::Slice<const uint64_t> ranks);
+rust::Vec<odgi::PathPosition> graph_project_sorted(const odgi::graph_t& graph, rust::Str path_name, rust::Slice<const uint64_t> positions);
+rust::Vec<odgi::StepPosition> graph_get_node_path_positions(const odgi::graph_t& graph, uint64_t node_id, rust::Slice<const rust::String> skip_paths);
+bool graph_has_path(const odgi::graph_t& graph, rust::Str path_name);
+odgi::GraphStats graph_get_stats(const odgi::graph_t& graph);
+bool graph_has_edge(
+    const odgi::graph_t& graph,
+    uint64_t from_node, bool from_orient,
+    uint64_t to_node, bool to_orient
+);
+int64_t graph_get_node_sort_offset(const odgi::graph_t& graph, uint64_t node_id);
+rust::Vec<uint64_t> graph_get_node_sort_offsets(const odgi::graph_t& graph);
+uint64_t graph_get_node_at_sort_offset(const odgi::graph_t& graph, uint64_t offset);
+rust::Vec<uint64_t> graph_get_node_order(const odgi::graph_t& graph);
+
+bool graph_create_node(odgi::graph_t& graph, uint64_t node_id, rust::Str sequence);
+bool graph_create_edge(
+    odgi::graph_t& graph,
+    uint64_t from_node, bool from_orient,
+    uint64_t to_node, bool to_orient
+);
+bool graph_create_path(odgi::graph_t& graph, rust::Str path_name);
+bool graph_append_step(odgi::graph_t& graph, rust::Str path_name, uint64_t node_id, bool is_forward);
+bool graph_destroy_path(odgi::graph_t& graph, rust::Str path_name);
+bool graph_truncate_path(odgi::graph_t& graph, rust::Str path_name, uint64_t length);
+bool graph_destroy_node(odgi::graph_t& graph, uint64_t node_id);
+bool graph_destroy_edge(
+    odgi::graph_t& graph,
+    uint64_t from_node, bool from_is_forward,
+    uint64_t to_node, bool to_is_forward
+);
+rust::Vec<uint64_t> graph_divide_node(odgi::graph_t& graph, uint64_t node_id, rust::Slice<const uint64_t> offsets);
+bool graph_apply_node_order(odgi::graph_t& graph, rust::Slice<const uint64_t> node_ids);
+uint64_t graph_get_path_step_count(const odgi::graph_t& graph, rust::Str path_name);
+
+// Raw handle functions. Handles and steps are passed in their packed integer form
+// and are not validated; the Rust side documents the requirements as `unsafe`.
+bool raw_has_node(const odgi::graph_t& graph, uint64_t node_id);
+uint64_t raw_get_handle(const odgi::graph_t& graph, uint64_t node_id, bool is_reverse);
+uint64_t raw_get_id(const odgi::graph_t& graph, uint64_t handle);
+uint64_t raw_get_length(const odgi::graph_t& graph, uint64_t handle);
+rust::String raw_get_sequence(const odgi::graph_t& graph, uint64_t handle);
+rust::Vec<uint64_t> raw_follow_edges(const odgi::graph_t& graph, uint64_t handle, bool go_left);
+uint64_t raw_get_degree(const odgi::graph_t& graph, uint64_t handle, bool go_left);
+rust::Vec<uint64_t> raw_get_edges(const odgi::graph_t& graph);
+int64_t raw_get_path_handle(const odgi::graph_t& graph, rust::Str path_name);
+odgi::StepHandle raw_path_begin(const odgi::graph_t& graph, uint64_t path);
+odgi::StepHandle raw_path_end(const odgi::graph_t& graph, uint64_t path);
+bool raw_has_next_step(const odgi::graph_t& graph, odgi::StepHandle step);
+bool raw_has_previous_step(const odgi::graph_t& graph, odgi::StepHandle step);
+odgi::StepHandle raw_get_next_step(const odgi::graph_t& graph, odgi::StepHandle step);
+odgi::StepHandle raw_get_previous_step(const odgi::graph_t& graph, odgi::StepHandle step);
+uint64_t raw_get_handle_of_step(const odgi::graph_t& graph, odgi::StepHandle step);
+uint64_t raw_get_path_handle_of_step(const odgi::graph_t& graph, odgi::StepHandle step);
+rust::Vec<odgi::StepHandle> raw_get_steps_on_handle(const odgi::graph_t& graph, uint64_t handle);
@@ -0,0 +1,80 @@
+// src/order.rs
+
+//! Applying a node order computed outside of odgi.
+
+use std::collections::HashSet;
+use super::graph::{Error, Graph};
+
+impl Graph {
+    /// Rearranges the graph's 1D order to follow `node_ids`.
+    ///
+    /// This is the in-memory counterpart of `odgi sort -s FILE`: the order can
+    /// come from a custom layout or clustering algorithm rather than from one
+    /// of odgi's sorters. Node IDs, sequences, edges, and paths are unchanged;
+    /// only the order, and with it the pangenome coordinates of
+    /// [`Graph::node_sort_offset`], changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `node_ids` is not a permutation of the graph's
+    /// nodes, that is if it names a node that does not exist, names a node
+    /// twice, or leaves a node out. The graph is unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let mut graph = Graph::load("pangenome.odgi").unwrap();
+    /// // Place the nodes in descending ID order.
+    /// let mut order: Vec<u64> = (1..=graph.node_count().unwrap()).collect();
+    /// order.reverse();
+    /// graph.apply_order(&order).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, node_ids), err))]
+    pub fn apply_order(&mut self, node_ids: &[u64]) -> Result<(), Error> {
+        let mut seen = HashSet::with_capacity(node_ids.len());
+        for &node_id in node_ids {
+            self.check_node(node_id)?;
+            if !seen.insert(node_id) {
+                return Err(Error::invalid_argument(format!("Node {} appears more than once in the order", node_id)));
+            }
+        }
+        if seen.len() as u64 != self.node_count()? {
+            return Err(Error::invalid_argument(format!(
+                "The order lists {} of the graph's {} nodes",
+                seen.len(),
+                self.node_count()?
+            )));
+        }
+        if !self.apply_node_order(node_ids)? {
+            return Err(Error::other("Failed to apply the node order"));
+        }
+        Ok(())
+    }
+
+    /// Reads a node order from a file and applies it with [`Graph::apply_order`].
+    ///
+    /// The file lists one node ID per line, as read by `odgi sort -s`. Blank
+    /// lines are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read, a line is not a node ID,
+    /// or the IDs are not a permutation of the graph's nodes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn load_order_file(&mut self, path: &str) -> Result<(), Error> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::io(format!("Failed to read order file '{}'", path), e))?;
+        let node_ids = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                line.trim()
+                    .parse()
+                    .map_err(|_| Error::invalid_format(format!("Line {} of '{}' is not a node ID: '{}'", i + 1, path, line)))
+            })
+            .collect::<Result<Vec<u64>, Error>>()?;
+        self.apply_order(&node_ids)
+    }
+}
@@ -0,0 +1,64 @@
+// src/orphans.rs
+
+//! Removing nodes and edges that no path traverses.
+
+use super::graph::{Error, Graph};
+
+/// What [`Graph::remove_orphaned_nodes`] removed from the graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrphanReport {
+    /// The number of removed nodes.
+    pub removed_nodes: u64,
+    /// The number of removed edges, including those attached to removed nodes.
+    pub removed_edges: u64,
+    /// The total sequence length of the removed nodes.
+    pub removed_bp: u64,
+}
+
+impl Graph {
+    /// Removes the nodes and edges that are not traversed by any path.
+    ///
+    /// A node is orphaned when no path step visits it, and an edge when no
+    /// pair of consecutive path steps crosses it in either direction. Removing
+    /// paths, for example to subset a pangenome to a few haplotypes, leaves
+    /// their private sequence behind; call this afterwards so the graph
+    /// actually shrinks. Node IDs of the remaining nodes are preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi fails to remove a node or an edge. The nodes
+    /// and edges removed before the failure stay removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let mut graph = Graph::load("subset.odgi").unwrap();
+    /// let report = graph.remove_orphaned_nodes().unwrap();
+    /// println!("Freed {} bp in {} nodes", report.removed_bp, report.removed_nodes);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn remove_orphaned_nodes(&mut self) -> Result<OrphanReport, Error> {
+        let mut report = OrphanReport::default();
+        let edges_before = self.raw_edges()?.len() as u64;
+
+        for node_id in self.node_ids()? {
+            if self.node_depth(node_id)? == 0 {
+                let len = self.get_node_len(node_id)?;
+                if self.remove_node(node_id)? {
+                    report.removed_nodes += 1;
+                    report.removed_bp += len;
+                }
+            }
+        }
+
+        for edge in self.edge_usage()? {
+            if edge.traversals == 0 {
+                self.remove_edge(edge.from_node, edge.from_orientation, edge.to_node, edge.to_orientation)?;
+            }
+        }
+
+        report.removed_edges = edges_before - self.raw_edges()?.len() as u64;
+        Ok(report)
+    }
+}
@@ -0,0 +1,117 @@
+// src/packed.rs
+
+//! A compact 2-bit encoding of DNA sequences.
+
+use std::fmt;
+use super::graph::{Error, Graph};
+
+/// The four bases in the order of their 2-bit codes.
+const BASES: [u8; 4] = *b"ACGT";
+
+/// A DNA sequence packed into 2 bits per base, returned by
+/// [`Graph::get_node_sequence_packed`].
+///
+/// Uppercase `A`, `C`, `G`, and `T` take 2 bits each. Every other symbol,
+/// including soft-masked (lowercase) bases and ambiguity codes, is kept in a
+/// sorted exception list, so unpacking always reproduces the original
+/// sequence exactly. Typical assembled sequence packs into about a quarter of
+/// its size.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PackedSequence {
+    len: usize,
+    /// Four bases per byte, first base in the lowest bits.
+    bits: Vec<u8>,
+    /// `(index, symbol)` for every position that is not uppercase `ACGT`.
+    exceptions: Vec<(usize, u8)>,
+}
+
+impl PackedSequence {
+    /// Packs a sequence.
+    pub fn new(sequence: &str) -> Self {
+        let bytes = sequence.as_bytes();
+        let mut bits = vec![0u8; bytes.len().div_ceil(4)];
+        let mut exceptions = Vec::new();
+        for (i, &symbol) in bytes.iter().enumerate() {
+            match BASES.iter().position(|&b| b == symbol) {
+                Some(code) => bits[i / 4] |= (code as u8) << (2 * (i % 4)),
+                None => exceptions.push((i, symbol)),
+            }
+        }
+        PackedSequence { len: bytes.len(), bits, exceptions }
+    }
+
+    /// Returns the number of bases.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the sequence has no bases.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the base at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        if index >= self.len {
+            return None;
+        }
+        if let Ok(e) = self.exceptions.binary_search_by_key(&index, |&(i, _)| i) {
+            return Some(self.exceptions[e].1);
+        }
+        Some(BASES[((self.bits[index / 4] >> (2 * (index % 4))) & 0b11) as usize])
+    }
+
+    /// Returns the positions and symbols that are not uppercase `ACGT`.
+    pub fn exceptions(&self) -> &[(usize, u8)] {
+        &self.exceptions
+    }
+
+    /// Returns the number of bytes the encoding occupies on the heap.
+    pub fn heap_size(&self) -> usize {
+        self.bits.capacity() + self.exceptions.capacity() * std::mem::size_of::<(usize, u8)>()
+    }
+
+    /// Unpacks the sequence into a `String`.
+    pub fn unpack(&self) -> String {
+        let mut bytes: Vec<u8> = (0..self.len)
+            .map(|i| BASES[((self.bits[i / 4] >> (2 * (i % 4))) & 0b11) as usize])
+            .collect();
+        for &(i, symbol) in &self.exceptions {
+            bytes[i] = symbol;
+        }
+        // Exceptions are copied from a `&str` byte by byte, so the result is valid UTF-8.
+        String::from_utf8(bytes).expect("packed sequences hold UTF-8 input")
+    }
+}
+
+impl fmt::Display for PackedSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.unpack())
+    }
+}
+
+impl Graph {
+    /// Gets the sequence of a node in a compact 2-bit encoding.
+    ///
+    /// This is meant for consumers that keep millions of node sequences in
+    /// memory, such as sequence indexes. See [`PackedSequence`] for how
+    /// symbols other than uppercase `ACGT` are stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let packed = graph.get_node_sequence_packed(1).unwrap();
+    /// assert_eq!(packed.unpack(), graph.get_node_sequence(1).unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn get_node_sequence_packed(&self, node_id: u64) -> Result<PackedSequence, Error> {
+        Ok(PackedSequence::new(&self.get_node_sequence(node_id)?))
+    }
+}
@@ -0,0 +1,83 @@
+// src/paf.rs
+
+//! PAF export of the alignments implied between paths.
+
+use std::io::Write;
+use super::graph::{Error, Graph};
+use super::path_alignment::{cigar, PathAlignment};
+use super::path_index::PathIndex;
+
+impl Graph {
+    /// Writes one PAF record per path describing how it aligns to `reference_path`.
+    ///
+    /// The alignment is implied by the graph: the longest colinear chain of
+    /// nodes that a path shares with the reference, weighted by node length,
+    /// anchors it, and the sequence between anchors becomes mismatches (for
+    /// equally long bubbles) or deletions and insertions. Paths that mostly run
+    /// through the reference in the opposite direction are reported on the `-`
+    /// strand. Each record carries the alignment as a `cg:Z:` CIGAR using the
+    /// `=`, `X`, `I`, and `D` operations; the mapping quality is `255`
+    /// (unavailable).
+    ///
+    /// Paths that share no node with the reference are skipped. Returns the
+    /// number of records written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the reference path does not exist or if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let mut out = std::fs::File::create("haplotypes_vs_grch38.paf").unwrap();
+    /// graph.paths_to_paf("GRCh38#0#chr1", &mut out).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer), err))]
+    pub fn paths_to_paf<W: Write>(&self, reference_path: &str, writer: &mut W) -> Result<usize, Error> {
+        let reference = PathIndex::new(self, reference_path)?
+            .ok_or_else(|| Error::path_not_found(reference_path))?;
+        let io_err = |e: std::io::Error| Error::io("Failed to write PAF output", e);
+
+        let mut written = 0;
+        for path_name in self.get_path_names()? {
+            if path_name == reference_path {
+                continue;
+            }
+            let Some(query) = PathIndex::new(self, &path_name)? else {
+                continue;
+            };
+            let query_length = query.length;
+            let alignment = PathAlignment::new(query, &reference);
+            let (Some((query_start, query_end)), Some((reference_start, reference_end))) =
+                (alignment.query_range(), alignment.reference_range(&reference))
+            else {
+                continue;
+            };
+
+            let ops = alignment.operations(self, &reference)?;
+            let matches: u64 = ops.iter().filter(|(op, _)| *op == '=').map(|(_, len)| len).sum();
+            let block_length: u64 = ops.iter().map(|(_, len)| len).sum();
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t255\tcg:Z:{}",
+                path_name,
+                query_length,
+                query_start,
+                query_end,
+                if alignment.is_reverse { '-' } else { '+' },
+                reference_path,
+                reference.length,
+                reference_start,
+                reference_end,
+                matches,
+                block_length,
+                cigar(&ops)
+            )
+            .map_err(io_err)?;
+            written += 1;
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,123 @@
+// src/pangenome_offset.rs
+
+//! Mapping pangenome and node coordinates back to path positions.
+
+use std::collections::HashMap;
+use super::graph::{Error, Graph};
+
+/// A path crossing a base of the graph, found by [`Graph::node_to_path_positions`]
+/// and [`Graph::paths_at_pangenome_offset`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCrossing {
+    /// The name of the path.
+    pub path_name: String,
+    /// The 0-based position on the path of the base at the pangenome coordinate.
+    pub position: u64,
+    /// `true` if the path visits the node in its forward orientation.
+    pub is_forward: bool,
+}
+
+/// The node at a pangenome coordinate and the paths crossing it, found by
+/// [`Graph::paths_at_pangenome_offset`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PangenomeOffset {
+    /// The node covering the coordinate.
+    pub node_id: u64,
+    /// The 0-based offset of the coordinate within the node's forward sequence.
+    pub offset: u64,
+    /// Every visit of a path to the node, in the order of [`Graph::get_path_names`]
+    /// and then along each path.
+    pub paths: Vec<PathCrossing>,
+}
+
+impl Graph {
+    /// Finds the node at an offset in the graph's 1D sort order and the
+    /// positions of all paths crossing it.
+    ///
+    /// This is the inverse of projecting path positions into the pangenome
+    /// coordinate system used by `odgi bin` and `odgi viz`. A path that visits
+    /// the node several times is reported once per visit. For a visit in the
+    /// reverse orientation, the position is that of the same base read on the
+    /// path's strand.
+    ///
+    /// Paths are located as by [`Graph::node_to_path_positions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::InvalidArgument`](crate::ErrorKind::InvalidArgument)
+    /// if the offset lies beyond the graph's total sequence length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let hit = graph.paths_at_pangenome_offset(1_000_000).unwrap();
+    /// for crossing in &hit.paths {
+    ///     println!("{}:{}", crossing.path_name, crossing.position);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn paths_at_pangenome_offset(&self, offset: u64) -> Result<PangenomeOffset, Error> {
+        let (node_id, offset) = self.node_at_sort_offset(offset)?;
+        let paths = self.node_to_path_positions(node_id, offset)?;
+        Ok(PangenomeOffset { node_id, offset, paths })
+    }
+
+    /// Finds every path position where a base of a node occurs.
+    ///
+    /// This is the inverse of [`Graph::project`]: `offset` is the 0-based
+    /// offset of the base within the node's forward sequence, and each visit
+    /// of a path to the node yields the position of that base on the path. For
+    /// a visit in the reverse orientation, the position is that of the same
+    /// base read on the path's strand. The crossings are ordered by path, in
+    /// the order of [`Graph::get_path_names`], and then along each path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist, or of kind
+    /// [`ErrorKind::InvalidArgument`](crate::ErrorKind::InvalidArgument) if the
+    /// offset lies past its end.
+    ///
+    /// # Performance
+    ///
+    /// Visits on paths indexed at load time (see [`LoadOptions::index_paths`](crate::LoadOptions::index_paths))
+    /// are looked up in the index, after one pass over the path's steps on
+    /// the first lookup. Any other visit is located by walking back from its
+    /// step to the start of its path, which takes time linear in the number
+    /// of steps before it; a node visited near the end of a chromosome-length
+    /// path costs about as much as walking the whole path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for crossing in graph.node_to_path_positions(42, 0).unwrap() {
+    ///     let strand = if crossing.is_forward { '+' } else { '-' };
+    ///     println!("{}:{}{}", crossing.path_name, crossing.position, strand);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn node_to_path_positions(&self, node_id: u64, offset: u64) -> Result<Vec<PathCrossing>, Error> {
+        let node_len = self.get_node_len(node_id)?;
+        if offset >= node_len {
+            return Err(Error::invalid_argument(format!("Offset {} is past the end of node {}", offset, node_id)));
+        }
+        let mut steps = self.node_path_positions(node_id)?;
+        if steps.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ranks: HashMap<String, usize> =
+            self.get_path_names()?.into_iter().enumerate().map(|(rank, name)| (name, rank)).collect();
+        steps.sort_by_key(|step| (ranks.get(&step.path_name).copied(), step.start));
+        Ok(steps
+            .into_iter()
+            .map(|step| {
+                let in_step = if step.is_forward { offset } else { node_len - 1 - offset };
+                PathCrossing { path_name: step.path_name, position: step.start + in_step, is_forward: step.is_forward }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,16 @@
+// src/pansn.rs
+
+//! Helpers for path names following the PanSN naming convention.
+//!
+//! PanSN names have the form `sample#haplotype#contig`, so that the sample a
+//! path belongs to can be recovered from its name alone.
+
+/// The separator between the fields of a PanSN name.
+pub(crate) const DELIMITER: char = '#';
+
+/// Returns the sample a path belongs to.
+///
+/// For PanSN names this is the first field; any other name is its own sample.
+pub(crate) fn sample_name(path_name: &str) -> &str {
+    path_name.split(DELIMITER).next().unwrap_or(path_name)
+}
@@ -0,0 +1,349 @@
+// src/path_alignment.rs
+
+//! The alignment between two paths implied by the nodes they share.
+//!
+//! Two paths through a variation graph are aligned wherever they visit the
+//! same node in the same orientation. The longest colinear chain of such
+//! shared steps, weighted by node length, serves as the anchors of the
+//! alignment; the sequence between consecutive anchors is a substitution,
+//! insertion, or deletion.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+use super::seq::bases_match;
+
+/// Reference nodes visited more often than this are not used as anchors.
+///
+/// Chaining considers every pair of matching steps, so highly repeated nodes
+/// would make it quadratic while contributing little to the alignment.
+pub(crate) const MAX_NODE_OCCURRENCES: usize = 256;
+
+/// A chain of shared steps between a query path and a reference path.
+pub(crate) struct PathAlignment {
+    /// The query path, reversed if it aligns to the reverse strand of the reference.
+    pub(crate) query: Arc<PathIndex>,
+    /// Whether the query is aligned in the opposite direction of the reference.
+    pub(crate) is_reverse: bool,
+    /// Pairs of query and reference step indices, increasing in both.
+    pub(crate) anchors: Vec<(usize, usize)>,
+}
+
+impl PathAlignment {
+    /// Aligns `query` to `reference` on whichever strand shares more sequence.
+    pub(crate) fn new(query: Arc<PathIndex>, reference: &PathIndex) -> Self {
+        let (forward_score, forward) = chain(&query, reference);
+        let reversed = query.reversed();
+        let (reverse_score, reverse) = chain(&reversed, reference);
+        if reverse_score > forward_score {
+            PathAlignment { query: Arc::new(reversed), is_reverse: true, anchors: reverse }
+        } else {
+            PathAlignment { query, is_reverse: false, anchors: forward }
+        }
+    }
+
+    /// Returns the aligned range of the query in its own forward coordinates.
+    pub(crate) fn query_range(&self) -> Option<(u64, u64)> {
+        let (start, end) = span(&self.query, self.anchors.iter().map(|&(i, _)| i))?;
+        if self.is_reverse {
+            Some((self.query.length - end, self.query.length - start))
+        } else {
+            Some((start, end))
+        }
+    }
+
+    /// Returns the aligned range of the reference.
+    pub(crate) fn reference_range(&self, reference: &PathIndex) -> Option<(u64, u64)> {
+        span(reference, self.anchors.iter().map(|&(_, j)| j))
+    }
+
+    /// Returns the operations for the sequence between two consecutive anchors.
+    fn gap_operations(&self, graph: &Graph, reference: &PathIndex, query_gap: (u64, u64), reference_gap: (u64, u64)) -> Result<Vec<(char, u64)>, Error> {
+        let mut ops = Vec::new();
+        let query_len = query_gap.1 - query_gap.0;
+        let reference_len = reference_gap.1 - reference_gap.0;
+        if query_len == reference_len {
+            let query_seq = self.query.sequence(graph, query_gap.0, query_gap.1)?;
+            let reference_seq = reference.sequence(graph, reference_gap.0, reference_gap.1)?;
+            for (q, r) in query_seq.bytes().zip(reference_seq.bytes()) {
+                push_op(&mut ops, if bases_match(q, r) { '=' } else { 'X' }, 1);
+            }
+        } else {
+            push_op(&mut ops, 'D', reference_len);
+            push_op(&mut ops, 'I', query_len);
+        }
+        Ok(ops)
+    }
+
+    /// Returns the query and reference ranges between anchor `k - 1` and anchor `k`.
+    pub(crate) fn gap(&self, reference: &PathIndex, k: usize) -> ((u64, u64), (u64, u64)) {
+        let (prev_i, prev_j) = self.anchors[k - 1];
+        let (i, j) = self.anchors[k];
+        (
+            (self.query.starts[prev_i] + self.query.lengths[prev_i], self.query.starts[i]),
+            (reference.starts[prev_j] + reference.lengths[prev_j], reference.starts[j]),
+        )
+    }
+
+    /// Returns the run-length encoded alignment operations (`=`, `X`, `I`, `D`)
+    /// from the first to the last anchor.
+    pub(crate) fn operations(&self, graph: &Graph, reference: &PathIndex) -> Result<Vec<(char, u64)>, Error> {
+        let mut ops = Vec::new();
+        for (k, &(i, _)) in self.anchors.iter().enumerate() {
+            if k > 0 {
+                let (query_gap, reference_gap) = self.gap(reference, k);
+                for (op, len) in self.gap_operations(graph, reference, query_gap, reference_gap)? {
+                    push_op(&mut ops, op, len);
+                }
+            }
+            push_op(&mut ops, '=', self.query.lengths[i]);
+        }
+        Ok(ops)
+    }
+}
+
+/// Appends an operation, merging it with the previous one if they are of the same kind.
+pub(crate) fn push_op(ops: &mut Vec<(char, u64)>, op: char, len: u64) {
+    if len == 0 {
+        return;
+    }
+    match ops.last_mut() {
+        Some((last, count)) if *last == op => *count += len,
+        _ => ops.push((op, len)),
+    }
+}
+
+/// Formats run-length encoded operations as a CIGAR string.
+pub(crate) fn cigar(ops: &[(char, u64)]) -> String {
+    ops.iter().map(|(op, len)| format!("{}{}", len, op)).collect()
+}
+
+fn span(index: &PathIndex, mut steps: impl DoubleEndedIterator<Item = usize>) -> Option<(u64, u64)> {
+    let first = steps.next()?;
+    let last = steps.next_back().unwrap_or(first);
+    Some((index.starts[first], index.starts[last] + index.lengths[last]))
+}
+
+/// Finds the heaviest chain of matching steps that is colinear in both paths.
+///
+/// This is a weighted longest increasing subsequence over the matching step
+/// pairs, using a Fenwick tree of prefix maxima over reference step indices.
+fn chain(query: &PathIndex, reference: &PathIndex) -> (u64, Vec<(usize, usize)>) {
+    let mut occurrences: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (j, step) in reference.steps.iter().enumerate() {
+        occurrences.entry(step.node_id).or_default().push(j);
+    }
+
+    let mut pairs = Vec::new();
+    for (i, step) in query.steps.iter().enumerate() {
+        let Some(js) = occurrences.get(&step.node_id).filter(|js| js.len() <= MAX_NODE_OCCURRENCES) else {
+            continue;
+        };
+        // Later reference steps first, so that two pairs with the same query
+        // step can never end up in the same chain.
+        for &j in js.iter().rev() {
+            if reference.steps[j].is_forward == step.is_forward {
+                pairs.push((i, j));
+            }
+        }
+    }
+
+    const NONE: usize = usize::MAX;
+    let mut tree = vec![(0u64, NONE); reference.steps.len() + 1];
+    let mut best: Vec<(u64, usize)> = Vec::with_capacity(pairs.len());
+    for (k, &(i, j)) in pairs.iter().enumerate() {
+        let (mut score, mut prev) = (0, NONE);
+        let mut x = j;
+        while x > 0 {
+            if tree[x].0 > score {
+                (score, prev) = tree[x];
+            }
+            x &= x - 1;
+        }
+        let total = score + query.lengths[i];
+        best.push((total, prev));
+        let mut x = j + 1;
+        while x < tree.len() {
+            if total > tree[x].0 {
+                tree[x] = (total, k);
+            }
+            x += x & x.wrapping_neg();
+        }
+    }
+
+    let Some((mut k, &(score, _))) = best.iter().enumerate().max_by_key(|(_, (score, _))| *score) else {
+        return (0, Vec::new());
+    };
+    let mut anchors = Vec::new();
+    while k != NONE {
+        anchors.push(pairs[k]);
+        k = best[k].1;
+    }
+    anchors.reverse();
+    (score, anchors)
+}
+
+/// The kind of an [`AlignmentBlock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlignmentBlockKind {
+    /// Both paths visit the same nodes in the same orientation.
+    Match,
+    /// The paths take different routes of sequence on both sides, such as the two sides of a bubble.
+    Substitution,
+    /// Sequence present only in the second path.
+    Insertion,
+    /// Sequence present only in the first path.
+    Deletion,
+    /// The second path visits the same nodes as the first, in reverse order and orientation.
+    Inversion,
+}
+
+/// A run of the alignment between two paths, returned as part of an [`ImpliedAlignment`].
+///
+/// Ranges are 0-based and half-open, in the forward coordinates of each path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignmentBlock {
+    /// What happens between the two paths in this block.
+    pub kind: AlignmentBlockKind,
+    /// The start of the block on the first path.
+    pub a_start: u64,
+    /// The end of the block on the first path.
+    pub a_end: u64,
+    /// The start of the block on the second path.
+    pub b_start: u64,
+    /// The end of the block on the second path.
+    pub b_end: u64,
+    /// The block as a CIGAR string with the `=`, `X`, `I`, and `D` operations.
+    ///
+    /// Inversions are described relative to the reverse complement of the
+    /// second path's sequence.
+    pub cigar: String,
+}
+
+/// The alignment between two paths implied by the nodes they share.
+///
+/// Returned by [`Graph::align_paths`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImpliedAlignment {
+    /// Whether the second path runs through the shared nodes in the opposite
+    /// direction of the first. The blocks then follow the first path forward
+    /// and the second path backward.
+    pub is_reverse: bool,
+    /// The blocks of the alignment, in order along the first path.
+    pub blocks: Vec<AlignmentBlock>,
+    /// The CIGAR of the whole alignment, from its first to its last matching node.
+    ///
+    /// Inversions appear as substitutions or as a deletion and insertion here.
+    pub cigar: String,
+}
+
+impl Graph {
+    /// Reconstructs the alignment between two embedded paths from the nodes they share.
+    ///
+    /// The alignment is anchored on the longest colinear chain of nodes that
+    /// both paths visit in the same orientation, weighted by node length, as
+    /// for [`Graph::paths_to_paf`]. Runs of shared nodes become
+    /// [`Match`](AlignmentBlockKind::Match) blocks. The sequence between two
+    /// anchors is classified as an insertion, a deletion, an inversion (when
+    /// `b` visits `a`'s nodes there in reverse order and orientation), or
+    /// otherwise a substitution. Sequence before the first and after the last
+    /// anchor is not aligned. If the paths share no nodes, the alignment has
+    /// no blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if either path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{AlignmentBlockKind, Graph};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let alignment = graph.align_paths("GRCh38#0#chr1", "HG002#1#chr1").unwrap();
+    /// for block in &alignment.blocks {
+    ///     if block.kind == AlignmentBlockKind::Inversion {
+    ///         println!("Inversion at {}-{}", block.a_start, block.a_end);
+    ///     }
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn align_paths(&self, a: &str, b: &str) -> Result<ImpliedAlignment, Error> {
+        let a_index = PathIndex::new(self, a)?.ok_or_else(|| Error::path_not_found(a))?;
+        let b_index = PathIndex::new(self, b)?.ok_or_else(|| Error::path_not_found(b))?;
+        let b_length = b_index.length;
+        let alignment = PathAlignment::new(b_index, &a_index);
+        let query = &alignment.query;
+
+        // Blocks are collected in the alignment's own (possibly reversed)
+        // coordinates of `b` and converted at the end.
+        let mut blocks: Vec<AlignmentBlock> = Vec::new();
+        for (k, &(i, j)) in alignment.anchors.iter().enumerate() {
+            if k > 0 {
+                let (query_gap, reference_gap) = alignment.gap(&a_index, k);
+                let kind = if query_gap.0 == query_gap.1 {
+                    AlignmentBlockKind::Deletion
+                } else if reference_gap.0 == reference_gap.1 {
+                    AlignmentBlockKind::Insertion
+                } else {
+                    let (prev_i, prev_j) = alignment.anchors[k - 1];
+                    let query_steps = &query.steps[prev_i + 1..i];
+                    let reference_steps = &a_index.steps[prev_j + 1..j];
+                    let inverted = query_steps.len() == reference_steps.len()
+                        && query_steps
+                            .iter()
+                            .rev()
+                            .zip(reference_steps)
+                            .all(|(q, r)| q.node_id == r.node_id && q.is_forward != r.is_forward);
+                    if inverted { AlignmentBlockKind::Inversion } else { AlignmentBlockKind::Substitution }
+                };
+                let ops = if kind == AlignmentBlockKind::Inversion {
+                    vec![('=', query_gap.1 - query_gap.0)]
+                } else {
+                    alignment.gap_operations(self, &a_index, query_gap, reference_gap)?
+                };
+                blocks.push(AlignmentBlock {
+                    kind,
+                    a_start: reference_gap.0,
+                    a_end: reference_gap.1,
+                    b_start: query_gap.0,
+                    b_end: query_gap.1,
+                    cigar: cigar(&ops),
+                });
+            }
+
+            let (a_start, b_start) = (a_index.starts[j], query.starts[i]);
+            let (a_end, b_end) = (a_start + a_index.lengths[j], b_start + query.lengths[i]);
+            match blocks.last_mut() {
+                Some(last) if last.kind == AlignmentBlockKind::Match && last.a_end == a_start && last.b_end == b_start => {
+                    last.a_end = a_end;
+                    last.b_end = b_end;
+                }
+                _ => blocks.push(AlignmentBlock {
+                    kind: AlignmentBlockKind::Match,
+                    a_start,
+                    a_end,
+                    b_start,
+                    b_end,
+                    cigar: String::new(),
+                }),
+            }
+        }
+
+        for block in &mut blocks {
+            if block.kind == AlignmentBlockKind::Match {
+                block.cigar = format!("{}=", block.a_end - block.a_start);
+            }
+            if alignment.is_reverse {
+                (block.b_start, block.b_end) = (b_length - block.b_end, b_length - block.b_start);
+            }
+        }
+
+        Ok(ImpliedAlignment {
+            is_reverse: alignment.is_reverse,
+            cigar: cigar(&alignment.operations(self, &a_index)?),
+            blocks,
+        })
+    }
+}
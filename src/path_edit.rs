@@ -0,0 +1,39 @@
+// src/path_edit.rs
+
+//! Writing whole paths into graphs that are being built or edited.
+
+use super::graph::{Error, Graph};
+
+impl Graph {
+    /// Creates a path that visits `steps` in order.
+    ///
+    /// Fails with [`ErrorKind::PathExists`](crate::ErrorKind::PathExists) if a
+    /// path with this name already exists, rather than appending to it.
+    pub(crate) fn create_path(
+        &mut self,
+        path_name: &str,
+        steps: impl IntoIterator<Item = (u64, bool)>,
+    ) -> Result<(), Error> {
+        if !self.add_path(path_name)? {
+            return Err(Error::path_exists(path_name));
+        }
+        self.extend_path(path_name, steps)
+    }
+
+    /// Appends `steps` to the end of an existing path.
+    ///
+    /// Fails with [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// at the first step on a node that does not exist, rather than skipping it.
+    pub(crate) fn extend_path(
+        &mut self,
+        path_name: &str,
+        steps: impl IntoIterator<Item = (u64, bool)>,
+    ) -> Result<(), Error> {
+        for (node_id, is_forward) in steps {
+            if !self.append_step(path_name, node_id, is_forward)? {
+                return Err(Error::node_not_found(node_id).context(format!("Path '{}'", path_name)));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+// src/path_identity.rs
+
+//! Sequence divergence between two embedded paths.
+//!
+//! The paths are anchored on the nodes they share, as for
+//! [`Graph::align_paths`], so shared nodes contribute no edits and are never
+//! read. Only the sequence between consecutive anchors is extracted and
+//! aligned, one gap at a time, which keeps memory proportional to the largest
+//! divergent region rather than to the length of the paths.
+
+use super::graph::{Error, Graph};
+use super::path_alignment::PathAlignment;
+use super::path_index::PathIndex;
+use super::seq::bases_match;
+
+/// Gaps whose dynamic programming matrix would exceed this many cells are not
+/// aligned base by base; their edit distance is taken as the longer side's length.
+const MAX_GAP_CELLS: u64 = 1 << 26;
+
+/// The divergence between two paths over their shared span, returned by [`Graph::path_identity`].
+///
+/// Ranges are 0-based and half-open, in the forward coordinates of each path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathIdentity {
+    /// Whether the second path runs through the shared nodes in the opposite direction of the first.
+    pub is_reverse: bool,
+    /// The start of the shared span on the first path.
+    pub a_start: u64,
+    /// The end of the shared span on the first path.
+    pub a_end: u64,
+    /// The start of the shared span on the second path.
+    pub b_start: u64,
+    /// The end of the shared span on the second path.
+    pub b_end: u64,
+    /// The number of substituted, inserted, and deleted bases within the span.
+    pub edit_distance: u64,
+}
+
+impl PathIdentity {
+    /// Returns the fraction of the span that is identical between the two
+    /// paths, i.e. one minus the edit distance over the longer of the two spans.
+    pub fn identity(&self) -> f64 {
+        let span = (self.a_end - self.a_start).max(self.b_end - self.b_start);
+        if span == 0 {
+            return 1.0;
+        }
+        1.0 - self.edit_distance as f64 / span as f64
+    }
+}
+
+impl Graph {
+    /// Computes the edit distance and identity between the sequences of two
+    /// embedded paths over the span they share.
+    ///
+    /// The span runs from the first to the last node of the longest colinear
+    /// chain of nodes that both paths visit in the same orientation, on
+    /// whichever strand of `b` shares more sequence with `a`. Between shared
+    /// nodes, the two sides are aligned with unit-cost edit distance, treating
+    /// IUPAC codes as matching any base they represent. Divergent regions too
+    /// large to align exactly, such as large structural variants, count as
+    /// many edits as their longer side. Returns `None` if the paths share no nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if either path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// if let Some(identity) = graph.path_identity("GRCh38#0#chr1", "HG002#1#chr1").unwrap() {
+    ///     println!("{} edits, {:.4} identity", identity.edit_distance, identity.identity());
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn path_identity(&self, a: &str, b: &str) -> Result<Option<PathIdentity>, Error> {
+        let a_index = PathIndex::new(self, a)?.ok_or_else(|| Error::path_not_found(a))?;
+        let b_index = PathIndex::new(self, b)?.ok_or_else(|| Error::path_not_found(b))?;
+        let alignment = PathAlignment::new(b_index, &a_index);
+        let (Some((a_start, a_end)), Some((b_start, b_end))) = (alignment.reference_range(&a_index), alignment.query_range()) else {
+            return Ok(None);
+        };
+
+        let mut distance = 0;
+        for k in 1..alignment.anchors.len() {
+            let (b_gap, a_gap) = alignment.gap(&a_index, k);
+            let (a_len, b_len) = (a_gap.1 - a_gap.0, b_gap.1 - b_gap.0);
+            distance += if a_len == 0 || b_len == 0 || a_len.saturating_mul(b_len) > MAX_GAP_CELLS {
+                a_len.max(b_len)
+            } else {
+                let a_seq = a_index.sequence(self, a_gap.0, a_gap.1)?;
+                let b_seq = alignment.query.sequence(self, b_gap.0, b_gap.1)?;
+                edit_distance(a_seq.as_bytes(), b_seq.as_bytes())
+            };
+        }
+
+        Ok(Some(PathIdentity { is_reverse: alignment.is_reverse, a_start, a_end, b_start, b_end, edit_distance: distance }))
+    }
+}
+
+/// Computes the unit-cost edit distance between two sequences in linear space.
+fn edit_distance(a: &[u8], b: &[u8]) -> u64 {
+    let mut row: Vec<u64> = (0..=b.len() as u64).collect();
+    for (i, &x) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i as u64 + 1;
+        for (j, &y) in b.iter().enumerate() {
+            let substitution = diagonal + u64::from(!bases_match(x, y));
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,135 @@
+// src/path_index.rs
+
+//! An in-memory index of the steps of a single path.
+//!
+//! Several operations need to look up many coordinates on the same path. Instead of
+//! walking the path from the start for every lookup, they build a [`PathIndex`] once
+//! and binary-search the cumulative step offsets. [`Graph::load_with`] can build
+//! them for chosen paths up front, in which case lookups share the graph's copy.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use super::ffi::PathStep;
+use super::graph::{Error, Graph, PathPosition};
+use super::seq::reverse_complement;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PathIndex {
+    pub(crate) steps: Vec<PathStep>,
+    /// The path offset of the first base of each step.
+    pub(crate) starts: Vec<u64>,
+    /// The length of each step's node.
+    pub(crate) lengths: Vec<u64>,
+    pub(crate) length: u64,
+    /// The steps visiting each node, filled in by the first [`PathIndex::steps_on_node`].
+    #[cfg_attr(feature = "docs-only", allow(dead_code))]
+    visits: OnceLock<HashMap<u64, Vec<usize>>>,
+}
+
+impl PathIndex {
+    /// Returns the graph's index of the path, building it unless the graph was
+    /// loaded with one, or `None` if the path does not exist.
+    pub(crate) fn new(graph: &Graph, path_name: &str) -> Result<Option<Arc<Self>>, Error> {
+        #[cfg(not(feature = "docs-only"))]
+        if let Some(index) = graph.path_indexes.get(path_name) {
+            return Ok(Some(Arc::clone(index)));
+        }
+        Ok(Self::build(graph, path_name)?.map(Arc::new))
+    }
+
+    /// Builds the index, or returns `None` if the path does not exist.
+    pub(crate) fn build(graph: &Graph, path_name: &str) -> Result<Option<Self>, Error> {
+        if !graph.has_path(path_name)? {
+            return Ok(None);
+        }
+        let steps = graph.raw_path_steps(path_name)?;
+        let node_ids: Vec<u64> = steps.iter().map(|step| step.node_id).collect();
+        let lengths = graph.node_lens(&node_ids)?;
+        let mut starts = Vec::with_capacity(steps.len());
+        let mut length = 0;
+        for &node_len in &lengths {
+            starts.push(length);
+            length += node_len;
+        }
+        Ok(Some(PathIndex { steps, starts, lengths, length, visits: OnceLock::new() }))
+    }
+
+    /// Returns the index of the same path read in the opposite direction.
+    ///
+    /// The steps are reversed and flipped, so position `p` of the result
+    /// corresponds to position `length - 1 - p` of the original on the other strand.
+    pub(crate) fn reversed(&self) -> Self {
+        let steps: Vec<PathStep> = self
+            .steps
+            .iter()
+            .rev()
+            .map(|step| PathStep { node_id: step.node_id, is_forward: !step.is_forward })
+            .collect();
+        let lengths: Vec<u64> = self.lengths.iter().rev().copied().collect();
+        let starts = lengths
+            .iter()
+            .scan(0, |pos, &len| {
+                let start = *pos;
+                *pos += len;
+                Some(start)
+            })
+            .collect();
+        PathIndex { steps, starts, lengths, length: self.length, visits: OnceLock::new() }
+    }
+
+    /// Returns the indices of the steps visiting `node_id`, in path order.
+    ///
+    /// The first call records the visits of every node with one pass over the steps.
+    #[cfg_attr(feature = "docs-only", allow(dead_code))]
+    pub(crate) fn steps_on_node(&self, node_id: u64) -> &[usize] {
+        let visits = self.visits.get_or_init(|| {
+            let mut visits: HashMap<u64, Vec<usize>> = HashMap::new();
+            for (i, step) in self.steps.iter().enumerate() {
+                visits.entry(step.node_id).or_default().push(i);
+            }
+            visits
+        });
+        visits.get(&node_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the index of the step covering path position `pos`.
+    pub(crate) fn step_at(&self, pos: u64) -> Option<usize> {
+        if pos >= self.length {
+            return None;
+        }
+        Some(self.starts.partition_point(|&start| start <= pos) - 1)
+    }
+
+    /// Returns the graph position of path position `pos`, as [`Graph::project`] reports it.
+    pub(crate) fn project(&self, pos: u64) -> Option<PathPosition> {
+        let i = self.step_at(pos)?;
+        let step = &self.steps[i];
+        let in_step = pos - self.starts[i];
+        let offset = if step.is_forward { in_step } else { self.lengths[i] - 1 - in_step };
+        Some(PathPosition { node_id: step.node_id, offset, is_forward: step.is_forward })
+    }
+
+    /// Returns the sequence spelled by the path between `start` (inclusive) and `end` (exclusive).
+    ///
+    /// The range is clamped to the end of the path.
+    pub(crate) fn sequence(&self, graph: &Graph, start: u64, end: u64) -> Result<String, Error> {
+        let end = end.min(self.length);
+        let mut sequence = String::with_capacity(end.saturating_sub(start) as usize);
+        let Some(first) = self.step_at(start) else {
+            return Ok(sequence);
+        };
+        for (step, &step_start) in self.steps.iter().zip(&self.starts).skip(first) {
+            if step_start >= end {
+                break;
+            }
+            let mut node_seq = graph.get_node_sequence(step.node_id)?;
+            if !step.is_forward {
+                node_seq = reverse_complement(&node_seq);
+            }
+            let from = start.saturating_sub(step_start) as usize;
+            let to = ((end - step_start) as usize).min(node_seq.len());
+            sequence.push_str(&node_seq[from..to]);
+        }
+        Ok(sequence)
+    }
+}
@@ -0,0 +1,88 @@
+// src/path_steps.rs
+
+//! Iterating over the steps of a path.
+
+use std::iter::Enumerate;
+use std::vec::IntoIter;
+use super::ffi::PathStep;
+use super::graph::{Error, Graph};
+
+/// A step of a path, yielded by [`PathSteps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RankedStep {
+    /// The 0-based position of the step in the path.
+    pub rank: u64,
+    /// The ID of the node visited by this step.
+    pub node_id: u64,
+    /// The orientation in which the path traverses the node.
+    pub is_forward: bool,
+}
+
+/// An iterator over the steps of a path, created by [`Graph::path_steps`].
+///
+/// The steps are read from the graph in a single call when the iterator is
+/// created, at 16 bytes per step, so a path of millions of steps is read
+/// without visiting its bases.
+#[derive(Debug, Clone)]
+pub struct PathSteps {
+    steps: Enumerate<IntoIter<PathStep>>,
+}
+
+fn ranked((rank, step): (usize, PathStep)) -> RankedStep {
+    RankedStep { rank: rank as u64, node_id: step.node_id, is_forward: step.is_forward }
+}
+
+impl Iterator for PathSteps {
+    type Item = RankedStep;
+
+    fn next(&mut self) -> Option<RankedStep> {
+        self.steps.next().map(ranked)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<RankedStep> {
+        self.steps.nth(n).map(ranked)
+    }
+}
+
+impl DoubleEndedIterator for PathSteps {
+    fn next_back(&mut self) -> Option<RankedStep> {
+        self.steps.next_back().map(ranked)
+    }
+}
+
+impl ExactSizeIterator for PathSteps {}
+
+impl Graph {
+    /// Iterates over the steps of a path in path order.
+    ///
+    /// Each step is yielded with its rank, so a path can be reconstructed,
+    /// compared, or exported node by node without projecting its bases one at
+    /// a time. The iterator is double-ended and knows its length, so the last
+    /// steps can be read with [`Iterator::rev`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let walk: Vec<String> = graph
+    ///     .path_steps("HG002#1#chr20")
+    ///     .unwrap()
+    ///     .map(|step| format!("{}{}", step.node_id, if step.is_forward { '+' } else { '-' }))
+    ///     .collect();
+    /// println!("{}", walk.join(","));
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn path_steps(&self, path_name: &str) -> Result<PathSteps, Error> {
+        self.check_path(path_name)?;
+        Ok(PathSteps { steps: self.raw_path_steps(path_name)?.into_iter().enumerate() })
+    }
+}
@@ -0,0 +1,161 @@
+// src/presence.rs
+
+//! Per-sample presence and copy number of nodes.
+
+use std::collections::{BTreeMap, HashSet};
+use super::graph::{Error, Graph};
+use super::pansn;
+
+/// The thresholds used by [`Graph::classify_nodes_by_presence`].
+///
+/// Both thresholds are fractions of the samples in the graph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PresenceThresholds {
+    /// Nodes traversed by at least this fraction of samples are core.
+    pub core: f64,
+    /// Nodes traversed by at most this fraction of samples are cloud.
+    pub cloud: f64,
+}
+
+impl Default for PresenceThresholds {
+    /// Core at 95% of samples or more, cloud at 5% or fewer.
+    fn default() -> Self {
+        PresenceThresholds { core: 0.95, cloud: 0.05 }
+    }
+}
+
+/// The presence class of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresenceClass {
+    /// Shared by (nearly) all samples.
+    Core,
+    /// Shared by some, but not most, samples.
+    Shell,
+    /// Private to one or very few samples.
+    Cloud,
+}
+
+/// The result of [`Graph::classify_nodes_by_presence`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresenceSummary {
+    /// The number of samples the fractions are relative to.
+    pub sample_count: usize,
+    /// Every node traversed by at least one path with its class, sorted by node ID.
+    pub nodes: Vec<(u64, PresenceClass)>,
+    /// The total sequence length of core nodes.
+    pub core_bp: u64,
+    /// The total sequence length of shell nodes.
+    pub shell_bp: u64,
+    /// The total sequence length of cloud nodes.
+    pub cloud_bp: u64,
+}
+
+impl Graph {
+    /// Classifies every node as core, shell, or cloud by the fraction of samples traversing it.
+    ///
+    /// Samples are derived from path names: for PanSN names
+    /// (`sample#haplotype#contig`) the sample is the first field, so all
+    /// haplotypes and contigs of one individual count once; any other path name
+    /// is a sample of its own. Nodes not traversed by any path belong to no
+    /// sample and are left out.
+    ///
+    /// The per-class base-pair totals describe how open the pangenome is: the
+    /// larger the shell and cloud relative to the core, the more sequence is
+    /// gained with each additional sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the nodes or paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Graph, PresenceThresholds};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let summary = graph.classify_nodes_by_presence(&PresenceThresholds::default()).unwrap();
+    /// println!(
+    ///     "{} samples: core {} bp, shell {} bp, cloud {} bp",
+    ///     summary.sample_count, summary.core_bp, summary.shell_bp, summary.cloud_bp
+    /// );
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn classify_nodes_by_presence(&self, thresholds: &PresenceThresholds) -> Result<PresenceSummary, Error> {
+        let path_names = self.get_path_names()?;
+        let sample_count = path_names
+            .iter()
+            .map(|name| pansn::sample_name(name))
+            .collect::<HashSet<_>>()
+            .len();
+
+        let mut summary = PresenceSummary {
+            sample_count,
+            nodes: Vec::new(),
+            core_bp: 0,
+            shell_bp: 0,
+            cloud_bp: 0,
+        };
+        for node_id in self.node_ids()? {
+            let paths = self.get_paths_on_node(node_id)?;
+            let samples: HashSet<&str> = paths.iter().map(|name| pansn::sample_name(name)).collect();
+            if samples.is_empty() {
+                continue;
+            }
+            let fraction = samples.len() as f64 / sample_count as f64;
+            let class = if fraction >= thresholds.core {
+                PresenceClass::Core
+            } else if fraction <= thresholds.cloud {
+                PresenceClass::Cloud
+            } else {
+                PresenceClass::Shell
+            };
+            let length = self.get_node_len(node_id)?;
+            match class {
+                PresenceClass::Core => summary.core_bp += length,
+                PresenceClass::Shell => summary.shell_bp += length,
+                PresenceClass::Cloud => summary.cloud_bp += length,
+            }
+            summary.nodes.push((node_id, class));
+        }
+        Ok(summary)
+    }
+
+    /// Counts how many times each sample's paths step on a node.
+    ///
+    /// Samples are derived from path names as in
+    /// [`Graph::classify_nodes_by_presence`], so the steps of all haplotypes of
+    /// a PanSN sample are added up. Every sample in the graph is listed, with a
+    /// count of `0` if none of its paths visit the node. A diploid sample
+    /// normally has a copy number of 2 on a core node; higher counts point to
+    /// collapsed repeats or copy number gains, lower ones to deletions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for (sample, copies) in graph.copy_number(42).unwrap() {
+    ///     if copies > 2 {
+    ///         println!("{} has {} copies of node 42", sample, copies);
+    ///     }
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn copy_number(&self, node_id: u64) -> Result<BTreeMap<String, u64>, Error> {
+        let mut counts: BTreeMap<String, u64> = self
+            .get_path_names()?
+            .iter()
+            .map(|name| (pansn::sample_name(name).to_string(), 0))
+            .collect();
+        // Paths are reported once per step, so repeated visits are counted.
+        for path_name in self.get_paths_on_node(node_id)? {
+            *counts.entry(pansn::sample_name(&path_name).to_string()).or_default() += 1;
+        }
+        Ok(counts)
+    }
+}
@@ -0,0 +1,48 @@
+// src/projection.rs
+
+//! Projecting many path coordinates at once.
+
+use super::graph::{Error, Graph, PathPosition};
+
+impl Graph {
+    /// Projects many 0-based linear coordinates on a path to graph coordinates.
+    ///
+    /// The result holds the projection of each position in `positions`, in the
+    /// same order, as [`Graph::project`] would return it, or `None` for
+    /// positions past the end of the path. Positions may be unsorted and repeated.
+    ///
+    /// The positions are projected in one call into the graph and one pass over
+    /// the path, instead of a call and a walk from the start of the path per
+    /// position, which makes projecting millions of positions practical.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of kind [`ErrorKind::PathNotFound`](crate::ErrorKind::PathNotFound)
+    /// if the path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let positions: Vec<u64> = (0..1_000_000).map(|i| i * 100).collect();
+    /// let projected = graph.project_batch("GRCh38#0#chr1", &positions).unwrap();
+    /// let on_path = projected.iter().flatten().count();
+    /// println!("{} of {} positions lie on the path", on_path, positions.len());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, positions), fields(n = positions.len()), err))]
+    pub fn project_batch(&self, path_name: &str, positions: &[u64]) -> Result<Vec<Option<PathPosition>>, Error> {
+        self.check_path(path_name)?;
+        let mut order: Vec<usize> = (0..positions.len()).collect();
+        order.sort_unstable_by_key(|&i| positions[i]);
+        let sorted: Vec<u64> = order.iter().map(|&i| positions[i]).collect();
+        let projected = self.project_sorted(path_name, &sorted)?;
+
+        // Positions past the end of the path are dropped from the end of the sorted projection.
+        let mut result = vec![None; positions.len()];
+        for (i, position) in order.into_iter().zip(projected) {
+            result[i] = Some(position);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,86 @@
+// src/protobuf.rs
+
+//! A minimal decoder for the protobuf wire format.
+//!
+//! Only decoding is needed, and only for a handful of message types, so this
+//! avoids pulling in a code generator and its build-time dependencies.
+
+use super::graph::Error;
+
+/// The value of a single field, by wire type.
+///
+/// None of the decoded messages use fixed-width fields, so their values are
+/// skipped rather than decoded.
+pub(crate) enum FieldValue<'a> {
+    Varint(u64),
+    Fixed64,
+    Bytes(&'a [u8]),
+    Fixed32,
+}
+
+/// Iterates over the fields of one encoded message.
+pub(crate) struct MessageReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> MessageReader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        MessageReader { buf, pos: 0 }
+    }
+
+    fn truncated() -> Error {
+        Error::invalid_format("Truncated protobuf message")
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let (value, len) = decode_varint(&self.buf[self.pos..]).ok_or_else(Self::truncated)?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.buf.len()).ok_or_else(Self::truncated)?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Returns the next field number and value, or `None` at the end of the message.
+    pub(crate) fn next_field(&mut self) -> Result<Option<(u32, FieldValue<'a>)>, Error> {
+        if self.pos >= self.buf.len() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let field = (key >> 3) as u32;
+        let value = match key & 7 {
+            0 => FieldValue::Varint(self.varint()?),
+            1 => {
+                self.take(8)?;
+                FieldValue::Fixed64
+            }
+            2 => {
+                let len = self.varint()? as usize;
+                FieldValue::Bytes(self.take(len)?)
+            }
+            5 => {
+                self.take(4)?;
+                FieldValue::Fixed32
+            }
+            wire_type => return Err(Error::invalid_format(format!("Unsupported protobuf wire type {}", wire_type))),
+        };
+        Ok(Some((field, value)))
+    }
+}
+
+/// Decodes a varint, returning its value and encoded length.
+pub(crate) fn decode_varint(buf: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, &byte) in buf.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
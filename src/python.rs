@@ -0,0 +1,243 @@
+// src/python.rs
+
+//! Python bindings, built as the `odgi_ffi` extension module with pyo3.
+//!
+//! Build and install the module into the active environment with
+//! `maturin develop --release`; `pyproject.toml` enables the `python` feature.
+//! Positions, edges, and steps are returned as tuples rather than classes:
+//!
+//! ```python
+//! import odgi_ffi
+//!
+//! odgi_ffi.gfa_to_odgi("graph.gfa", "graph.og")
+//! graph = odgi_ffi.Graph.load("graph.og")
+//! node_id, offset, is_forward = graph.project("x", 7)
+//! ```
+
+use pyo3::exceptions::{PyIOError, PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use super::graph::{Error, ErrorKind, Graph};
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> Self {
+        let message = error.to_string();
+        match error.kind() {
+            ErrorKind::Io { .. } => PyIOError::new_err(message),
+            ErrorKind::PathNotFound(_) | ErrorKind::NodeNotFound(_) => PyKeyError::new_err(message),
+            ErrorKind::InvalidFormat(_) | ErrorKind::InvalidArgument(_) | ErrorKind::VersionMismatch { .. } => {
+                PyValueError::new_err(message)
+            }
+            _ => PyRuntimeError::new_err(message),
+        }
+    }
+}
+
+/// A pangenome graph loaded from an ODGI file.
+#[pyclass(name = "Graph", module = "odgi_ffi", frozen)]
+struct PyGraph {
+    inner: Graph,
+}
+
+#[pymethods]
+impl PyGraph {
+    /// Loads an ODGI graph from a file, which may be gzip- or zstd-compressed.
+    #[staticmethod]
+    fn load(py: Python<'_>, path: &str) -> PyResult<Self> {
+        let inner = py.allow_threads(|| Graph::load(path))?;
+        Ok(PyGraph { inner })
+    }
+
+    /// Returns the total number of nodes in the graph.
+    fn node_count(&self) -> PyResult<u64> {
+        Ok(self.inner.node_count()?)
+    }
+
+    /// Returns the total number of edges in the graph.
+    fn edge_count(&self) -> PyResult<u64> {
+        Ok(self.inner.edge_count()?)
+    }
+
+    /// Returns the total number of paths in the graph.
+    fn path_count(&self) -> PyResult<u64> {
+        Ok(self.inner.path_count()?)
+    }
+
+    /// Returns the total length of all node sequences in base pairs.
+    fn total_length(&self) -> PyResult<u64> {
+        Ok(self.inner.total_length()?)
+    }
+
+    /// Returns the names of all paths in the graph, in creation order.
+    fn get_path_names(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.get_path_names()?)
+    }
+
+    /// Projects a 0-based position on a path to `(node_id, offset, is_forward)`.
+    ///
+    /// Raises `KeyError` if the path does not exist and `ValueError` if it is
+    /// not longer than `pos`.
+    fn project(&self, path_name: &str, pos: u64) -> PyResult<(u64, u64, bool)> {
+        let position = self.inner.project(path_name, pos)?;
+        Ok((position.node_id, position.offset, position.is_forward))
+    }
+
+    /// Gets the sequence of a node, raising `KeyError` if the node does not exist.
+    fn get_node_sequence(&self, node_id: u64) -> PyResult<String> {
+        Ok(self.inner.get_node_sequence(node_id)?)
+    }
+
+    /// Gets the length of a node's sequence.
+    fn get_node_len(&self, node_id: u64) -> PyResult<u64> {
+        Ok(self.inner.get_node_len(node_id)?)
+    }
+
+    /// Gets the outgoing edges of a node as `(to_node, from_orientation, to_orientation)`.
+    fn get_successors(&self, node_id: u64) -> PyResult<Vec<(u64, bool, bool)>> {
+        Ok(self
+            .inner
+            .get_successors(node_id)?
+            .into_iter()
+            .map(|e| (e.to_node, e.from_orientation, e.to_orientation))
+            .collect())
+    }
+
+    /// Gets the incoming edges of a node as `(from_node, from_orientation, to_orientation)`.
+    fn get_predecessors(&self, node_id: u64) -> PyResult<Vec<(u64, bool, bool)>> {
+        Ok(self
+            .inner
+            .get_predecessors(node_id)?
+            .into_iter()
+            .map(|e| (e.to_node, e.from_orientation, e.to_orientation))
+            .collect())
+    }
+
+    /// Gets the names of the paths stepping on a node, once per visit.
+    fn get_paths_on_node(&self, node_id: u64) -> PyResult<Vec<String>> {
+        Ok(self.inner.get_paths_on_node(node_id)?)
+    }
+
+    /// Returns the number of path steps on a node, counting repeated visits.
+    fn node_depth(&self, node_id: u64) -> PyResult<u64> {
+        Ok(self.inner.node_depth(node_id)?)
+    }
+
+    /// Gets one page of the steps on a node as `(path_name, is_forward)`.
+    #[pyo3(signature = (node_id, offset = 0, limit = u64::MAX))]
+    fn get_steps_on_node(&self, node_id: u64, offset: u64, limit: u64) -> PyResult<Vec<(String, bool)>> {
+        Ok(self
+            .inner
+            .get_steps_on_node(node_id, offset, limit)?
+            .into_iter()
+            .map(|s| (s.path_name, s.is_forward))
+            .collect())
+    }
+
+    /// Gets the length of a path in base pairs, raising `KeyError` if it does not exist.
+    fn get_path_length(&self, path_name: &str) -> PyResult<u64> {
+        Ok(self.inner.get_path_length(path_name)?)
+    }
+
+    /// Gets the node after the first visit of `node_id` on a path.
+    fn get_next_node_on_path(&self, node_id: u64, path_name: &str) -> PyResult<Option<u64>> {
+        Ok(self.inner.get_next_node_on_path(node_id, path_name)?)
+    }
+
+    /// Gets the names of the paths that traverse an oriented edge.
+    fn get_paths_on_edge(
+        &self,
+        from_node: u64,
+        from_orientation: bool,
+        to_node: u64,
+        to_orientation: bool,
+    ) -> PyResult<Vec<String>> {
+        Ok(self
+            .inner
+            .get_paths_on_edge(from_node, from_orientation, to_node, to_orientation)?)
+    }
+
+    /// Gets the offset of a node's first base in the graph's 1D sort order.
+    fn node_sort_offset(&self, node_id: u64) -> PyResult<u64> {
+        Ok(self.inner.node_sort_offset(node_id)?)
+    }
+
+    /// Gets a dict mapping every node ID to its offset in the 1D sort order.
+    fn node_sort_offsets(&self) -> PyResult<std::collections::HashMap<u64, u64>> {
+        Ok(self.inner.node_sort_offsets()?)
+    }
+
+    /// Finds the `(node_id, offset_in_node)` covering an offset in the 1D sort order.
+    fn node_at_sort_offset(&self, offset: u64) -> PyResult<(u64, u64)> {
+        Ok(self.inner.node_at_sort_offset(offset)?)
+    }
+
+    /// Computes the graph's 128-bit fingerprint.
+    fn fingerprint(&self, py: Python<'_>) -> PyResult<u128> {
+        Ok(py.allow_threads(|| self.inner.fingerprint())?)
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "<odgi_ffi.Graph with {} nodes and {} paths>",
+            self.inner.node_count()?,
+            self.inner.path_count()?
+        ))
+    }
+}
+
+/// Converts a GFA file to an ODGI file with the bundled `odgi build`.
+#[pyfunction]
+fn gfa_to_odgi(py: Python<'_>, gfa_path: &str, odgi_path: &str) -> PyResult<()> {
+    py.allow_threads(|| super::gfa_to_odgi(gfa_path, odgi_path))?;
+    Ok(())
+}
+
+/// Converts an ODGI file to a GFA file with the bundled `odgi view`.
+#[pyfunction]
+fn odgi_to_gfa(py: Python<'_>, odgi_path: &str, gfa_path: &str) -> PyResult<()> {
+    py.allow_threads(|| super::odgi_to_gfa(odgi_path, gfa_path))?;
+    Ok(())
+}
+
+/// The `odgi_ffi` Python module.
+#[pymodule]
+fn odgi_ffi(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyGraph>()?;
+    m.add_function(wrap_pyfunction!(gfa_to_odgi, m)?)?;
+    m.add_function(wrap_pyfunction!(odgi_to_gfa, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::odgi_ffi;
+    use pyo3::exceptions::{PyIOError, PyKeyError};
+    use pyo3::prelude::*;
+    use pyo3::wrap_pymodule;
+
+    #[test]
+    fn test_module_loads_and_queries_a_graph() {
+        pyo3::prepare_freethreaded_python();
+        let odgi_file = tempfile::NamedTempFile::new().unwrap();
+        let odgi_path = odgi_file.path().to_str().unwrap();
+
+        Python::with_gil(|py| -> PyResult<()> {
+            let module = wrap_pymodule!(odgi_ffi)(py).into_bound(py);
+            module.getattr("gfa_to_odgi")?.call1(("test_data/queries.gfa", odgi_path))?;
+            let graph = module.getattr("Graph")?.getattr("load")?.call1((odgi_path,))?;
+
+            assert_eq!(graph.call_method0("node_count")?.extract::<u64>()?, 4);
+            let mut names: Vec<String> = graph.call_method0("get_path_names")?.extract()?;
+            names.sort();
+            assert_eq!(names, vec!["x", "y", "z"]);
+            assert_eq!(graph.call_method1("project", ("x", 7))?.extract::<(u64, u64, bool)>()?, (2, 0, true));
+
+            // Library errors surface as the matching Python exception.
+            let error = module.getattr("Graph")?.getattr("load")?.call1(("test_data/missing.og",)).unwrap_err();
+            assert!(error.is_instance_of::<PyIOError>(py), "unexpected error: {}", error);
+            let error = graph.call_method1("get_path_length", ("missing",)).unwrap_err();
+            assert!(error.is_instance_of::<PyKeyError>(py), "unexpected error: {}", error);
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,68 @@
+// src/query_metrics.rs
+
+//! Query-layer metrics, recorded through the [`metrics`](https://docs.rs/metrics) facade.
+//!
+//! Without the `metrics` feature, every recorder here is a no-op that compiles away.
+
+/// Counts public [`Graph`](crate::Graph) queries, labeled by `query`.
+#[cfg(feature = "metrics")]
+const QUERIES: &str = "odgi_ffi_queries_total";
+/// The latency of public queries in seconds, labeled by `query`.
+#[cfg(feature = "metrics")]
+const QUERY_DURATION: &str = "odgi_ffi_query_duration_seconds";
+/// Counts calls into the C++ layer, labeled by `function`.
+#[cfg(feature = "metrics")]
+const FFI_CALLS: &str = "odgi_ffi_ffi_calls_total";
+/// Counts lookups in internal caches that were answered from the cache, labeled by `cache`.
+#[cfg(feature = "metrics")]
+const CACHE_HITS: &str = "odgi_ffi_cache_hits_total";
+/// Counts lookups in internal caches that had to query the graph, labeled by `cache`.
+#[cfg(feature = "metrics")]
+const CACHE_MISSES: &str = "odgi_ffi_cache_misses_total";
+
+/// Records the latency of a public query when dropped.
+pub(crate) struct QueryTimer {
+    #[cfg(feature = "metrics")]
+    query: &'static str,
+    #[cfg(feature = "metrics")]
+    start: std::time::Instant,
+}
+
+/// Counts a public query and its call into the C++ layer, and starts timing it.
+#[cfg(feature = "metrics")]
+pub(crate) fn query(query: &'static str) -> QueryTimer {
+    metrics::counter!(QUERIES, "query" => query).increment(1);
+    ffi_call(query);
+    QueryTimer { query, start: std::time::Instant::now() }
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn query(_query: &'static str) -> QueryTimer {
+    QueryTimer {}
+}
+
+#[cfg(feature = "metrics")]
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        metrics::histogram!(QUERY_DURATION, "query" => self.query).record(self.start.elapsed().as_secs_f64());
+    }
+}
+
+/// Counts a call into the C++ layer made on behalf of a higher-level operation.
+#[cfg(feature = "metrics")]
+pub(crate) fn ffi_call(function: &'static str) {
+    metrics::counter!(FFI_CALLS, "function" => function).increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn ffi_call(_function: &'static str) {}
+
+/// Counts a lookup in an internal cache.
+#[cfg(feature = "metrics")]
+pub(crate) fn cache_lookup(cache: &'static str, hit: bool) {
+    let name = if hit { CACHE_HITS } else { CACHE_MISSES };
+    metrics::counter!(name, "cache" => cache).increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn cache_lookup(_cache: &'static str, _hit: bool) {}
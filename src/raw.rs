@@ -0,0 +1,298 @@
+// src/raw.rs
+
+//! Thin, low-level access to odgi's handle-based API.
+//!
+//! The safe [`Graph`] API speaks in node IDs and path names. Some algorithms
+//! are easier or faster to write against the handles that odgi uses
+//! internally: packed integers that identify a node in one orientation, or a
+//! step of a path. This module exposes them for functionality that the safe API
+//! does not wrap yet.
+//!
+//! Handles are obtained through [`Graph::raw`] and the checked constructors
+//! [`RawGraph::handle`] and [`RawGraph::path`]. Functions that accept a handle
+//! are `unsafe`, because odgi does not validate handles: passing one that does
+//! not belong to the graph reads out of bounds. The handles returned by this
+//! module for a graph are always valid for that graph, since a [`RawGraph`]
+//! borrows it immutably and nothing can be removed while it exists. Like the
+//! rest of the crate, every function returns an exception thrown by odgi as an
+//! [`Error`] of kind [`ErrorKind::Exception`](crate::ErrorKind::Exception).
+//!
+//! Iteration callbacks run in Rust over results collected by a single call into
+//! C++, so stopping early saves the callback work but not the traversal.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use odgi_ffi::Graph;
+//! let graph = Graph::load("my_graph.odgi").unwrap();
+//! let raw = graph.raw();
+//! let handle = raw.handle(1, false).unwrap();
+//! // SAFETY: `handle` and the handles passed to the callback come from `raw`.
+//! unsafe {
+//!     raw.follow_edges(handle, false, |next| {
+//!         let node_id = raw.node_id(next).unwrap();
+//!         println!("1+ -> {}{}", node_id, if next.is_reverse() { '-' } else { '+' });
+//!         true
+//!     })
+//!     .unwrap();
+//! }
+//! ```
+
+use super::ffi;
+use super::graph::{Error, Graph};
+use super::query_metrics;
+
+/// A node in one orientation, packed the way odgi stores it.
+///
+/// The lowest bit holds the orientation and the remaining bits identify the
+/// node; the mapping from node IDs to these numbers is internal to the graph,
+/// so use [`RawGraph::handle`] and [`RawGraph::node_id`] to convert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RawHandle(pub u64);
+
+impl RawHandle {
+    /// Packs a node number and an orientation into a handle.
+    pub fn pack(number: u64, is_reverse: bool) -> Self {
+        RawHandle((number << 1) | u64::from(is_reverse))
+    }
+
+    /// Returns the node number packed into the handle.
+    pub fn number(self) -> u64 {
+        self.0 >> 1
+    }
+
+    /// Returns `true` if the handle is the reverse strand of its node.
+    pub fn is_reverse(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    /// Returns the handle of the same node in the other orientation.
+    pub fn flip(self) -> Self {
+        RawHandle(self.0 ^ 1)
+    }
+}
+
+/// A path, packed the way odgi stores it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RawPath(pub u64);
+
+/// A step of a path, as the two integers that odgi packs it into.
+///
+/// Besides the steps that visit nodes, each path has a past-the-end step,
+/// returned by [`RawGraph::path_end`], which does not visit any node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawStep(pub [i64; 2]);
+
+impl From<ffi::StepHandle> for RawStep {
+    fn from(step: ffi::StepHandle) -> Self {
+        RawStep([step.first, step.second])
+    }
+}
+
+impl From<RawStep> for ffi::StepHandle {
+    fn from(step: RawStep) -> Self {
+        ffi::StepHandle { first: step.0[0], second: step.0[1] }
+    }
+}
+
+/// Low-level access to a graph through its handles. Created by [`Graph::raw`].
+#[derive(Clone, Copy)]
+pub struct RawGraph<'a> {
+    graph: &'a ffi::graph_t,
+}
+
+impl Graph {
+    /// Returns a low-level view of the graph that works with odgi's handles.
+    ///
+    /// See the [`raw`](crate::raw) module for the safety requirements.
+    pub fn raw(&self) -> RawGraph<'_> {
+        RawGraph { graph: self.graph_t() }
+    }
+}
+
+impl RawGraph<'_> {
+    /// Returns the handle of a node in the given orientation.
+    ///
+    /// Fails with [`ErrorKind::NodeNotFound`](crate::ErrorKind::NodeNotFound)
+    /// if the node does not exist.
+    pub fn handle(&self, node_id: u64, is_reverse: bool) -> Result<RawHandle, Error> {
+        query_metrics::ffi_call("raw_get_handle");
+        // odgi does not validate node IDs, so check the ID first.
+        if !ffi::raw_has_node(self.graph, node_id)? {
+            return Err(Error::node_not_found(node_id));
+        }
+        Ok(RawHandle(ffi::raw_get_handle(self.graph, node_id, is_reverse)?))
+    }
+
+    /// Returns the handle of a path.
+    ///
+    /// Fails with [`ErrorKind::PathNotFound`](crate::ErrorKind::PathNotFound)
+    /// if no path has this name.
+    pub fn path(&self, path_name: &str) -> Result<RawPath, Error> {
+        query_metrics::ffi_call("raw_get_path_handle");
+        let path = ffi::raw_get_path_handle(self.graph, path_name)?;
+        u64::try_from(path).map(RawPath).map_err(|_| Error::path_not_found(path_name))
+    }
+
+    /// Calls `f` with both handles of every edge, stopping early if it returns `false`.
+    ///
+    /// Each edge is visited once, in one of its two equivalent orientations.
+    /// Returns `false` if the iteration was stopped early.
+    pub fn for_each_edge(&self, mut f: impl FnMut(RawHandle, RawHandle) -> bool) -> Result<bool, Error> {
+        query_metrics::ffi_call("raw_get_edges");
+        Ok(ffi::raw_get_edges(self.graph)?
+            .chunks_exact(2)
+            .all(|edge| f(RawHandle(edge[0]), RawHandle(edge[1]))))
+    }
+
+    /// Returns the ID of the node of a handle.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been obtained from this graph.
+    pub unsafe fn node_id(&self, handle: RawHandle) -> Result<u64, Error> {
+        query_metrics::ffi_call("raw_get_id");
+        Ok(ffi::raw_get_id(self.graph, handle.0)?)
+    }
+
+    /// Returns the length of the node of a handle.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been obtained from this graph.
+    pub unsafe fn length(&self, handle: RawHandle) -> Result<u64, Error> {
+        query_metrics::ffi_call("raw_get_length");
+        Ok(ffi::raw_get_length(self.graph, handle.0)?)
+    }
+
+    /// Returns the sequence of a handle, reverse-complemented for reverse handles.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been obtained from this graph.
+    pub unsafe fn sequence(&self, handle: RawHandle) -> Result<String, Error> {
+        query_metrics::ffi_call("raw_get_sequence");
+        Ok(ffi::raw_get_sequence(self.graph, handle.0)?)
+    }
+
+    /// Calls `f` with each handle adjacent to the right side of `handle`, or its
+    /// left side if `go_left` is `true`, stopping early if `f` returns `false`.
+    ///
+    /// Returns `false` if the iteration was stopped early.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been obtained from this graph.
+    pub unsafe fn follow_edges(&self, handle: RawHandle, go_left: bool, mut f: impl FnMut(RawHandle) -> bool) -> Result<bool, Error> {
+        query_metrics::ffi_call("raw_follow_edges");
+        Ok(ffi::raw_follow_edges(self.graph, handle.0, go_left)?
+            .into_iter()
+            .all(|next| f(RawHandle(next))))
+    }
+
+    /// Returns the number of edges on the right side of `handle`, or its left side if `go_left` is `true`.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been obtained from this graph.
+    pub unsafe fn degree(&self, handle: RawHandle, go_left: bool) -> Result<u64, Error> {
+        query_metrics::ffi_call("raw_get_degree");
+        Ok(ffi::raw_get_degree(self.graph, handle.0, go_left)?)
+    }
+
+    /// Calls `f` with every step on the node of `handle`, stopping early if it returns `false`.
+    ///
+    /// Returns `false` if the iteration was stopped early.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been obtained from this graph.
+    pub unsafe fn for_each_step_on_handle(&self, handle: RawHandle, mut f: impl FnMut(RawStep) -> bool) -> Result<bool, Error> {
+        query_metrics::ffi_call("raw_get_steps_on_handle");
+        Ok(ffi::raw_get_steps_on_handle(self.graph, handle.0)?
+            .into_iter()
+            .all(|step| f(RawStep::from(step))))
+    }
+
+    /// Returns the first step of a path, which is its [`RawGraph::path_end`] if the path is empty.
+    ///
+    /// # Safety
+    ///
+    /// `path` must have been obtained from this graph.
+    pub unsafe fn path_begin(&self, path: RawPath) -> Result<RawStep, Error> {
+        query_metrics::ffi_call("raw_path_begin");
+        Ok(ffi::raw_path_begin(self.graph, path.0)?.into())
+    }
+
+    /// Returns the past-the-end step of a path.
+    ///
+    /// # Safety
+    ///
+    /// `path` must have been obtained from this graph.
+    pub unsafe fn path_end(&self, path: RawPath) -> Result<RawStep, Error> {
+        query_metrics::ffi_call("raw_path_end");
+        Ok(ffi::raw_path_end(self.graph, path.0)?.into())
+    }
+
+    /// Returns `true` if a step visiting a node follows `step` on its path.
+    ///
+    /// # Safety
+    ///
+    /// `step` must have been obtained from this graph and must visit a node.
+    pub unsafe fn has_next_step(&self, step: RawStep) -> Result<bool, Error> {
+        query_metrics::ffi_call("raw_has_next_step");
+        Ok(ffi::raw_has_next_step(self.graph, step.into())?)
+    }
+
+    /// Returns `true` if a step precedes `step` on its path.
+    ///
+    /// # Safety
+    ///
+    /// `step` must have been obtained from this graph and must visit a node.
+    pub unsafe fn has_previous_step(&self, step: RawStep) -> Result<bool, Error> {
+        query_metrics::ffi_call("raw_has_previous_step");
+        Ok(ffi::raw_has_previous_step(self.graph, step.into())?)
+    }
+
+    /// Returns the step after `step`, which is the path's [`RawGraph::path_end`]
+    /// after its last step.
+    ///
+    /// # Safety
+    ///
+    /// `step` must have been obtained from this graph and must visit a node.
+    pub unsafe fn next_step(&self, step: RawStep) -> Result<RawStep, Error> {
+        query_metrics::ffi_call("raw_get_next_step");
+        Ok(ffi::raw_get_next_step(self.graph, step.into())?.into())
+    }
+
+    /// Returns the step before `step`.
+    ///
+    /// # Safety
+    ///
+    /// `step` must have been obtained from this graph, and
+    /// [`RawGraph::has_previous_step`] must be `true` for it.
+    pub unsafe fn previous_step(&self, step: RawStep) -> Result<RawStep, Error> {
+        query_metrics::ffi_call("raw_get_previous_step");
+        Ok(ffi::raw_get_previous_step(self.graph, step.into())?.into())
+    }
+
+    /// Returns the handle visited by a step, in the orientation of the visit.
+    ///
+    /// # Safety
+    ///
+    /// `step` must have been obtained from this graph and must visit a node.
+    pub unsafe fn handle_of_step(&self, step: RawStep) -> Result<RawHandle, Error> {
+        query_metrics::ffi_call("raw_get_handle_of_step");
+        Ok(RawHandle(ffi::raw_get_handle_of_step(self.graph, step.into())?))
+    }
+
+    /// Returns the path a step belongs to.
+    ///
+    /// # Safety
+    ///
+    /// `step` must have been obtained from this graph.
+    pub unsafe fn path_of_step(&self, step: RawStep) -> Result<RawPath, Error> {
+        query_metrics::ffi_call("raw_get_path_handle_of_step");
+        Ok(RawPath(ffi::raw_get_path_handle_of_step(self.graph, step.into())?))
+    }
+}
@@ -0,0 +1,159 @@
+// src/region.rs
+
+//! Regions of embedded paths, written the way samtools writes them.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use super::graph::{Error, Graph};
+use super::pansn;
+use super::path_index::PathIndex;
+
+/// An interval of an embedded path.
+///
+/// Coordinates are 0-based and half-open. Regions are usually parsed from
+/// samtools-style strings, which are 1-based and inclusive:
+///
+/// | String                      | Region                          |
+/// |-----------------------------|---------------------------------|
+/// | `chr1`                      | the whole path                  |
+/// | `chr1:10,001`               | from offset 10,000 to the end   |
+/// | `chr1:10,001-20,000`        | offsets 10,000 to 20,000        |
+/// | `HG002#1#chr1:10,001-20,000`| the same on a PanSN-named path  |
+///
+/// Thousands separators (`,` or `_`) are ignored. Path names may themselves
+/// contain `:`; a suffix after the last `:` that is not a coordinate range is
+/// taken to be part of the name.
+///
+/// # Examples
+///
+/// ```rust
+/// use odgi_ffi::GraphRegion;
+///
+/// let region: GraphRegion = "HG002#1#chr1:10,001-20,000".parse().unwrap();
+/// assert_eq!(region, GraphRegion::new("HG002#1#chr1", 10_000, 20_000));
+/// assert_eq!(region.sample(), "HG002");
+/// assert_eq!(region.to_string(), "HG002#1#chr1:10001-20000");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphRegion {
+    /// The name of the path.
+    pub path: String,
+    /// The 0-based offset of the first base.
+    pub start: u64,
+    /// The offset past the last base, or `None` to run to the end of the path.
+    pub end: Option<u64>,
+}
+
+impl GraphRegion {
+    /// Creates the region `start..end` of a path.
+    pub fn new(path: &str, start: u64, end: u64) -> Self {
+        GraphRegion { path: path.to_string(), start, end: Some(end) }
+    }
+
+    /// Creates a region covering a whole path.
+    pub fn whole_path(path: &str) -> Self {
+        GraphRegion { path: path.to_string(), start: 0, end: None }
+    }
+
+    /// Returns the sample of the region's path, which is the first field of a PanSN name.
+    pub fn sample(&self) -> &str {
+        pansn::sample_name(&self.path)
+    }
+
+    /// Returns the region's bounds on a path of `length` bp, with the end clamped to the path.
+    pub(crate) fn bounds(&self, length: u64) -> Result<(u64, u64), Error> {
+        let end = self.end.unwrap_or(length).min(length);
+        if self.start >= end {
+            return Err(Error::invalid_argument(format!(
+                "Region {} is empty or starts past the end of path '{}' ({} bp)",
+                self, self.path, length
+            )));
+        }
+        Ok((self.start, end))
+    }
+
+    /// Indexes the region's path and returns the index with the region's bounds on it.
+    pub(crate) fn index(&self, graph: &Graph) -> Result<(Arc<PathIndex>, u64, u64), Error> {
+        let index = PathIndex::new(graph, &self.path)?
+            .ok_or_else(|| Error::path_not_found(&self.path))?;
+        let (start, end) = self.bounds(index.length)?;
+        Ok((index, start, end))
+    }
+}
+
+/// Parses a 1-based coordinate, ignoring thousands separators.
+fn parse_coordinate(text: &str) -> Option<u64> {
+    let digits: String = text.chars().filter(|&c| c != ',' && c != '_').collect();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+impl FromStr for GraphRegion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        let invalid = |reason: &str| Error::invalid_format(format!("Invalid region '{}': {}", s, reason));
+        let parsed = s.rsplit_once(':').and_then(|(path, range)| {
+            let (first, last) = match range.split_once('-') {
+                Some((first, last)) => (parse_coordinate(first)?, Some(parse_coordinate(last)?)),
+                None => (parse_coordinate(range)?, None),
+            };
+            Some((path, first, last))
+        });
+        let region = match parsed {
+            Some((path, first, last)) => {
+                if first == 0 {
+                    return Err(invalid("coordinates are 1-based"));
+                }
+                if last.is_some_and(|last| last < first) {
+                    return Err(invalid("the end is before the start"));
+                }
+                GraphRegion { path: path.to_string(), start: first - 1, end: last }
+            }
+            None => GraphRegion::whole_path(s),
+        };
+        if region.path.is_empty() {
+            return Err(invalid("the path name is empty"));
+        }
+        Ok(region)
+    }
+}
+
+impl fmt::Display for GraphRegion {
+    /// Formats the region as a samtools-style string, which parses back to the same region.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.start, self.end) {
+            (0, None) => write!(f, "{}", self.path),
+            (start, None) => write!(f, "{}:{}", self.path, start + 1),
+            (start, Some(end)) => write!(f, "{}:{}-{}", self.path, start + 1, end),
+        }
+    }
+}
+
+impl Graph {
+    /// Returns the sequence spelled by a path over a region.
+    ///
+    /// The end of the region is clamped to the end of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist or the region is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let sequence = graph.region_sequence(&"GRCh38#0#chr6:31,972,001-31,974,000".parse().unwrap()).unwrap();
+    /// assert_eq!(sequence.len(), 2000);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn region_sequence(&self, region: &GraphRegion) -> Result<String, Error> {
+        let (index, start, end) = region.index(self)?;
+        index.sequence(self, start, end)
+    }
+}
@@ -0,0 +1,122 @@
+// src/remote.rs
+
+//! Loading graphs from HTTP(S) and S3 URLs.
+//!
+//! odgi deserializes graphs from files, so a remote graph is streamed into a
+//! temporary local file first and loaded from there. Compressed graphs are
+//! detected from their contents, as for local files.
+
+use tempfile::NamedTempFile;
+use super::graph::{Error, Graph};
+
+/// Returns the HTTP(S) URL to fetch `url` from.
+///
+/// With the `s3` feature, `s3://bucket/key` URLs are fetched anonymously from
+/// the bucket's virtual-hosted endpoint, or from `$AWS_ENDPOINT_URL/bucket/key`
+/// if that variable is set, as for S3-compatible stores such as MinIO.
+fn http_url(url: &str) -> Result<String, Error> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return Ok(url.to_string());
+    }
+    #[cfg(feature = "s3")]
+    if let Some(location) = url.strip_prefix("s3://") {
+        return s3_http_url(location, std::env::var("AWS_ENDPOINT_URL").ok().as_deref())
+            .ok_or_else(|| Error::invalid_argument(format!("Invalid S3 URL '{}': expected s3://bucket/key", url)));
+    }
+    Err(Error::unsupported(format!("Unsupported URL scheme in '{}'", url)))
+}
+
+/// Returns the HTTP(S) URL of the S3 object at `location`, given as `bucket/key`.
+///
+/// Returns `None` if the bucket or the key is empty.
+#[cfg(feature = "s3")]
+fn s3_http_url(location: &str, endpoint: Option<&str>) -> Option<String> {
+    let (bucket, key) = location.split_once('/').filter(|(bucket, key)| !bucket.is_empty() && !key.is_empty())?;
+    Some(match endpoint {
+        Some(endpoint) => format!("{}/{}/{}", endpoint.trim_end_matches('/'), bucket, key),
+        None => format!("https://{}.s3.amazonaws.com/{}", bucket, key),
+    })
+}
+
+/// Streams the body of `url` into a new temporary file.
+fn download(url: &str) -> Result<NamedTempFile, Error> {
+    let response = ureq::get(&http_url(url)?)
+        .call()
+        .map_err(|e| Error::io(format!("Failed to fetch '{}'", url), std::io::Error::other(e)))?;
+    let mut file = NamedTempFile::new().map_err(|e| Error::io("Failed to create temporary file", e))?;
+    std::io::copy(&mut response.into_reader(), &mut file)
+        .map_err(|e| Error::io(format!("Failed to download '{}'", url), e))?;
+    Ok(file)
+}
+
+impl Graph {
+    /// Loads an ODGI graph from an HTTP(S) URL, or from S3 with the `s3` feature.
+    ///
+    /// The file is streamed into a temporary file, which is removed once the
+    /// graph has been loaded, so services running in the cloud do not need a
+    /// separate download step. gzip- and zstd-compressed graphs are
+    /// decompressed as by [`Graph::load`].
+    ///
+    /// `s3://bucket/key` URLs are fetched without request signing, so the
+    /// object must be publicly readable; use a presigned `https://` URL for
+    /// private objects. Set `AWS_ENDPOINT_URL` to fetch from an S3-compatible
+    /// store instead of AWS.
+    ///
+    /// Requires the `http` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the URL scheme is not supported, the request
+    /// fails or returns an error status, or the downloaded file cannot be
+    /// loaded as by [`Graph::load`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let graph = Graph::load_url("https://example.org/graphs/chr20.og.zst").unwrap();
+    /// println!("{} nodes", graph.node_count().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+    pub fn load_url(url: &str) -> Result<Self, Error> {
+        let file = download(url)?;
+        let path = file
+            .path()
+            .to_str()
+            .ok_or_else(|| Error::other(format!("Temporary file for '{}' has a non-UTF-8 path", url)))?;
+        Graph::load(path).map_err(|e| e.context(format!("Failed to load graph downloaded from '{}'", url)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::http_url;
+
+    #[test]
+    fn test_http_url_passes_http_through() {
+        let url = "https://example.org/graphs/chr20.og.zst";
+        assert_eq!(http_url(url).unwrap(), url);
+        assert!(http_url("ftp://example.org/chr20.og").is_err());
+    }
+
+    #[cfg(feature = "s3")]
+    #[test]
+    fn test_http_url_resolves_s3() {
+        use super::s3_http_url;
+
+        assert_eq!(
+            s3_http_url("bucket/graphs/chr20.og", None).as_deref(),
+            Some("https://bucket.s3.amazonaws.com/graphs/chr20.og")
+        );
+        // A trailing slash on the endpoint does not double up in the URL.
+        assert_eq!(
+            s3_http_url("bucket/chr20.og", Some("http://localhost:9000/")).as_deref(),
+            Some("http://localhost:9000/bucket/chr20.og")
+        );
+        assert_eq!(s3_http_url("bucket/", None), None);
+        assert_eq!(s3_http_url("/chr20.og", None), None);
+        assert_eq!(s3_http_url("bucket", None), None);
+        assert!(http_url("s3:///chr20.og").is_err());
+    }
+}
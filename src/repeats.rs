@@ -0,0 +1,72 @@
+// src/repeats.rs
+
+//! Nodes that a path visits more than once.
+
+use std::collections::BTreeMap;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+
+/// A node visited more than once by a path, returned by [`Graph::path_repeats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathRepeat {
+    /// The ID of the repeated node.
+    pub node_id: u64,
+    /// The length of the node.
+    pub node_len: u64,
+    /// Every visit, as the path offset of the step and whether the node is traversed forward.
+    pub visits: Vec<(u64, bool)>,
+}
+
+impl PathRepeat {
+    /// Returns the number of times the path visits the node.
+    pub fn visit_count(&self) -> usize {
+        self.visits.len()
+    }
+
+    /// Returns whether some visits traverse the node in opposite orientations,
+    /// as in inverted duplications or hairpins.
+    pub fn is_inverted(&self) -> bool {
+        self.visits.iter().any(|&(_, is_forward)| is_forward != self.visits[0].1)
+    }
+}
+
+impl Graph {
+    /// Lists the nodes that a path visits more than once.
+    ///
+    /// Haplotypes normally traverse each node at most once, so repeated visits
+    /// point to tandem duplications and copy number variation collapsed onto
+    /// the same nodes, or to haplotypes threaded through the graph incorrectly.
+    /// Closely spaced visits of a run of nodes suggest a tandem repeat, and
+    /// visits in both orientations an inverted duplication.
+    ///
+    /// The repeats are sorted by node ID, and their visits by path offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for repeat in graph.path_repeats("HG002#1#chr1").unwrap() {
+    ///     println!("Node {} is visited {} times", repeat.node_id, repeat.visit_count());
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn path_repeats(&self, path_name: &str) -> Result<Vec<PathRepeat>, Error> {
+        let index = PathIndex::new(self, path_name)?.ok_or_else(|| Error::path_not_found(path_name))?;
+
+        let mut visits: BTreeMap<u64, Vec<(u64, bool)>> = BTreeMap::new();
+        for (step, &start) in index.steps.iter().zip(&index.starts) {
+            visits.entry(step.node_id).or_default().push((start, step.is_forward));
+        }
+
+        visits
+            .into_iter()
+            .filter(|(_, visits)| visits.len() > 1)
+            .map(|(node_id, visits)| Ok(PathRepeat { node_id, node_len: self.get_node_len(node_id)?, visits }))
+            .collect()
+    }
+}
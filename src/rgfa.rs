@@ -0,0 +1,366 @@
+// src/rgfa.rs
+
+//! Support for the reference GFA (rGFA) dialect used by minigraph.
+//!
+//! In rGFA every segment is tied to a "stable sequence" through three tags:
+//!
+//! - `SN:Z` – the name of the stable sequence the segment was taken from.
+//! - `SO:i` – the 0-based offset of the segment on that stable sequence.
+//! - `SR:i` – the rank of the stable sequence (`0` for the reference).
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use super::edge_key::EdgeKey;
+use super::graph::{Error, Graph};
+use super::seq::reverse_complement;
+
+/// The stable sequence tags of a segment read from an rGFA file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgfaSegment {
+    /// The ID of the node created for this segment.
+    pub node_id: u64,
+    /// The name of the segment in the rGFA file.
+    pub segment_name: String,
+    /// The name of the stable sequence the segment belongs to (`SN` tag).
+    pub stable_name: String,
+    /// The 0-based offset of the segment on its stable sequence (`SO` tag).
+    pub stable_offset: u64,
+    /// The rank of the stable sequence (`SR` tag).
+    pub rank: u64,
+}
+
+/// A segment line as read from the file, before node IDs are assigned.
+struct SegmentRecord {
+    name: String,
+    sequence: String,
+    stable_name: Option<String>,
+    stable_offset: Option<u64>,
+    rank: Option<u64>,
+}
+
+/// Where a node sits on the stable sequence it has been assigned to.
+struct Placement {
+    /// Index of the stable sequence, which doubles as its rank.
+    rank: usize,
+    /// Offset of the node's first base on the stable sequence.
+    offset: u64,
+    /// Whether the stable sequence traverses the node in forward orientation.
+    is_forward: bool,
+}
+
+impl Graph {
+    /// Writes the graph in rGFA format, using `reference_path` as the rank-0 stable sequence.
+    ///
+    /// Every node is assigned to the first path that visits it, with the reference
+    /// path taking precedence and all other paths following in the order returned
+    /// by [`Graph::get_path_names`]. Segments are emitted on the forward strand of
+    /// their stable sequence, so nodes that their path visits in reverse have their
+    /// sequence reverse-complemented and their link orientations flipped. Links carry
+    /// the rank of the first stable sequence that traverses them.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The destination for the rGFA text.
+    /// * `reference_path` - The name of the path to use as the reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the reference path does not exist, if a node is not
+    /// visited by any path (rGFA requires a stable coordinate for every segment),
+    /// or if writing to `writer` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// let mut out = std::fs::File::create("my_graph.rgfa").unwrap();
+    /// graph.write_rgfa(&mut out, "chm13#chr1").expect("Failed to write rGFA");
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, writer), err))]
+    pub fn write_rgfa<W: Write>(&self, writer: &mut W, reference_path: &str) -> Result<(), Error> {
+        let mut stable_names = self.get_path_names()?;
+        if !stable_names.iter().any(|p| p == reference_path) {
+            return Err(Error::path_not_found(reference_path));
+        }
+        stable_names.retain(|p| p != reference_path);
+        stable_names.insert(0, reference_path.to_string());
+
+        let mut placements: HashMap<u64, Placement> = HashMap::new();
+        let mut edge_ranks: HashMap<EdgeKey, usize> = HashMap::new();
+        for (rank, name) in stable_names.iter().enumerate() {
+            let steps = self.raw_path_steps(name)?;
+            let mut offset = 0;
+            for step in &steps {
+                placements.entry(step.node_id).or_insert(Placement {
+                    rank,
+                    offset,
+                    is_forward: step.is_forward,
+                });
+                offset += self.get_node_len(step.node_id)?;
+            }
+            for pair in steps.windows(2) {
+                let key = EdgeKey::between(&pair[0], &pair[1]).canonicalized();
+                edge_ranks.entry(key).or_insert(rank);
+            }
+        }
+
+        let io_err = |e: std::io::Error| Error::io("Failed to write rGFA output", e);
+
+        writeln!(writer, "H\tVN:Z:1.0").map_err(io_err)?;
+        for node_id in self.node_ids()? {
+            let placement = placements.get(&node_id).ok_or_else(|| {
+                Error::invalid_argument(format!("Node {} is not covered by any path and has no stable coordinate", node_id))
+            })?;
+            let mut sequence = self.get_node_sequence(node_id)?;
+            if !placement.is_forward {
+                sequence = reverse_complement(&sequence);
+            }
+            writeln!(
+                writer,
+                "S\t{}\t{}\tLN:i:{}\tSN:Z:{}\tSO:i:{}\tSR:i:{}",
+                node_id,
+                sequence,
+                sequence.len(),
+                stable_names[placement.rank],
+                placement.offset,
+                placement.rank
+            )
+            .map_err(io_err)?;
+        }
+
+        for edge in self.raw_edges()? {
+            let from = &placements[&edge.from_node];
+            let to = &placements[&edge.to_node];
+            let key = EdgeKey::from(&edge).canonicalized();
+            let rank = edge_ranks
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| from.rank.max(to.rank));
+            writeln!(
+                writer,
+                "L\t{}\t{}\t{}\t{}\t0M\tSR:i:{}",
+                edge.from_node,
+                orientation_symbol(edge.from_orientation == from.is_forward),
+                edge.to_node,
+                orientation_symbol(edge.to_orientation == to.is_forward),
+                rank
+            )
+            .map_err(io_err)?;
+        }
+        Ok(())
+    }
+
+    /// Loads an rGFA file into memory, turning its stable sequences into paths.
+    ///
+    /// Segments are given node IDs equal to their names when every name is a
+    /// positive integer, and sequential IDs in file order otherwise. Links and
+    /// any `P` lines are imported as-is. A `P` line may repeat the name of a
+    /// stable sequence path only if it walks the same segments forward.
+    ///
+    /// The stable sequence tags are preserved in two ways. First, every stable
+    /// sequence is embedded as a path: its segments are ordered by `SO` and each
+    /// contiguous run becomes a path. A stable sequence covered by a single run
+    /// starting at offset 0 keeps its `SN` name; otherwise each run is named
+    /// `SN:start-end`. Second, the tags of every segment are returned alongside
+    /// the graph, together with the node ID of each segment, so they can be used
+    /// as annotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A string slice that holds the path to the rGFA file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file cannot be read, if a line is malformed,
+    /// if a link or path refers to an unknown segment, if two `P` lines
+    /// share a name, or if a `P` line shares the name of a stable sequence path
+    /// but not its steps.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let (graph, segments) = Graph::load_rgfa("minigraph.rgfa").unwrap();
+    /// for segment in &segments {
+    ///     println!("{} -> node {} at {}:{}",
+    ///              segment.segment_name, segment.node_id,
+    ///              segment.stable_name, segment.stable_offset);
+    /// }
+    /// println!("Stable sequences: {:?}", graph.get_path_names().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+    pub fn load_rgfa(path: &str) -> Result<(Graph, Vec<RgfaSegment>), Error> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::io(format!("Failed to read rGFA file '{}'", path), e))?;
+        let invalid = |line_no: usize, reason: &str| {
+            Error::invalid_format(format!("Invalid rGFA line {} in '{}': {}", line_no + 1, path, reason))
+        };
+
+        let mut segments: Vec<SegmentRecord> = Vec::new();
+        let mut links: Vec<(usize, &str, &str, &str, &str)> = Vec::new();
+        let mut paths: Vec<(usize, &str, &str)> = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields[0] {
+                "S" => {
+                    if fields.len() < 3 {
+                        return Err(invalid(line_no, "segment lines need a name and a sequence"));
+                    }
+                    let mut record = SegmentRecord {
+                        name: fields[1].to_string(),
+                        sequence: fields[2].to_string(),
+                        stable_name: None,
+                        stable_offset: None,
+                        rank: None,
+                    };
+                    for tag in &fields[3..] {
+                        let value = |prefix: &str| tag.strip_prefix(prefix);
+                        if let Some(name) = value("SN:Z:") {
+                            record.stable_name = Some(name.to_string());
+                        } else if let Some(offset) = value("SO:i:") {
+                            record.stable_offset = Some(offset.parse().map_err(|_| invalid(line_no, "bad SO tag"))?);
+                        } else if let Some(rank) = value("SR:i:") {
+                            record.rank = Some(rank.parse().map_err(|_| invalid(line_no, "bad SR tag"))?);
+                        }
+                    }
+                    segments.push(record);
+                }
+                "L" => {
+                    if fields.len() < 5 {
+                        return Err(invalid(line_no, "link lines need two oriented segments"));
+                    }
+                    links.push((line_no, fields[1], fields[2], fields[3], fields[4]));
+                }
+                "P" => {
+                    if fields.len() < 3 {
+                        return Err(invalid(line_no, "path lines need a name and steps"));
+                    }
+                    paths.push((line_no, fields[1], fields[2]));
+                }
+                _ => {}
+            }
+        }
+
+        // Keep numeric segment names as node IDs whenever that is possible.
+        let numeric: Option<Vec<u64>> = segments
+            .iter()
+            .map(|s| s.name.parse::<u64>().ok().filter(|&id| id > 0))
+            .collect();
+        let ids: Vec<u64> = match numeric {
+            Some(ids) if ids.iter().collect::<HashSet<_>>().len() == ids.len() => ids,
+            _ => (1..=segments.len() as u64).collect(),
+        };
+        let id_of: HashMap<&str, u64> = segments
+            .iter()
+            .zip(&ids)
+            .map(|(s, &id)| (s.name.as_str(), id))
+            .collect();
+
+        let mut graph = Graph::empty()?;
+        for (segment, &id) in segments.iter().zip(&ids) {
+            if !graph.add_node(id, &segment.sequence)? {
+                return Err(Error::invalid_format(format!("Duplicate segment '{}' in '{}'", segment.name, path)));
+            }
+        }
+
+        let oriented = |line_no: usize, name: &str, orientation: &str| -> Result<(u64, bool), Error> {
+            let id = id_of
+                .get(name)
+                .copied()
+                .ok_or_else(|| invalid(line_no, &format!("unknown segment '{}'", name)))?;
+            match orientation {
+                "+" => Ok((id, true)),
+                "-" => Ok((id, false)),
+                _ => Err(invalid(line_no, &format!("bad orientation '{}'", orientation))),
+            }
+        };
+        for (line_no, from, from_orient, to, to_orient) in links {
+            let (from_id, from_forward) = oriented(line_no, from, from_orient)?;
+            let (to_id, to_forward) = oriented(line_no, to, to_orient)?;
+            graph.add_edge(from_id, from_forward, to_id, to_forward)?;
+        }
+        let mut explicit: HashMap<&str, usize> = HashMap::new();
+        for (line_no, name, steps) in paths {
+            if !graph.add_path(name)? {
+                return Err(invalid(line_no, &format!("duplicate path '{}'", name)));
+            }
+            let steps = steps
+                .split(',')
+                .map(|step| {
+                    let orientation = if step.ends_with('+') || step.ends_with('-') { &step[step.len() - 1..] } else { "" };
+                    oriented(line_no, &step[..step.len() - orientation.len()], orientation)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            graph.extend_path(name, steps)?;
+            explicit.insert(name, line_no);
+        }
+
+        // Embed every stable sequence as one path per contiguous run of segments.
+        let mut stable: BTreeMap<&str, Vec<(u64, u64, u64)>> = BTreeMap::new();
+        for (segment, &id) in segments.iter().zip(&ids) {
+            if let (Some(name), Some(offset)) = (&segment.stable_name, segment.stable_offset) {
+                stable
+                    .entry(name.as_str())
+                    .or_default()
+                    .push((offset, segment.sequence.len() as u64, id));
+            }
+        }
+        for (stable_name, mut placed) in stable {
+            placed.sort();
+            let mut runs: Vec<Vec<(u64, u64, u64)>> = Vec::new();
+            for entry in placed {
+                match runs.last_mut() {
+                    Some(run) if run.last().map(|&(o, l, _)| o + l) == Some(entry.0) => run.push(entry),
+                    _ => runs.push(vec![entry]),
+                }
+            }
+            let whole = runs.len() == 1 && runs[0][0].0 == 0;
+            for run in runs {
+                let start = run[0].0;
+                let end = run.last().map(|&(o, l, _)| o + l).unwrap_or(start);
+                let path_name = if whole {
+                    stable_name.to_string()
+                } else {
+                    format!("{}:{}-{}", stable_name, start, end)
+                };
+                let steps: Vec<(u64, bool)> = run.into_iter().map(|(_, _, id)| (id, true)).collect();
+                // A path given explicitly in the file must walk the stable sequence it is named after.
+                if let Some(&line_no) = explicit.get(path_name.as_str()) {
+                    let explicit_steps = graph.raw_path_steps(&path_name)?;
+                    if !explicit_steps.iter().map(|step| (step.node_id, step.is_forward)).eq(steps) {
+                        return Err(invalid(
+                            line_no,
+                            &format!("path '{}' does not follow the stable sequence of the same name", path_name),
+                        ));
+                    }
+                } else {
+                    graph.add_path(&path_name)?;
+                    graph.extend_path(&path_name, steps)?;
+                }
+            }
+        }
+
+        let tags = segments
+            .into_iter()
+            .zip(ids)
+            .filter_map(|(segment, node_id)| {
+                Some(RgfaSegment {
+                    node_id,
+                    stable_name: segment.stable_name?,
+                    stable_offset: segment.stable_offset?,
+                    rank: segment.rank.unwrap_or(0),
+                    segment_name: segment.name,
+                })
+            })
+            .collect();
+
+        Ok((graph, tags))
+    }
+}
+
+/// Returns the GFA orientation symbol for a strand.
+pub(crate) fn orientation_symbol(is_forward: bool) -> char {
+    if is_forward { '+' } else { '-' }
+}
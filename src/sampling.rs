@@ -0,0 +1,122 @@
+// src/sampling.rs
+
+//! Reproducible random samples of nodes, paths, and path positions.
+//!
+//! Samples are drawn as ranks with a seeded SplitMix64 generator, which
+//! produces the same sequence on every platform, and only the sampled ranks
+//! are resolved to node IDs, path names, or graph positions.
+
+use std::collections::HashSet;
+use super::graph::{Error, Graph, PathPosition};
+
+/// The SplitMix64 pseudo-random number generator.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+/// Draws `k` distinct numbers from `0..n` with Floyd's algorithm, in ascending order.
+fn distinct_ranks(n: u64, k: usize, seed: u64) -> Vec<u64> {
+    let k = (k as u64).min(n);
+    let mut rng = SplitMix64(seed);
+    let mut chosen = HashSet::with_capacity(k as usize);
+    for j in n - k..n {
+        let t = rng.below(j + 1);
+        if !chosen.insert(t) {
+            chosen.insert(j);
+        }
+    }
+    let mut ranks: Vec<u64> = chosen.into_iter().collect();
+    ranks.sort_unstable();
+    ranks
+}
+
+impl Graph {
+    /// Draws a uniform random sample of `n` distinct node IDs.
+    ///
+    /// The same `seed` always yields the same sample for the same graph. If
+    /// the graph has at most `n` nodes, all of them are returned. The IDs are
+    /// returned in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for node_id in graph.sample_nodes(100, 42).unwrap() {
+    ///     assert!(graph.get_node_len(node_id).unwrap() > 0);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn sample_nodes(&self, n: usize, seed: u64) -> Result<Vec<u64>, Error> {
+        self.node_ids_at_ranks(&distinct_ranks(self.node_count()?, n, seed))
+    }
+
+    /// Draws a uniform random sample of `n` distinct path names.
+    ///
+    /// The same `seed` always yields the same sample for the same graph. If
+    /// the graph has at most `n` paths, all of them are returned. The names are
+    /// returned in the order of [`Graph::get_path_names`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi throws while reading the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let haplotypes = graph.sample_paths(10, 7).unwrap();
+    /// println!("Benchmarking on {:?}", haplotypes);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn sample_paths(&self, n: usize, seed: u64) -> Result<Vec<String>, Error> {
+        self.path_names_at_ranks(&distinct_ranks(self.path_count()?, n, seed))
+    }
+
+    /// Draws a uniform random sample of `n` distinct positions along a path.
+    ///
+    /// Each sampled path offset is returned with its projection, as
+    /// [`Graph::project`] would report it, in ascending order of offset. All
+    /// positions are projected in a single pass over the path. The same `seed`
+    /// always yields the same sample for the same graph. If the path is at most
+    /// `n` bp long, every position is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for (offset, position) in graph.sample_positions("GRCh38#0#chr1", 1000, 1).unwrap() {
+    ///     println!("{}\t{}\t{}", offset, position.node_id, position.offset);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn sample_positions(&self, path_name: &str, n: usize, seed: u64) -> Result<Vec<(u64, PathPosition)>, Error> {
+        let length = self.get_path_length(path_name)?;
+        let offsets = distinct_ranks(length, n, seed);
+        let positions = self.project_sorted(path_name, &offsets)?;
+        Ok(offsets.into_iter().zip(positions).collect())
+    }
+}
@@ -0,0 +1,104 @@
+// src/seq.rs
+
+//! Helpers for working with DNA sequences that may contain IUPAC ambiguity codes.
+//!
+//! Older assemblies and consensus sequences use codes such as `R` (A or G) or
+//! `N` (any base). Comparing them byte by byte reports spurious mismatches, so
+//! the crate's alignment features compare bases with [`bases_match`], which
+//! treats two symbols as matching if they can stand for a common base. The
+//! same functions are available here for callers that post-process sequences.
+//!
+//! # Examples
+//!
+//! ```
+//! use odgi_ffi::seq;
+//!
+//! assert!(seq::sequences_match("ACGT", "RCNt"));
+//! assert_eq!(seq::reverse_complement("AcRN"), "NYgT");
+//! assert_eq!(seq::normalize("acgu-x"), "ACGTNN");
+//! ```
+
+/// Returns the set of bases a symbol can stand for, as a bit mask (A=1, C=2, G=4, T=8).
+///
+/// `U` is read as `T`. Anything that is not an IUPAC nucleotide code has an empty set.
+fn base_set(symbol: u8) -> u8 {
+    match symbol.to_ascii_uppercase() {
+        b'A' => 0b0001,
+        b'C' => 0b0010,
+        b'G' => 0b0100,
+        b'T' | b'U' => 0b1000,
+        b'R' => 0b0101,
+        b'Y' => 0b1010,
+        b'S' => 0b0110,
+        b'W' => 0b1001,
+        b'K' => 0b1100,
+        b'M' => 0b0011,
+        b'B' => 0b1110,
+        b'D' => 0b1101,
+        b'H' => 0b1011,
+        b'V' => 0b0111,
+        b'N' => 0b1111,
+        _ => 0,
+    }
+}
+
+/// Returns whether two bases match, honouring IUPAC ambiguity codes.
+///
+/// Two symbols match if they can stand for a common base, so `R` matches `A`
+/// and `G`, and `N` matches every base. Case is ignored. Symbols that are not
+/// nucleotide codes only match themselves.
+pub fn bases_match(a: u8, b: u8) -> bool {
+    base_set(a) & base_set(b) != 0 || a.eq_ignore_ascii_case(&b)
+}
+
+/// Returns whether two sequences have the same length and match base by base.
+///
+/// See [`bases_match`] for how ambiguity codes are compared.
+pub fn sequences_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).all(|(x, y)| bases_match(x, y))
+}
+
+/// Returns the complement of a base, including IUPAC ambiguity codes.
+///
+/// Case is preserved, and symbols without a complement are returned unchanged.
+pub fn complement(base: char) -> char {
+    let complemented = match base.to_ascii_uppercase() {
+        'A' => 'T',
+        'C' => 'G',
+        'G' => 'C',
+        'T' | 'U' => 'A',
+        'R' => 'Y',
+        'Y' => 'R',
+        'K' => 'M',
+        'M' => 'K',
+        'B' => 'V',
+        'V' => 'B',
+        'D' => 'H',
+        'H' => 'D',
+        _ => return base,
+    };
+    if base.is_ascii_lowercase() { complemented.to_ascii_lowercase() } else { complemented }
+}
+
+/// Reverse-complements a DNA sequence, preserving case and passing through unknown symbols.
+///
+/// Ambiguity codes are complemented as well, so `R` becomes `Y`; `S`, `W`, and
+/// `N` are their own complements.
+pub fn reverse_complement(sequence: &str) -> String {
+    sequence.chars().rev().map(complement).collect()
+}
+
+/// Normalizes a sequence for comparison or export.
+///
+/// Bases are upper-cased, which drops soft-masking, `U` becomes `T`, and every
+/// symbol that is not an IUPAC nucleotide code becomes `N`.
+pub fn normalize(sequence: &str) -> String {
+    sequence
+        .bytes()
+        .map(|b| match b.to_ascii_uppercase() {
+            b'U' => 'T',
+            upper if base_set(upper) != 0 => upper as char,
+            _ => 'N',
+        })
+        .collect()
+}
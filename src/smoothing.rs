@@ -0,0 +1,271 @@
+// src/smoothing.rs
+
+//! Collapsing small bubbles into a single allele.
+
+use std::collections::{HashMap, HashSet};
+use super::graph::{Error, Graph};
+
+/// How [`Graph::simplify_small_bubbles`] chooses the allele to keep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BubbleStrategy {
+    /// Keep the allele traversed by the most path steps. Ties go to the allele
+    /// with the lowest node ID, then to the deletion.
+    MostTraversed,
+    /// Keep the shortest allele, preferring a deletion if there is one.
+    Shortest,
+    /// Keep the allele taken by the named path, and fall back to
+    /// [`BubbleStrategy::MostTraversed`] for bubbles that it does not cross.
+    FollowPath(String),
+}
+
+/// A bubble collapsed by [`Graph::simplify_small_bubbles`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BubbleEdit {
+    /// The node the bubble opens from, as `(node_id, is_forward)`.
+    pub source: (u64, bool),
+    /// The node the bubble closes into, as `(node_id, is_forward)`.
+    pub sink: (u64, bool),
+    /// The allele node that was kept, or `None` if the deletion was kept.
+    pub kept: Option<u64>,
+    /// The allele nodes that were removed, in ascending order.
+    pub removed: Vec<u64>,
+    /// The number of path traversals of the bubble that were rewritten.
+    pub rewritten_traversals: u64,
+}
+
+/// An edge between two oriented nodes, as `((node_id, is_forward), (node_id, is_forward))`.
+type OrientedEdge = ((u64, bool), (u64, bool));
+
+/// A simple bubble: every allele is a single node or the direct edge from source to sink.
+pub(crate) struct Bubble {
+    pub(crate) source: (u64, bool),
+    pub(crate) sink: (u64, bool),
+    /// Allele nodes in their orientation from source to sink.
+    pub(crate) alleles: Vec<(u64, bool)>,
+    pub(crate) has_deletion: bool,
+}
+
+impl Graph {
+    /// Returns a copy of the graph with small bubbles collapsed to a single allele.
+    ///
+    /// A small bubble is a site where every path from a source node to a sink
+    /// node either passes through exactly one allele node of at most
+    /// `max_allele_len` bp, or follows a direct edge from source to sink (a
+    /// deletion), and allele nodes have no other edges. This covers SNPs and
+    /// short indels. Larger or nested variation is left untouched.
+    ///
+    /// For each bubble, the allele chosen by `strategy` is kept and the others
+    /// are removed together with their edges. Paths through removed alleles are
+    /// rerouted through the kept one, so every path still runs from its first to
+    /// its last node, while its sequence may change. Node IDs are preserved. The
+    /// result serves as a simplified backbone for visualization and long-range
+    /// analyses; every change is listed in the returned [`BubbleEdit`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi fails to build the simplified graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{BubbleStrategy, Graph};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let (backbone, edits) = graph.simplify_small_bubbles(10, BubbleStrategy::MostTraversed).unwrap();
+    /// println!("Collapsed {} bubbles into {} nodes", edits.len(), backbone.node_count().unwrap());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn simplify_small_bubbles(
+        &self,
+        max_allele_len: u64,
+        strategy: BubbleStrategy,
+    ) -> Result<(Graph, Vec<BubbleEdit>), Error> {
+        let bubbles = self.small_bubbles(max_allele_len)?;
+        let paths = self
+            .get_path_names()?
+            .into_iter()
+            .map(|name| {
+                let steps = self.raw_path_steps(&name)?.iter().map(|s| (s.node_id, s.is_forward)).collect();
+                Ok((name, steps))
+            })
+            .collect::<Result<Vec<(String, Vec<(u64, bool)>)>, Error>>()?;
+
+        // Index the bubbles by allele node and by deletion edge, in both directions.
+        let mut by_allele: HashMap<u64, usize> = HashMap::new();
+        let mut by_deletion: HashMap<OrientedEdge, usize> = HashMap::new();
+        for (b, bubble) in bubbles.iter().enumerate() {
+            for &(node_id, _) in &bubble.alleles {
+                by_allele.insert(node_id, b);
+            }
+            if bubble.has_deletion {
+                by_deletion.insert((bubble.source, bubble.sink), b);
+                by_deletion.insert((flip(bubble.sink), flip(bubble.source)), b);
+            }
+        }
+
+        // Count the traversals of each allele, with `None` for the deletion.
+        let mut counts: Vec<HashMap<Option<u64>, u64>> = vec![HashMap::new(); bubbles.len()];
+        let mut followed: Vec<Option<Option<u64>>> = vec![None; bubbles.len()];
+        for (name, steps) in &paths {
+            let is_followed = matches!(&strategy, BubbleStrategy::FollowPath(p) if p == name);
+            for (i, &step) in steps.iter().enumerate() {
+                let traversal = match by_allele.get(&step.0) {
+                    Some(&b) => Some((b, Some(step.0))),
+                    None => {
+                        let deletion = steps.get(i + 1).and_then(|&next| by_deletion.get(&(step, next)));
+                        deletion.map(|&b| (b, None))
+                    }
+                };
+                if let Some((b, allele)) = traversal {
+                    *counts[b].entry(allele).or_default() += 1;
+                    if is_followed && followed[b].is_none() {
+                        followed[b] = Some(allele);
+                    }
+                }
+            }
+        }
+
+        let mut edits = Vec::with_capacity(bubbles.len());
+        let mut kept_allele: Vec<Option<(u64, bool)>> = Vec::with_capacity(bubbles.len());
+        let mut removed: HashSet<u64> = HashSet::new();
+        for (b, bubble) in bubbles.iter().enumerate() {
+            let mut candidates: Vec<Option<u64>> = bubble.alleles.iter().map(|&(id, _)| Some(id)).collect();
+            candidates.sort();
+            if bubble.has_deletion {
+                candidates.push(None);
+            }
+            let count = |allele: &Option<u64>| counts[b].get(allele).copied().unwrap_or(0);
+            let most_traversed = *candidates
+                .iter()
+                .rev()
+                .max_by_key(|allele| count(allele))
+                .expect("bubbles have at least two alleles");
+            let kept = match &strategy {
+                BubbleStrategy::Shortest if bubble.has_deletion => None,
+                BubbleStrategy::Shortest => {
+                    let mut shortest: Option<(u64, u64)> = None;
+                    for &id in candidates.iter().flatten() {
+                        let len = self.get_node_len(id)?;
+                        if shortest.is_none_or(|(shortest_len, _)| len < shortest_len) {
+                            shortest = Some((len, id));
+                        }
+                    }
+                    shortest.map(|(_, id)| id)
+                }
+                BubbleStrategy::FollowPath(_) => followed[b].unwrap_or(most_traversed),
+                BubbleStrategy::MostTraversed => most_traversed,
+            };
+            let removed_alleles: Vec<u64> = candidates.iter().flatten().copied().filter(|&id| Some(id) != kept).collect();
+            removed.extend(&removed_alleles);
+            edits.push(BubbleEdit {
+                source: bubble.source,
+                sink: bubble.sink,
+                kept,
+                rewritten_traversals: candidates.iter().filter(|&a| *a != kept).map(count).sum(),
+                removed: removed_alleles,
+            });
+            kept_allele.push(kept.map(|id| *bubble.alleles.iter().find(|a| a.0 == id).unwrap()));
+        }
+
+        let mut simplified = Graph::empty()?;
+        for node_id in self.node_ids()? {
+            if !removed.contains(&node_id) {
+                simplified.add_node(node_id, &self.get_node_sequence(node_id)?)?;
+            }
+        }
+        for edge in self.raw_edges()? {
+            let (from, to) = ((edge.from_node, edge.from_orientation), (edge.to_node, edge.to_orientation));
+            if removed.contains(&from.0) || removed.contains(&to.0) {
+                continue;
+            }
+            // A deletion edge only survives if the deletion was kept.
+            if by_deletion.get(&(from, to)).is_some_and(|&b| kept_allele[b].is_some()) {
+                continue;
+            }
+            simplified.add_edge(from.0, from.1, to.0, to.1)?;
+        }
+
+        for (name, steps) in &paths {
+            simplified.add_path(name)?;
+            for (i, &(node_id, is_forward)) in steps.iter().enumerate() {
+                match by_allele.get(&node_id) {
+                    // Traversals of an allele enter it from the source in its bubble orientation.
+                    Some(&b) => {
+                        let along = bubbles[b].alleles.contains(&(node_id, is_forward));
+                        if let Some((kept_id, kept_forward)) = kept_allele[b] {
+                            simplified.append_step(name, kept_id, kept_forward == along)?;
+                        }
+                    }
+                    None => {
+                        simplified.append_step(name, node_id, is_forward)?;
+                        let deletion = steps.get(i + 1).and_then(|&next| by_deletion.get(&((node_id, is_forward), next)));
+                        if let Some(&b) = deletion
+                            && let Some((kept_id, kept_forward)) = kept_allele[b]
+                        {
+                            let along = (node_id, is_forward) == bubbles[b].source;
+                            simplified.append_step(name, kept_id, kept_forward == along)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((simplified, edits))
+    }
+
+    /// Finds every simple bubble whose alleles are at most `max_allele_len` bp.
+    pub(crate) fn small_bubbles(&self, max_allele_len: u64) -> Result<Vec<Bubble>, Error> {
+        let successors = |node_id: u64, is_forward: bool| -> Result<Vec<(u64, bool)>, Error> {
+            Ok(self
+                .get_successors(node_id)?
+                .iter()
+                .filter(|e| e.from_orientation == is_forward)
+                .map(|e| (e.to_node, e.to_orientation))
+                .collect())
+        };
+
+        let mut bubbles = Vec::new();
+        for node_id in self.node_ids()? {
+            for is_forward in [true, false] {
+                let source = (node_id, is_forward);
+                let mut sink = None;
+                let mut alleles = Vec::new();
+                let mut has_deletion = false;
+                let mut is_bubble = true;
+                for next in successors(node_id, is_forward)? {
+                    let next_successors = successors(next.0, next.1)?;
+                    let next_predecessors = successors(next.0, !next.1)?;
+                    let is_allele = next.0 != node_id
+                        && next_successors.len() == 1
+                        && next_predecessors == [flip(source)]
+                        && self.get_node_len(next.0)? <= max_allele_len;
+                    let end = if is_allele { next_successors[0] } else { next };
+                    if sink.is_some_and(|s| s != end) {
+                        is_bubble = false;
+                        break;
+                    }
+                    sink = Some(end);
+                    if is_allele {
+                        alleles.push(next);
+                    } else {
+                        has_deletion = true;
+                    }
+                }
+                let Some(sink) = sink else { continue };
+                // Report each bubble once, from the end with the lower node ID.
+                if !is_bubble || sink.0 <= node_id || alleles.iter().any(|a| a.0 == sink.0) {
+                    continue;
+                }
+                if alleles.len() + has_deletion as usize >= 2 {
+                    bubbles.push(Bubble { source, sink, alleles, has_deletion });
+                }
+            }
+        }
+        Ok(bubbles)
+    }
+}
+
+/// Returns the same handle on the other strand.
+fn flip((node_id, is_forward): (u64, bool)) -> (u64, bool) {
+    (node_id, !is_forward)
+}
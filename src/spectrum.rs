@@ -0,0 +1,80 @@
+// src/spectrum.rs
+
+//! K-mer multiplicity spectra.
+
+use std::collections::{BTreeMap, HashMap};
+use super::graph::{Error, Graph};
+use super::kmer::{Kmers, MAX_K};
+use super::path_index::PathIndex;
+
+/// Which sequences [`Graph::kmer_spectrum`] counts k-mers in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KmerWeighting {
+    /// Count the k-mers within each node sequence once, regardless of how many
+    /// paths visit the node. K-mers spanning node boundaries are not counted.
+    #[default]
+    Nodes,
+    /// Count the k-mers of every path's sequence, including those spanning node
+    /// boundaries, so that sequence shared by many haplotypes is weighted by
+    /// how often it occurs in them.
+    Paths,
+}
+
+impl Graph {
+    /// Returns the k-mer spectrum of the graph.
+    ///
+    /// K-mers are counted canonically (a k-mer and its reverse complement are
+    /// the same k-mer) and case-insensitively; k-mers containing bases other
+    /// than `A`, `C`, `G`, and `T` are skipped. The result maps each
+    /// multiplicity to the number of distinct k-mers that occur that many
+    /// times, which gives a quick picture of repeat content without exporting
+    /// the sequences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `k` is `0` or larger than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::{Graph, KmerWeighting};
+    ///
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// let spectrum = graph.kmer_spectrum(21, KmerWeighting::Nodes).unwrap();
+    /// let repeated: u64 = spectrum.range(2..).map(|(_, count)| count).sum();
+    /// println!("{} distinct 21-mers occur more than once", repeated);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn kmer_spectrum(&self, k: usize, weighting: KmerWeighting) -> Result<BTreeMap<u64, u64>, Error> {
+        if !(1..=MAX_K).contains(&k) {
+            return Err(Error::invalid_argument(format!("k-mer length must be between 1 and {}, got {}", MAX_K, k)));
+        }
+
+        let mut counts: HashMap<u64, u64> = HashMap::new();
+        let mut count = |sequence: &str| {
+            for (_, forward, reverse) in Kmers::new(sequence.as_bytes(), k) {
+                *counts.entry(forward.min(reverse)).or_default() += 1;
+            }
+        };
+        match weighting {
+            KmerWeighting::Nodes => {
+                for node_id in self.node_ids()? {
+                    count(&self.get_node_sequence(node_id)?);
+                }
+            }
+            KmerWeighting::Paths => {
+                for path_name in self.get_path_names()? {
+                    if let Some(index) = PathIndex::new(self, &path_name)? {
+                        count(&index.sequence(self, 0, index.length)?);
+                    }
+                }
+            }
+        }
+
+        let mut spectrum = BTreeMap::new();
+        for multiplicity in counts.into_values() {
+            *spectrum.entry(multiplicity).or_default() += 1;
+        }
+        Ok(spectrum)
+    }
+}
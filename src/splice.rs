@@ -0,0 +1,87 @@
+// src/splice.rs
+
+//! Editing the steps of an embedded path.
+
+use std::ops::Range;
+use super::graph::{Error, Graph};
+
+impl Graph {
+    /// Replaces a run of steps on a path with a new sequence of oriented nodes.
+    ///
+    /// `step_range` selects the steps to remove by their 0-based index on the
+    /// path, and `replacement` lists the `(node_id, is_forward)` steps to put in
+    /// their place. An empty range inserts the replacement before the step at
+    /// `step_range.start` (or at the end of the path if it equals the step
+    /// count), and an empty replacement deletes the selected steps.
+    ///
+    /// The edit is validated before the path is changed: every replacement node
+    /// must exist, and each pair of consecutive steps in the edited path,
+    /// including those across both ends of the spliced run, must be connected by
+    /// an edge in the graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist, the range is out of
+    /// bounds, a replacement node does not exist, or the edit would create a
+    /// step between two unconnected nodes. The path is unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let mut graph = Graph::load("my_graph.odgi").unwrap();
+    /// // Re-thread the second step of path 'x' through node 3 instead.
+    /// graph.splice_path("x", 1..2, &[(3, true)]).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, replacement), err))]
+    pub fn splice_path(
+        &mut self,
+        path_name: &str,
+        step_range: Range<usize>,
+        replacement: &[(u64, bool)],
+    ) -> Result<(), Error> {
+        self.check_path(path_name)?;
+        let steps: Vec<(u64, bool)> = self
+            .raw_path_steps(path_name)?
+            .into_iter()
+            .map(|step| (step.node_id, step.is_forward))
+            .collect();
+        if step_range.start > step_range.end || step_range.end > steps.len() {
+            return Err(Error::invalid_argument(format!(
+                "Step range {:?} is out of bounds for path '{}' with {} steps",
+                step_range, path_name, steps.len()
+            )));
+        }
+        if !replacement.is_empty() {
+            let node_ids = self.node_ids()?;
+            if let Some(&(node_id, _)) = replacement.iter().find(|(id, _)| node_ids.binary_search(id).is_err()) {
+                return Err(Error::node_not_found(node_id));
+            }
+        }
+
+        let mut edited = Vec::with_capacity(steps.len() - step_range.len() + replacement.len());
+        edited.extend_from_slice(&steps[..step_range.start]);
+        edited.extend_from_slice(replacement);
+        edited.extend_from_slice(&steps[step_range.end..]);
+
+        // Only the junctions touched by the edit need to be checked.
+        let first = step_range.start.saturating_sub(1);
+        let last = (step_range.start + replacement.len() + 1).min(edited.len());
+        for pair in edited[first..last].windows(2) {
+            let ((from, from_forward), (to, to_forward)) = (pair[0], pair[1]);
+            if !self.edge_exists(from, from_forward, to, to_forward)? {
+                return Err(Error::invalid_argument(format!(
+                    "Nodes {}{} and {}{} are not connected by an edge",
+                    from,
+                    if from_forward { '+' } else { '-' },
+                    to,
+                    if to_forward { '+' } else { '-' }
+                )));
+            }
+        }
+
+        // Rewriting the path in place keeps its position among the graph's paths.
+        self.truncate_path(path_name, step_range.start as u64)?;
+        self.extend_path(path_name, replacement.iter().chain(&steps[step_range.end..]).copied())
+    }
+}
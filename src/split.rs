@@ -0,0 +1,104 @@
+// src/split.rs
+
+//! Partitioning a graph into subgraphs by path name.
+
+use std::collections::{BTreeMap, HashSet};
+use super::graph::{Error, Graph};
+
+impl Graph {
+    /// Splits the graph into one subgraph per path name prefix.
+    ///
+    /// The prefix of a path is the part of its name before the first occurrence
+    /// of `delimiter`; paths whose names do not contain the delimiter form their
+    /// own group under their full name. For example, with `":"` as the delimiter,
+    /// the paths `chr1:0-1000` and `chr1:5000-6000` end up in the graph for
+    /// `chr1`. Use [`Graph::split_by_path_name`] for naming schemes that need
+    /// more than a delimiter.
+    ///
+    /// See [`Graph::split_by_path_name`] for how the subgraphs are built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi fails to build a subgraph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("whole_genome.odgi").unwrap();
+    /// for (contig, subgraph) in graph.split_by_path_prefix(":").unwrap() {
+    ///     println!("{}: {} nodes", contig, subgraph.node_count().unwrap());
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn split_by_path_prefix(&self, delimiter: &str) -> Result<Vec<(String, Graph)>, Error> {
+        self.split_by_path_name(|name| {
+            let prefix = match name.find(delimiter) {
+                Some(end) if !delimiter.is_empty() => &name[..end],
+                _ => name,
+            };
+            Some(prefix.to_string())
+        })
+    }
+
+    /// Splits the graph into one subgraph per group of paths.
+    ///
+    /// `group_of` is called with every path name and returns the name of the
+    /// group the path belongs to, or `None` to leave the path out. This makes it
+    /// possible to group by any part of the name, e.g. the contig field of
+    /// PanSN names (`sample#haplotype#contig`) or the capture of a regex.
+    ///
+    /// Each subgraph contains the paths of one group, every node they visit, and
+    /// every edge between two of those nodes. Node IDs and sequences are
+    /// preserved, so nodes shared by several groups appear in each of their
+    /// subgraphs; nodes not visited by any included path are dropped. The groups
+    /// are returned sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if odgi fails to build a subgraph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("whole_genome.odgi").unwrap();
+    /// // Group PanSN paths such as `HG002#1#chr1` by contig.
+    /// let per_contig = graph.split_by_path_name(|name| name.rsplit('#').next().map(str::to_string)).unwrap();
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, group_of), err))]
+    pub fn split_by_path_name<F>(&self, mut group_of: F) -> Result<Vec<(String, Graph)>, Error>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for path_name in self.get_path_names()? {
+            if let Some(group) = group_of(&path_name) {
+                groups.entry(group).or_default().push(path_name);
+            }
+        }
+
+        let edges = self.raw_edges()?;
+        groups
+            .into_iter()
+            .map(|(group, path_names)| {
+                let steps = path_names.iter().map(|name| self.raw_path_steps(name)).collect::<Result<Vec<_>, Error>>()?;
+                let nodes: HashSet<u64> = steps.iter().flatten().map(|step| step.node_id).collect();
+
+                let mut subgraph = Graph::empty()?;
+                let mut sorted_nodes: Vec<u64> = nodes.iter().copied().collect();
+                sorted_nodes.sort_unstable();
+                for node_id in sorted_nodes {
+                    subgraph.add_node(node_id, &self.get_node_sequence(node_id)?)?;
+                }
+                for edge in edges.iter().filter(|e| nodes.contains(&e.from_node) && nodes.contains(&e.to_node)) {
+                    subgraph.add_edge(edge.from_node, edge.from_orientation, edge.to_node, edge.to_orientation)?;
+                }
+                for (name, path_steps) in path_names.iter().zip(&steps) {
+                    subgraph.create_path(name, path_steps.iter().map(|step| (step.node_id, step.is_forward)))?;
+                }
+                Ok((group, subgraph))
+            })
+            .collect()
+    }
+}
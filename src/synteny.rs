@@ -0,0 +1,126 @@
+// src/synteny.rs
+
+//! Synteny blocks between two paths.
+
+use std::collections::HashMap;
+use super::graph::{Error, Graph};
+use super::path_alignment::MAX_NODE_OCCURRENCES;
+use super::path_index::PathIndex;
+
+/// A maximal run of nodes shared by two paths, found by [`Graph::shared_segments`].
+///
+/// Coordinates are 0-based and half-open on each path's own forward strand,
+/// so both ranges span the same number of base pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedSegment {
+    /// The start of the block on the first path.
+    pub a_start: u64,
+    /// The end of the block on the first path.
+    pub a_end: u64,
+    /// The start of the block on the second path.
+    pub b_start: u64,
+    /// The end of the block on the second path.
+    pub b_end: u64,
+    /// `true` if the second path traverses the block in the opposite
+    /// direction and orientation of the first.
+    pub is_reverse: bool,
+    /// The number of nodes in the block.
+    pub node_count: u64,
+}
+
+impl Graph {
+    /// Finds the maximal collinear runs of nodes shared by two paths.
+    ///
+    /// A run is a stretch of consecutive steps on `path_a` that is matched by
+    /// consecutive steps on `path_b`, either visiting the same nodes in the same
+    /// orientation, or visiting them in reverse order and opposite orientation,
+    /// as across an inversion. Runs are extended as far as possible and those
+    /// shorter than `min_bp` are dropped. Blocks are ordered by their position on
+    /// `path_a`, then on `path_b`.
+    ///
+    /// A node visited more than a few hundred times by `path_b` is not used to
+    /// start or extend blocks, which keeps highly repetitive nodes from
+    /// making the search quadratic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if either path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// let graph = Graph::load("pangenome.odgi").unwrap();
+    /// for block in graph.shared_segments("GRCh38#0#chr1", "CHM13#0#chr1", 10_000).unwrap() {
+    ///     let strand = if block.is_reverse { '-' } else { '+' };
+    ///     println!("{}-{} {} {}-{}", block.a_start, block.a_end, strand, block.b_start, block.b_end);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn shared_segments(&self, path_a: &str, path_b: &str, min_bp: u64) -> Result<Vec<SharedSegment>, Error> {
+        let a = PathIndex::new(self, path_a)?.ok_or_else(|| Error::path_not_found(path_a))?;
+        let b = PathIndex::new(self, path_b)?.ok_or_else(|| Error::path_not_found(path_b))?;
+
+        let mut occurrences: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (j, step) in b.steps.iter().enumerate() {
+            occurrences.entry(step.node_id).or_default().push(j);
+        }
+        let occurrences_of = |node_id: u64| {
+            occurrences
+                .get(&node_id)
+                .filter(|js| js.len() <= MAX_NODE_OCCURRENCES)
+                .map(Vec::as_slice)
+                .unwrap_or(&[])
+        };
+        // Whether step `i` of `a` pairs with step `j` of `b` on the given strand.
+        let pairs = |i: usize, j: usize, is_reverse: bool| {
+            let (sa, sb) = (&a.steps[i], &b.steps[j]);
+            sa.node_id == sb.node_id
+                && (sa.is_forward == sb.is_forward) != is_reverse
+                && !occurrences_of(sa.node_id).is_empty()
+        };
+
+        let mut segments = Vec::new();
+        for (i, step) in a.steps.iter().enumerate() {
+            for &j in occurrences_of(step.node_id) {
+                let is_reverse = step.is_forward != b.steps[j].is_forward;
+                // The step before `i` on `a` pairs with the step before `j` on `b`
+                // in the direction `b` is read.
+                let previous = |i: usize, j: usize| -> Option<(usize, usize)> {
+                    let j = if is_reverse { j.checked_add(1).filter(|&j| j < b.steps.len()) } else { j.checked_sub(1) };
+                    Some((i.checked_sub(1)?, j?))
+                };
+                if previous(i, j).is_some_and(|(pi, pj)| pairs(pi, pj, is_reverse)) {
+                    continue;
+                }
+                let (mut end_i, mut end_j) = (i, j);
+                loop {
+                    let next_j = if is_reverse { end_j.checked_sub(1) } else { Some(end_j + 1).filter(|&j| j < b.steps.len()) };
+                    match next_j {
+                        Some(nj) if end_i + 1 < a.steps.len() && pairs(end_i + 1, nj, is_reverse) => {
+                            end_i += 1;
+                            end_j = nj;
+                        }
+                        _ => break,
+                    }
+                }
+                let a_start = a.starts[i];
+                let a_end = a.starts[end_i] + a.lengths[end_i];
+                if a_end - a_start < min_bp {
+                    continue;
+                }
+                let (first_j, last_j) = if is_reverse { (end_j, j) } else { (j, end_j) };
+                segments.push(SharedSegment {
+                    a_start,
+                    a_end,
+                    b_start: b.starts[first_j],
+                    b_end: b.starts[last_j] + b.lengths[last_j],
+                    is_reverse,
+                    node_count: (end_i - i + 1) as u64,
+                });
+            }
+        }
+        segments.sort_by_key(|s| (s.a_start, s.b_start));
+        Ok(segments)
+    }
+}
@@ -0,0 +1,151 @@
+// src/testing.rs
+
+//! Synthetic graphs for tests and benchmarks.
+//!
+//! [`random_graph`] builds pangenome-like graphs in memory: a backbone of
+//! nodes with SNP, insertion, and deletion bubbles between them, and
+//! haplotype paths that pick an allele at every bubble. The same options and
+//! seed always produce the same graph, which makes the graphs suitable for
+//! property-based tests and reproducible performance benchmarks of code built
+//! on this crate.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use odgi_ffi::testing::{random_graph, RandomGraphOptions};
+//!
+//! let options = RandomGraphOptions { backbone_nodes: 10_000, haplotype_count: 32, ..Default::default() };
+//! let graph = random_graph(&options).unwrap();
+//! assert_eq!(graph.get_path_names().unwrap().len(), 32);
+//! ```
+
+use std::ops::RangeInclusive;
+use super::graph::{Error, Graph};
+use super::sampling::SplitMix64;
+
+/// Options controlling [`random_graph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomGraphOptions {
+    /// The number of backbone nodes, which every haplotype visits.
+    pub backbone_nodes: usize,
+    /// The range of backbone node lengths.
+    pub backbone_len: RangeInclusive<u64>,
+    /// The probability of a bubble between two consecutive backbone nodes.
+    pub bubble_density: f64,
+    /// The maximum length of an allele in a bubble. Alleles of length 1 on
+    /// both sides form SNPs.
+    pub max_allele_len: u64,
+    /// The number of haplotype paths, named `hap0`, `hap1`, and so on.
+    pub haplotype_count: usize,
+    /// The seed of the random number generator.
+    pub seed: u64,
+}
+
+impl Default for RandomGraphOptions {
+    /// 100 backbone nodes of 1-32 bp, a bubble after every fifth node on average, and 4 haplotypes.
+    fn default() -> Self {
+        RandomGraphOptions {
+            backbone_nodes: 100,
+            backbone_len: 1..=32,
+            bubble_density: 0.2,
+            max_allele_len: 10,
+            haplotype_count: 4,
+            seed: 0,
+        }
+    }
+}
+
+/// A bubble between two backbone nodes: its two alleles, either of which may be a deletion.
+type Bubble = [Option<u64>; 2];
+
+/// Generates a random pangenome-like graph.
+///
+/// Node IDs are assigned from 1 in the order backbone node, then the
+/// alleles of the bubble that follows it. Each bubble has a reference allele
+/// node and either an alternative allele node or, with probability 1/4, a
+/// deletion edge that skips the bubble. Each haplotype picks either allele of
+/// every bubble with equal probability, so allele nodes that no haplotype
+/// picks can remain.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if odgi fails to build the graph.
+///
+/// # Panics
+///
+/// Panics if `backbone_len` starts at `0` or is empty, or if `max_allele_len` is `0`.
+pub fn random_graph(options: &RandomGraphOptions) -> Result<Graph, Error> {
+    assert!(
+        *options.backbone_len.start() > 0 && !options.backbone_len.is_empty(),
+        "backbone_len must be a non-empty range of positive lengths"
+    );
+    assert!(options.max_allele_len > 0, "max_allele_len must be positive");
+
+    let mut rng = SplitMix64(options.seed);
+    let mut graph = Graph::empty()?;
+    let mut next_id = 1;
+    let mut add_node = |graph: &mut Graph, rng: &mut SplitMix64, len: u64| -> Result<u64, Error> {
+        let sequence: String = (0..len).map(|_| b"ACGT"[rng.below(4) as usize] as char).collect();
+        graph.add_node(next_id, &sequence)?;
+        next_id += 1;
+        Ok(next_id - 1)
+    };
+
+    let (min_len, max_len) = (*options.backbone_len.start(), *options.backbone_len.end());
+    let mut backbone = Vec::with_capacity(options.backbone_nodes);
+    let mut bubbles: Vec<Option<Bubble>> = Vec::with_capacity(options.backbone_nodes);
+    for i in 0..options.backbone_nodes {
+        let len = min_len + rng.below(max_len - min_len + 1);
+        let node = add_node(&mut graph, &mut rng, len)?;
+        if let Some(&prev) = backbone.last() {
+            match bubbles.last() {
+                Some(Some(bubble)) => {
+                    for allele in bubble.iter() {
+                        match allele {
+                            Some(allele) => graph.add_edge(*allele, true, node, true)?,
+                            None => graph.add_edge(prev, true, node, true)?,
+                        };
+                    }
+                }
+                _ => {
+                    graph.add_edge(prev, true, node, true)?;
+                }
+            }
+        }
+        backbone.push(node);
+
+        let has_bubble = i + 1 < options.backbone_nodes && (rng.next_u64() as f64 / u64::MAX as f64) < options.bubble_density;
+        bubbles.push(if has_bubble {
+            let snp = rng.below(2) == 0;
+            let allele_len = |rng: &mut SplitMix64| if snp { 1 } else { 1 + rng.below(options.max_allele_len) };
+            let len = allele_len(&mut rng);
+            let reference = add_node(&mut graph, &mut rng, len)?;
+            let alternative = if !snp && rng.below(4) == 0 {
+                None
+            } else {
+                let len = allele_len(&mut rng);
+                Some(add_node(&mut graph, &mut rng, len)?)
+            };
+            for allele in [reference].into_iter().chain(alternative) {
+                graph.add_edge(node, true, allele, true)?;
+            }
+            Some([Some(reference), alternative])
+        } else {
+            None
+        });
+    }
+
+    for h in 0..options.haplotype_count {
+        let name = format!("hap{}", h);
+        graph.add_path(&name)?;
+        for (&node, bubble) in backbone.iter().zip(&bubbles) {
+            graph.append_step(&name, node, true)?;
+            if let Some(bubble) = bubble
+                && let Some(allele) = bubble[rng.below(2) as usize]
+            {
+                graph.append_step(&name, allele, true)?;
+            }
+        }
+    }
+    Ok(graph)
+}
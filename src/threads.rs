@@ -0,0 +1,49 @@
+// src/threads.rs
+
+//! Controlling the threads of odgi's own parallel loops.
+//!
+//! odgi parallelizes some of its internal loops with OpenMP. By default
+//! OpenMP uses one thread per CPU, or `OMP_NUM_THREADS` if it is set when the
+//! process starts. Services that share a machine can lower this at run time.
+
+#[cfg(not(feature = "docs-only"))]
+use super::ffi;
+
+/// Sets the number of threads odgi's OpenMP loops use; `0` uses the number of CPUs.
+///
+/// Like `omp_set_num_threads`, the setting applies to odgi calls made from the
+/// calling thread, so a thread pool should set it on each of its workers. The
+/// crate's own parallelism, such as [`Graph::load_many`](crate::Graph::load_many),
+/// uses Rust threads and takes its thread count as an argument instead.
+///
+/// This has no effect in builds with the `no-openmp` or `wasm` features, where
+/// odgi runs on one thread.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// odgi_ffi::set_odgi_threads(4);
+/// assert!(odgi_ffi::odgi_threads() <= 4);
+/// ```
+#[cfg(not(feature = "docs-only"))]
+pub fn set_odgi_threads(threads: usize) {
+    ffi::set_num_threads(threads as u64);
+}
+
+/// Sets the number of threads odgi's OpenMP loops use; `0` uses the number of CPUs.
+#[cfg(feature = "docs-only")]
+pub fn set_odgi_threads(_threads: usize) {}
+
+/// Returns the number of threads odgi's OpenMP loops use on the calling thread.
+///
+/// This is `1` in builds with the `no-openmp` or `wasm` features.
+#[cfg(not(feature = "docs-only"))]
+pub fn odgi_threads() -> usize {
+    ffi::get_num_threads() as usize
+}
+
+/// Returns the number of threads odgi's OpenMP loops use on the calling thread.
+#[cfg(feature = "docs-only")]
+pub fn odgi_threads() -> usize {
+    1
+}
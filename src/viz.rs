@@ -0,0 +1,277 @@
+// src/viz.rs
+
+//! Self-contained SVG and HTML snapshots of a graph region.
+//!
+//! [`render_region_svg`] draws the local graph around an interval of a path:
+//! the nodes in pangenome order, the edges between them, and one ribbon per
+//! haplotype showing which nodes it visits and in which orientation. The
+//! output needs no external assets, so it can be embedded directly in reports
+//! and notebooks without going through the `odgi viz` raster pipeline.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use super::edge_key::EdgeKey;
+use super::graph::{Error, Graph};
+use super::region::GraphRegion;
+
+/// The color of ribbon segments on forward steps.
+const FORWARD_COLOR: &str = "#4e79a7";
+/// The color of ribbon segments on reverse steps.
+const REVERSE_COLOR: &str = "#e15759";
+const MARGIN: f64 = 10.0;
+const NODE_HEIGHT: f64 = 16.0;
+/// The horizontal space between neighboring nodes.
+const NODE_GAP: f64 = 6.0;
+const LANE_HEIGHT: f64 = 10.0;
+const LABEL_WIDTH: f64 = 160.0;
+/// The narrowest a node is drawn, however short its sequence.
+const MIN_NODE_WIDTH: f64 = 2.0;
+
+/// Options controlling [`render_region_svg`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VizOptions {
+    /// The width of the drawing in pixels. Regions with many nodes are drawn
+    /// wider so that every node stays visible.
+    pub width: u32,
+    /// How many edges away from the path's nodes to include other nodes, such
+    /// as the alternative alleles of bubbles. `0` draws only the path's nodes.
+    pub context: usize,
+    /// The maximum number of haplotype ribbons. The region's path is always drawn first.
+    pub max_paths: usize,
+    /// Whether to label ribbons with their path names.
+    pub show_labels: bool,
+}
+
+impl Default for VizOptions {
+    /// A 1000 pixel wide drawing with one edge of context, up to 50 labeled ribbons.
+    fn default() -> Self {
+        VizOptions { width: 1000, context: 1, max_paths: 50, show_labels: true }
+    }
+}
+
+/// Escapes text for use in XML content and attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the local graph around `region` as a self-contained SVG document.
+///
+/// The drawing contains the nodes the region's path visits in it and, as set
+/// by [`VizOptions::context`], nodes a few edges away. Nodes are laid out from
+/// left to right in pangenome order (see [`Graph::node_sort_offset`]) with
+/// widths proportional to their length, and edges are drawn as arcs above
+/// them. Below, every path that visits the drawn nodes gets a ribbon with a
+/// segment under each node it visits, blue for forward and red for reverse
+/// steps, and a link between nodes it visits that are joined by an edge.
+/// Hovering over a node shows its ID, length, and sequence.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the path does not exist or the region is empty. The
+/// end of the region is clamped to the end of the path.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use odgi_ffi::{viz, Graph};
+///
+/// let graph = Graph::load("pangenome.odgi").unwrap();
+/// let svg = viz::render_region_svg(&graph, &"GRCh38#0#chr6:31,972,001-31,974,000".parse().unwrap(), &Default::default()).unwrap();
+/// std::fs::write("c4a.svg", svg).unwrap();
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph), err))]
+pub fn render_region_svg(graph: &Graph, region: &GraphRegion, options: &VizOptions) -> Result<String, Error> {
+    let (index, start, end) = region.index(graph)?;
+
+    // The path's nodes in the region, then their neighborhood.
+    let first = index.step_at(start).expect("the region lies within the path");
+    let last = index.step_at(end - 1).expect("the region lies within the path");
+    let mut included: HashSet<u64> = index.steps[first..=last].iter().map(|step| step.node_id).collect();
+    let mut frontier: Vec<u64> = included.iter().copied().collect();
+    for _ in 0..options.context {
+        let mut next = Vec::new();
+        for node_id in frontier {
+            let successors = graph.get_successors(node_id)?.into_iter().map(|edge| edge.to_node);
+            let predecessors = graph.get_predecessors(node_id)?.into_iter().map(|edge| edge.to_node);
+            for neighbor in successors.chain(predecessors) {
+                if included.insert(neighbor) {
+                    next.push(neighbor);
+                }
+            }
+        }
+        frontier = next;
+    }
+    let mut nodes: Vec<u64> = included.into_iter().collect();
+    let sort_offsets = graph.node_sort_offsets()?;
+    nodes.sort_by_key(|node_id| (sort_offsets.get(node_id).copied().unwrap_or(u64::MAX), *node_id));
+    // Successors are drawn once as arcs and again in every lane, so look them up once.
+    let successors = nodes
+        .iter()
+        .map(|&node_id| Ok((node_id, graph.get_successors(node_id)?)))
+        .collect::<Result<HashMap<u64, Vec<_>>, Error>>()?;
+
+    // Horizontal extent of each node in pixels.
+    let lengths = nodes.iter().map(|&node_id| graph.get_node_len(node_id)).collect::<Result<Vec<u64>, Error>>()?;
+    let total_bp: u64 = lengths.iter().sum();
+    let label_width = if options.show_labels { LABEL_WIDTH } else { 0.0 };
+    let gaps = NODE_GAP * nodes.len().saturating_sub(1) as f64;
+    let available = options.width as f64 - 2.0 * MARGIN - label_width - gaps;
+    let scale = (available / total_bp.max(1) as f64).max(0.0);
+    let mut extents: HashMap<u64, (f64, f64)> = HashMap::new();
+    let mut x = MARGIN + label_width;
+    for (&node_id, &len) in nodes.iter().zip(&lengths) {
+        let width = (len as f64 * scale).max(MIN_NODE_WIDTH);
+        extents.insert(node_id, (x, x + width));
+        x += width + NODE_GAP;
+    }
+    let width = (x - NODE_GAP + MARGIN).max(options.width as f64);
+
+    // The region's path first, then the other paths in graph order.
+    let mut visits: BTreeMap<usize, HashMap<u64, Vec<bool>>> = BTreeMap::new();
+    let mut path_names = graph.get_path_names()?;
+    let focal = path_names.iter().position(|p| *p == region.path).expect("the path exists");
+    path_names[..=focal].rotate_right(1);
+    let rank: HashMap<&str, usize> = path_names.iter().enumerate().map(|(i, p)| (p.as_str(), i)).collect();
+    for &node_id in &nodes {
+        for step in graph.steps_on_node(node_id) {
+            let step = step?;
+            let i = rank[step.path_name.as_str()];
+            visits.entry(i).or_default().entry(node_id).or_default().push(step.is_forward);
+        }
+    }
+    let lanes: Vec<(usize, HashMap<u64, Vec<bool>>)> = visits.into_iter().take(options.max_paths).collect();
+
+    let node_top = MARGIN + 2.0 * NODE_HEIGHT;
+    let lanes_top = node_top + NODE_HEIGHT + LANE_HEIGHT;
+    let height = lanes_top + lanes.len() as f64 * LANE_HEIGHT + MARGIN;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="0 0 {:.0} {:.0}" font-family="sans-serif" font-size="9">"#,
+        width, height, width, height
+    );
+    let _ = writeln!(svg, "<title>{}</title>", escape(&GraphRegion::new(&region.path, start, end).to_string()));
+
+    // Edges as arcs above the nodes, leaving and entering each handle on the side it is read from.
+    let mut edges = BTreeSet::new();
+    for &node_id in &nodes {
+        for edge in &successors[&node_id] {
+            if extents.contains_key(&edge.to_node) {
+                edges.insert(EdgeKey::from_successor(node_id, edge).canonicalized());
+            }
+        }
+    }
+    let _ = writeln!(svg, r##"<g fill="none" stroke="#999" stroke-width="1">"##);
+    for EdgeKey { from_node: from, from_orientation: from_forward, to_node: to, to_orientation: to_forward } in edges {
+        let (from_start, from_end) = extents[&from];
+        let (to_start, to_end) = extents[&to];
+        let x1 = if from_forward { from_end } else { from_start };
+        let x2 = if to_forward { to_start } else { to_end };
+        let lift = ((x2 - x1).abs() / 4.0).clamp(4.0, 2.0 * NODE_HEIGHT - 2.0);
+        let _ = writeln!(
+            svg,
+            r#"<path d="M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}"/>"#,
+            x1,
+            node_top,
+            x1,
+            node_top - lift,
+            x2,
+            node_top - lift,
+            x2,
+            node_top
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+
+    let _ = writeln!(svg, r##"<g fill="#ddd" stroke="#555" stroke-width="0.5">"##);
+    for (&node_id, &len) in nodes.iter().zip(&lengths) {
+        let (x1, x2) = extents[&node_id];
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}"><title>node {} ({} bp) {}</title></rect>"#,
+            x1,
+            node_top,
+            x2 - x1,
+            NODE_HEIGHT,
+            node_id,
+            len,
+            escape(&graph.get_node_sequence(node_id)?)
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+
+    // Ribbons, one lane per path.
+    for (lane, (i, lane_visits)) in lanes.iter().enumerate() {
+        let name = escape(&path_names[*i]);
+        let y = lanes_top + lane as f64 * LANE_HEIGHT;
+        let _ = writeln!(svg, "<g><title>{}</title>", name);
+        if options.show_labels {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+                MARGIN + label_width - NODE_GAP,
+                y + LANE_HEIGHT / 2.0,
+                name
+            );
+        }
+        for &node_id in &nodes {
+            let Some(orientations) = lane_visits.get(&node_id) else { continue };
+            let (x1, x2) = extents[&node_id];
+            // Nodes visited in both orientations are split between the two colors.
+            let forward = orientations.iter().any(|&is_forward| is_forward);
+            let reverse = orientations.iter().any(|&is_forward| !is_forward);
+            let segments = match (forward, reverse) {
+                (true, true) => vec![(FORWARD_COLOR, x1, (x1 + x2) / 2.0), (REVERSE_COLOR, (x1 + x2) / 2.0, x2)],
+                (true, false) => vec![(FORWARD_COLOR, x1, x2)],
+                _ => vec![(REVERSE_COLOR, x1, x2)],
+            };
+            for (color, from, to) in segments {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+                    from,
+                    y + 1.0,
+                    to - from,
+                    LANE_HEIGHT - 2.0,
+                    color
+                );
+            }
+            for edge in &successors[&node_id] {
+                if edge.to_node != node_id && lane_visits.contains_key(&edge.to_node) {
+                    let (to_start, to_end) = extents[&edge.to_node];
+                    let (x1, x2) = if to_start >= x2 { (x2, to_start) } else { (to_end, x1) };
+                    let _ = writeln!(
+                        svg,
+                        r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#777" stroke-width="1"/>"##,
+                        x1,
+                        y + LANE_HEIGHT / 2.0,
+                        x2,
+                        y + LANE_HEIGHT / 2.0
+                    );
+                }
+            }
+        }
+        let _ = writeln!(svg, "</g>");
+    }
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Renders the same drawing as [`render_region_svg`], wrapped in a minimal standalone HTML page.
+///
+/// # Errors
+///
+/// Returns an [`Error`] in the same cases as [`render_region_svg`].
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(graph), err))]
+pub fn render_region_html(graph: &Graph, region: &GraphRegion, options: &VizOptions) -> Result<String, Error> {
+    let svg = render_region_svg(graph, region, options)?;
+    let title = escape(&region.to_string());
+    Ok(format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1 style=\"font-family: sans-serif; font-size: 14px\">{}</h1>\n{}</body>\n</html>\n",
+        title, title, svg
+    ))
+}
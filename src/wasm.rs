@@ -0,0 +1,879 @@
+// src/wasm.rs
+
+//! A pure-Rust graph store that backs [`Graph`] in `wasm` builds.
+//!
+//! Builds for `wasm32-unknown-unknown` cannot link the C++ library, so this
+//! module keeps the nodes, edges, and paths in Rust collections and answers the
+//! same queries over them. Graphs are built from GFA text held in memory, such
+//! as a buffer fetched by a browser, with [`Graph::from_gfa_bytes`].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use super::compression::{GZIP_MAGIC, ZSTD_MAGIC};
+use super::edge_key::EdgeKey;
+use super::ffi;
+use super::graph::{Edge, Error, Graph, GraphStats, NodeStep, PathPosition};
+use super::query_metrics;
+
+/// A node in one orientation, as `(node_id, is_forward)`.
+type Side = (u64, bool);
+
+fn flip((node_id, is_forward): Side) -> Side {
+    (node_id, !is_forward)
+}
+
+/// A path and the oriented nodes it visits.
+#[derive(Debug, Clone)]
+struct MemoryPath {
+    name: String,
+    steps: Vec<Side>,
+}
+
+/// The nodes, edges, and paths of a graph.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MemoryGraph {
+    /// Node sequences by ID.
+    sequences: HashMap<u64, String>,
+    /// Node IDs in the graph's 1D order, which is the order they were added in.
+    order: Vec<u64>,
+    /// The sides reached by leaving each side to the right.
+    right: HashMap<Side, Vec<Side>>,
+    /// Paths by ID. IDs are assigned in creation order and never reused.
+    paths: BTreeMap<u64, MemoryPath>,
+    path_ids: HashMap<String, u64>,
+    next_path_id: u64,
+    /// The steps on each node as `(path ID, step index)`.
+    node_steps: HashMap<u64, Vec<(u64, usize)>>,
+}
+
+impl MemoryGraph {
+    fn path(&self, path_name: &str) -> Option<&MemoryPath> {
+        self.path_ids.get(path_name).and_then(|id| self.paths.get(id))
+    }
+
+    fn right_of(&self, side: Side) -> &[Side] {
+        self.right.get(&side).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns the sides from which an edge leads into `side`.
+    fn left_of(&self, side: Side) -> Vec<Side> {
+        self.right_of(flip(side)).iter().map(|&s| flip(s)).collect()
+    }
+
+    fn has_edge(&self, from: Side, to: Side) -> bool {
+        self.right_of(from).contains(&to)
+    }
+
+    fn create_edge(&mut self, from: Side, to: Side) {
+        if self.has_edge(from, to) {
+            return;
+        }
+        self.right.entry(from).or_default().push(to);
+        // An edge that leaves a side and returns along its reverse is its own flip.
+        if (flip(to), flip(from)) != (from, to) {
+            self.right.entry(flip(to)).or_default().push(flip(from));
+        }
+    }
+
+    fn destroy_edge(&mut self, from: Side, to: Side) -> bool {
+        if !self.has_edge(from, to) {
+            return false;
+        }
+        let mut remove = |from: Side, to: Side| {
+            if let Some(sides) = self.right.get_mut(&from) {
+                sides.retain(|&s| s != to);
+            }
+        };
+        remove(from, to);
+        remove(flip(to), flip(from));
+        true
+    }
+
+    /// Returns every edge touching `node_id` once, as `(from, to)` pairs.
+    fn edges_of(&self, node_id: u64) -> Vec<(Side, Side)> {
+        let mut edges = HashSet::new();
+        for is_forward in [true, false] {
+            let side = (node_id, is_forward);
+            for &to in self.right_of(side) {
+                edges.insert(EdgeKey::canonical(node_id, is_forward, to.0, to.1));
+            }
+        }
+        let mut edges: Vec<EdgeKey> = edges.into_iter().collect();
+        edges.sort();
+        edges
+            .into_iter()
+            .map(|e| ((e.from_node, e.from_orientation), (e.to_node, e.to_orientation)))
+            .collect()
+    }
+
+    /// Rebuilds the steps on every node after paths were rewritten.
+    fn reindex_steps(&mut self) {
+        self.node_steps.clear();
+        for (&path_id, path) in &self.paths {
+            for (index, &(node_id, _)) in path.steps.iter().enumerate() {
+                self.node_steps.entry(node_id).or_default().push((path_id, index));
+            }
+        }
+    }
+
+    /// Returns the step at `index` on the path with ID `path_id`.
+    fn step(&self, path_id: u64, index: usize) -> Option<(&MemoryPath, Side)> {
+        let path = self.paths.get(&path_id)?;
+        Some((path, *path.steps.get(index)?))
+    }
+
+    fn node_len(&self, node_id: u64) -> u64 {
+        self.sequences.get(&node_id).map_or(0, |s| s.len() as u64)
+    }
+}
+
+impl Graph {
+    /// Builds a graph from GFA text held in memory.
+    ///
+    /// This is how `wasm` builds load graphs, since they cannot read ODGI files:
+    /// a browser-based viewer fetches a GFA file as bytes and passes them here.
+    /// Gzip- and zstd-compressed buffers are decompressed first.
+    ///
+    /// Segment (`S`), link (`L`), and path (`P`) lines are read; other lines are
+    /// ignored. Numeric segment names are kept as node IDs if they are all
+    /// positive and distinct; otherwise the segments are numbered from 1 in file order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the buffer cannot be decompressed, is not UTF-8,
+    /// has a malformed line, or has a link or path that refers to an unknown segment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use odgi_ffi::Graph;
+    ///
+    /// let gfa = b"S\t1\tGATTACA\nS\t2\tT\nL\t1\t+\t2\t+\t0M\nP\tx\t1+,2+\t*\n";
+    /// let graph = Graph::from_gfa_bytes(gfa).unwrap();
+    /// assert_eq!(graph.get_path_length("x").unwrap(), 8);
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(bytes), err))]
+    pub fn from_gfa_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let _timer = query_metrics::query("from_gfa_bytes");
+        let decompressed;
+        let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut buffer = Vec::new();
+            flate2::read::MultiGzDecoder::new(bytes)
+                .read_to_end(&mut buffer)
+                .map_err(|e| Error::io("Failed to decompress GFA", e))?;
+            decompressed = buffer;
+            &decompressed[..]
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            decompressed = zstd::stream::decode_all(bytes)
+                .map_err(|e| Error::io("Failed to decompress GFA", e))?;
+            &decompressed[..]
+        } else {
+            bytes
+        };
+        let text = std::str::from_utf8(bytes).map_err(|e| Error::invalid_format(format!("GFA is not valid UTF-8: {}", e)))?;
+        let invalid = |line_no: usize, reason: &str| Error::invalid_format(format!("Invalid GFA line {}: {}", line_no + 1, reason));
+
+        let mut segments: Vec<(&str, &str)> = Vec::new();
+        let mut links: Vec<(usize, [&str; 4])> = Vec::new();
+        let mut paths: Vec<(usize, &str, &str)> = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields[0] {
+                "S" if fields.len() < 3 => return Err(invalid(line_no, "segment lines need a name and a sequence")),
+                "S" => segments.push((fields[1], fields[2])),
+                "L" if fields.len() < 5 => return Err(invalid(line_no, "link lines need two oriented segments")),
+                "L" => links.push((line_no, [fields[1], fields[2], fields[3], fields[4]])),
+                "P" if fields.len() < 3 => return Err(invalid(line_no, "path lines need a name and steps")),
+                "P" => paths.push((line_no, fields[1], fields[2])),
+                _ => {}
+            }
+        }
+
+        let numeric: Option<Vec<u64>> = segments
+            .iter()
+            .map(|(name, _)| name.parse::<u64>().ok().filter(|&id| id > 0))
+            .collect();
+        let ids: Vec<u64> = match numeric {
+            Some(ids) if ids.iter().collect::<HashSet<_>>().len() == ids.len() => ids,
+            _ => (1..=segments.len() as u64).collect(),
+        };
+        let id_of: HashMap<&str, u64> = segments.iter().zip(&ids).map(|(&(name, _), &id)| (name, id)).collect();
+
+        let mut graph = Graph::empty()?;
+        for (&(name, sequence), &id) in segments.iter().zip(&ids) {
+            if !graph.add_node(id, sequence)? {
+                return Err(Error::invalid_format(format!("Duplicate segment '{}' in GFA", name)));
+            }
+        }
+
+        let oriented = |line_no: usize, name: &str, orientation: &str| -> Result<Side, Error> {
+            let id = id_of
+                .get(name)
+                .copied()
+                .ok_or_else(|| invalid(line_no, &format!("unknown segment '{}'", name)))?;
+            match orientation {
+                "+" => Ok((id, true)),
+                "-" => Ok((id, false)),
+                _ => Err(invalid(line_no, &format!("bad orientation '{}'", orientation))),
+            }
+        };
+        for (line_no, [from, from_orient, to, to_orient]) in links {
+            let (from_id, from_forward) = oriented(line_no, from, from_orient)?;
+            let (to_id, to_forward) = oriented(line_no, to, to_orient)?;
+            graph.add_edge(from_id, from_forward, to_id, to_forward)?;
+        }
+        for (line_no, name, steps) in paths {
+            if !graph.add_path(name)? {
+                return Err(invalid(line_no, &format!("duplicate path '{}'", name)));
+            }
+            for step in steps.split(',') {
+                let orientation = if step.ends_with('+') || step.ends_with('-') { &step[step.len() - 1..] } else { "" };
+                let (id, is_forward) = oriented(line_no, &step[..step.len() - orientation.len()], orientation)?;
+                graph.append_step(name, id, is_forward)?;
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Builds a graph from GFA text.
+    ///
+    /// This is [`Graph::from_gfa_bytes`] for text that is already a `str`.
+    pub fn from_gfa_str(gfa: &str) -> Result<Self, Error> {
+        Self::from_gfa_bytes(gfa.as_bytes())
+    }
+
+    /// Builds a graph from a GFA file, as [`Graph::from_gfa_bytes`] does from its contents.
+    pub fn from_gfa_file(path: &str) -> Result<Self, Error> {
+        let bytes = std::fs::read(path).map_err(|e| Error::io(format!("Failed to read GFA file '{}'", path), e))?;
+        Self::from_gfa_bytes(&bytes)
+    }
+
+    /// Loads an ODGI graph from a file into memory.
+    ///
+    /// ODGI files can only be read by the C++ library, which `wasm` builds do
+    /// not link, so this always returns an [`Error`]. Use
+    /// [`Graph::from_gfa_bytes`] instead.
+    pub fn load(path: &str) -> Result<Self, Error> {
+        Err(Error::unsupported(format!(
+            "Cannot load '{}': reading ODGI files needs the C++ library, which wasm builds do not link; \
+             use Graph::from_gfa_bytes instead",
+            path
+        )))
+    }
+
+    /// Loads an ODGI graph from a buffer in memory.
+    ///
+    /// Like [`Graph::load`], this always returns an [`Error`] in `wasm` builds.
+    pub fn load_from_bytes(_bytes: &[u8]) -> Result<Self, Error> {
+        Err(Error::unsupported(
+            "Cannot load an ODGI graph: reading ODGI data needs the C++ library, which wasm builds do not link; \
+             use Graph::from_gfa_bytes instead",
+        ))
+    }
+
+    /// Loads an ODGI graph from any reader, such as a network stream.
+    ///
+    /// Like [`Graph::load`], this always returns an [`Error`] in `wasm` builds.
+    pub fn load_from_reader<R: std::io::Read>(_reader: R) -> Result<Self, Error> {
+        Self::load_from_bytes(&[])
+    }
+
+    /// Writes the graph to a file in the ODGI format.
+    ///
+    /// Writing ODGI files needs the C++ library, which `wasm` builds do not
+    /// link, so this always returns an [`Error`].
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        Err(Error::unsupported(format!(
+            "Cannot save '{}': writing ODGI files needs the C++ library, which wasm builds do not link",
+            path
+        )))
+    }
+
+    /// Returns the total number of nodes in the graph.
+    pub fn node_count(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("node_count");
+        Ok(self.store.sequences.len() as u64)
+    }
+
+    /// Returns the total number of edges in the graph.
+    pub fn edge_count(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("edge_count");
+        Ok(self.raw_edges()?.len() as u64)
+    }
+
+    /// Returns the total number of paths in the graph.
+    pub fn path_count(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("path_count");
+        Ok(self.store.paths.len() as u64)
+    }
+
+    /// Returns the total length of all node sequences in base pairs.
+    pub fn total_length(&self) -> Result<u64, Error> {
+        let _timer = query_metrics::query("total_length");
+        Ok(self.store.sequences.values().map(|s| s.len() as u64).sum())
+    }
+
+    /// Returns `true` if the graph has a node with the given ID.
+    pub fn has_node(&self, node_id: u64) -> Result<bool, Error> {
+        let _timer = query_metrics::query("has_node");
+        Ok(self.store.sequences.contains_key(&node_id))
+    }
+
+    /// Returns summary statistics of the whole graph.
+    pub fn stats(&self) -> Result<GraphStats, Error> {
+        let _timer = query_metrics::query("stats");
+        let edges = self.raw_edges()?;
+        Ok(GraphStats {
+            node_count: self.store.sequences.len() as u64,
+            edge_count: edges.len() as u64,
+            total_length: self.store.sequences.values().map(|s| s.len() as u64).sum(),
+            path_count: self.store.paths.len() as u64,
+            step_count: self.store.paths.values().map(|p| p.steps.len() as u64).sum(),
+            self_loop_count: edges.iter().filter(|e| e.from_node == e.to_node).count() as u64,
+        })
+    }
+
+    /// Returns a list of all path names in the graph.
+    pub fn get_path_names(&self) -> Result<Vec<String>, Error> {
+        let _timer = query_metrics::query("get_path_names");
+        Ok(self.store.paths.values().map(|p| p.name.clone()).collect())
+    }
+
+    /// Returns `true` if the graph has a path with the given name.
+    pub fn has_path(&self, path_name: &str) -> Result<bool, Error> {
+        let _timer = query_metrics::query("has_path");
+        Ok(self.store.path_ids.contains_key(path_name))
+    }
+
+    /// Projects a 0-based linear coordinate on a path to graph coordinates.
+    pub fn project(&self, path_name: &str, pos: u64) -> Result<PathPosition, Error> {
+        let _timer = query_metrics::query("project");
+        self.check_path(path_name)?;
+        self.project_sorted(path_name, &[pos])?
+            .pop()
+            .ok_or_else(|| Error::invalid_argument(format!("Position {} is past the end of path '{}'", pos, path_name)))
+    }
+
+    /// Gets the DNA sequence for a given node ID.
+    pub fn get_node_sequence(&self, node_id: u64) -> Result<String, Error> {
+        let _timer = query_metrics::query("get_node_sequence");
+        self.store.sequences.get(&node_id).cloned().ok_or_else(|| Error::node_not_found(node_id))
+    }
+
+    /// Gets the length of the sequence for a given node ID.
+    pub fn get_node_len(&self, node_id: u64) -> Result<u64, Error> {
+        let _timer = query_metrics::query("get_node_len");
+        self.check_node(node_id)?;
+        Ok(self.store.node_len(node_id))
+    }
+
+    /// Gets all successor edges for a given node ID.
+    pub fn get_successors(&self, node_id: u64) -> Result<Vec<Edge>, Error> {
+        let _timer = query_metrics::query("get_successors");
+        self.check_node(node_id)?;
+        let mut edges: Vec<Edge> = [true, false]
+            .into_iter()
+            .flat_map(|from_orientation| {
+                self.store.right_of((node_id, from_orientation)).iter().map(move |&(to_node, to_orientation)| {
+                    Edge { to_node, from_orientation, to_orientation }
+                })
+            })
+            .collect();
+        edges.sort_by_key(|e| (!e.from_orientation, e.to_node, !e.to_orientation));
+        Ok(edges)
+    }
+
+    /// Gets all predecessor edges for a given node ID.
+    pub fn get_predecessors(&self, node_id: u64) -> Result<Vec<Edge>, Error> {
+        let _timer = query_metrics::query("get_predecessors");
+        self.check_node(node_id)?;
+        let mut edges: Vec<Edge> = [true, false]
+            .into_iter()
+            .flat_map(|to_orientation| {
+                self.store.left_of((node_id, to_orientation)).into_iter().map(move |(to_node, from_orientation)| {
+                    Edge { to_node, from_orientation, to_orientation }
+                })
+            })
+            .collect();
+        edges.sort_by_key(|e| (!e.to_orientation, e.to_node, !e.from_orientation));
+        Ok(edges)
+    }
+
+    /// Gets the DNA sequence for a given node ID into a reusable buffer.
+    pub fn get_node_sequence_into(&self, node_id: u64, sequence: &mut String) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_node_sequence_into");
+        sequence.clear();
+        let bases = self.store.sequences.get(&node_id).ok_or_else(|| Error::node_not_found(node_id))?;
+        sequence.push_str(bases);
+        Ok(())
+    }
+
+    /// Gets the DNA sequence for a given node ID as bytes.
+    pub fn get_node_sequence_bytes(&self, node_id: u64) -> Result<Vec<u8>, Error> {
+        let _timer = query_metrics::query("get_node_sequence_bytes");
+        self.store
+            .sequences
+            .get(&node_id)
+            .map(|bases| bases.as_bytes().to_vec())
+            .ok_or_else(|| Error::node_not_found(node_id))
+    }
+
+    /// Gets the DNA sequence for a given node ID as bytes into a reusable buffer.
+    pub fn get_node_sequence_bytes_into(&self, node_id: u64, sequence: &mut Vec<u8>) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_node_sequence_bytes_into");
+        sequence.clear();
+        let bases = self.store.sequences.get(&node_id).ok_or_else(|| Error::node_not_found(node_id))?;
+        sequence.extend_from_slice(bases.as_bytes());
+        Ok(())
+    }
+
+    /// Gets all successor edges for a given node ID into a reusable buffer.
+    pub fn get_successors_into(&self, node_id: u64, edges: &mut Vec<Edge>) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_successors_into");
+        edges.clear();
+        self.check_node(node_id)?;
+        for from_orientation in [true, false] {
+            edges.extend(self.store.right_of((node_id, from_orientation)).iter().map(|&(to_node, to_orientation)| {
+                Edge { to_node, from_orientation, to_orientation }
+            }));
+        }
+        edges.sort_unstable_by_key(|e| (!e.from_orientation, e.to_node, !e.to_orientation));
+        Ok(())
+    }
+
+    /// Gets all predecessor edges for a given node ID into a reusable buffer.
+    pub fn get_predecessors_into(&self, node_id: u64, edges: &mut Vec<Edge>) -> Result<(), Error> {
+        let _timer = query_metrics::query("get_predecessors_into");
+        edges.clear();
+        self.check_node(node_id)?;
+        for to_orientation in [true, false] {
+            // Entering a side is leaving its reverse, so no intermediate list is needed.
+            edges.extend(self.store.right_of((node_id, !to_orientation)).iter().map(|&(to_node, is_forward)| {
+                Edge { to_node, from_orientation: !is_forward, to_orientation }
+            }));
+        }
+        edges.sort_unstable_by_key(|e| (!e.to_orientation, e.to_node, !e.from_orientation));
+        Ok(())
+    }
+
+    /// Gets the names of all paths that step on a given node ID.
+    pub fn get_paths_on_node(&self, node_id: u64) -> Result<Vec<String>, Error> {
+        let _timer = query_metrics::query("get_paths_on_node");
+        self.check_node(node_id)?;
+        let Some(steps) = self.store.node_steps.get(&node_id) else {
+            return Ok(Vec::new());
+        };
+        Ok(steps
+            .iter()
+            .filter_map(|&(path_id, _)| self.store.paths.get(&path_id))
+            .map(|path| path.name.clone())
+            .collect())
+    }
+
+    /// Returns the depth of a node: the number of path steps on it.
+    pub fn node_depth(&self, node_id: u64) -> Result<u64, Error> {
+        let _timer = query_metrics::query("node_depth");
+        self.check_node(node_id)?;
+        Ok(self.store.node_steps.get(&node_id).map_or(0, |steps| steps.len() as u64))
+    }
+
+    /// Gets one page of the steps on a node.
+    pub fn get_steps_on_node(&self, node_id: u64, offset: u64, limit: u64) -> Result<Vec<NodeStep>, Error> {
+        let _timer = query_metrics::query("get_steps_on_node");
+        self.check_node(node_id)?;
+        let Some(steps) = self.store.node_steps.get(&node_id) else {
+            return Ok(Vec::new());
+        };
+        Ok(steps
+            .iter()
+            .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .filter_map(|&(path_id, index)| self.store.step(path_id, index))
+            .map(|(path, (_, is_forward))| NodeStep { path_name: path.name.clone(), is_forward })
+            .collect())
+    }
+
+    /// Gets the total length of a path in base pairs.
+    pub fn get_path_length(&self, path_name: &str) -> Result<u64, Error> {
+        let _timer = query_metrics::query("get_path_length");
+        let path = self.store.path(path_name).ok_or_else(|| Error::path_not_found(path_name))?;
+        Ok(path.steps.iter().map(|&(node_id, _)| self.store.node_len(node_id)).sum())
+    }
+
+    /// Gets the next node ID on a given path from a specified node.
+    pub fn get_next_node_on_path(&self, node_id: u64, path_name: &str) -> Result<Option<u64>, Error> {
+        let _timer = query_metrics::query("get_next_node_on_path");
+        let steps = &self.store.path(path_name).ok_or_else(|| Error::path_not_found(path_name))?.steps;
+        self.check_node(node_id)?;
+        let Some(index) = steps.iter().position(|&(id, _)| id == node_id) else {
+            return Ok(None);
+        };
+        Ok(steps.get(index + 1).map(|&(id, _)| id))
+    }
+
+    /// Gets the names of all paths that traverse a specific directed edge.
+    pub fn get_paths_on_edge(
+        &self,
+        from_node: u64,
+        from_orientation: bool,
+        to_node: u64,
+        to_orientation: bool,
+    ) -> Result<Vec<String>, Error> {
+        let _timer = query_metrics::query("get_paths_on_edge");
+        self.check_node(from_node)?;
+        self.check_node(to_node)?;
+        let Some(steps) = self.store.node_steps.get(&from_node) else {
+            return Ok(Vec::new());
+        };
+        let mut paths: Vec<String> = steps
+            .iter()
+            .filter_map(|&(path_id, index)| {
+                let (path, from) = self.store.step(path_id, index)?;
+                let to = *path.steps.get(index + 1)?;
+                (from == (from_node, from_orientation) && to == (to_node, to_orientation)).then(|| path.name.clone())
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    /// Gets the offset of a node's first base in the graph's current 1D sort order.
+    pub fn node_sort_offset(&self, node_id: u64) -> Result<u64, Error> {
+        let _timer = query_metrics::query("node_sort_offset");
+        let mut offset = 0;
+        for &id in &self.store.order {
+            if id == node_id {
+                return Ok(offset);
+            }
+            offset += self.store.node_len(id);
+        }
+        Err(Error::node_not_found(node_id))
+    }
+
+    /// Gets the 1D sort offset of every node in a single pass.
+    pub fn node_sort_offsets(&self) -> Result<HashMap<u64, u64>, Error> {
+        let _timer = query_metrics::query("node_sort_offsets");
+        let mut offsets = HashMap::with_capacity(self.store.order.len());
+        let mut offset = 0;
+        for &id in &self.store.order {
+            offsets.insert(id, offset);
+            offset += self.store.node_len(id);
+        }
+        Ok(offsets)
+    }
+
+    /// Finds the node covering a given offset in the graph's current 1D sort order.
+    pub fn node_at_sort_offset(&self, offset: u64) -> Result<(u64, u64), Error> {
+        let _timer = query_metrics::query("node_at_sort_offset");
+        let mut node_start = 0;
+        for &id in &self.store.order {
+            let node_len = self.store.node_len(id);
+            if offset < node_start + node_len {
+                return Ok((id, offset - node_start));
+            }
+            node_start += node_len;
+        }
+        Err(Error::invalid_argument(format!("Offset {} is past the end of the graph's sort order", offset)))
+    }
+
+    /// Reads through every node, edge, and path step of the graph once.
+    ///
+    /// This is a no-op here, since the whole graph is already held in memory.
+    pub fn prefault(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub(crate) fn node_ids(&self) -> Result<Vec<u64>, Error> {
+        let mut ids: Vec<u64> = self.store.sequences.keys().copied().collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    pub(crate) fn raw_path_steps(&self, path_name: &str) -> Result<Vec<ffi::PathStep>, Error> {
+        Ok(self
+            .store
+            .path(path_name)
+            .map(|p| p.steps.iter().map(|&(node_id, is_forward)| ffi::PathStep { node_id, is_forward }).collect())
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn raw_edges(&self) -> Result<Vec<ffi::GraphEdge>, Error> {
+        let mut edges: Vec<EdgeKey> = self
+            .store
+            .right
+            .iter()
+            .flat_map(|(&(from_id, from_forward), sides)| {
+                sides.iter().map(move |&(to_id, to_forward)| EdgeKey::canonical(from_id, from_forward, to_id, to_forward))
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        edges.sort_by_key(|e| (e.from_node, !e.from_orientation, e.to_node, !e.to_orientation));
+        Ok(edges
+            .into_iter()
+            .map(|e| ffi::GraphEdge {
+                from_node: e.from_node,
+                from_orientation: e.from_orientation,
+                to_node: e.to_node,
+                to_orientation: e.to_orientation,
+            })
+            .collect())
+    }
+
+    pub(crate) fn node_ids_at_ranks(&self, ranks: &[u64]) -> Result<Vec<u64>, Error> {
+        let ids = self.node_ids()?;
+        Ok(ranks.iter().filter_map(|&rank| ids.get(rank as usize).copied()).collect())
+    }
+
+    pub(crate) fn path_names_at_ranks(&self, ranks: &[u64]) -> Result<Vec<String>, Error> {
+        let names = self.get_path_names()?;
+        Ok(ranks.iter().filter_map(|&rank| names.get(rank as usize).cloned()).collect())
+    }
+
+    pub(crate) fn project_sorted(&self, path_name: &str, positions: &[u64]) -> Result<Vec<PathPosition>, Error> {
+        let mut result = Vec::new();
+        let Some(path) = self.store.path(path_name) else {
+            return Ok(result);
+        };
+        let mut next = 0;
+        let mut current_pos = 0;
+        for &(node_id, is_forward) in &path.steps {
+            if next == positions.len() {
+                break;
+            }
+            let node_len = self.store.node_len(node_id);
+            while next < positions.len() && positions[next] < current_pos + node_len {
+                let offset_in_step = positions[next] - current_pos;
+                let offset = if is_forward { offset_in_step } else { node_len - 1 - offset_in_step };
+                result.push(PathPosition { node_id, offset, is_forward });
+                next += 1;
+            }
+            current_pos += node_len;
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn node_path_positions(&self, node_id: u64) -> Result<Vec<ffi::StepPosition>, Error> {
+        let Some(steps) = self.store.node_steps.get(&node_id) else {
+            return Ok(Vec::new());
+        };
+        Ok(steps
+            .iter()
+            .filter_map(|&(path_id, index)| {
+                let (path, (_, is_forward)) = self.store.step(path_id, index)?;
+                let start = path.steps[..index].iter().map(|&(id, _)| self.store.node_len(id)).sum();
+                Some(ffi::StepPosition { path_name: path.name.clone(), start, is_forward })
+            })
+            .collect())
+    }
+
+    pub(crate) fn node_lens(&self, node_ids: &[u64]) -> Result<Vec<u64>, Error> {
+        Ok(node_ids.iter().map(|&id| self.store.node_len(id)).collect())
+    }
+
+    pub(crate) fn edge_exists(&self, from: u64, from_forward: bool, to: u64, to_forward: bool) -> Result<bool, Error> {
+        Ok(self.store.has_edge((from, from_forward), (to, to_forward)))
+    }
+
+    pub(crate) fn check_node(&self, node_id: u64) -> Result<(), Error> {
+        if self.store.sequences.contains_key(&node_id) { Ok(()) } else { Err(Error::node_not_found(node_id)) }
+    }
+
+    pub(crate) fn check_path(&self, path_name: &str) -> Result<(), Error> {
+        if self.store.path_ids.contains_key(path_name) { Ok(()) } else { Err(Error::path_not_found(path_name)) }
+    }
+
+    pub(crate) fn empty() -> Result<Self, Error> {
+        Ok(Graph { _inner: (), store: MemoryGraph::default() })
+    }
+
+    pub(crate) fn add_node(&mut self, node_id: u64, sequence: &str) -> Result<bool, Error> {
+        if node_id == 0 || self.store.sequences.contains_key(&node_id) {
+            return Ok(false);
+        }
+        self.store.sequences.insert(node_id, sequence.to_string());
+        self.store.order.push(node_id);
+        Ok(true)
+    }
+
+    pub(crate) fn add_edge(
+        &mut self,
+        from_node: u64,
+        from_orientation: bool,
+        to_node: u64,
+        to_orientation: bool,
+    ) -> Result<bool, Error> {
+        let store = &mut self.store;
+        if !store.sequences.contains_key(&from_node) || !store.sequences.contains_key(&to_node) {
+            return Ok(false);
+        }
+        store.create_edge((from_node, from_orientation), (to_node, to_orientation));
+        Ok(true)
+    }
+
+    pub(crate) fn add_path(&mut self, path_name: &str) -> Result<bool, Error> {
+        let store = &mut self.store;
+        if store.path_ids.contains_key(path_name) {
+            return Ok(false);
+        }
+        let path_id = store.next_path_id;
+        store.next_path_id += 1;
+        store.path_ids.insert(path_name.to_string(), path_id);
+        store.paths.insert(path_id, MemoryPath { name: path_name.to_string(), steps: Vec::new() });
+        Ok(true)
+    }
+
+    pub(crate) fn append_step(&mut self, path_name: &str, node_id: u64, is_forward: bool) -> Result<bool, Error> {
+        let store = &mut self.store;
+        let Some(&path_id) = store.path_ids.get(path_name) else {
+            return Ok(false);
+        };
+        if !store.sequences.contains_key(&node_id) {
+            return Ok(false);
+        }
+        let steps = &mut store.paths.get_mut(&path_id).expect("path IDs index existing paths").steps;
+        steps.push((node_id, is_forward));
+        store.node_steps.entry(node_id).or_default().push((path_id, steps.len() - 1));
+        Ok(true)
+    }
+
+    pub(crate) fn remove_path(&mut self, path_name: &str) -> Result<bool, Error> {
+        let store = &mut self.store;
+        let Some(path_id) = store.path_ids.remove(path_name) else {
+            return Ok(false);
+        };
+        if let Some(path) = store.paths.remove(&path_id) {
+            for (node_id, _) in path.steps {
+                if let Some(steps) = store.node_steps.get_mut(&node_id) {
+                    steps.retain(|&(id, _)| id != path_id);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn truncate_path(&mut self, path_name: &str, length: u64) -> Result<bool, Error> {
+        let store = &mut self.store;
+        let Some(&path_id) = store.path_ids.get(path_name) else {
+            return Ok(false);
+        };
+        let steps = &mut store.paths.get_mut(&path_id).expect("path IDs index existing paths").steps;
+        let length = (length as usize).min(steps.len());
+        for (node_id, _) in steps.drain(length..) {
+            if let Some(node_steps) = store.node_steps.get_mut(&node_id) {
+                node_steps.retain(|&(id, index)| id != path_id || index < length);
+            }
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn remove_node(&mut self, node_id: u64) -> Result<bool, Error> {
+        let store = &mut self.store;
+        if store.sequences.remove(&node_id).is_none() {
+            return Ok(false);
+        }
+        for (from, to) in store.edges_of(node_id) {
+            store.destroy_edge(from, to);
+        }
+        store.right.remove(&(node_id, true));
+        store.right.remove(&(node_id, false));
+        store.order.retain(|&id| id != node_id);
+        if store.node_steps.remove(&node_id).is_some() {
+            for path in store.paths.values_mut() {
+                path.steps.retain(|&(id, _)| id != node_id);
+            }
+            store.reindex_steps();
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn remove_edge(
+        &mut self,
+        from_node: u64,
+        from_orientation: bool,
+        to_node: u64,
+        to_orientation: bool,
+    ) -> Result<bool, Error> {
+        Ok(self.store.destroy_edge((from_node, from_orientation), (to_node, to_orientation)))
+    }
+
+    pub(crate) fn divide_node(&mut self, node_id: u64, offsets: &[u64]) -> Result<Vec<u64>, Error> {
+        let store = &mut self.store;
+        let Some(sequence) = store.sequences.get(&node_id).cloned() else {
+            return Ok(Vec::new());
+        };
+        let mut cuts: Vec<usize> = offsets
+            .iter()
+            .map(|&o| o as usize)
+            .filter(|&o| o > 0 && o < sequence.len())
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        if cuts.is_empty() {
+            return Ok(vec![node_id]);
+        }
+
+        // The first piece keeps the node's ID; the others take fresh IDs and
+        // follow it in the 1D order.
+        let mut next_id = store.sequences.keys().max().copied().unwrap_or(0) + 1;
+        let bounds: Vec<usize> = std::iter::once(0).chain(cuts).chain(std::iter::once(sequence.len())).collect();
+        let mut pieces = Vec::with_capacity(bounds.len() - 1);
+        for (i, range) in bounds.windows(2).enumerate() {
+            let piece = if i == 0 {
+                node_id
+            } else {
+                next_id += 1;
+                next_id - 1
+            };
+            store.sequences.insert(piece, sequence[range[0]..range[1]].to_string());
+            pieces.push(piece);
+        }
+        let rank = store.order.iter().position(|&id| id == node_id).expect("nodes are in the 1D order");
+        store.order.splice(rank + 1..rank + 1, pieces[1..].iter().copied());
+
+        // Edges leave the node's forward strand from the last piece and enter it at the first.
+        let first = pieces[0];
+        let last = *pieces.last().expect("a divided node has pieces");
+        let leaving = |(id, is_forward): Side| if id == node_id { (if is_forward { last } else { first }, is_forward) } else { (id, is_forward) };
+        let entering = |(id, is_forward): Side| if id == node_id { (if is_forward { first } else { last }, is_forward) } else { (id, is_forward) };
+        for (from, to) in store.edges_of(node_id) {
+            store.destroy_edge(from, to);
+            store.create_edge(leaving(from), entering(to));
+        }
+        for pair in pieces.windows(2) {
+            store.create_edge((pair[0], true), (pair[1], true));
+        }
+
+        for path in store.paths.values_mut() {
+            if !path.steps.iter().any(|&(id, _)| id == node_id) {
+                continue;
+            }
+            path.steps = path
+                .steps
+                .iter()
+                .flat_map(|&(id, is_forward)| -> Vec<Side> {
+                    match (id == node_id, is_forward) {
+                        (false, _) => vec![(id, is_forward)],
+                        (true, true) => pieces.iter().map(|&p| (p, true)).collect(),
+                        (true, false) => pieces.iter().rev().map(|&p| (p, false)).collect(),
+                    }
+                })
+                .collect();
+        }
+        store.reindex_steps();
+        Ok(pieces)
+    }
+
+    pub(crate) fn node_order(&self) -> Result<Vec<u64>, Error> {
+        Ok(self.store.order.clone())
+    }
+
+    pub(crate) fn apply_node_order(&mut self, node_ids: &[u64]) -> Result<bool, Error> {
+        if !node_ids.iter().all(|id| self.store.sequences.contains_key(id)) {
+            return Ok(false);
+        }
+        self.store.order = node_ids.to_vec();
+        Ok(true)
+    }
+}
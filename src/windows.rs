@@ -0,0 +1,185 @@
+// src/windows.rs
+
+//! Sliding-window summaries along a path.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use super::graph::{Error, Graph};
+use super::path_index::PathIndex;
+use super::query_metrics;
+
+/// Summary statistics for one window along a path, as produced by [`Graph::windows`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowStats {
+    /// The 0-based start of the window on the path (inclusive).
+    pub start: u64,
+    /// The end of the window on the path (exclusive).
+    pub end: u64,
+    /// The mean number of path steps on the nodes covering each base of the window.
+    pub mean_depth: f64,
+    /// The number of distinct paths that visit at least one node in the window.
+    pub distinct_haplotypes: usize,
+    /// The fraction of `G`/`C` bases among the unambiguous bases of the window.
+    ///
+    /// This is `0.0` if the window has no `A`/`C`/`G`/`T` bases.
+    pub gc_content: f64,
+    /// The number of distinct nodes overlapping the window.
+    pub node_count: usize,
+    /// The fraction of the window's bases that the path traverses in reverse orientation.
+    pub inversion_fraction: f64,
+}
+
+/// An iterator over the windows of a path, created by [`Graph::windows`].
+///
+/// If odgi throws while summarizing a window, the error is yielded and the
+/// iteration ends.
+pub struct PathWindows<'a> {
+    graph: &'a Graph,
+    index: Arc<PathIndex>,
+    size: u64,
+    step: u64,
+    next_start: Option<u64>,
+    /// Step count and interned path IDs of each node in the current window.
+    node_cache: HashMap<u64, (u64, Vec<usize>)>,
+    /// IDs of the path names seen so far, numbered in the order they were first seen.
+    path_ids: HashMap<String, usize>,
+}
+
+impl Graph {
+    /// Returns an iterator of summary statistics over sliding windows along a path.
+    ///
+    /// Windows of `size` base pairs start every `step` base pairs from the start of
+    /// the path. The last window is truncated at the end of the path, and iteration
+    /// stops once a window reaches the end. The path is indexed once up front and
+    /// per-node information is cached, so scanning a whole chromosome does not
+    /// repeat the cost of projecting or extracting each region separately.
+    ///
+    /// # Arguments
+    ///
+    /// * `path_name` - The path to scan.
+    /// * `size` - The window size in base pairs.
+    /// * `step` - The distance between consecutive window starts in base pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the path does not exist or if `size` or `step` is `0`.
+    /// Errors while summarizing a window are yielded by the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use odgi_ffi::Graph;
+    /// # let graph = Graph::load("my_graph.odgi").unwrap();
+    /// for window in graph.windows("grch38#chr1", 10_000, 5_000).unwrap() {
+    ///     let window = window.unwrap();
+    ///     println!("{}-{}: depth {:.2}, GC {:.2}, {} haplotypes",
+    ///              window.start, window.end, window.mean_depth,
+    ///              window.gc_content, window.distinct_haplotypes);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err))]
+    pub fn windows(&self, path_name: &str, size: u64, step: u64) -> Result<PathWindows<'_>, Error> {
+        if size == 0 || step == 0 {
+            return Err(Error::invalid_argument("Window size and step must be greater than 0"));
+        }
+        let index = PathIndex::new(self, path_name)?
+            .ok_or_else(|| Error::path_not_found(path_name))?;
+        let next_start = if index.length > 0 { Some(0) } else { None };
+        Ok(PathWindows {
+            graph: self,
+            index,
+            size,
+            step,
+            next_start,
+            node_cache: HashMap::new(),
+            path_ids: HashMap::new(),
+        })
+    }
+}
+
+impl Iterator for PathWindows<'_> {
+    type Item = Result<WindowStats, Error>;
+
+    fn next(&mut self) -> Option<Result<WindowStats, Error>> {
+        let start = self.next_start?;
+        let end = (start + self.size).min(self.index.length);
+        self.next_start = if end < self.index.length { Some(start + self.step) } else { None };
+
+        let first = self.index.step_at(start)?;
+        let window = self.window(start, end, first);
+        if window.is_err() {
+            self.next_start = None;
+        }
+        Some(window)
+    }
+}
+
+impl PathWindows<'_> {
+    /// Summarizes the window from `start` to `end`, whose first step is `first`.
+    fn window(&mut self, start: u64, end: u64, first: usize) -> Result<WindowStats, Error> {
+        let mut weighted_depth = 0;
+        let mut reverse_bases = 0;
+        let mut nodes = HashSet::new();
+        let mut haplotypes = HashSet::new();
+
+        // Carry over the cached nodes that are still in the window and drop the
+        // rest, so the cache never holds more than one window's nodes.
+        let graph = self.graph;
+        let mut previous = std::mem::take(&mut self.node_cache);
+        let steps = self.index.steps.iter().zip(self.index.starts.iter().zip(&self.index.lengths));
+        for (step, (&step_start, &step_len)) in steps.skip(first) {
+            if step_start >= end {
+                break;
+            }
+            let hit = self.node_cache.contains_key(&step.node_id) || previous.contains_key(&step.node_id);
+            query_metrics::cache_lookup("windows_nodes", hit);
+            let (depth, paths) = match self.node_cache.entry(step.node_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => match previous.remove(&step.node_id) {
+                    Some(cached) => entry.insert(cached),
+                    None => {
+                        let paths = graph
+                            .get_paths_on_node(step.node_id)?
+                            .into_iter()
+                            .map(|name| {
+                                let next_id = self.path_ids.len();
+                                *self.path_ids.entry(name).or_insert(next_id)
+                            })
+                            .collect();
+                        entry.insert((graph.node_depth(step.node_id)?, paths))
+                    }
+                },
+            };
+
+            let overlap = (step_start + step_len).min(end) - step_start.max(start);
+            weighted_depth += *depth * overlap;
+            if !step.is_forward {
+                reverse_bases += overlap;
+            }
+            nodes.insert(step.node_id);
+            haplotypes.extend(paths.iter().copied());
+        }
+
+        let sequence = self.index.sequence(self.graph, start, end)?;
+        let (mut gc, mut at) = (0u64, 0u64);
+        for base in sequence.bytes() {
+            match base.to_ascii_uppercase() {
+                b'G' | b'C' => gc += 1,
+                b'A' | b'T' => at += 1,
+                _ => {}
+            }
+        }
+
+        let width = (end - start) as f64;
+        Ok(WindowStats {
+            start,
+            end,
+            mean_depth: weighted_depth as f64 / width,
+            distinct_haplotypes: haplotypes.len(),
+            gc_content: if gc + at > 0 { gc as f64 / (gc + at) as f64 } else { 0.0 },
+            node_count: nodes.len(),
+            inversion_fraction: reverse_bases as f64 / width,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+// File: tests/align_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::{align, ErrorKind, GraphRegion};
+
+#[test]
+fn test_poa_region_follows_alternative_allele() {
+    let (graph, _temp_file) = setup_graph();
+
+    // The region of path 'x' includes the G allele of node 3, which the query carries.
+    let alignment = align::poa_region(&graph, &GraphRegion::new("x", 5, 10), "ACAGGT").unwrap();
+    assert_eq!(alignment.walk, vec![(1, true), (3, true), (4, true)]);
+    assert_eq!((alignment.walk_start, alignment.walk_end), (4, 2));
+    assert_eq!(alignment.cigar(), "6=");
+    assert_eq!(alignment.edit_distance, 0);
+    assert_eq!(alignment.identity(), 1.0);
+}
+
+#[test]
+fn test_poa_region_with_edits() {
+    let (graph, _temp_file) = setup_graph();
+
+    // One inserted base relative to GATTACATGTC.
+    let alignment = align::poa_region(&graph, &GraphRegion::new("x", 0, 11), "GATTACATTGTC").unwrap();
+    assert_eq!(alignment.edit_distance, 1);
+    assert_eq!(alignment.walk, vec![(1, true), (2, true), (4, true)]);
+    assert_eq!(alignment.operations.iter().filter(|&&(op, _)| op == 'I').map(|&(_, len)| len).sum::<u64>(), 1);
+}
+
+#[test]
+fn test_poa_region_errors() {
+    let (graph, _temp_file) = setup_graph();
+
+    assert!(align::poa_region(&graph, &GraphRegion::new("missing", 0, 5), "ACGT").is_err());
+    assert!(align::poa_region(&graph, &GraphRegion::new("x", 5, 5), "ACGT").is_err());
+    assert!(align::poa_region(&graph, &GraphRegion::new("x", 11, 20), "ACGT").is_err());
+
+    // The end of a region is clamped to the end of the path.
+    let alignment = align::poa_region(&graph, &"x:1-100".parse().unwrap(), "GATTACATGTC").unwrap();
+    assert_eq!(alignment.edit_distance, 0);
+
+    // The alignment matrix is bounded rather than allocated for any query length.
+    let long_query = "A".repeat(10_000_000);
+    let err = align::poa_region(&graph, &GraphRegion::new("x", 0, 11), &long_query).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_)), "unexpected error: {}", err);
+}
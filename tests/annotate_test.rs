@@ -0,0 +1,77 @@
+// File: tests/annotate_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::annotate::{self, LiftoverStatus};
+use std::io::Cursor;
+
+const GFF: &str = "##gff-version 3\n\
+x\ttest\tgene\t1\t9\t.\t+\t.\tID=gene1;Name=GENE%3B1\n\
+x\ttest\tmRNA\t1\t9\t.\t+\t.\tID=tx1;Parent=gene1\n\
+x\ttest\texon\t1\t3\t.\t+\t.\tID=exon1;Parent=tx1\n\
+x\ttest\tCDS\t8\t9\t.\t+\t2\tID=cds1;Parent=tx1\n\
+chr9\ttest\tgene\t1\t5\t.\t-\t.\tID=elsewhere\n\
+##FASTA\n\
+>x\n\
+GATTACATGTC\n";
+
+#[test]
+fn test_load_gff3() {
+    let (graph, _temp_file) = setup_graph();
+    let annotation = annotate::load_gff3(&graph, Cursor::new(GFF), "x").unwrap();
+
+    // Features on other sequences are skipped, and the FASTA section is not parsed.
+    assert_eq!(annotation.reference_path(), "x");
+    assert_eq!(annotation.features().len(), 4);
+    let cds = &annotation.features()[3];
+    assert_eq!((cds.start, cds.end, cds.phase), (7, 9, Some(2)));
+    assert_eq!(cds.parents(), vec!["tx1"]);
+    assert_eq!(cds.nodes, vec![2, 4]);
+
+    // Path 'x' visits nodes 1, 2, and 4; the gene covers all of them.
+    let genes = annotation.genes_overlapping_node(2);
+    assert_eq!(genes.len(), 1);
+    assert_eq!(genes[0].name(), Some("GENE;1"));
+    assert_eq!(annotation.features_on_node(2).len(), 3);
+    assert!(annotation.genes_overlapping_node(3).is_empty());
+    assert_eq!(annotation.nodes_for_gene("GENE;1"), vec![1, 2, 4]);
+    assert_eq!(annotation.nodes_for_gene("gene1"), vec![1, 2, 4]);
+    assert!(annotation.nodes_for_gene("tx1").is_empty());
+}
+
+#[test]
+fn test_load_gff3_errors() {
+    let (graph, _temp_file) = setup_graph();
+    assert!(annotate::load_gff3(&graph, Cursor::new(GFF), "missing").is_err());
+    assert!(annotate::load_gff3(&graph, Cursor::new("x\ttest\tgene\t1\t12\t.\t+\t.\tID=g\n"), "x").is_err());
+    assert!(annotate::load_gff3(&graph, Cursor::new("x\ttest\tgene\t0\t5\t.\t+\t.\tID=g\n"), "x").is_err());
+    assert!(annotate::load_gff3(&graph, Cursor::new("x\ttest\tgene\t1\t5\n"), "x").is_err());
+}
+
+#[test]
+fn test_liftover_genes() {
+    let gff = "x\ttest\tgene\t1\t11\t.\t+\t.\tID=gene1\n\
+               x\ttest\tmRNA\t1\t11\t.\t+\t.\tID=tx1;Parent=gene1\n\
+               x\ttest\tCDS\t1\t7\t.\t+\t0\tID=cds1;Parent=tx1\n\
+               x\ttest\tCDS\t9\t11\t.\t+\t2\tID=cds2;Parent=tx1\n";
+    let (graph, _temp_file) = setup_graph();
+    let annotation = annotate::load_gff3(&graph, Cursor::new(gff), "x").unwrap();
+
+    // Path 'y' differs from 'x' only by the SNP between nodes 1 and 4, so the model lifts intact.
+    let lifted = annotate::liftover_genes(&graph, &annotation, "y").unwrap();
+    assert_eq!(lifted.len(), 1);
+    assert_eq!(lifted[0].status, LiftoverStatus::Intact);
+    assert_eq!(lifted[0].features.len(), 4);
+    let cds2 = &lifted[0].features[3];
+    assert_eq!((cds2.seqid.as_str(), cds2.start, cds2.end, cds2.phase), ("y", 8, 11, Some(2)));
+    assert_eq!(cds2.nodes, vec![4]);
+
+    // Path 'z' ends after node 2, so only the first CDS can be lifted.
+    let lifted = annotate::liftover_genes(&graph, &annotation, "z").unwrap();
+    assert_eq!(lifted[0].status, LiftoverStatus::Partial);
+    assert_eq!(lifted[0].features.len(), 1);
+    assert_eq!(lifted[0].features[0].id(), Some("cds1"));
+    assert_eq!(lifted[0].unmapped.len(), 3);
+
+    assert!(annotate::liftover_genes(&graph, &annotation, "missing").is_err());
+}
@@ -0,0 +1,30 @@
+// File: tests/bubbles_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::bubbles;
+
+#[test]
+fn test_snarls() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Nodes 2 and 3 are the alleles of a single bubble between nodes 1 and 4.
+    let snarls = bubbles::snarls(&graph).unwrap();
+    assert_eq!(snarls.len(), 1);
+    assert_eq!((snarls[0].start, snarls[0].end), ((1, true), (4, true)));
+    assert_eq!(snarls[0].alleles, vec![(2, true), (3, true)]);
+    assert!(!snarls[0].has_deletion);
+}
+
+#[test]
+fn test_write_snarls() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    assert_eq!(bubbles::write_snarls(&graph, &mut out).unwrap(), 1);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "{\"start\":{\"node_id\":\"1\"},\"end\":{\"node_id\":\"4\"},\"type\":\"ULTRABUBBLE\",\
+         \"start_end_reachable\":true,\"directed_acyclic_net_graph\":true}\n"
+    );
+}
@@ -0,0 +1,60 @@
+// File: tests/command_test.rs
+use odgi_ffi::{gfa_to_odgi, odgi_executable, Graph, OdgiCommand};
+use std::time::Duration;
+
+#[test]
+fn test_odgi_command_stats() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let odgi_path = temp_dir.path().join("queries.odgi");
+    gfa_to_odgi("test_data/queries.gfa", odgi_path.to_str().unwrap()).unwrap();
+
+    let output = OdgiCommand::new("stats")
+        .arg("-i")
+        .arg(&odgi_path)
+        .arg("-S")
+        .run()
+        .expect("odgi stats should run");
+    assert!(output.status.success());
+    assert!(!output.stdout.is_empty());
+}
+
+#[test]
+fn test_odgi_command_temp_output() {
+    let mut command = OdgiCommand::new("build");
+    command
+        .args(["-g", "test_data/queries.gfa"])
+        .temp_output("-o", "queries.odgi")
+        .timeout(Duration::from_secs(60));
+    let output = command.run().expect("odgi build should run");
+    assert!(output.status.success());
+    assert_eq!(output.temp_outputs.len(), 1);
+
+    let graph = Graph::load(output.temp_outputs[0].to_str().unwrap()).unwrap();
+    assert_eq!(graph.node_count().unwrap(), 4);
+
+    // The temporary file is removed together with the output.
+    let temp_path = output.temp_outputs[0].clone();
+    drop(output);
+    assert!(!temp_path.exists());
+}
+
+#[test]
+fn test_odgi_command_failure_status() {
+    let output = OdgiCommand::new("build")
+        .args(["-g", "test_data/does_not_exist.gfa", "-o", "/dev/null"])
+        .run()
+        .expect("The process itself should start");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_odgi_executable() {
+    let exe = odgi_executable();
+    assert!(exe.is_file(), "The bundled odgi executable should exist at {}", exe.display());
+
+    let status = std::process::Command::new(&exe)
+        .arg("version")
+        .status()
+        .expect("The bundled odgi executable should run");
+    assert!(status.success());
+}
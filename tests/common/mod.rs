@@ -0,0 +1,49 @@
+// File: tests/common/mod.rs
+//! Graph fixtures shared by the integration tests.
+// Each test crate uses only some of the fixtures.
+#![allow(dead_code)]
+
+#[cfg(any(not(feature = "docs-only"), feature = "wasm"))]
+use odgi_ffi::Graph;
+#[cfg(not(feature = "docs-only"))]
+use odgi_ffi::gfa_to_odgi;
+#[cfg(not(feature = "docs-only"))]
+use std::io::Write;
+#[cfg(not(feature = "docs-only"))]
+use tempfile::NamedTempFile;
+
+/// The small graph most tests query: nodes 1-4 with paths x, y, and z.
+pub const QUERIES_GFA: &str = "test_data/queries.gfa";
+
+/// Converts a GFA file to a temporary ODGI file and loads it.
+///
+/// The returned file must outlive the graph's use in the test.
+#[cfg(not(feature = "docs-only"))]
+pub fn load_gfa_file(gfa_path: &str) -> (Graph, NamedTempFile) {
+    let odgi_file = NamedTempFile::new().expect("Failed to create temp ODGI file");
+    let odgi_path = odgi_file.path().to_str().unwrap();
+    gfa_to_odgi(gfa_path, odgi_path).expect("Test setup: GFA to ODGI conversion failed");
+    let graph = Graph::load(odgi_path).expect("Test setup: Failed to load ODGI graph");
+    (graph, odgi_file)
+}
+
+/// Converts GFA text to a temporary ODGI file and loads it.
+#[cfg(not(feature = "docs-only"))]
+pub fn load_gfa(gfa: &str) -> (Graph, NamedTempFile) {
+    let mut gfa_file = NamedTempFile::new().expect("Failed to create temp GFA file");
+    gfa_file.write_all(gfa.as_bytes()).unwrap();
+    load_gfa_file(gfa_file.path().to_str().unwrap())
+}
+
+/// Converts queries.gfa to a temporary ODGI file and loads it.
+#[cfg(not(feature = "docs-only"))]
+pub fn setup_graph() -> (Graph, NamedTempFile) {
+    load_gfa_file(QUERIES_GFA)
+}
+
+/// Builds queries.gfa in the pure-Rust store.
+#[cfg(feature = "wasm")]
+pub fn setup_memory_graph() -> Graph {
+    let gfa = std::fs::read(QUERIES_GFA).expect("Failed to read queries.gfa");
+    Graph::from_gfa_bytes(&gfa).expect("Test setup: Failed to build graph from GFA")
+}
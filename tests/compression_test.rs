@@ -0,0 +1,48 @@
+// File: tests/compression_test.rs
+use odgi_ffi::{inspect, Graph};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+/// Writes `data` to a temporary file with the given suffix.
+fn write_temp(data: &[u8], suffix: &str) -> NamedTempFile {
+    let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+    file.write_all(data).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+#[test]
+fn test_load_gzip_compressed() {
+    let raw = std::fs::read("test_data/tiny.odgi").unwrap();
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&raw).unwrap();
+    let compressed = write_temp(&encoder.finish().unwrap(), ".og.gz");
+
+    let graph = Graph::load(compressed.path().to_str().unwrap()).expect("Gzipped graph should load");
+    assert_eq!(graph.node_count().unwrap(), 2);
+    assert_eq!(graph.get_node_sequence(1).unwrap(), "A");
+
+    let info = inspect(compressed.path().to_str().unwrap()).unwrap();
+    assert_eq!(info.node_count, 2);
+}
+
+#[test]
+fn test_load_zstd_compressed() {
+    let raw = std::fs::read("test_data/tiny.odgi").unwrap();
+    // The format is detected from the contents, not the file name.
+    let compressed = write_temp(&zstd::encode_all(&raw[..], 3).unwrap(), ".odgi");
+
+    let graph = Graph::load(compressed.path().to_str().unwrap()).expect("Zstd-compressed graph should load");
+    assert_eq!(graph.node_count().unwrap(), 2);
+}
+
+#[test]
+fn test_load_truncated_gzip() {
+    let raw = std::fs::read("test_data/tiny.odgi").unwrap();
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&raw).unwrap();
+    let bytes = encoder.finish().unwrap();
+    let truncated = write_temp(&bytes[..bytes.len() / 2], ".og.gz");
+
+    assert!(Graph::load(truncated.path().to_str().unwrap()).is_err());
+}
@@ -1,7 +1,11 @@
 // File: tests/conversion_test.rs
 
 // We need the Graph struct to load the final GFA and verify it.
-use odgi_ffi::{gfa_to_odgi, odgi_to_gfa, Graph};
+use odgi_ffi::{
+    gfa_to_odgi, gfa_to_odgi_cancellable, gfa_to_odgi_with_diagnostics, gfa_to_odgi_with_timeout, odgi_to_gfa,
+    CancellationToken, Diagnostic, DiagnosticKind, DiagnosticLevel, Graph,
+};
+use std::time::Duration;
 // REMOVED: use std::fs; // This was unused.
 
 #[test]
@@ -46,7 +50,125 @@ fn test_gfa_odgi_roundtrip() {
     let final_graph = Graph::load(final_odgi_str)
         .expect("Failed to load the final, round-tripped ODGI graph.");
     
-    assert_eq!(final_graph.node_count(), 2, "The final graph should have 2 nodes.");
+    assert_eq!(final_graph.node_count().unwrap(), 2, "The final graph should have 2 nodes.");
 
     println!("Successfully performed GFA -> ODGI -> GFA roundtrip and verified graph integrity.");
-}
\ No newline at end of file
+}
+#[test]
+fn test_cancelled_conversion() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let output_odgi_path = temp_dir.path().join("tiny.odgi");
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let err = gfa_to_odgi_cancellable("test_data/tiny.gfa", output_odgi_path.to_str().unwrap(), &token)
+        .expect_err("A cancelled conversion should fail");
+    assert!(err.is_cancelled());
+}
+
+#[test]
+fn test_conversion_timeout() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let output_odgi_path = temp_dir.path().join("tiny.odgi");
+    let output_odgi_str = output_odgi_path.to_str().unwrap();
+
+    // A zero timeout expires before the child can finish.
+    let err = gfa_to_odgi_with_timeout("test_data/tiny.gfa", output_odgi_str, Duration::ZERO)
+        .expect_err("A zero timeout should fail");
+    assert!(err.is_timeout());
+    assert!(!err.is_cancelled());
+
+    // A generous timeout behaves like the plain conversion.
+    gfa_to_odgi_with_timeout("test_data/tiny.gfa", output_odgi_str, Duration::from_secs(60))
+        .expect("Conversion within the timeout should succeed");
+    assert!(output_odgi_path.exists());
+}
+
+#[test]
+fn test_conversion_diagnostics() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let output_odgi_path = temp_dir.path().join("queries.odgi");
+
+    let diagnostics = gfa_to_odgi_with_diagnostics("test_data/queries.gfa", output_odgi_path.to_str().unwrap())
+        .expect("Conversion should succeed");
+    assert!(diagnostics.iter().all(|d| d.level != DiagnosticLevel::Error));
+}
+
+#[test]
+fn test_parse_diagnostics() {
+    let stderr = "\n[odgi::gfa_to_handle] warning: skipping unsupported tag XY:Z:foo\n\
+                  [odgi::build] skipped 2 W lines\n\
+                  odgi version 0.9.0\n\
+                  [odgi::view] error: could not open file\n";
+    let diagnostics = Diagnostic::parse_all(stderr);
+    assert_eq!(diagnostics.len(), 4);
+
+    assert_eq!(diagnostics[0].source.as_deref(), Some("odgi::gfa_to_handle"));
+    assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnknownTag);
+
+    assert_eq!(diagnostics[1].kind, DiagnosticKind::DroppedElement);
+    assert_eq!(diagnostics[1].message, "skipped 2 W lines");
+
+    assert_eq!(diagnostics[2].source, None);
+    assert_eq!(diagnostics[2].level, DiagnosticLevel::Info);
+    assert_eq!(diagnostics[2].kind, DiagnosticKind::Version);
+
+    assert_eq!(diagnostics[3].level, DiagnosticLevel::Error);
+}
+
+#[test]
+fn test_failed_conversion_leaves_no_output() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let output_odgi_path = temp_dir.path().join("missing.odgi");
+
+    assert!(gfa_to_odgi("test_data/does_not_exist.gfa", output_odgi_path.to_str().unwrap()).is_err());
+
+    // Neither the target nor the temporary file it would have been renamed from remain.
+    assert!(!output_odgi_path.exists());
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_conversion_output_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let output_odgi_path = temp_dir.path().join("queries.odgi");
+    let output_odgi_str = output_odgi_path.to_str().unwrap();
+
+    gfa_to_odgi("test_data/queries.gfa", output_odgi_str).unwrap();
+    // New files get the same permissions as a file created in place.
+    let reference_path = temp_dir.path().join("reference");
+    std::fs::File::create(&reference_path).unwrap();
+    let mode = std::fs::metadata(&output_odgi_path).unwrap().permissions().mode();
+    let reference_mode = std::fs::metadata(&reference_path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, reference_mode & 0o777);
+
+    // Overwriting keeps the permissions the file already had.
+    std::fs::set_permissions(&output_odgi_path, std::fs::Permissions::from_mode(0o640)).unwrap();
+    gfa_to_odgi("test_data/queries.gfa", output_odgi_str).unwrap();
+    let mode = std::fs::metadata(&output_odgi_path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+}
+
+#[test]
+fn test_to_gfa() {
+    let graph = Graph::from_gfa_file("test_data/queries.gfa").unwrap();
+
+    let mut gfa = Vec::new();
+    graph.to_gfa(&mut gfa).expect("Writing GFA failed");
+    let gfa = String::from_utf8(gfa).unwrap();
+    let lines: Vec<&str> = gfa.lines().collect();
+    assert_eq!(lines[0], "H\tVN:Z:1.0");
+    assert_eq!(lines.iter().filter(|l| l.starts_with("S\t")).count(), 4);
+    assert!(lines.contains(&"S\t1\tGATTACA"));
+    assert!(lines.contains(&"L\t1\t+\t2\t+\t0M"));
+    assert_eq!(lines.iter().filter(|l| l.starts_with("L\t")).count(), 4);
+    assert!(lines.contains(&"P\tx\t1+,2+,4+\t*"));
+
+    // The output reads back to the same graph.
+    let reread = Graph::from_gfa_str(&gfa).unwrap();
+    assert_eq!(reread.fingerprint().unwrap(), graph.fingerprint().unwrap());
+}
@@ -0,0 +1,56 @@
+// File: tests/depth_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::DepthOptions;
+
+#[test]
+fn test_depth_bedgraph_per_base() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Node 1 is on x, y and z; nodes 2 and 4 are each on two paths.
+    let mut out = Vec::new();
+    graph.write_depth_bedgraph("x", &mut out, &DepthOptions::default()).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "x\t0\t7\t3\nx\t7\t11\t2\n");
+}
+
+#[test]
+fn test_depth_bedgraph_windows() {
+    let (graph, _temp_file) = setup_graph();
+
+    let options = DepthOptions { window: Some(5), ..Default::default() };
+    let mut out = Vec::new();
+    graph.write_depth_bedgraph("x", &mut out, &options).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "x\t0\t5\t3\nx\t5\t10\t2.4\nx\t10\t11\t2\n");
+
+    let bad = DepthOptions { window: Some(0), ..Default::default() };
+    assert!(graph.write_depth_bedgraph("x", &mut Vec::new(), &bad).is_err());
+    assert!(graph.write_depth_bedgraph("nonexistent_path", &mut Vec::new(), &options).is_err());
+}
+
+#[test]
+fn test_path_windows() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Path 'x' spells GATTACA + T + GTC = GATTACATGTC (11 bp).
+    let windows: Vec<_> = graph.windows("x", 7, 4).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(windows.len(), 2);
+
+    let first = &windows[0];
+    assert_eq!((first.start, first.end), (0, 7));
+    assert_eq!(first.mean_depth, 3.0);
+    assert_eq!(first.distinct_haplotypes, 3);
+    assert_eq!(first.node_count, 1);
+    assert_eq!(first.gc_content, 2.0 / 7.0);
+    assert_eq!(first.inversion_fraction, 0.0);
+
+    // The second window (ACATGTC) is truncated at the end of the path.
+    let second = &windows[1];
+    assert_eq!((second.start, second.end), (4, 11));
+    assert_eq!(second.node_count, 3);
+    assert_eq!(second.distinct_haplotypes, 3);
+    assert_eq!(second.mean_depth, (3.0 * 3.0 + 2.0 * 4.0) / 7.0);
+
+    assert!(graph.windows("x", 0, 1).is_err());
+    assert!(graph.windows("nonexistent_path", 10, 10).is_err());
+}
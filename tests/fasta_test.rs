@@ -0,0 +1,114 @@
+// File: tests/fasta_test.rs
+mod common;
+
+use common::{load_gfa, setup_graph};
+use odgi_ffi::{gfa_to_odgi, CancellationToken, Graph, NodeFastaOptions};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_extract_fasta_from_bed() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Path 'x' spells GATTACA + T + GTC = GATTACATGTC.
+    let bed = "track name=test\nx\t0\t7\tfirst_node\nx\t6\t10\t.\t0\t-\ny\t7\t8\n";
+    let mut out = Vec::new();
+    let written = graph.extract_fasta_from_bed(bed.as_bytes(), &mut out).unwrap();
+    assert_eq!(written, 3);
+
+    let fasta = String::from_utf8(out).unwrap();
+    assert_eq!(fasta, ">first_node\nGATTACA\n>x:6-10\nACAT\n>y:7-8\nG\n");
+}
+
+#[test]
+fn test_extract_fasta_from_bed_header_prefixes() {
+    // Only the keywords themselves start headers, not path names beginning with them.
+    let (graph, _temp_file) = load_gfa("H\tVN:Z:1.0\nS\t1\tGATTACA\nP\ttrack1\t1+\t*\nP\tbrowser_hap\t1-\t*\n");
+    let bed = "track\nbrowser position track1:1-7\ntrack1\t0\t4\nbrowser_hap\t0\t3\n";
+    let mut out = Vec::new();
+    assert_eq!(graph.extract_fasta_from_bed(bed.as_bytes(), &mut out).unwrap(), 2);
+    assert_eq!(String::from_utf8(out).unwrap(), ">track1:0-4\nGATT\n>browser_hap:0-3\nTGT\n");
+}
+
+#[test]
+fn test_extract_fasta_from_bed_errors() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    assert!(graph.extract_fasta_from_bed("nonexistent_path\t0\t1\n".as_bytes(), &mut out).is_err());
+    assert!(graph.extract_fasta_from_bed("x\t0\t100\n".as_bytes(), &mut out).is_err());
+    assert!(graph.extract_fasta_from_bed("x\tzero\t1\n".as_bytes(), &mut out).is_err());
+}
+
+#[test]
+fn test_write_nodes_fasta() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    graph.write_nodes_fasta(&mut out, &NodeFastaOptions::default()).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), ">1\nGATTACA\n>2\nT\n>3\nG\n>4\nGTC\n");
+
+    let options = NodeFastaOptions { include_length: true, include_depth: true };
+    let mut out = Vec::new();
+    graph.write_nodes_fasta(&mut out, &options).unwrap();
+    let fasta = String::from_utf8(out).unwrap();
+    assert!(fasta.starts_with(">1 len=7 depth=3\nGATTACA\n"));
+    assert!(fasta.contains(">3 len=1 depth=1\nG\n"));
+}
+
+#[test]
+fn test_extract_fasta_from_bed_cancelled() {
+    let (graph, _temp_file) = setup_graph();
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let mut out = Vec::new();
+    let err = graph
+        .extract_fasta_from_bed_cancellable("x\t0\t7\n".as_bytes(), &mut out, &token)
+        .unwrap_err();
+    assert!(err.is_cancelled());
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_write_sequence_dictionary() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    graph.write_sequence_dictionary(&mut out, &["x", "z"]).unwrap();
+    let dict = String::from_utf8(out).unwrap();
+
+    let expected_x = format!("{:x}", md5::compute(b"GATTACATGTC"));
+    let expected_z = format!("{:x}", md5::compute(b"GATTACAT"));
+    assert_eq!(
+        dict,
+        format!("@HD\tVN:1.6\n@SQ\tSN:x\tLN:11\tM5:{}\n@SQ\tSN:z\tLN:8\tM5:{}\n", expected_x, expected_z)
+    );
+
+    assert!(graph.write_sequence_dictionary(&mut Vec::new(), &["x", "missing"]).is_err());
+}
+
+#[test]
+fn test_soft_masking() {
+    let mut gfa = NamedTempFile::new().unwrap();
+    write!(gfa, "H\tVN:Z:1.0\nS\t1\tACgt\nS\t2\tnnnn\nL\t1\t+\t2\t-\t0M\nP\tp\t1+,2-\t*\n").unwrap();
+    let odgi = NamedTempFile::new().unwrap();
+    gfa_to_odgi(gfa.path().to_str().unwrap(), odgi.path().to_str().unwrap()).unwrap();
+    let graph = Graph::load(odgi.path().to_str().unwrap()).unwrap();
+
+    // Lowercase bases survive conversion and retrieval, including on the reverse strand.
+    assert_eq!(graph.get_node_sequence(1).unwrap(), "ACgt");
+    let mut out = Vec::new();
+    graph.extract_fasta_from_bed("p\t0\t8\n".as_bytes(), &mut out).unwrap();
+    assert!(String::from_utf8(out).unwrap().contains("ACgtnnnn"));
+
+    assert_eq!(graph.masked_fraction(1).unwrap(), 0.5);
+    assert_eq!(graph.masked_fraction(2).unwrap(), 1.0);
+    assert!(graph.masked_fraction(99).is_err());
+
+    assert_eq!(graph.path_masked_fraction("p", 0, 2).unwrap(), 0.0);
+    assert_eq!(graph.path_masked_fraction("p", 2, 100).unwrap(), 1.0);
+    assert!(graph.path_masked_fraction("p", 8, 10).is_err());
+    assert!(graph.path_masked_fraction("missing", 0, 1).is_err());
+}
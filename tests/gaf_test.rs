@@ -0,0 +1,223 @@
+// File: tests/gaf_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::{gaf, gam};
+use std::io::{Cursor, Write};
+
+#[test]
+fn test_read_gaf_records() {
+    let gaf_text = "# comment\nread1\t20\t2\t13\t+\t>1<3>4\t11\t0\t11\t10\t11\t60\tcg:Z:7=1X3=\n";
+    let records: Vec<_> = gaf::records(Cursor::new(gaf_text)).collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.query_name, "read1");
+    assert_eq!((record.query_start, record.query_end), (2, 13));
+    assert_eq!(record.walk, vec![(1, true), (3, false), (4, true)]);
+    assert_eq!(record.mapping_quality, 60);
+    assert_eq!(record.cigar, Some(vec![('=', 7), ('X', 1), ('=', 3)]));
+
+    let bad = gaf::records(Cursor::new("read\t1\t0\t1\t+\tchr1:0-10\t10\t0\t1\t1\t1\t0\n")).next().unwrap();
+    assert!(bad.is_err());
+}
+
+#[test]
+fn test_surject_to_reference() {
+    let (graph, _temp_file) = setup_graph();
+
+    let gaf_text = "\
+        read1\t11\t0\t11\t+\t>1>3>4\t11\t0\t11\t11\t11\t60\n\
+        read2\t8\t0\t8\t+\t>1>2\t8\t0\t8\t7\t8\t30\tcg:Z:7=1X\n\
+        read3\t11\t0\t11\t+\t<4<2<1\t11\t0\t11\t10\t11\t60\tcg:Z:3=1X7=\n";
+    let mut out = Vec::new();
+    let written = gaf::surject(&graph, Cursor::new(gaf_text), "x", &mut out).unwrap();
+    let paf = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = paf.lines().collect();
+
+    assert_eq!(written, 4);
+    assert_eq!(
+        lines,
+        vec![
+            // Node 3 is not on 'x', so read1 is split around it.
+            "read1\t11\t0\t7\t+\tx\t11\t0\t7\t7\t7\t60\tcg:Z:7M",
+            "read1\t11\t8\t11\t+\tx\t11\t8\t11\t3\t3\t60\tcg:Z:3M",
+            "read2\t8\t0\t8\t+\tx\t11\t0\t8\t7\t8\t30\tcg:Z:7=1X",
+            // A walk against the reference lands on the reverse strand.
+            "read3\t11\t0\t11\t-\tx\t11\t0\t11\t10\t11\t60\tcg:Z:7=1X3=",
+        ]
+    );
+}
+
+/// Appends a protobuf varint to `out`.
+fn varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Appends a length-delimited protobuf field to `out`.
+fn bytes_field(out: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    varint(out, (field << 3) | 2);
+    varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+/// Appends a varint protobuf field to `out`.
+fn varint_field(out: &mut Vec<u8>, field: u64, value: u64) {
+    varint(out, field << 3);
+    varint(out, value);
+}
+
+/// A vg `Edit` as `(from_length, to_length, sequence)`.
+type GamEdit<'a> = (u64, u64, &'a str);
+
+/// Encodes a vg `Alignment` whose mappings are `(node_id, offset, edits)`.
+fn gam_alignment(name: &str, sequence: &str, mappings: &[(u64, u64, &[GamEdit])]) -> Vec<u8> {
+    let mut path = Vec::new();
+    for &(node_id, offset, edits) in mappings {
+        let mut position = Vec::new();
+        varint_field(&mut position, 1, node_id);
+        varint_field(&mut position, 2, offset);
+        let mut mapping = Vec::new();
+        bytes_field(&mut mapping, 1, &position);
+        for &(from, to, edit_sequence) in edits {
+            let mut edit = Vec::new();
+            varint_field(&mut edit, 1, from);
+            varint_field(&mut edit, 2, to);
+            if !edit_sequence.is_empty() {
+                bytes_field(&mut edit, 3, edit_sequence.as_bytes());
+            }
+            bytes_field(&mut mapping, 2, &edit);
+        }
+        bytes_field(&mut path, 2, &mapping);
+    }
+    let mut alignment = Vec::new();
+    bytes_field(&mut alignment, 1, sequence.as_bytes());
+    bytes_field(&mut alignment, 2, &path);
+    bytes_field(&mut alignment, 3, name.as_bytes());
+    varint_field(&mut alignment, 5, 42);
+    alignment
+}
+
+/// Wraps messages into a single tagged GAM group.
+fn gam_group(messages: &[Vec<u8>]) -> Vec<u8> {
+    let mut group = Vec::new();
+    varint(&mut group, messages.len() as u64 + 1);
+    varint(&mut group, 3);
+    group.extend_from_slice(b"GAM");
+    for message in messages {
+        varint(&mut group, message.len() as u64);
+        group.extend_from_slice(message);
+    }
+    group
+}
+
+#[test]
+fn test_read_gam_records() {
+    let (graph, _temp_file) = setup_graph();
+
+    // TTACA on node 1, a mismatch on node 2, GT on node 4, then a soft-clipped GG.
+    let read = gam_alignment(
+        "read1",
+        "TTACAAGTGG",
+        &[
+            (1, 2, &[(5, 5, "")]),
+            (2, 0, &[(1, 1, "A")]),
+            (4, 0, &[(2, 2, ""), (0, 2, "GG")]),
+        ],
+    );
+    let unmapped = gam_alignment("unmapped", "ACGT", &[]);
+    let stream = gam_group(&[read, unmapped]);
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&stream).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    for input in [stream, compressed] {
+        let records: Vec<_> = gam::records(Cursor::new(input), &graph).collect::<Result<_, _>>().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.query_name, "read1");
+        assert_eq!((record.query_length, record.query_start, record.query_end), (10, 0, 8));
+        assert_eq!(record.walk, vec![(1, true), (2, true), (4, true)]);
+        assert_eq!((record.walk_length, record.walk_start, record.walk_end), (11, 2, 10));
+        assert_eq!(record.mapping_quality, 42);
+        assert_eq!(record.cigar, Some(vec![('=', 5), ('X', 1), ('=', 2)]));
+    }
+
+    // Nodes that are not in the graph are reported instead of silently dropped.
+    let foreign = gam_group(&[gam_alignment("read2", "A", &[(99, 0, &[(1, 1, "")])])]);
+    let result = gam::records(Cursor::new(foreign), &graph).next().unwrap();
+    assert!(result.is_err());
+
+    // A message length beyond the end of the stream is an error, not an allocation.
+    let mut truncated = Vec::new();
+    varint(&mut truncated, 1);
+    varint(&mut truncated, u64::MAX >> 1);
+    let result = gam::records(Cursor::new(truncated), &graph).next().unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_inject_alignments() {
+    let gaf_text = "read1\t8\t0\t8\t+\t>1>2>4\t11\t2\t10\t8\t8\t60\n\
+                    read1\t20\t12\t20\t+\t>1>2>4\t11\t2\t10\t8\t8\t60\n";
+    let alignments: Vec<_> = gaf::records(Cursor::new(gaf_text)).collect::<Result<_, _>>().unwrap();
+
+    // Without splitting, the end nodes are visited in full.
+    let (mut graph, _temp_file) = setup_graph();
+    assert_eq!(graph.inject_alignments(&alignments, "reads#", false).unwrap(), 2);
+    assert_eq!(graph.get_path_length("reads#read1").unwrap(), 11);
+    assert_eq!(graph.get_path_length("reads#read1:12-20").unwrap(), 11);
+    assert_eq!(graph.node_count().unwrap(), 4);
+
+    // With splitting, nodes 1 and 4 are divided and the paths spell only the aligned bases.
+    let (mut graph, _temp_file) = setup_graph();
+    graph.inject_alignments(&alignments[..1], "reads#", true).unwrap();
+    assert_eq!(graph.get_path_length("reads#read1").unwrap(), 8);
+    assert_eq!(graph.node_count().unwrap(), 6);
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+
+    // Walks over missing edges are rejected without touching the graph.
+    let bad: Vec<_> = gaf::records(Cursor::new("read2\t2\t0\t2\t+\t>2>3\t2\t0\t2\t2\t2\t60\n"))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(graph.inject_alignments(&bad, "reads#", true).is_err());
+    assert!(graph.get_path_length("reads#read2").is_err());
+}
+
+#[test]
+fn test_add_assembly() {
+    // ctg1 carries flanks and a SNP relative to path 'x', ctg2 is the reverse
+    // complement of the start of 'x', and ctg3 is not aligned at all.
+    let fasta = ">ctg1 primary\nCCGATTACAAGTCAA\n>ctg2\nATGTAATC\n>ctg3\nACGT\n";
+    let alignments = "ctg1\t15\t2\t13\t+\t>1>2>4\t11\t0\t11\t10\t11\t60\tcg:Z:7=1X3=\n\
+                      ctg2\t8\t0\t8\t-\tx\t11\t0\t8\t8\t8\t60\n";
+    let (mut graph, _temp_file) = setup_graph();
+    let report = graph.add_assembly(Cursor::new(fasta), Cursor::new(alignments), "HG002#1").unwrap();
+
+    assert_eq!(report.paths, vec!["HG002#1#ctg1", "HG002#1#ctg2", "HG002#1#ctg3"]);
+    // The flanks, the SNP allele, and ctg3 are novel; everything else reuses nodes 1, 2, and 4.
+    assert_eq!((report.new_nodes, report.novel_bp, report.reused_bp), (4, 9, 18));
+    assert_eq!(graph.node_count().unwrap(), 8);
+    assert!(graph.get_paths_on_node(1).unwrap().contains(&"HG002#1#ctg2".to_string()));
+    assert!(!graph.get_paths_on_node(2).unwrap().contains(&"HG002#1#ctg1".to_string()));
+
+    // The new paths spell their contigs.
+    let bed = "HG002#1#ctg1\t0\t15\nHG002#1#ctg2\t0\t8\nHG002#1#ctg3\t0\t4\n";
+    let mut out = Vec::new();
+    graph.extract_fasta_from_bed(Cursor::new(bed), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    for contig in ["CCGATTACAAGTCAA", "ATGTAATC", "ACGT"] {
+        assert!(out.lines().any(|line| line == contig), "{} missing from {}", contig, out);
+    }
+
+    // Adding the same sample again, or alignments to unknown contigs, fails without changes.
+    assert!(graph.add_assembly(Cursor::new(fasta), Cursor::new(""), "HG002#1").is_err());
+    let unknown = "ctg9\t8\t0\t8\t+\tx\t11\t0\t8\t8\t8\t60\n";
+    assert!(graph.add_assembly(Cursor::new(fasta), Cursor::new(unknown), "HG002#2").is_err());
+    assert_eq!(graph.node_count().unwrap(), 8);
+}
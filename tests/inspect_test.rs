@@ -0,0 +1,55 @@
+// File: tests/inspect_test.rs
+use odgi_ffi::{gfa_to_odgi, inspect};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_inspect_header() {
+    let info = inspect("test_data/tiny.odgi").expect("Header inspection should succeed");
+
+    assert_eq!(info.format_version, 1);
+    assert_eq!(info.node_count, 2);
+    assert_eq!(info.edge_count, 1);
+    assert_eq!(info.path_count, 0);
+    assert_eq!(info.min_node_id, 1);
+    assert_eq!(info.max_node_id, 2);
+}
+
+#[test]
+fn test_inspect_converted_graph() {
+    let odgi_file = NamedTempFile::new().unwrap();
+    let odgi_path = odgi_file.path().to_str().unwrap();
+    gfa_to_odgi("test_data/queries.gfa", odgi_path).unwrap();
+
+    let info = inspect(odgi_path).unwrap();
+    assert_eq!(info.node_count, 4);
+    assert_eq!(info.edge_count, 4);
+    assert_eq!(info.path_count, 3);
+}
+
+#[test]
+fn test_inspect_rejects_non_odgi_files() {
+    assert!(inspect("test_data/tiny.gfa").is_err());
+    assert!(inspect("nonexistent.odgi").is_err());
+
+    let mut short_file = NamedTempFile::new().unwrap();
+    short_file.write_all(&[0x76, 0x80]).unwrap();
+    assert!(inspect(short_file.path().to_str().unwrap()).is_err());
+}
+
+#[test]
+fn test_load_reports_version_mismatch() {
+    let mut foreign_file = NamedTempFile::new().unwrap();
+    foreign_file.write_all(&[0xde, 0xad, 0xbe, 0xef]).unwrap();
+    foreign_file.write_all(&[0u8; 64]).unwrap();
+
+    let err = odgi_ffi::Graph::load(foreign_file.path().to_str().unwrap())
+        .err()
+        .expect("Loading a file with an unknown magic number should fail");
+    assert!(err.is_version_mismatch());
+    assert!(err.to_string().contains("0xdeadbeef"));
+
+    // Missing files are not version mismatches.
+    let err = odgi_ffi::Graph::load("nonexistent.odgi").err().unwrap();
+    assert!(!err.is_version_mismatch());
+}
@@ -0,0 +1,66 @@
+// File: tests/interop_test.rs
+mod common;
+
+use common::{load_gfa, setup_graph};
+use odgi_ffi::interop;
+
+#[test]
+fn test_match_nodes_renumbered() {
+    let (a, _a_file) = setup_graph();
+    let (b, _b_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t10\tGTC\nS\t20\tGATTACA\nS\t30\tG\nS\t40\tT\n\
+         L\t20\t+\t40\t+\t0M\nL\t20\t+\t30\t+\t0M\nL\t40\t+\t10\t+\t0M\nL\t30\t+\t10\t+\t0M\n",
+    );
+
+    let matches = interop::match_nodes(&a, &b).unwrap();
+    let pairs: Vec<(u64, u64)> = matches.iter().map(|m| (m.a_node, m.b_node)).collect();
+    assert_eq!(pairs, vec![(1, 20), (2, 40), (3, 30), (4, 10)]);
+    assert!(matches.iter().all(|m| m.confidence == 1.0));
+}
+
+#[test]
+fn test_match_nodes_by_topology() {
+    // Nodes 2 and 4 share a sequence and differ only in their neighbours.
+    let (a, _a_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tCC\nS\t2\tA\nS\t3\tGG\nS\t4\tA\nS\t5\tTT\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t5\t+\t0M\n",
+    );
+    let (b, _b_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t6\tA\nS\t7\tTT\nS\t8\tCC\nS\t9\tA\nS\t10\tGG\n\
+         L\t8\t+\t9\t+\t0M\nL\t9\t+\t10\t+\t0M\nL\t10\t+\t6\t+\t0M\nL\t6\t+\t7\t+\t0M\n",
+    );
+
+    let matches = interop::match_nodes(&a, &b).unwrap();
+    let find = |a_node| matches.iter().find(|m| m.a_node == a_node).unwrap();
+    assert_eq!(find(2).b_node, 9);
+    assert_eq!(find(4).b_node, 6);
+    assert!(find(2).confidence < find(1).confidence);
+}
+
+#[test]
+fn test_liftover_across_graphs() {
+    use odgi_ffi::{liftover, PathPosition};
+
+    // Both graphs spell "GATTACATGTC" along path 'x', chopped differently.
+    let (a, _a_file) = setup_graph();
+    let (b, _b_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tGATT\nS\t2\tACAT\nS\t3\tGTC\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nP\tx\t1+,2+,3+\t*\n",
+    );
+
+    let positions = [
+        PathPosition { node_id: 1, offset: 5, is_forward: true },
+        PathPosition { node_id: 4, offset: 0, is_forward: false },
+        // Node 3 is not on the reference path.
+        PathPosition { node_id: 3, offset: 0, is_forward: true },
+    ];
+    let lifted = liftover::across_graphs(&a, &b, "x", &positions).unwrap();
+
+    let first = lifted[0].as_ref().unwrap();
+    assert_eq!((first.node_id, first.offset, first.is_forward), (2, 1, true));
+    let second = lifted[1].as_ref().unwrap();
+    assert_eq!((second.node_id, second.offset, second.is_forward), (3, 0, false));
+    assert!(lifted[2].is_none());
+
+    assert!(liftover::across_graphs(&a, &b, "y", &positions).is_err());
+}
@@ -1,6 +1,6 @@
 // tests/load_graph_test.rs
 
-use odgi_ffi::Graph;
+use odgi_ffi::{ErrorKind, Graph};
 
 #[test]
 fn test_safe_graph_api() {
@@ -12,10 +12,92 @@ fn test_safe_graph_api() {
     println!("Successfully loaded graph using the safe API.");
 
     // 2. Call the safe `node_count` method.
-    let count = graph.node_count();
+    let count = graph.node_count().unwrap();
     println!("Graph has {} nodes.", count);
 
     // 3. Assert that the result is correct for our test graph.
     assert_eq!(count, 2, "The node count should be 2 for the test graph.");
     println!("Node count is correct.");
-}
\ No newline at end of file
+}
+#[test]
+fn test_load_many() {
+    let paths = ["test_data/tiny.odgi", "test_data/missing.odgi", "test_data/tiny.odgi"];
+
+    let reports = std::sync::Mutex::new(Vec::new());
+    let results = Graph::load_many_with_progress(&paths, 2, |p| reports.lock().unwrap().push(p));
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().node_count().unwrap(), 2);
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap().node_count().unwrap(), 2);
+
+    // One report per file, with the counts increasing to the total.
+    let reports = reports.into_inner().unwrap();
+    let completed: Vec<usize> = reports.iter().map(|p| p.completed).collect();
+    assert_eq!(completed, vec![1, 2, 3]);
+    assert_eq!(reports.iter().filter(|p| !p.succeeded).count(), 1);
+    let last = reports.last().unwrap();
+    assert_eq!((last.total, last.bytes_completed), (3, last.total_bytes));
+
+    assert!(Graph::load_many(&paths[..1], 0)[0].is_ok());
+    assert!(Graph::load_many::<&str>(&[], 4).is_empty());
+}
+
+#[test]
+fn test_prefault() {
+    let graph = Graph::load("test_data/tiny.odgi").unwrap();
+    graph.prefault().unwrap();
+    assert_eq!(graph.node_count().unwrap(), 2);
+}
+
+#[test]
+fn test_odgi_threads() {
+    odgi_ffi::set_odgi_threads(2);
+    let expected = if cfg!(feature = "no-openmp") { 1 } else { 2 };
+    assert_eq!(odgi_ffi::odgi_threads(), expected);
+
+    // Zero restores one thread per CPU.
+    odgi_ffi::set_odgi_threads(0);
+    assert!(odgi_ffi::odgi_threads() >= 1);
+}
+
+#[test]
+fn test_from_gfa() {
+    let graph = Graph::from_gfa_file("test_data/queries.gfa").unwrap();
+    assert_eq!(graph.node_count().unwrap(), 4);
+    assert_eq!(graph.get_path_names().unwrap(), vec!["x", "y", "z"]);
+    assert_eq!(graph.get_node_sequence(1).unwrap(), "GATTACA");
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+
+    let gfa = std::fs::read_to_string("test_data/queries.gfa").unwrap();
+    let from_str = Graph::from_gfa_str(&gfa).unwrap();
+    assert_eq!(from_str.fingerprint().unwrap(), graph.fingerprint().unwrap());
+
+    assert!(Graph::from_gfa_file("test_data/missing.gfa").is_err());
+}
+
+#[test]
+fn test_load_from_bytes() {
+    let bytes = std::fs::read("test_data/tiny.odgi").unwrap();
+    let graph = Graph::load_from_bytes(&bytes).unwrap();
+    assert_eq!(graph.node_count().unwrap(), 2);
+
+    let graph = Graph::load_from_reader(std::io::Cursor::new(&bytes)).unwrap();
+    assert_eq!(graph.node_count().unwrap(), 2);
+
+    let compressed = zstd::encode_all(&bytes[..], 3).unwrap();
+    assert_eq!(Graph::load_from_bytes(&compressed).unwrap().node_count().unwrap(), 2);
+
+    assert!(Graph::load_from_bytes(b"not a graph").is_err());
+    assert!(Graph::load_from_bytes(&[]).is_err());
+}
+
+#[test]
+fn test_load_truncated_graph() {
+    let bytes = std::fs::read("test_data/tiny.odgi").unwrap();
+    let err = Graph::load_from_bytes(&bytes[..100]).err().expect("a truncated graph does not load");
+    assert!(matches!(err.kind(), ErrorKind::Exception(_)), "unexpected error: {}", err);
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), &bytes[..100]).unwrap();
+    assert!(Graph::load(file.path().to_str().unwrap()).is_err());
+}
@@ -0,0 +1,100 @@
+// File: tests/metrics_test.rs
+// Run with `cargo test --features metrics --test metrics_test`.
+#![cfg(feature = "metrics")]
+mod common;
+
+use common::{load_gfa, setup_graph};
+use metrics::{Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Counts counter increments and histogram samples.
+#[derive(Default)]
+struct Tally(AtomicU64);
+
+impl CounterFn for Tally {
+    fn increment(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    fn absolute(&self, value: u64) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+}
+
+impl HistogramFn for Tally {
+    fn record(&self, _value: f64) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Records every counter and histogram under `name{label=value}`.
+#[derive(Default)]
+struct TestRecorder {
+    tallies: Mutex<HashMap<String, Arc<Tally>>>,
+}
+
+impl TestRecorder {
+    fn tally(&self, key: &Key) -> Arc<Tally> {
+        let labels: Vec<String> = key.labels().map(|label| format!("{}={}", label.key(), label.value())).collect();
+        let name = format!("{}{{{}}}", key.name(), labels.join(","));
+        self.tallies.lock().unwrap().entry(name).or_default().clone()
+    }
+
+    /// Returns the counter's total, or the histogram's sample count.
+    fn get(&self, name: &str) -> u64 {
+        self.tallies.lock().unwrap().get(name).map_or(0, |tally| tally.0.load(Ordering::Relaxed))
+    }
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.tally(key))
+    }
+
+    fn register_gauge(&self, _key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.tally(key))
+    }
+}
+
+#[test]
+fn test_query_metrics() {
+    let (graph, _temp_file) = setup_graph();
+    let recorder = TestRecorder::default();
+
+    let names = metrics::with_local_recorder(&recorder, || graph.get_path_names().unwrap());
+    assert_eq!(names.len(), 3);
+    assert_eq!(recorder.get("odgi_ffi_queries_total{query=get_path_names}"), 1);
+    assert_eq!(recorder.get("odgi_ffi_ffi_calls_total{function=get_path_names}"), 1);
+    assert_eq!(recorder.get("odgi_ffi_query_duration_seconds{query=get_path_names}"), 1);
+}
+
+#[test]
+fn test_cache_metrics() {
+    let (graph, _temp_file) = setup_graph();
+    let recorder = TestRecorder::default();
+
+    // Path x is 1+ (7 bp), 2+ (1 bp), 4+ (3 bp). The first window sees nodes 1 and 2,
+    // and the second finds them in the cache and only looks up node 4.
+    let windows = metrics::with_local_recorder(&recorder, || graph.windows("x", 8, 4).unwrap().count());
+    assert_eq!(windows, 2);
+    assert_eq!(recorder.get("odgi_ffi_cache_misses_total{cache=windows_nodes}"), 3);
+    assert_eq!(recorder.get("odgi_ffi_cache_hits_total{cache=windows_nodes}"), 2);
+
+    // A node that leaves the window is evicted, so revisiting it later misses again.
+    let (looped, _looped_file) = load_gfa("H\tVN:Z:1.0\nS\t1\tAAAA\nS\t2\tCCCC\nL\t1\t+\t2\t+\t0M\nL\t2\t+\t1\t+\t0M\nP\tp\t1+,2+,1+\t*\n");
+    let recorder = TestRecorder::default();
+    let windows = metrics::with_local_recorder(&recorder, || looped.windows("p", 4, 4).unwrap().count());
+    assert_eq!(windows, 3);
+    assert_eq!(recorder.get("odgi_ffi_cache_misses_total{cache=windows_nodes}"), 3);
+    assert_eq!(recorder.get("odgi_ffi_cache_hits_total{cache=windows_nodes}"), 0);
+}
@@ -0,0 +1,136 @@
+// File: tests/paf_test.rs
+mod common;
+
+use common::{load_gfa, setup_graph};
+
+#[test]
+fn test_paths_to_paf_substitution() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    assert_eq!(graph.paths_to_paf("x", &mut out).unwrap(), 2);
+    let paf = String::from_utf8(out).unwrap();
+
+    // Path 'y' takes the G allele where 'x' has a T.
+    assert!(paf.contains("y\t11\t0\t11\t+\tx\t11\t0\t11\t10\t11\t255\tcg:Z:7=1X3=\n"));
+    // Path 'z' ends early.
+    assert!(paf.contains("z\t8\t0\t8\t+\tx\t11\t0\t8\t8\t8\t255\tcg:Z:8=\n"));
+}
+
+#[test]
+fn test_paths_to_paf_deletion_on_reverse_strand() {
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tTT\nS\t3\tGGG\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t3\t+\t0M\n\
+         P\tref\t1+,2+,3+\t*\nP\tq\t3-,1-\t*\n",
+    );
+
+    let mut out = Vec::new();
+    graph.paths_to_paf("ref", &mut out).unwrap();
+    let paf = String::from_utf8(out).unwrap();
+    assert_eq!(paf, "q\t7\t0\t7\t-\tref\t9\t0\t9\t7\t9\t255\tcg:Z:4=2D3=\n");
+
+    assert!(graph.paths_to_paf("missing", &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_shared_segments() {
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tTT\nS\t3\tGGG\nS\t4\tA\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t1\t+\t3\t-\t0M\nL\t2\t-\t4\t+\t0M\n\
+         P\ta\t1+,2+,3+,4+\t*\nP\tb\t1+,3-,2-,4+\t*\n",
+    );
+
+    // Nodes 2 and 3 are inverted on 'b' between the shared flanks.
+    let blocks = graph.shared_segments("a", "b", 2).unwrap();
+    let spans: Vec<(u64, u64, u64, u64, bool, u64)> = blocks
+        .iter()
+        .map(|s| (s.a_start, s.a_end, s.b_start, s.b_end, s.is_reverse, s.node_count))
+        .collect();
+    assert_eq!(spans, vec![(0, 4, 0, 4, false, 1), (4, 9, 4, 9, true, 2)]);
+
+    assert_eq!(graph.shared_segments("a", "b", 0).unwrap().len(), 3);
+    assert_eq!(graph.shared_segments("a", "a", 0).unwrap().len(), 1);
+    assert!(graph.shared_segments("a", "missing", 0).is_err());
+}
+
+#[test]
+fn test_write_chain() {
+    use odgi_ffi::liftover;
+
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tTT\nS\t3\tGGG\nS\t4\tA\nS\t5\tC\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t3\t+\t5\t+\t0M\n\
+         P\told\t1+,2+,3+,4+\t*\nP\tnew\t1+,3+,5+\t*\n",
+    );
+
+    let mut out = Vec::new();
+    liftover::write_chain(&graph, "old", "new", &mut out).unwrap();
+    let chain = String::from_utf8(out).unwrap();
+
+    // Node 2 is deleted in 'new'; the trailing A/C difference ends the alignment.
+    assert_eq!(chain, "chain 7 old 10 + 0 9 new 8 + 0 7 1\n4\t2\t0\n3\n\n");
+
+    assert!(liftover::write_chain(&graph, "old", "missing", &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_align_paths() {
+    use odgi_ffi::AlignmentBlockKind;
+
+    // 'b' skips node 2, inverts node 4, and inserts node 7 before the end.
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tTT\nS\t3\tGGG\nS\t4\tAAC\nS\t5\tCA\nS\t6\tTTT\nS\t7\tG\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t5\t+\t0M\n\
+         L\t3\t+\t4\t-\t0M\nL\t4\t-\t5\t+\t0M\nL\t5\t+\t6\t+\t0M\nL\t5\t+\t7\t+\t0M\nL\t7\t+\t6\t+\t0M\n\
+         P\ta\t1+,2+,3+,4+,5+,6+\t*\nP\tb\t1+,3+,4-,5+,7+,6+\t*\n",
+    );
+
+    let alignment = graph.align_paths("a", "b").unwrap();
+    assert!(!alignment.is_reverse);
+    let blocks: Vec<(AlignmentBlockKind, u64, u64, u64, u64, &str)> = alignment
+        .blocks
+        .iter()
+        .map(|b| (b.kind, b.a_start, b.a_end, b.b_start, b.b_end, b.cigar.as_str()))
+        .collect();
+    assert_eq!(
+        blocks,
+        vec![
+            (AlignmentBlockKind::Match, 0, 4, 0, 4, "4="),
+            (AlignmentBlockKind::Deletion, 4, 6, 4, 4, "2D"),
+            (AlignmentBlockKind::Match, 6, 9, 4, 7, "3="),
+            (AlignmentBlockKind::Inversion, 9, 12, 7, 10, "3="),
+            (AlignmentBlockKind::Match, 12, 14, 10, 12, "2="),
+            (AlignmentBlockKind::Insertion, 14, 14, 12, 13, "1I"),
+            (AlignmentBlockKind::Match, 14, 17, 13, 16, "3="),
+        ]
+    );
+    // The inverted AAC reads as GTT on 'b', three mismatches in the overall CIGAR.
+    assert_eq!(alignment.cigar, "4=2D3=3X2=1I3=");
+
+    assert!(graph.align_paths("a", "missing").is_err());
+}
+
+#[test]
+fn test_path_identity() {
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tTT\nS\t3\tGGG\nS\t4\tAAC\nS\t5\tCA\nS\t6\tTTT\nS\t7\tG\nS\t8\tCC\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t5\t+\t0M\n\
+         L\t3\t+\t4\t-\t0M\nL\t4\t-\t5\t+\t0M\nL\t5\t+\t6\t+\t0M\nL\t5\t+\t7\t+\t0M\nL\t7\t+\t6\t+\t0M\nL\t8\t+\t8\t+\t0M\n\
+         P\ta\t1+,2+,3+,4+,5+,6+\t*\nP\tb\t1+,3+,4-,5+,7+,6+\t*\nP\tc\t8+\t*\n",
+    );
+
+    // Two deleted bases, AAC against GTT (three substitutions), and one inserted base.
+    let identity = graph.path_identity("a", "b").unwrap().unwrap();
+    assert!(!identity.is_reverse);
+    assert_eq!((identity.a_start, identity.a_end, identity.b_start, identity.b_end), (0, 17, 0, 16));
+    assert_eq!(identity.edit_distance, 6);
+    assert!((identity.identity() - 11.0 / 17.0).abs() < 1e-9);
+
+    let same = graph.path_identity("a", "a").unwrap().unwrap();
+    assert_eq!(same.edit_distance, 0);
+    assert_eq!(same.identity(), 1.0);
+
+    assert!(graph.path_identity("a", "c").unwrap().is_none());
+    assert!(graph.path_identity("a", "missing").is_err());
+}
@@ -0,0 +1,63 @@
+// File: tests/presence_test.rs
+mod common;
+
+use common::load_gfa;
+use odgi_ffi::{Graph, PresenceClass, PresenceThresholds};
+use tempfile::NamedTempFile;
+
+/// Loads a graph whose paths belong to three PanSN samples.
+fn pansn_graph() -> (Graph, NamedTempFile) {
+    load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGTACGT\nS\t2\tTT\nS\t3\tG\nS\t4\tCCC\nS\t5\tAAAA\n\
+         L\t1\t+\t2\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t2\t+\t4\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t5\t+\t0M\n\
+         P\tA#1#chr1\t1+,2+,4+\t*\nP\tA#2#chr1\t1+,3+,4+,5+\t*\nP\tB#1#chr1\t1+,2+,4+\t*\nP\tC#1#chr1\t1+,2+,4+\t*\n",
+    )
+}
+
+#[test]
+fn test_classify_nodes_by_presence() {
+    let (graph, _temp_file) = pansn_graph();
+
+    let thresholds = PresenceThresholds { core: 1.0, cloud: 0.34 };
+    let summary = graph.classify_nodes_by_presence(&thresholds).unwrap();
+
+    // Both haplotypes of sample A count as a single sample.
+    assert_eq!(summary.sample_count, 3);
+    assert_eq!(
+        summary.nodes,
+        vec![
+            (1, PresenceClass::Core),
+            (2, PresenceClass::Core),
+            (3, PresenceClass::Cloud),
+            (4, PresenceClass::Core),
+            (5, PresenceClass::Cloud),
+        ]
+    );
+    assert_eq!(summary.core_bp, 13);
+    assert_eq!(summary.shell_bp, 0);
+    assert_eq!(summary.cloud_bp, 5);
+}
+
+#[test]
+fn test_classify_nodes_shell() {
+    let (graph, _temp_file) = pansn_graph();
+
+    let summary = graph.classify_nodes_by_presence(&PresenceThresholds { core: 1.0, cloud: 0.0 }).unwrap();
+    assert_eq!(summary.shell_bp, 5);
+    assert!(summary.nodes.iter().all(|&(_, class)| class != PresenceClass::Cloud));
+}
+
+#[test]
+fn test_copy_number() {
+    let (graph, _temp_file) = pansn_graph();
+
+    let copies = graph.copy_number(1).unwrap();
+    let expected: Vec<(String, u64)> = vec![("A".into(), 2), ("B".into(), 1), ("C".into(), 1)];
+    assert_eq!(copies.into_iter().collect::<Vec<_>>(), expected);
+
+    // Samples that do not visit the node are reported with zero copies.
+    let copies = graph.copy_number(5).unwrap();
+    assert_eq!(copies["A"], 1);
+    assert_eq!(copies["B"], 0);
+    assert_eq!(copies.len(), 3);
+}
@@ -1,32 +1,18 @@
 // File: tests/query_graph_test.rs
-use odgi_ffi::{gfa_to_odgi, Graph};
-use tempfile::NamedTempFile;
-
-/// A helper function to set up the graph for each test.
-/// It converts our new queries.gfa to a temporary ODGI file
-/// and loads it into a Graph object.
-fn setup_graph() -> (Graph, tempfile::NamedTempFile) {
-    let gfa_path = "test_data/queries.gfa";
-
-    // Use a temporary file for the ODGI output to keep tests clean.
-    let odgi_temp_file = NamedTempFile::new().expect("Failed to create temp ODGI file");
-    let odgi_path = odgi_temp_file.path().to_str().unwrap();
-
-    gfa_to_odgi(gfa_path, odgi_path).expect("Test setup: GFA to ODGI conversion failed");
-    let graph = Graph::load(odgi_path).expect("Test setup: Failed to load ODGI graph");
+mod common;
 
-    (graph, odgi_temp_file)
-}
+use common::{load_gfa, setup_graph};
+use odgi_ffi::testing::{random_graph, RandomGraphOptions};
+use odgi_ffi::{gfa_to_odgi, Direction, EdgeKey, ErrorKind, FlipOptions, Graph, GraphStats, Handle, KmerWeighting, LoadOptions, MinimizerHit, MinimizerIndex, PathIndexing, RankedStep};
+use std::io::Write;
+use tempfile::NamedTempFile;
 
 #[test]
 fn test_get_path_names() {
     let (graph, _temp_file) = setup_graph();
 
-    let mut path_names = graph.get_path_names();
-    // The order of path names is not guaranteed, so we sort for a stable comparison.
-    path_names.sort();
-
-    assert_eq!(path_names, vec!["x", "y", "z"]);
+    // Paths are listed in the order of the GFA's P lines.
+    assert_eq!(graph.get_path_names().unwrap(), vec!["x", "y", "z"]);
 }
 
 #[test]
@@ -34,16 +20,17 @@ fn test_get_node_properties() {
     let (graph, _temp_file) = setup_graph();
 
     // Test sequence content
-    assert_eq!(graph.get_node_sequence(1), "GATTACA");
-    assert_eq!(graph.get_node_sequence(4), "GTC");
+    assert_eq!(graph.get_node_sequence(1).unwrap(), "GATTACA");
+    assert_eq!(graph.get_node_sequence(4).unwrap(), "GTC");
     
     // Test sequence length
-    assert_eq!(graph.get_node_len(1), 7);
-    assert_eq!(graph.get_node_len(2), 1);
+    assert_eq!(graph.get_node_len(1).unwrap(), 7);
+    assert_eq!(graph.get_node_len(2).unwrap(), 1);
 
     // Test a non-existent node
-    assert_eq!(graph.get_node_sequence(999), "");
-    assert_eq!(graph.get_node_len(999), 0);
+    let err = graph.get_node_sequence(999).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NodeNotFound(999)), "unexpected error: {}", err);
+    assert!(matches!(graph.get_node_len(999).unwrap_err().kind(), ErrorKind::NodeNotFound(999)));
 }
 
 
@@ -78,8 +65,10 @@ fn test_project_coordinates() {
     assert!(p4.is_forward);
     
     // Test projections that should fail
-    assert!(graph.project("x", 100).is_none(), "Position out of bounds should fail");
-    assert!(graph.project("nonexistent_path", 0).is_none(), "Non-existent path should fail");
+    let err = graph.project("x", 100).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_)), "Position out of bounds should fail: {}", err);
+    let err = graph.project("nonexistent_path", 0).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::PathNotFound(_)), "Non-existent path should fail: {}", err);
 }
 
 
@@ -88,7 +77,7 @@ fn test_get_successors() {
     let (graph, _temp_file) = setup_graph();
 
     // Node 1 should have two successors: 2 and 3.
-    let succs = graph.get_successors(1);
+    let succs = graph.get_successors(1).unwrap();
     assert_eq!(succs.len(), 2, "Node 1 should have two successors");
     
     // Check that an edge to node 2 exists with the correct orientations.
@@ -102,7 +91,7 @@ fn test_get_successors() {
     ), "Should find edge 1+ -> 3+");
 
     // Node 2 has two successors: 2+ -> 4+ and the implicit 2- -> 1-.
-    let succs_2 = graph.get_successors(2);
+    let succs_2 = graph.get_successors(2).unwrap();
     assert_eq!(succs_2.len(), 2, "Node 2 should have two successors");
 
     // Check for the edge 2+ -> 4+
@@ -117,26 +106,839 @@ fn test_get_successors() {
     ), "Should find edge 2- -> 1-");
 }
 
+#[test]
+fn test_into_variants_reuse_buffers() {
+    let (graph, _temp_file) = setup_graph();
+    let key = |e: &odgi_ffi::Edge| (e.to_node, e.from_orientation, e.to_orientation);
+
+    let mut sequence = String::from("leftover");
+    let mut bytes = b"leftover".to_vec();
+    let mut edges = Vec::new();
+    for node_id in 1..=4 {
+        graph.get_node_sequence_into(node_id, &mut sequence).unwrap();
+        assert_eq!(sequence, graph.get_node_sequence(node_id).unwrap());
+        graph.get_node_sequence_bytes_into(node_id, &mut bytes).unwrap();
+        assert_eq!(bytes, graph.get_node_sequence(node_id).unwrap().as_bytes());
+        assert_eq!(graph.get_node_sequence_bytes(node_id).unwrap(), bytes);
+
+        graph.get_successors_into(node_id, &mut edges).unwrap();
+        let expected: Vec<_> = graph.get_successors(node_id).unwrap().iter().map(key).collect();
+        assert_eq!(edges.iter().map(key).collect::<Vec<_>>(), expected);
+
+        graph.get_predecessors_into(node_id, &mut edges).unwrap();
+        let expected: Vec<_> = graph.get_predecessors(node_id).unwrap().iter().map(key).collect();
+        assert_eq!(edges.iter().map(key).collect::<Vec<_>>(), expected);
+    }
+    // Node 5 does not exist, so the buffers are cleared and an error is returned.
+    assert!(graph.get_node_sequence_into(5, &mut sequence).is_err());
+    assert!(graph.get_node_sequence_bytes_into(5, &mut bytes).is_err());
+    assert!(graph.get_predecessors_into(5, &mut edges).is_err());
+    assert!(sequence.is_empty());
+    assert!(bytes.is_empty());
+    assert!(edges.is_empty());
+}
+
+#[test]
+fn test_adjacency_order() {
+    let (graph, _temp_file) = setup_graph();
+
+    let successors: Vec<(bool, u64, bool)> =
+        graph.get_successors(2).unwrap().iter().map(|e| (e.from_orientation, e.to_node, e.to_orientation)).collect();
+    assert_eq!(successors, vec![(true, 4, true), (false, 1, false)]);
+
+    let predecessors: Vec<(u64, bool, bool)> =
+        graph.get_predecessors(4).unwrap().iter().map(|e| (e.to_node, e.from_orientation, e.to_orientation)).collect();
+    assert_eq!(predecessors, vec![(2, true, true), (3, true, true)]);
+
+    let edges: Vec<(u64, u64)> = graph.edge_usage().unwrap().iter().map(|e| (e.from_node, e.to_node)).collect();
+    assert_eq!(edges, vec![(1, 2), (1, 3), (2, 4), (3, 4)]);
+}
+
+#[test]
+fn test_handle_neighbors() {
+    let (graph, _temp_file) = setup_graph();
+
+    let right = graph.neighbors(Handle::forward(1), Direction::Right).unwrap();
+    assert_eq!(right, vec![Handle::forward(2), Handle::forward(3)]);
+    assert!(graph.neighbors(Handle::forward(1), Direction::Left).unwrap().is_empty());
+
+    // Reading node 4 backwards leads back to nodes 2 and 3 on their reverse strands.
+    let reverse = Handle::new(4, false);
+    assert_eq!(graph.neighbors(reverse, Direction::Right).unwrap(), vec![Handle::new(2, false), Handle::new(3, false)]);
+    assert!(graph.neighbors(reverse, Direction::Left).unwrap().is_empty());
+    assert_eq!(graph.neighbors(Handle::forward(2), Direction::Left).unwrap(), vec![Handle::forward(1)]);
+
+    assert_eq!(graph.sequence(Handle::forward(4)).unwrap(), "GTC");
+    assert_eq!(graph.sequence(reverse).unwrap(), "GAC");
+    assert_eq!(reverse.flip(), Handle::forward(4));
+    assert!(graph.neighbors(Handle::forward(99), Direction::Right).is_err());
+}
+
+#[test]
+fn test_edges() {
+    let (graph, _temp_file) = setup_graph();
+
+    let edges: Vec<(Handle, Handle)> = graph.edges().unwrap().collect();
+    assert_eq!(
+        edges,
+        vec![
+            (Handle::forward(1), Handle::forward(2)),
+            (Handle::forward(1), Handle::forward(3)),
+            (Handle::forward(2), Handle::forward(4)),
+            (Handle::forward(3), Handle::forward(4)),
+        ]
+    );
+    assert_eq!(graph.edges().unwrap().len(), 4);
+
+    // Every edge is reachable from its source handle.
+    for (from, to) in graph.edges().unwrap() {
+        assert!(graph.neighbors(from, Direction::Right).unwrap().contains(&to));
+    }
+}
+
 #[test]
 fn test_get_paths_on_node() {
     let (graph, _temp_file) = setup_graph();
 
     // Node 1 is on all three paths
-    let mut paths_on_1 = graph.get_paths_on_node(1);
+    let mut paths_on_1 = graph.get_paths_on_node(1).unwrap();
     paths_on_1.sort();
     assert_eq!(paths_on_1, vec!["x", "y", "z"]);
 
     // Node 3 is only on path 'y'
-    let paths_on_3 = graph.get_paths_on_node(3);
+    let paths_on_3 = graph.get_paths_on_node(3).unwrap();
     assert_eq!(paths_on_3, vec!["y"]);
 
     // Node 4 is on paths 'x' and 'y'
-    let mut paths_on_4 = graph.get_paths_on_node(4);
+    let mut paths_on_4 = graph.get_paths_on_node(4).unwrap();
     paths_on_4.sort();
     assert_eq!(paths_on_4, vec!["x", "y"]);
 
-    // Test a node with no paths
-    // (Our GFA doesn't have one, but we can test a non-existent ID)
-    let paths_on_999 = graph.get_paths_on_node(999);
-    assert!(paths_on_999.is_empty());
-}
\ No newline at end of file
+    // A non-existent node is an error rather than a node without paths.
+    assert!(matches!(graph.get_paths_on_node(999).unwrap_err().kind(), ErrorKind::NodeNotFound(999)));
+}
+
+#[test]
+fn test_node_sort_offsets() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Nodes are stored in input order: 1 (7 bp), 2 (1 bp), 3 (1 bp), 4 (3 bp).
+    assert_eq!(graph.node_sort_offset(1).unwrap(), 0);
+    assert_eq!(graph.node_sort_offset(2).unwrap(), 7);
+    assert_eq!(graph.node_sort_offset(4).unwrap(), 9);
+    assert!(matches!(graph.node_sort_offset(999).unwrap_err().kind(), ErrorKind::NodeNotFound(999)));
+
+    // The inverse lookup returns the node and the offset within it.
+    assert_eq!(graph.node_at_sort_offset(0).unwrap(), (1, 0));
+    assert_eq!(graph.node_at_sort_offset(8).unwrap(), (3, 0));
+    assert_eq!(graph.node_at_sort_offset(11).unwrap(), (4, 2));
+    assert!(matches!(graph.node_at_sort_offset(12).unwrap_err().kind(), ErrorKind::InvalidArgument(_)));
+
+    // The batch lookup agrees with the single-node one.
+    let offsets = graph.node_sort_offsets().unwrap();
+    assert_eq!(offsets.len(), 4);
+    assert_eq!(offsets[&1], 0);
+    assert_eq!(offsets[&2], 7);
+    assert_eq!(offsets[&3], 8);
+    assert_eq!(offsets[&4], 9);
+}
+
+#[test]
+fn test_apply_order() {
+    let (mut graph, _temp_file) = setup_graph();
+
+    graph.apply_order(&[4, 3, 2, 1]).unwrap();
+    assert_eq!(graph.node_sort_offset(4).unwrap(), 0);
+    assert_eq!(graph.node_sort_offset(1).unwrap(), 5);
+    assert_eq!(graph.get_node_sequence(1).unwrap(), "GATTACA");
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+
+    assert!(graph.apply_order(&[1, 2, 3]).is_err());
+    assert!(graph.apply_order(&[1, 2, 3, 3]).is_err());
+    assert!(graph.apply_order(&[1, 2, 3, 5]).is_err());
+    assert_eq!(graph.node_sort_offset(4).unwrap(), 0);
+
+    let mut order_file = NamedTempFile::new().unwrap();
+    writeln!(order_file, "2\n1\n\n4\n3").unwrap();
+    graph.load_order_file(order_file.path().to_str().unwrap()).unwrap();
+    assert_eq!(graph.node_sort_offset(2).unwrap(), 0);
+    assert_eq!(graph.node_sort_offset(3).unwrap(), 11);
+}
+
+#[test]
+fn test_save() {
+    let (mut graph, _temp_file) = setup_graph();
+    graph.apply_order(&[4, 3, 2, 1]).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let saved_path = dir.path().join("saved.og");
+    let saved_path = saved_path.to_str().unwrap();
+    graph.save(saved_path).unwrap();
+
+    let saved = Graph::load(saved_path).unwrap();
+    assert_eq!(saved.node_count().unwrap(), 4);
+    assert_eq!(saved.get_path_names().unwrap(), vec!["x", "y", "z"]);
+    assert_eq!(saved.get_path_length("x").unwrap(), 11);
+    // The saved graph keeps the order applied in memory.
+    assert_eq!(saved.node_sort_offset(4).unwrap(), 0);
+    // Only the graph itself is left in the directory.
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    assert!(graph.save(dir.path().join("missing/saved.og").to_str().unwrap()).is_err());
+}
+
+#[test]
+fn test_paths_at_pangenome_offset() {
+    let (graph, _temp_file) = setup_graph();
+
+    let hit = graph.paths_at_pangenome_offset(10).unwrap();
+    assert_eq!((hit.node_id, hit.offset), (4, 1));
+    let positions: Vec<(&str, u64)> = hit.paths.iter().map(|p| (p.path_name.as_str(), p.position)).collect();
+    assert_eq!(positions, vec![("x", 9), ("y", 9)]);
+    assert!(hit.paths.iter().all(|p| p.is_forward));
+
+    let hit = graph.paths_at_pangenome_offset(7).unwrap();
+    assert_eq!(hit.node_id, 2);
+    let names: Vec<&str> = hit.paths.iter().map(|p| p.path_name.as_str()).collect();
+    assert_eq!(names, vec!["x", "z"]);
+
+    assert!(matches!(graph.paths_at_pangenome_offset(12).unwrap_err().kind(), ErrorKind::InvalidArgument(_)));
+}
+
+#[test]
+fn test_node_to_path_positions() {
+    let (graph, temp_file) = setup_graph();
+
+    let crossings = graph.node_to_path_positions(1, 3).unwrap();
+    let positions: Vec<(&str, u64)> = crossings.iter().map(|c| (c.path_name.as_str(), c.position)).collect();
+    assert_eq!(positions, vec![("x", 3), ("y", 3), ("z", 3)]);
+
+    // Every crossing projects back to the same base.
+    for crossing in graph.node_to_path_positions(4, 2).unwrap() {
+        let position = graph.project(&crossing.path_name, crossing.position).unwrap();
+        assert_eq!((position.node_id, position.offset), (4, 2));
+        assert_eq!(crossing.position, 10);
+    }
+
+    assert!(matches!(graph.node_to_path_positions(4, 3).unwrap_err().kind(), ErrorKind::InvalidArgument(_)));
+    assert!(matches!(graph.node_to_path_positions(99, 0).unwrap_err().kind(), ErrorKind::NodeNotFound(99)));
+
+    // Paths indexed at load time are located through their index, with the same result.
+    let path = temp_file.path().to_str().unwrap();
+    let options = LoadOptions { index_paths: PathIndexing::Paths(vec!["y".to_string()]), ..Default::default() };
+    let indexed = Graph::load_with(path, &options).unwrap();
+    for (node_id, offset) in [(1, 3), (3, 0), (4, 2)] {
+        assert_eq!(indexed.node_to_path_positions(node_id, offset).unwrap(), graph.node_to_path_positions(node_id, offset).unwrap());
+    }
+
+    // A path visiting the node twice is reported once per visit, and lookups
+    // follow edits to the graph.
+    let (mut edited, _odgi) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tGATTACA\nS\t3\tG\nS\t4\tGTC\n\
+         L\t1\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t1\t+\t0M\n\
+         P\trev\t4-,3-,1-\t*\nP\tdup\t1+,3+,4+,1+\t*\n",
+    );
+    let summary = |graph: &Graph| -> Vec<(String, u64, bool)> {
+        graph.node_to_path_positions(1, 0).unwrap().into_iter().map(|c| (c.path_name, c.position, c.is_forward)).collect()
+    };
+    assert_eq!(
+        summary(&edited),
+        vec![("rev".to_string(), 10, false), ("dup".to_string(), 0, true), ("dup".to_string(), 11, true)]
+    );
+    let options = FlipOptions { keep: vec!["dup".to_string()], suffix: None, ..Default::default() };
+    assert_eq!(edited.flip_paths(&options).unwrap(), vec!["rev".to_string()]);
+    assert_eq!(
+        summary(&edited),
+        vec![("dup".to_string(), 0, true), ("dup".to_string(), 11, true), ("rev".to_string(), 0, true)]
+    );
+}
+
+#[test]
+fn test_fingerprint() {
+    let (graph, _temp_file) = setup_graph();
+    let (same_graph, _other_temp_file) = setup_graph();
+    assert_eq!(graph.fingerprint().unwrap(), same_graph.fingerprint().unwrap());
+
+    // Renumbering the nodes does not change the fingerprint.
+    let mut renumbered = NamedTempFile::new().unwrap();
+    write!(
+        renumbered,
+        "H\tVN:Z:1.0\nS\t10\tGTC\nS\t20\tGATTACA\nS\t30\tG\nS\t40\tT\n\
+         L\t20\t+\t40\t+\t0M\nL\t20\t+\t30\t+\t0M\nL\t40\t+\t10\t+\t0M\nL\t30\t+\t10\t+\t0M\n\
+         P\tz\t20+,40+\t*\nP\tx\t20+,40+,10+\t*\nP\ty\t20+,30+,10+\t*\n"
+    )
+    .unwrap();
+    let renumbered_odgi = NamedTempFile::new().unwrap();
+    gfa_to_odgi(renumbered.path().to_str().unwrap(), renumbered_odgi.path().to_str().unwrap()).unwrap();
+    let renumbered_graph = Graph::load(renumbered_odgi.path().to_str().unwrap()).unwrap();
+    assert_eq!(graph.fingerprint().unwrap(), renumbered_graph.fingerprint().unwrap());
+
+    // A different graph gets a different fingerprint.
+    let tiny = Graph::load("test_data/tiny.odgi").unwrap();
+    assert_ne!(graph.fingerprint().unwrap(), tiny.fingerprint().unwrap());
+}
+
+#[test]
+fn test_edge_usage() {
+    let (graph, _temp_file) = setup_graph();
+
+    let usage: Vec<(u64, u64, u64)> = graph
+        .edge_usage()
+        .unwrap()
+        .iter()
+        .map(|e| (e.from_node, e.to_node, e.traversals))
+        .collect();
+    // Paths x and z both traverse 1->2, only y uses 1->3 and 3->4.
+    assert_eq!(usage, vec![(1, 2, 2), (1, 3, 1), (2, 4, 1), (3, 4, 1)]);
+}
+
+#[test]
+fn test_edge_keys() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Successor edges and usage records of the same edge produce the same key.
+    let successors: Vec<EdgeKey> = graph.get_successors(1).unwrap().iter().map(|e| EdgeKey::from_successor(1, e)).collect();
+    let usage: Vec<EdgeKey> = graph.edge_usage().unwrap().iter().filter(|e| e.from_node == 1).map(EdgeKey::from).collect();
+    for key in &successors {
+        assert!(usage.iter().any(|k| k.canonicalized() == key.canonicalized()));
+    }
+
+    // 1+ -> 2+ read from the other strand is 2- -> 1-.
+    let key = EdgeKey::new(2, false, 1, false);
+    assert!(!key.is_canonical());
+    assert_eq!(key.flipped(), EdgeKey::new(1, true, 2, true));
+    assert_eq!(key.canonicalized(), EdgeKey::canonical(1, true, 2, true));
+    assert!(key.canonicalized().is_canonical());
+}
+
+#[test]
+fn test_nearest_reference_position() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Nodes on the reference anchor to their own start.
+    let anchor = graph.nearest_reference_position(4, &["y"], 10).unwrap().unwrap();
+    assert_eq!((anchor.path_name.as_str(), anchor.position, anchor.distance), ("y", 8, 0));
+
+    // Node 2 is adjacent to the end of node 1 on 'y'.
+    let anchor = graph.nearest_reference_position(2, &["y"], 10).unwrap().unwrap();
+    assert_eq!((anchor.position, anchor.distance), (7, 0));
+
+    // A three-node insertion: the middle node is 2 bp from the reference on the left.
+    let mut gfa = NamedTempFile::new().unwrap();
+    write!(
+        gfa,
+        "H\tVN:Z:1.0\nS\t1\tAAAA\nS\t2\tCC\nS\t3\tGGG\nS\t4\tTTTT\nS\t5\tA\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t5\t+\t0M\nL\t5\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t1\t+\t4\t+\t0M\n\
+         P\tref\t1+,4+\t*\n"
+    )
+    .unwrap();
+    let odgi = NamedTempFile::new().unwrap();
+    gfa_to_odgi(gfa.path().to_str().unwrap(), odgi.path().to_str().unwrap()).unwrap();
+    let insertion = Graph::load(odgi.path().to_str().unwrap()).unwrap();
+
+    let anchor = insertion.nearest_reference_position(5, &["ref"], 10).unwrap().unwrap();
+    assert_eq!((anchor.position, anchor.distance), (4, 2));
+    assert!(insertion.nearest_reference_position(5, &["ref"], 1).unwrap().is_none());
+
+    assert!(insertion.nearest_reference_position(5, &["missing"], 10).is_err());
+    assert!(insertion.nearest_reference_position(99, &["ref"], 10).is_err());
+}
+
+#[test]
+fn test_nonreference_regions() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Node 3 is the only node off path 'x', a SNP alternative to node 2 at offset 7.
+    let regions = graph.nonreference_regions("x").unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].nodes, vec![3]);
+    assert_eq!(regions[0].length, 1);
+    assert_eq!(regions[0].reference_range, Some(7..8));
+
+    assert!(graph.nonreference_regions("nonexistent_path").is_err());
+}
+
+#[test]
+fn test_get_node_sequence_packed() {
+    let (graph, _temp_file) = setup_graph();
+
+    let packed = graph.get_node_sequence_packed(1).unwrap();
+    assert_eq!(packed.len(), 7);
+    assert_eq!(packed.unpack(), "GATTACA");
+    assert_eq!(packed.get(1), Some(b'A'));
+    assert_eq!(packed.get(7), None);
+    assert!(packed.exceptions().is_empty());
+    assert!(graph.get_node_sequence_packed(99).is_err());
+
+    // Symbols outside uppercase ACGT round-trip through the exception list.
+    let mixed = odgi_ffi::PackedSequence::new("ACgtNNRACGTA");
+    assert_eq!(mixed.to_string(), "ACgtNNRACGTA");
+    assert_eq!(mixed.exceptions(), &[(2, b'g'), (3, b't'), (4, b'N'), (5, b'N'), (6, b'R')]);
+    assert_eq!(mixed.get(6), Some(b'R'));
+    assert_eq!(mixed.get(11), Some(b'A'));
+}
+
+#[test]
+fn test_kmer_spectrum() {
+    let (graph, _temp_file) = setup_graph();
+
+    // The five canonical 3-mers of GATTACA and GAC from GTC are all distinct.
+    let spectrum = graph.kmer_spectrum(3, KmerWeighting::Nodes).unwrap();
+    assert_eq!(spectrum.into_iter().collect::<Vec<_>>(), vec![(1, 6)]);
+
+    // Path weighting counts across node boundaries and once per haplotype.
+    let spectrum = graph.kmer_spectrum(3, KmerWeighting::Paths).unwrap();
+    assert_eq!(spectrum.into_iter().collect::<Vec<_>>(), vec![(1, 3), (2, 1), (3, 5), (4, 1)]);
+
+    assert!(graph.kmer_spectrum(0, KmerWeighting::Nodes).is_err());
+    assert!(graph.kmer_spectrum(33, KmerWeighting::Nodes).is_err());
+}
+
+#[test]
+fn test_minimizer_index() {
+    let (graph, _temp_file) = setup_graph();
+
+    // With w = 1, every k-mer is a minimizer.
+    let index = graph.build_minimizer_index(3, 1).unwrap();
+    assert_eq!((index.k(), index.w()), (3, 1));
+    let seeds = index.lookup("GATTACA");
+    assert_eq!(seeds.len(), 5);
+
+    // GAT is not canonical; its reverse complement ATC starts at offset 4 of node 1's reverse strand.
+    let (offset, is_forward, hits) = seeds[0];
+    assert_eq!((offset, is_forward), (0, false));
+    assert_eq!(hits, &[MinimizerHit { node_id: 1, offset: 4, is_forward: false }]);
+
+    // K-mers spanning node boundaries are found on the paths.
+    let (_, _, hits) = index.lookup("CAG")[0];
+    assert_eq!(hits, &[MinimizerHit { node_id: 1, offset: 5, is_forward: true }]);
+    assert!(index.lookup("CCCC").is_empty());
+
+    let mut bytes = Vec::new();
+    index.write_to(&mut bytes).unwrap();
+    assert_eq!(MinimizerIndex::read_from(bytes.as_slice()).unwrap(), index);
+    assert!(MinimizerIndex::read_from(&b"not an index"[..]).is_err());
+
+    assert!(graph.build_minimizer_index(0, 1).is_err());
+    assert!(graph.build_minimizer_index(3, 0).is_err());
+}
+
+#[test]
+fn test_saved_minimizer_indexes() {
+    let (graph, _temp_file) = setup_graph();
+    let indexes = vec![graph.build_minimizer_index(3, 1).unwrap(), graph.build_minimizer_index(5, 2).unwrap()];
+    let index_file = NamedTempFile::new().unwrap();
+    let index_path = index_file.path().to_str().unwrap();
+    graph.save_minimizer_indexes(index_path, &indexes).unwrap();
+    assert_eq!(graph.load_minimizer_indexes(index_path).unwrap(), indexes);
+
+    // Indexes saved for another graph are stale.
+    let other = random_graph(&RandomGraphOptions::default()).unwrap();
+    let err = other.load_minimizer_indexes(index_path).unwrap_err();
+    assert!(err.to_string().contains("different graph"));
+
+    std::fs::write(index_path, b"not an index file").unwrap();
+    assert!(graph.load_minimizer_indexes(index_path).is_err());
+    assert!(graph.load_minimizer_indexes("test_data/missing.idx").is_err());
+}
+
+#[test]
+fn test_find_mems() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Path 'y' spells GATTACAGGTC, which the query contains between two mismatching flanks.
+    let mems = graph.find_mems("CCGATTACAGGTCAA", 8).unwrap();
+    assert_eq!(mems.len(), 1);
+    assert_eq!((mems[0].query_start, mems[0].query_end), (2, 13));
+    assert_eq!(mems[0].walk, vec![(1, true), (3, true), (4, true)]);
+    assert_eq!((mems[0].walk_start, mems[0].walk_end), (0, 3));
+
+    // Ambiguity codes in the query extend the match across the node they fall on.
+    let mems = graph.find_mems("CCGATTACAGGNCAA", 8).unwrap();
+    assert_eq!((mems[0].query_start, mems[0].query_end), (2, 13));
+
+    // A match on the reverse strand is reported with reversed nodes.
+    let mems = graph.find_mems("CCTGTAATC", 9).unwrap();
+    assert_eq!(mems.len(), 1);
+    assert_eq!(mems[0].walk, vec![(4, false), (3, false), (1, false)]);
+    assert_eq!((mems[0].walk_start, mems[0].walk_end), (2, 7));
+
+    // A prebuilt index can be reused across queries.
+    let index = graph.build_minimizer_index(5, 4).unwrap();
+    assert_eq!(graph.find_mems_with_index(&index, "TTTTGATTACATGTCTTTT", 8).unwrap()[0].query_end, 15);
+    assert!(graph.find_mems("GATTACA", 0).is_err());
+}
+
+#[test]
+fn test_path_cursor() {
+    let (graph, _temp_file) = setup_graph();
+    let mut cursor = graph.path_cursor("x").unwrap();
+    assert_eq!((cursor.path_length(), cursor.step_count()), (11, 3));
+
+    let start = cursor.position().unwrap();
+    assert_eq!((start.node_id, start.offset, start.is_forward), (1, 0, true));
+
+    // Seeking agrees with projection, moving forward and backward.
+    for pos in [5, 7, 9, 10, 8, 0, 10] {
+        assert!(cursor.seek(pos));
+        let (position, projected) = (cursor.position().unwrap(), graph.project("x", pos).unwrap());
+        assert_eq!((position.node_id, position.offset), (projected.node_id, projected.offset));
+        assert_eq!(cursor.path_offset(), pos);
+    }
+    assert!(!cursor.seek(11));
+    assert_eq!(cursor.path_offset(), 10);
+
+    // The cursor is on node 4; stepping moves to the start of each step.
+    assert!(!cursor.next_step());
+    assert!(cursor.prev_step());
+    assert_eq!((cursor.step_index(), cursor.path_offset()), (1, 7));
+    assert!(cursor.prev_step());
+    assert!(!cursor.prev_step());
+    assert!(cursor.next_step());
+    assert_eq!(cursor.position().unwrap().node_id, 2);
+
+    assert!(graph.path_cursor("missing").is_err());
+
+    // On reverse steps, offsets are reported in node coordinates like `project`.
+    let mut gfa = NamedTempFile::new().unwrap();
+    write!(gfa, "H\tVN:Z:1.0\nS\t1\tAAAA\nS\t2\tCCG\nL\t1\t+\t2\t-\t0M\nP\tr\t1+,2-\t*\n").unwrap();
+    let odgi = NamedTempFile::new().unwrap();
+    gfa_to_odgi(gfa.path().to_str().unwrap(), odgi.path().to_str().unwrap()).unwrap();
+    let reversed = Graph::load(odgi.path().to_str().unwrap()).unwrap();
+    let mut cursor = reversed.path_cursor("r").unwrap();
+    for pos in 0..7 {
+        assert!(cursor.seek(pos));
+        let (position, projected) = (cursor.position().unwrap(), reversed.project("r", pos).unwrap());
+        assert_eq!((position.node_id, position.offset, position.is_forward), (projected.node_id, projected.offset, projected.is_forward));
+    }
+}
+
+#[test]
+fn test_path_repeats() {
+    let (graph, _temp_file) = setup_graph();
+    assert!(graph.path_repeats("x").unwrap().is_empty());
+    assert!(graph.path_repeats("missing").is_err());
+
+    // A tandem duplication of nodes 2 and 3, followed by an inverted copy of node 1.
+    let mut gfa = NamedTempFile::new().unwrap();
+    write!(
+        gfa,
+        "H\tVN:Z:1.0\nS\t1\tAAAA\nS\t2\tCC\nS\t3\tGGG\n\
+         L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t3\t+\t2\t+\t0M\nL\t3\t+\t1\t-\t0M\n\
+         P\tdup\t1+,2+,3+,2+,3+,1-\t*\n"
+    )
+    .unwrap();
+    let odgi = NamedTempFile::new().unwrap();
+    gfa_to_odgi(gfa.path().to_str().unwrap(), odgi.path().to_str().unwrap()).unwrap();
+    let duplicated = Graph::load(odgi.path().to_str().unwrap()).unwrap();
+
+    let repeats = duplicated.path_repeats("dup").unwrap();
+    type RepeatSummary = (u64, u64, Vec<(u64, bool)>, bool);
+    let summary: Vec<RepeatSummary> = repeats
+        .iter()
+        .map(|r| (r.node_id, r.node_len, r.visits.clone(), r.is_inverted()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, 4, vec![(0, true), (14, false)], true),
+            (2, 2, vec![(4, true), (9, true)], false),
+            (3, 3, vec![(6, true), (11, true)], false),
+        ]
+    );
+    assert!(repeats.iter().all(|r| r.visit_count() == 2));
+}
+
+#[test]
+fn test_inversions() {
+    let (graph, _temp_file) = setup_graph();
+    assert!(graph.inversions("x").unwrap().is_empty());
+    assert!(graph.inversions("missing").is_err());
+
+    // Nodes 2 and 3 are inverted on 'inv', as is the last node.
+    let mut gfa = NamedTempFile::new().unwrap();
+    write!(
+        gfa,
+        "H\tVN:Z:1.0\nS\t1\tAAAA\nS\t2\tCC\nS\t3\tGGG\nS\t4\tTT\nS\t5\tA\n\
+         L\t1\t+\t3\t-\t0M\nL\t3\t-\t2\t-\t0M\nL\t2\t-\t4\t+\t0M\nL\t4\t+\t5\t-\t0M\n\
+         P\tinv\t1+,3-,2-,4+,5-\t*\n"
+    )
+    .unwrap();
+    let odgi = NamedTempFile::new().unwrap();
+    gfa_to_odgi(gfa.path().to_str().unwrap(), odgi.path().to_str().unwrap()).unwrap();
+    let inverted = Graph::load(odgi.path().to_str().unwrap()).unwrap();
+
+    let runs: Vec<(u64, u64, u64, usize, usize)> = inverted
+        .inversions("inv")
+        .unwrap()
+        .iter()
+        .map(|r| (r.start, r.end, r.length(), r.first_step, r.step_count))
+        .collect();
+    assert_eq!(runs, vec![(4, 9, 5, 1, 2), (11, 12, 1, 4, 1)]);
+}
+
+#[test]
+fn test_steps_on_node() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Streaming yields the same paths as the buffered query, in the same order.
+    let streamed: Vec<String> = graph.steps_on_node(1).map(|step| step.unwrap().path_name).collect();
+    assert_eq!(streamed, graph.get_paths_on_node(1).unwrap());
+    assert_eq!(streamed.len(), 3);
+    assert!(graph.steps_on_node(1).all(|step| step.unwrap().is_forward));
+
+    let page = graph.get_steps_on_node(1, 1, 1).unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].path_name, streamed[1]);
+    assert_eq!(graph.steps_on_node(1).skip(2).map(|step| step.unwrap().path_name).collect::<Vec<_>>(), streamed[2..]);
+    assert!(graph.get_steps_on_node(1, 3, 10).unwrap().is_empty());
+
+    // A missing node ends the stream after a single error.
+    let missing: Vec<_> = graph.steps_on_node(999).collect();
+    assert_eq!(missing.len(), 1);
+    assert!(matches!(missing[0].as_ref().unwrap_err().kind(), ErrorKind::NodeNotFound(999)));
+}
+
+#[test]
+fn test_sampling() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Samples are distinct, sorted, and reproducible for a seed.
+    let nodes = graph.sample_nodes(2, 7).unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert!(nodes.windows(2).all(|w| w[0] < w[1]));
+    assert!(nodes.iter().all(|&id| (1..=4).contains(&id)));
+    assert_eq!(graph.sample_nodes(2, 7).unwrap(), nodes);
+    assert_eq!(graph.sample_nodes(10, 7).unwrap(), vec![1, 2, 3, 4]);
+
+    let paths = graph.sample_paths(2, 3).unwrap();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(graph.sample_paths(2, 3).unwrap(), paths);
+    assert_eq!(graph.sample_paths(5, 3).unwrap(), vec!["x", "y", "z"]);
+
+    let positions = graph.sample_positions("x", 4, 11).unwrap();
+    assert_eq!(positions.len(), 4);
+    for (offset, position) in &positions {
+        let projected = graph.project("x", *offset).unwrap();
+        assert_eq!((position.node_id, position.offset), (projected.node_id, projected.offset));
+    }
+    assert_eq!(graph.sample_positions("x", 100, 11).unwrap().len(), 11);
+    assert!(graph.sample_positions("missing", 1, 0).is_err());
+}
+
+#[test]
+fn test_raw_handles() {
+    let (graph, _temp_file) = setup_graph();
+    let raw = graph.raw();
+
+    assert!(matches!(raw.handle(5, false).unwrap_err().kind(), ErrorKind::NodeNotFound(5)));
+    assert!(matches!(raw.path("missing").unwrap_err().kind(), ErrorKind::PathNotFound(_)));
+    let handle = raw.handle(1, false).unwrap();
+    assert_eq!(handle.flip().flip(), handle);
+    assert!(handle.flip().is_reverse());
+
+    // SAFETY: every handle and step below comes from `raw`.
+    unsafe {
+        assert_eq!(raw.node_id(handle).unwrap(), 1);
+        assert_eq!(raw.sequence(handle.flip()).unwrap(), "TGTAATC");
+        assert_eq!(raw.degree(handle, false).unwrap(), 2);
+
+        let mut next = Vec::new();
+        raw.follow_edges(handle, false, |h| {
+            next.push(raw.node_id(h).unwrap());
+            true
+        })
+        .unwrap();
+        next.sort();
+        assert_eq!(next, vec![2, 3]);
+
+        // Walk path 'y' step by step.
+        let path = raw.path("y").unwrap();
+        let mut step = raw.path_begin(path).unwrap();
+        let mut visited = vec![raw.node_id(raw.handle_of_step(step).unwrap()).unwrap()];
+        while raw.has_next_step(step).unwrap() {
+            step = raw.next_step(step).unwrap();
+            visited.push(raw.node_id(raw.handle_of_step(step).unwrap()).unwrap());
+        }
+        assert_eq!(visited, vec![1, 3, 4]);
+        assert_eq!(raw.path_of_step(step).unwrap(), path);
+
+        let mut steps_on_4 = 0;
+        raw.for_each_step_on_handle(raw.handle(4, false).unwrap(), |_| {
+            steps_on_4 += 1;
+            true
+        })
+        .unwrap();
+        assert_eq!(steps_on_4, 2);
+    }
+
+    let mut edges = 0;
+    assert!(!raw
+        .for_each_edge(|_, _| {
+            edges += 1;
+            edges < 2
+        })
+        .unwrap());
+    assert_eq!(edges, 2);
+}
+
+#[test]
+fn test_load_with_options() {
+    let (_graph, temp_file) = setup_graph();
+    let path = temp_file.path().to_str().unwrap();
+
+    let options = LoadOptions {
+        validate: true,
+        prefault: true,
+        index_paths: PathIndexing::Paths(vec!["x".to_string()]),
+        threads: 2,
+    };
+    let graph = Graph::load_with(path, &options).expect("every path follows the graph's edges");
+    assert_eq!(graph.node_count().unwrap(), 4);
+    assert_eq!(graph.get_path_names().unwrap(), vec!["x", "y", "z"]);
+
+    assert_eq!(graph.region_sequence(&"x:2-4".parse().unwrap()).unwrap(), "ATT");
+
+    assert!(Graph::load_with("does_not_exist.og", &LoadOptions::default()).is_err());
+    let missing = LoadOptions { index_paths: PathIndexing::Paths(vec!["missing".to_string()]), ..Default::default() };
+    assert!(Graph::load_with(path, &missing).is_err());
+
+    // Indexes built at load time are dropped once the graph is edited.
+    let mut indexed = Graph::load_with(path, &LoadOptions { index_paths: PathIndexing::All, ..Default::default() }).unwrap();
+    assert_eq!(indexed.region_sequence(&"x".parse().unwrap()).unwrap(), "GATTACATGTC");
+    let flip_all = FlipOptions { min_reverse_fraction: -1.0, suffix: None, ..Default::default() };
+    indexed.flip_paths(&flip_all).unwrap();
+    assert_eq!(indexed.region_sequence(&"x".parse().unwrap()).unwrap(), "GACATGTAATC");
+}
+
+#[test]
+fn test_path_steps() {
+    let (graph, _temp_file) = setup_graph();
+
+    let steps: Vec<RankedStep> = graph.path_steps("x").unwrap().collect();
+    let expected = [(0, 1), (1, 2), (2, 4)];
+    assert_eq!(steps.len(), expected.len());
+    for (step, &(rank, node_id)) in steps.iter().zip(&expected) {
+        assert_eq!(step.rank, rank);
+        assert_eq!(step.node_id, node_id);
+        assert!(step.is_forward);
+    }
+
+    let mut steps = graph.path_steps("y").unwrap();
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps.next_back().map(|s| (s.rank, s.node_id)), Some((2, 4)));
+    assert_eq!(steps.next().map(|s| (s.rank, s.node_id)), Some((0, 1)));
+    assert_eq!(steps.len(), 1);
+
+    assert!(graph.path_steps("missing").is_err());
+}
+
+#[test]
+fn test_project_batch() {
+    let (graph, _temp_file) = setup_graph();
+
+    let positions = [9, 0, 11, 7, 0, 10];
+    let projected = graph.project_batch("x", &positions).unwrap();
+    assert_eq!(projected.len(), positions.len());
+    for (&pos, batch) in positions.iter().zip(&projected) {
+        let single = graph.project("x", pos).ok();
+        assert_eq!(
+            batch.as_ref().map(|p| (p.node_id, p.offset, p.is_forward)),
+            single.map(|p| (p.node_id, p.offset, p.is_forward))
+        );
+    }
+    assert!(projected[2].is_none());
+    assert_eq!(projected[3].as_ref().map(|p| p.node_id), Some(2));
+
+    assert!(matches!(graph.project_batch("missing", &[0, 1]).unwrap_err().kind(), ErrorKind::PathNotFound(_)));
+    assert!(graph.project_batch("x", &[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_error_kinds() {
+    let (graph, _temp_file) = setup_graph();
+
+    let err = graph.path_steps("missing").err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::PathNotFound(name) if name == "missing"));
+    assert_eq!(err.to_string(), "Path 'missing' not found in graph");
+
+    let err = graph.kmer_spectrum(0, KmerWeighting::Nodes).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_)));
+
+    let err = MinimizerIndex::read_from(&b"not an index"[..]).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::InvalidFormat(_)));
+
+    let err = Graph::load("test_data/does_not_exist.og").err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::Io { source, .. } if source.kind() == std::io::ErrorKind::NotFound));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn test_existence_queries() {
+    let (graph, _temp_file) = setup_graph();
+
+    assert!((1..=4).all(|node_id| graph.has_node(node_id).unwrap()));
+    assert!(!graph.has_node(0).unwrap());
+    assert!(!graph.has_node(5).unwrap());
+
+    assert!(graph.has_path("x").unwrap() && graph.has_path("z").unwrap());
+    assert!(!graph.has_path("missing").unwrap());
+
+    let (a, b) = (Handle::forward(1), Handle::forward(2));
+    assert!(graph.has_edge(a, b).unwrap());
+    assert!(graph.has_edge(b.flip(), a.flip()).unwrap());
+    assert!(!graph.has_edge(b, a).unwrap());
+    assert!(!graph.has_edge(a, Handle::forward(4)).unwrap());
+    assert!(!graph.has_edge(a, Handle::forward(99)).unwrap());
+}
+
+#[test]
+fn test_graph_stats() {
+    let (graph, _temp_file) = setup_graph();
+
+    let expected = GraphStats {
+        node_count: 4,
+        edge_count: 4,
+        total_length: 12,
+        path_count: 3,
+        step_count: 8,
+        self_loop_count: 0,
+    };
+    let stats = graph.stats().unwrap();
+    assert_eq!(stats, expected);
+    assert_eq!(stats.node_count, graph.node_count().unwrap());
+}
+
+#[test]
+fn test_size_queries() {
+    let (graph, _temp_file) = setup_graph();
+
+    assert_eq!(graph.edge_count().unwrap(), 4);
+    assert_eq!(graph.edge_count().unwrap() as usize, graph.edges().unwrap().len());
+    assert_eq!(graph.path_count().unwrap(), 3);
+    assert_eq!(graph.total_length().unwrap(), 12);
+}
+
+#[test]
+fn test_node_depth() {
+    let (graph, _temp_file) = setup_graph();
+
+    assert_eq!(graph.node_depth(1).unwrap(), 3);
+    assert_eq!(graph.node_depth(2).unwrap(), 2);
+    assert_eq!(graph.node_depth(3).unwrap(), 1);
+    assert_eq!(graph.node_depth(4).unwrap(), 2);
+    assert!(matches!(graph.node_depth(99).unwrap_err().kind(), ErrorKind::NodeNotFound(99)));
+
+    // A path that loops back through a node counts once per visit.
+    let gfa = concat!(
+        "H\tVN:Z:1.0\n",
+        "S\t1\tA\n",
+        "S\t2\tC\n",
+        "L\t1\t+\t2\t+\t0M\n",
+        "L\t2\t+\t1\t+\t0M\n",
+        "P\tloop\t1+,2+,1+\t*\n",
+    );
+    let looping = Graph::from_gfa_str(gfa).unwrap();
+    assert_eq!(looping.node_depth(1).unwrap(), 2);
+    assert_eq!(looping.get_paths_on_node(1).unwrap(), vec!["loop", "loop"]);
+}
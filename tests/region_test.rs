@@ -0,0 +1,80 @@
+// File: tests/region_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::{liftover, DepthOptions, GraphRegion};
+
+#[test]
+fn test_parse_region() {
+    let region: GraphRegion = "chr1:10,001-20,000".parse().unwrap();
+    assert_eq!(region, GraphRegion::new("chr1", 10_000, 20_000));
+    assert_eq!("chr1".parse::<GraphRegion>().unwrap(), GraphRegion::whole_path("chr1"));
+    assert_eq!("chr1:5".parse::<GraphRegion>().unwrap().start, 4);
+
+    // A suffix that is not a range belongs to the path name.
+    let region: GraphRegion = "chr1:0-5:1-3".parse().unwrap();
+    assert_eq!(region, GraphRegion::new("chr1:0-5", 0, 3));
+    assert_eq!("HG002#1#chr1:x".parse::<GraphRegion>().unwrap().path, "HG002#1#chr1:x");
+    assert_eq!(region.sample(), "chr1:0-5");
+
+    assert!("chr1:0-5".parse::<GraphRegion>().is_err());
+    assert!("chr1:10-5".parse::<GraphRegion>().is_err());
+    assert!(":1-3".parse::<GraphRegion>().is_err());
+
+    for text in ["chr1", "chr1:5", "HG002#1#chr1:1-3"] {
+        assert_eq!(text.parse::<GraphRegion>().unwrap().to_string(), text);
+    }
+}
+
+#[test]
+fn test_region_queries() {
+    let (graph, _temp_file) = setup_graph();
+
+    assert_eq!(graph.region_sequence(&"x:8-11".parse().unwrap()).unwrap(), "TGTC");
+    assert_eq!(graph.region_sequence(&"z:8-100".parse().unwrap()).unwrap(), "T");
+    assert!(graph.region_sequence(&"x:12".parse().unwrap()).is_err());
+    assert!(graph.region_sequence(&"missing".parse().unwrap()).is_err());
+
+    // Node 1 is visited by all three paths; nodes 2 and 4 by two each.
+    let options = DepthOptions::default();
+    assert_eq!(graph.region_depth(&"x:1-7".parse().unwrap(), &options).unwrap(), 3.0);
+    assert_eq!(graph.region_depth(&"x:8".parse().unwrap(), &options).unwrap(), 2.0);
+}
+
+#[test]
+fn test_lift_region() {
+    let (graph, _temp_file) = setup_graph();
+
+    // Node 2 at x:8 is not on 'y', so only node 4 lifts over.
+    let lifted = liftover::lift_region(&graph, &"x:8-11".parse().unwrap(), "y").unwrap();
+    assert_eq!(lifted, Some(GraphRegion::new("y", 8, 11)));
+    assert_eq!(liftover::lift_region(&graph, &"x:8".parse().unwrap(), "z").unwrap(), Some(GraphRegion::new("z", 7, 8)));
+    assert_eq!(liftover::lift_region(&graph, &"x:8-8".parse().unwrap(), "y").unwrap(), None);
+    assert!(liftover::lift_region(&graph, &"x".parse().unwrap(), "missing").is_err());
+}
+
+#[test]
+fn test_bed_batches() {
+    let (graph, _temp_file) = setup_graph();
+    let bed = "track name=test\nx\t7\t11\nmissing\t0\t1\ny\t0\t8\nx\t11\t11\n";
+
+    let sequences: Vec<_> = graph.sequences_from_bed(bed.as_bytes()).collect();
+    assert_eq!(sequences.len(), 4);
+    assert_eq!(sequences[0].as_ref().unwrap(), &(GraphRegion::new("x", 7, 11), "TGTC".to_string()));
+    assert!(sequences[1].is_err());
+    assert_eq!(sequences[2].as_ref().unwrap().1, "GATTACAG");
+    assert_eq!(sequences[3].as_ref().unwrap().1, "");
+
+    let projections: Vec<_> = graph.project_bed(bed.as_bytes()).collect();
+    let (first, last) = &projections[0].as_ref().unwrap().1;
+    assert_eq!((first.node_id, first.offset, last.node_id, last.offset), (2, 0, 4, 2));
+    assert!(projections[3].is_err());
+
+    let depths: Vec<_> = graph.depths_from_bed(bed.as_bytes(), &DepthOptions::default()).collect();
+    assert_eq!(depths[0].as_ref().unwrap().1, 2.0);
+
+    let lifted: Vec<_> = liftover::lift_bed(&graph, bed.as_bytes(), "y").unwrap().collect();
+    assert_eq!(lifted[0].as_ref().unwrap().1, Some(GraphRegion::new("y", 8, 11)));
+    assert_eq!(lifted[2].as_ref().unwrap().1, Some(GraphRegion::new("y", 0, 8)));
+    assert!(liftover::lift_bed(&graph, bed.as_bytes(), "missing").is_err());
+}
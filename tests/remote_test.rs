@@ -0,0 +1,11 @@
+// File: tests/remote_test.rs
+// Run with `cargo test --features http --test remote_test`.
+#![cfg(all(feature = "http", not(feature = "docs-only")))]
+use odgi_ffi::{ErrorKind, Graph};
+
+#[test]
+fn test_load_url_rejects_unsupported_schemes() {
+    let err = Graph::load_url("ftp://example.org/graph.og").err().expect("ftp URLs are not supported");
+    assert!(err.to_string().contains("Unsupported URL scheme"), "unexpected error: {}", err);
+    assert!(matches!(err.kind(), ErrorKind::Unsupported(_)));
+}
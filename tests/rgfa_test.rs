@@ -0,0 +1,121 @@
+// File: tests/rgfa_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::{ErrorKind, Graph};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_write_rgfa() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    graph.write_rgfa(&mut out, "x").expect("rGFA export should succeed");
+    let rgfa = String::from_utf8(out).unwrap();
+
+    // Nodes on the reference path are rank 0, with offsets along path 'x'.
+    assert!(rgfa.contains("S\t1\tGATTACA\tLN:i:7\tSN:Z:x\tSO:i:0\tSR:i:0"));
+    assert!(rgfa.contains("S\t2\tT\tLN:i:1\tSN:Z:x\tSO:i:7\tSR:i:0"));
+    assert!(rgfa.contains("S\t4\tGTC\tLN:i:3\tSN:Z:x\tSO:i:8\tSR:i:0"));
+
+    // Node 3 is only reachable through path 'y', starting at offset 7.
+    let node_3 = rgfa.lines().find(|l| l.starts_with("S\t3\t")).expect("Node 3 should be written");
+    assert!(node_3.contains("SN:Z:y\tSO:i:7"));
+    assert!(!node_3.contains("SR:i:0"));
+
+    assert_eq!(rgfa.lines().filter(|l| l.starts_with("L\t")).count(), 4);
+}
+
+#[test]
+fn test_write_rgfa_missing_reference() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut out = Vec::new();
+    assert!(graph.write_rgfa(&mut out, "nonexistent_path").is_err());
+}
+
+#[test]
+fn test_load_rgfa() {
+    let mut rgfa_file = NamedTempFile::new().unwrap();
+    writeln!(rgfa_file, "S\ts1\tGATTACA\tSN:Z:chr1\tSO:i:0\tSR:i:0").unwrap();
+    writeln!(rgfa_file, "S\ts2\tT\tSN:Z:chr1\tSO:i:7\tSR:i:0").unwrap();
+    writeln!(rgfa_file, "S\ts3\tGG\tSN:Z:sample\tSO:i:100\tSR:i:1").unwrap();
+    writeln!(rgfa_file, "L\ts1\t+\ts2\t+\t0M\tSR:i:0").unwrap();
+    writeln!(rgfa_file, "L\ts1\t+\ts3\t+\t0M\tSR:i:1").unwrap();
+    rgfa_file.flush().unwrap();
+
+    let (graph, segments) = Graph::load_rgfa(rgfa_file.path().to_str().unwrap())
+        .expect("rGFA import should succeed");
+
+    // Non-numeric segment names are assigned sequential node IDs.
+    assert_eq!(graph.node_count().unwrap(), 3);
+    assert_eq!(graph.get_node_sequence(3).unwrap(), "GG");
+
+    // The reference becomes a path; the partial stable sequence keeps its coordinates.
+    let mut path_names = graph.get_path_names().unwrap();
+    path_names.sort();
+    assert_eq!(path_names, vec!["chr1", "sample:100-102"]);
+    assert_eq!(graph.get_path_length("chr1").unwrap(), 8);
+
+    // The tags themselves are returned alongside the graph.
+    assert_eq!(segments.len(), 3);
+    let s3 = segments.iter().find(|s| s.segment_name == "s3").unwrap();
+    assert_eq!(s3.node_id, 3);
+    assert_eq!(s3.stable_name, "sample");
+    assert_eq!(s3.stable_offset, 100);
+    assert_eq!(s3.rank, 1);
+}
+
+#[test]
+fn test_load_rgfa_duplicate_path() {
+    let mut rgfa_file = NamedTempFile::new().unwrap();
+    writeln!(rgfa_file, "S\ts1\tGATTACA\tSN:Z:chr1\tSO:i:0\tSR:i:0").unwrap();
+    writeln!(rgfa_file, "S\ts2\tT\tSN:Z:chr1\tSO:i:7\tSR:i:0").unwrap();
+    writeln!(rgfa_file, "L\ts1\t+\ts2\t+\t0M\tSR:i:0").unwrap();
+    writeln!(rgfa_file, "P\tx\ts1+\t*").unwrap();
+    writeln!(rgfa_file, "P\tx\ts2+\t*").unwrap();
+    rgfa_file.flush().unwrap();
+
+    let err = Graph::load_rgfa(rgfa_file.path().to_str().unwrap()).err().unwrap();
+    assert!(err.to_string().contains("duplicate path 'x'"), "{}", err);
+}
+
+#[test]
+fn test_load_rgfa_stable_path_collision() {
+    let write_rgfa = |path_line: &str| {
+        let mut rgfa_file = NamedTempFile::new().unwrap();
+        writeln!(rgfa_file, "S\ts1\tGATTACA\tSN:Z:chr1\tSO:i:0\tSR:i:0").unwrap();
+        writeln!(rgfa_file, "S\ts2\tT\tSN:Z:chr1\tSO:i:7\tSR:i:0").unwrap();
+        writeln!(rgfa_file, "L\ts1\t+\ts2\t+\t0M\tSR:i:0").unwrap();
+        writeln!(rgfa_file, "{}", path_line).unwrap();
+        rgfa_file.flush().unwrap();
+        rgfa_file
+    };
+
+    // A `P` line that walks the stable sequence it is named after is accepted.
+    let rgfa_file = write_rgfa("P\tchr1\ts1+,s2+\t*");
+    let (graph, _) = Graph::load_rgfa(rgfa_file.path().to_str().unwrap()).unwrap();
+    assert_eq!(graph.get_path_names().unwrap(), vec!["chr1"]);
+    assert_eq!(graph.get_path_length("chr1").unwrap(), 8);
+
+    // One that walks other steps would silently replace the stable sequence.
+    let rgfa_file = write_rgfa("P\tchr1\ts1+\t*");
+    let err = Graph::load_rgfa(rgfa_file.path().to_str().unwrap()).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::InvalidFormat(_)), "unexpected error: {}", err);
+    assert!(err.to_string().contains("line 4") && err.to_string().contains("'chr1'"), "{}", err);
+}
+
+#[test]
+fn test_rgfa_roundtrip() {
+    let (graph, _temp_file) = setup_graph();
+
+    let mut rgfa_file = NamedTempFile::new().unwrap();
+    graph.write_rgfa(&mut rgfa_file, "x").unwrap();
+    rgfa_file.flush().unwrap();
+
+    let (reloaded, segments) = Graph::load_rgfa(rgfa_file.path().to_str().unwrap()).unwrap();
+    assert_eq!(reloaded.node_count().unwrap(), 4);
+    assert_eq!(segments.len(), 4);
+    assert_eq!(reloaded.get_path_length("x").unwrap(), 11);
+}
@@ -0,0 +1,27 @@
+// File: tests/seq_test.rs
+use odgi_ffi::seq;
+
+#[test]
+fn test_iupac_matching() {
+    assert!(seq::bases_match(b'A', b'a'));
+    assert!(seq::bases_match(b'R', b'G'));
+    assert!(seq::bases_match(b'N', b'T'));
+    assert!(seq::bases_match(b'S', b'B'));
+    assert!(!seq::bases_match(b'R', b'C'));
+    assert!(!seq::bases_match(b'A', b'-'));
+    assert!(seq::bases_match(b'-', b'-'));
+
+    assert!(seq::sequences_match("GATTACA", "GAYWMCN"));
+    assert!(!seq::sequences_match("GATTACA", "GATTAC"));
+}
+
+#[test]
+fn test_reverse_complement_and_normalize() {
+    assert_eq!(seq::reverse_complement("GATTACA"), "TGTAATC");
+    assert_eq!(seq::reverse_complement("acgtRYKMBVDHSWN"), "NWSDHBVKMRYacgt");
+    assert_eq!(seq::reverse_complement("A*C"), "G*T");
+    assert_eq!(seq::complement('u'), 'a');
+
+    assert_eq!(seq::normalize("gattaca"), "GATTACA");
+    assert_eq!(seq::normalize("ACGU ryn."), "ACGTNRYNN");
+}
@@ -0,0 +1,106 @@
+// File: tests/splice_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::{gfa_to_odgi, FlipOptions, Graph, OrphanReport};
+
+#[test]
+fn test_splice_replace() {
+    let (mut graph, _temp_file) = setup_graph();
+    let path_names = graph.get_path_names().unwrap();
+
+    // x = 1+,2+,4+ becomes 1+,3+,4+.
+    graph.splice_path("x", 1..2, &[(3, true)]).expect("Replacement should succeed");
+    assert_eq!(graph.get_next_node_on_path(1, "x").unwrap(), Some(3));
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+    assert!(graph.get_paths_on_node(2).unwrap().iter().all(|p| p != "x"));
+    // The path keeps its place among the others.
+    assert_eq!(graph.get_path_names().unwrap(), path_names);
+}
+
+#[test]
+fn test_splice_insert_and_delete() {
+    let (mut graph, _temp_file) = setup_graph();
+
+    // z = 1+,2+ is extended with 4+ at the end.
+    graph.splice_path("z", 2..2, &[(4, true)]).unwrap();
+    assert_eq!(graph.get_path_length("z").unwrap(), 11);
+
+    // Deleting the last two steps leaves only node 1.
+    graph.splice_path("z", 1..3, &[]).unwrap();
+    assert_eq!(graph.get_path_length("z").unwrap(), 7);
+}
+
+#[test]
+fn test_splice_rollback() {
+    let (mut graph, _temp_file) = setup_graph();
+
+    let checkpoint = graph.checkpoint();
+    graph.splice_path("x", 1..2, &[(3, true)]).unwrap();
+    graph.rollback(checkpoint).unwrap();
+    assert_eq!(graph.get_next_node_on_path(1, "x").unwrap(), Some(2));
+    assert!(graph.get_paths_on_node(2).unwrap().iter().any(|p| p == "x"));
+}
+
+#[test]
+fn test_remove_orphaned_nodes() {
+    let (mut graph, _temp_file) = setup_graph();
+
+    // After rerouting x through node 3 and trimming z to node 1, no path visits node 2.
+    graph.splice_path("x", 1..2, &[(3, true)]).unwrap();
+    graph.splice_path("z", 1..2, &[]).unwrap();
+
+    let report = graph.remove_orphaned_nodes().unwrap();
+    assert_eq!(report, OrphanReport { removed_nodes: 1, removed_edges: 2, removed_bp: 1 });
+    assert_eq!(graph.node_count().unwrap(), 3);
+    assert!(graph.get_node_len(2).is_err());
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+
+    assert_eq!(graph.remove_orphaned_nodes().unwrap(), OrphanReport::default());
+}
+
+#[test]
+fn test_splice_rejects_invalid_edits() {
+    let (mut graph, _temp_file) = setup_graph();
+
+    // Nodes 1 and 4 are not adjacent, so deleting node 2 from 'x' is invalid.
+    assert!(graph.splice_path("x", 1..2, &[]).is_err());
+    // Node 2 cannot follow node 3.
+    assert!(graph.splice_path("y", 2..3, &[(2, true)]).is_err());
+    assert!(graph.splice_path("x", 2..5, &[]).is_err());
+    assert!(graph.splice_path("x", 1..2, &[(99, true)]).is_err());
+    assert!(graph.splice_path("missing", 0..0, &[]).is_err());
+
+    // Failed edits leave the path untouched.
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+    assert_eq!(graph.get_next_node_on_path(1, "x").unwrap(), Some(2));
+}
+
+#[test]
+fn test_flip_paths() {
+    // 'rev' and 'ref' run through the graph on the reverse strand.
+    let mut gfa = tempfile::NamedTempFile::new().unwrap();
+    std::io::Write::write_all(
+        &mut gfa,
+        b"H\tVN:Z:1.0\nS\t1\tGATTACA\nS\t2\tT\nS\t3\tG\nS\t4\tGTC\n\
+          L\t1\t+\t2\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t2\t+\t4\t+\t0M\nL\t3\t+\t4\t+\t0M\n\
+          P\tx\t1+,2+,4+\t*\nP\trev\t4-,3-,1-\t*\nP\tref\t4-,2-,1-\t*\n",
+    )
+    .unwrap();
+    let odgi = tempfile::NamedTempFile::new().unwrap();
+    gfa_to_odgi(gfa.path().to_str().unwrap(), odgi.path().to_str().unwrap()).unwrap();
+    let mut graph = Graph::load(odgi.path().to_str().unwrap()).unwrap();
+
+    let options = FlipOptions { keep: vec!["ref".to_string()], ..Default::default() };
+    assert_eq!(graph.flip_paths(&options).unwrap(), vec!["rev".to_string()]);
+    assert!(graph.get_path_names().unwrap().contains(&"rev_inv".to_string()));
+    assert!(!graph.get_path_names().unwrap().contains(&"rev".to_string()));
+    assert_eq!(graph.get_next_node_on_path(1, "rev_inv").unwrap(), Some(3));
+    assert!(graph.inversions("rev_inv").unwrap().is_empty());
+    assert_eq!(graph.inversions("ref").unwrap().len(), 1);
+
+    // Nothing is left to flip, and unknown paths to keep are rejected.
+    assert!(graph.flip_paths(&options).unwrap().is_empty());
+    let missing = FlipOptions { keep: vec!["missing".to_string()], ..Default::default() };
+    assert!(graph.flip_paths(&missing).is_err());
+}
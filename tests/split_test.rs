@@ -0,0 +1,284 @@
+// File: tests/split_test.rs
+mod common;
+
+use common::{load_gfa, setup_graph};
+use odgi_ffi::{BubbleStrategy, ConsensusOptions, ConsensusTieBreak, EdgeKey, ErrorKind, Graph, GraphRegion, NRunHandling};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const TWO_CONTIGS: &str = "H\tVN:Z:1.0\n\
+    S\t1\tACGT\nS\t2\tT\nS\t3\tG\nS\t4\tCCCC\nS\t5\tAA\n\
+    L\t1\t+\t2\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t4\t+\t5\t+\t0M\n\
+    P\tchr1:0-5\t1+,2+\t*\nP\tchr1:100-105\t1+,3+\t*\nP\tchr2\t4+,5+\t*\n";
+
+#[test]
+fn test_split_by_path_prefix() {
+    let (graph, _temp_file) = load_gfa(TWO_CONTIGS);
+
+    let parts = graph.split_by_path_prefix(":").unwrap();
+    let names: Vec<&str> = parts.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["chr1", "chr2"]);
+
+    let chr1 = &parts[0].1;
+    assert_eq!(chr1.node_count().unwrap(), 3);
+    let mut chr1_paths = chr1.get_path_names().unwrap();
+    chr1_paths.sort();
+    assert_eq!(chr1_paths, vec!["chr1:0-5", "chr1:100-105"]);
+    assert_eq!(chr1.get_node_sequence(1).unwrap(), "ACGT");
+    assert_eq!(chr1.get_successors(1).unwrap().len(), 2);
+
+    let chr2 = &parts[1].1;
+    assert_eq!(chr2.node_count().unwrap(), 2);
+    assert_eq!(chr2.get_path_length("chr2").unwrap(), 6);
+}
+
+#[test]
+fn test_split_by_path_name_skips_paths() {
+    let (graph, _temp_file) = load_gfa(TWO_CONTIGS);
+
+    let parts = graph.split_by_path_name(|name| name.starts_with("chr2").then(|| "only".to_string())).unwrap();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].1.get_path_names().unwrap(), vec!["chr2"]);
+    assert_eq!(parts[0].1.node_count().unwrap(), 2);
+}
+
+#[test]
+fn test_merge_paths_from() {
+    let (mut base, _base_file) = load_gfa("H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nS\t3\tG\nL\t1\t+\t2\t+\t0M\nP\tref\t1+,2+\t*\n");
+    let (haplotypes, _haplotype_file) = load_gfa("H\tVN:Z:1.0\nS\t1\tACGT\nS\t3\tG\nL\t1\t+\t3\t+\t0M\nP\thap1\t1+,3+\t*\n");
+
+    assert_eq!(base.merge_paths_from(&haplotypes, None).unwrap(), 1);
+    assert_eq!(base.get_path_length("hap1").unwrap(), 5);
+    assert_eq!(base.get_next_node_on_path(1, "hap1").unwrap(), Some(3));
+    // The edge traversed by the new path was added.
+    assert_eq!(base.get_successors(1).unwrap().len(), 2);
+
+    // Importing the same path again fails without modifying the graph.
+    assert!(base.merge_paths_from(&haplotypes, None).is_err());
+    assert_eq!(base.get_path_names().unwrap().len(), 2);
+}
+
+#[test]
+fn test_merge_paths_from_with_mapping() {
+    let (mut base, _base_file) = load_gfa("H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nL\t1\t+\t2\t+\t0M\n");
+    let (other, _other_file) = load_gfa("H\tVN:Z:1.0\nS\t7\tACGT\nS\t8\tT\nL\t7\t+\t8\t+\t0M\nP\tp\t7+,8+\t*\n");
+
+    // Without a mapping, node 7 does not exist in the base graph.
+    assert!(base.merge_paths_from(&other, None).is_err());
+
+    let mapping = std::collections::HashMap::from([(7, 1), (8, 2)]);
+    assert_eq!(base.merge_paths_from(&other, Some(&mapping)).unwrap(), 1);
+    assert_eq!(base.get_paths_on_node(2).unwrap(), vec!["p"]);
+
+    // Mapping onto a node with a different sequence is rejected.
+    let (mut fresh, _fresh_file) = load_gfa("H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nL\t1\t+\t2\t+\t0M\n");
+    let swapped = std::collections::HashMap::from([(7, 2), (8, 1)]);
+    assert!(fresh.merge_paths_from(&other, Some(&swapped)).is_err());
+}
+
+#[test]
+fn test_mask_by_depth() {
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nS\t3\tGG\nL\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t3\t+\t0M\n\
+         P\ta\t1+,2+,3+\t*\nP\tb\t1+,3+\t*\n",
+    );
+
+    // Node 2 is only visited once.
+    let (masked, report) = graph.mask_by_depth(2, 10).unwrap();
+    assert_eq!(masked.node_count().unwrap(), 2);
+    assert_eq!(report.removed_nodes, vec![2]);
+    assert_eq!(report.removed_bp, 1);
+    assert_eq!(report.removed_steps, 1);
+
+    // Path 'a' is split around node 2, path 'b' is untouched.
+    let expected_fragments = vec!["a:0-4".to_string(), "a:5-7".to_string()];
+    assert_eq!(report.split_paths, vec![("a".to_string(), expected_fragments)]);
+    let mut path_names = masked.get_path_names().unwrap();
+    path_names.sort();
+    assert_eq!(path_names, vec!["a:0-4", "a:5-7", "b"]);
+    assert_eq!(masked.get_path_length("b").unwrap(), 6);
+    assert_eq!(masked.get_successors(1).unwrap().len(), 1);
+
+    // A fragment may not take over the name of another path.
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nS\t3\tGG\nL\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t3\t+\t0M\n\
+         P\ta\t1+,2+,3+\t*\nP\tb\t1+,3+\t*\nP\ta:0-4\t1+\t*\n",
+    );
+    let err = graph.mask_by_depth(2, 10).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::PathExists(name) if name == "a:0-4"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_simplify_small_bubbles() {
+    // A SNP between nodes 1 and 4 and a deletion of node 5 between nodes 4 and 6.
+    let (graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\n\
+         S\t1\tGATTACA\nS\t2\tT\nS\t3\tG\nS\t4\tGTC\nS\t5\tA\nS\t6\tCC\n\
+         L\t1\t+\t2\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t2\t+\t4\t+\t0M\nL\t3\t+\t4\t+\t0M\n\
+         L\t4\t+\t5\t+\t0M\nL\t5\t+\t6\t+\t0M\nL\t4\t+\t6\t+\t0M\n\
+         P\tx\t1+,2+,4+,5+,6+\t*\nP\ty\t1+,3+,4+,6+\t*\nP\tz\t1+,2+,4+,6+\t*\n",
+    );
+
+    let (simplified, edits) = graph.simplify_small_bubbles(1, BubbleStrategy::MostTraversed).unwrap();
+    assert_eq!(edits.len(), 2);
+    assert_eq!((edits[0].source, edits[0].sink), ((1, true), (4, true)));
+    assert_eq!((edits[0].kept, edits[0].removed.clone(), edits[0].rewritten_traversals), (Some(2), vec![3], 1));
+    assert_eq!((edits[1].kept, edits[1].removed.clone(), edits[1].rewritten_traversals), (None, vec![5], 1));
+    assert_eq!(simplified.node_count().unwrap(), 4);
+    assert_eq!(simplified.get_path_length("x").unwrap(), 13);
+    assert_eq!(simplified.get_path_length("y").unwrap(), 13);
+
+    // Following path 'x' keeps node 5 and threads the other paths through it.
+    let (simplified, edits) = graph.simplify_small_bubbles(1, BubbleStrategy::FollowPath("x".to_string())).unwrap();
+    assert_eq!((edits[1].kept, edits[1].rewritten_traversals), (Some(5), 2));
+    assert_eq!(simplified.get_path_length("y").unwrap(), 14);
+
+    // Alleles longer than the limit are left alone.
+    let (_, edits) = graph.simplify_small_bubbles(0, BubbleStrategy::Shortest).unwrap();
+    assert!(edits.is_empty());
+}
+
+const N_RUNS: &str = "H\tVN:Z:1.0\n\
+    S\t1\tACNNNNGT\nS\t2\tTTNA\nS\t3\tCC\n\
+    L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\n\
+    P\tp\t1+,2+,3+\t*\nP\tq\t3-,2-,1-\t*\n";
+
+#[test]
+fn test_n_runs() {
+    let (graph, _temp_file) = load_gfa(N_RUNS);
+
+    let runs = graph.n_runs().unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!((runs[0].node_id, runs[0].offset, runs[0].length), (1, 2, 4));
+    assert_eq!(runs[0].path_positions, vec![("p".to_string(), 2), ("q".to_string(), 8)]);
+    assert_eq!((runs[1].node_id, runs[1].offset, runs[1].length), (2, 2, 1));
+    assert_eq!(runs[1].path_positions, vec![("p".to_string(), 10), ("q".to_string(), 3)]);
+}
+
+#[test]
+fn test_handle_n_runs() {
+    let (graph, _temp_file) = load_gfa(N_RUNS);
+
+    // Splitting turns every run into a node of its own and keeps the paths intact.
+    let split = graph.handle_n_runs(NRunHandling::Split, 1).unwrap();
+    assert_eq!(split.node_count().unwrap(), 7);
+    assert_eq!(split.get_path_length("p").unwrap(), 14);
+    assert!(split.n_runs().unwrap().iter().all(|run| run.offset == 0 && split.get_node_len(run.node_id).unwrap() == run.length));
+
+    // Dropping removes the long run only and splits the paths around it.
+    let dropped = graph.handle_n_runs(NRunHandling::Drop, 2).unwrap();
+    assert_eq!(dropped.node_count().unwrap(), 4);
+    let mut names = dropped.get_path_names().unwrap();
+    names.sort();
+    assert_eq!(names, vec!["p:0-2", "p:6-14", "q:0-8", "q:12-14"]);
+    assert_eq!(dropped.n_runs().unwrap().len(), 1);
+
+    assert_eq!(graph.handle_n_runs(NRunHandling::Keep, 1).unwrap().fingerprint().unwrap(), graph.fingerprint().unwrap());
+
+    // A fragment may not take over the name of another path.
+    let (graph, _temp_file) = load_gfa(&format!("{}P\tp:6-14\t3+\t*\n", N_RUNS));
+    let err = graph.handle_n_runs(NRunHandling::Drop, 2).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::PathExists(name) if name == "p:6-14"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_gfa_to_odgi_with_n_runs() {
+    let mut gfa_file = NamedTempFile::new().unwrap();
+    gfa_file.write_all(N_RUNS.as_bytes()).unwrap();
+    let odgi_file = NamedTempFile::new().unwrap();
+    let odgi_path = odgi_file.path().to_str().unwrap();
+
+    odgi_ffi::gfa_to_odgi_with_n_runs(gfa_file.path().to_str().unwrap(), odgi_path, NRunHandling::Drop, 1).unwrap();
+    let graph = Graph::load(odgi_path).unwrap();
+    assert!(graph.n_runs().unwrap().is_empty());
+    assert_eq!(graph.get_path_names().unwrap().len(), 6);
+}
+
+#[test]
+fn test_consensus_path() {
+    // Two paths take the T allele and one takes the G allele, which is longer.
+    let (mut graph, _temp_file) = load_gfa(
+        "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nS\t3\tGG\nS\t4\tCCCC\n\
+         L\t1\t+\t2\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t2\t+\t4\t+\t0M\nL\t3\t+\t4\t+\t0M\n\
+         P\tx\t1+,2+,4+\t*\nP\ty\t1+,3+,4+\t*\nP\tz\t1+,2+\t*\n",
+    );
+
+    let walk = graph.consensus_path("consensus", &ConsensusOptions::default()).unwrap();
+    assert_eq!(walk, vec![(1, true), (2, true), (4, true)]);
+    assert_eq!(graph.get_path_length("consensus").unwrap(), 9);
+    assert!(graph.consensus_path("consensus", &ConsensusOptions::default()).is_err());
+
+    // Only the edge from node 1 to node 2 is supported by two paths.
+    let strict = ConsensusOptions { min_support: 2, ..Default::default() };
+    assert_eq!(graph.consensus_path("strict", &strict).unwrap(), vec![(1, true), (2, true)]);
+
+    // A region starts the walk at its first node and stops it at its last.
+    let locus = ConsensusOptions { region: Some(GraphRegion::new("x", 4, 5)), ..Default::default() };
+    assert_eq!(graph.consensus_path("locus", &locus).unwrap(), vec![(2, true)]);
+    let locus = ConsensusOptions { region: Some("x:1-5".parse().unwrap()), ..Default::default() };
+    assert_eq!(graph.consensus_path("locus2", &locus).unwrap(), vec![(1, true), (2, true)]);
+    let empty = ConsensusOptions { region: Some(GraphRegion::new("x", 9, 9)), ..Default::default() };
+    assert!(graph.consensus_path("empty", &empty).is_err());
+}
+
+#[test]
+fn test_consensus_path_tie_breaks() {
+    let gfa = "H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tT\nS\t3\tGG\nS\t4\tCCCC\n\
+               L\t1\t+\t2\t+\t0M\nL\t1\t+\t3\t+\t0M\nL\t2\t+\t4\t+\t0M\nL\t3\t+\t4\t+\t0M\n\
+               P\tx\t1+,2+,4+\t*\nP\ty\t1+,3+,4+\t*\n";
+    let (mut graph, _temp_file) = load_gfa(gfa);
+
+    let lowest = graph.consensus_path("lowest", &ConsensusOptions::default()).unwrap();
+    assert_eq!(lowest, vec![(1, true), (2, true), (4, true)]);
+
+    let longest = ConsensusOptions { tie_break: ConsensusTieBreak::LongestNode, ..Default::default() };
+    assert_eq!(graph.consensus_path("longest", &longest).unwrap(), vec![(1, true), (3, true), (4, true)]);
+
+    let follow = ConsensusOptions { tie_break: ConsensusTieBreak::FollowPath("y".to_string()), ..Default::default() };
+    assert_eq!(graph.consensus_path("follow", &follow).unwrap(), vec![(1, true), (3, true), (4, true)]);
+
+    let missing = ConsensusOptions { tie_break: ConsensusTieBreak::FollowPath("missing".to_string()), ..Default::default() };
+    assert!(graph.consensus_path("other", &missing).is_err());
+}
+
+#[test]
+fn test_checkpoint_rollback() {
+    let (mut graph, _temp_file) = load_gfa(TWO_CONTIGS);
+    let names = graph.get_path_names().unwrap();
+
+    let outer = graph.checkpoint();
+    graph.consensus_path("first", &ConsensusOptions::default()).unwrap();
+    let inner = graph.checkpoint();
+    graph.consensus_path("second", &ConsensusOptions::default()).unwrap();
+
+    // Rolling back the inner checkpoint keeps the edits made before it.
+    graph.rollback(inner).unwrap();
+    assert!(graph.rollback(inner).is_err());
+    assert!(graph.get_path_length("second").is_err());
+    assert!(graph.get_path_length("first").is_ok());
+
+    graph.rollback(outer).unwrap();
+    assert_eq!(graph.get_path_names().unwrap(), names);
+
+    // Committed edits are kept and can no longer be rolled back.
+    let checkpoint = graph.checkpoint();
+    graph.consensus_path("kept", &ConsensusOptions::default()).unwrap();
+    graph.commit(checkpoint).unwrap();
+    assert!(graph.rollback(checkpoint).is_err());
+    assert!(graph.get_path_length("kept").is_ok());
+}
+
+#[test]
+fn test_articulation_points_and_bridges() {
+    let (graph, _temp_file) = load_gfa(TWO_CONTIGS);
+    assert_eq!(graph.articulation_points().unwrap(), vec![1]);
+    assert_eq!(
+        graph.bridges().unwrap(),
+        vec![EdgeKey::new(1, true, 2, true), EdgeKey::new(1, true, 3, true), EdgeKey::new(4, true, 5, true)]
+    );
+
+    // Closing the bubble leaves no single point of failure.
+    let (graph, _temp_file) = setup_graph();
+    assert!(graph.articulation_points().unwrap().is_empty());
+    assert!(graph.bridges().unwrap().is_empty());
+}
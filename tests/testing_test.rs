@@ -0,0 +1,37 @@
+// File: tests/testing_test.rs
+use odgi_ffi::testing::{random_graph, RandomGraphOptions};
+
+#[test]
+fn test_random_graph() {
+    let options = RandomGraphOptions { backbone_nodes: 50, haplotype_count: 6, seed: 17, ..Default::default() };
+    let graph = random_graph(&options).unwrap();
+
+    assert!(graph.node_count().unwrap() >= 50);
+    assert_eq!(graph.get_path_names().unwrap(), (0..6).map(|h| format!("hap{}", h)).collect::<Vec<_>>());
+
+    // Every haplotype visits the backbone, so its length is at least that of the backbone nodes.
+    for name in graph.get_path_names().unwrap() {
+        assert!(graph.get_path_length(&name).unwrap() >= 50);
+        assert!(graph.path_repeats(&name).unwrap().is_empty());
+    }
+    // Haplotypes only take edges of the graph.
+    let supported: u64 = graph.edge_usage().unwrap().iter().map(|e| e.traversals).sum();
+    let steps: u64 = graph.get_path_names().unwrap().iter().map(|p| graph.path_cursor(p).unwrap().step_count() as u64 - 1).sum();
+    assert_eq!(supported, steps);
+}
+
+#[test]
+fn test_random_graph_is_reproducible() {
+    let options = RandomGraphOptions { bubble_density: 0.5, ..Default::default() };
+    let (a, b) = (random_graph(&options).unwrap(), random_graph(&options).unwrap());
+    assert_eq!(a.node_count().unwrap(), b.node_count().unwrap());
+    assert_eq!(a.fingerprint().unwrap(), b.fingerprint().unwrap());
+
+    let other = random_graph(&RandomGraphOptions { seed: 1, ..options }).unwrap();
+    assert_ne!(a.fingerprint().unwrap(), other.fingerprint().unwrap());
+
+    // Without bubbles, the graph is a single chain that all haplotypes share.
+    let chain = random_graph(&RandomGraphOptions { bubble_density: 0.0, backbone_nodes: 5, ..Default::default() }).unwrap();
+    assert_eq!(chain.node_count().unwrap(), 5);
+    assert_eq!(chain.edge_usage().unwrap().len(), 4);
+}
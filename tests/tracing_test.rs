@@ -0,0 +1,79 @@
+// File: tests/tracing_test.rs
+// Run with `cargo test --features tracing --test tracing_test`.
+#![cfg(feature = "tracing")]
+mod common;
+
+use common::setup_graph;
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Formats a span's or event's fields as `name=value` pairs.
+#[derive(Default)]
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        write!(self.0, "{}={:?}", field.name(), value).unwrap();
+    }
+}
+
+/// Captures every span as `name{fields}` and every event as `LEVEL fields`.
+#[derive(Clone, Default)]
+struct CapturingSubscriber {
+    spans: Arc<Mutex<Vec<String>>>,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl Subscriber for CapturingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::default();
+        span.record(&mut fields);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(format!("{}{{{}}}", span.metadata().name(), fields.0));
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(format!("{} {}", event.metadata().level(), fields.0));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_instrumented_query_spans() {
+    let (graph, _temp_file) = setup_graph();
+    let subscriber = CapturingSubscriber::default();
+
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        assert!(graph.path_cursor("x").is_ok());
+        assert!(graph.path_cursor("missing").is_err());
+    });
+
+    let spans = subscriber.spans.lock().unwrap();
+    assert!(spans.contains(&"path_cursor{path_name=\"x\"}".to_string()), "spans: {:?}", spans);
+    assert!(spans.contains(&"path_cursor{path_name=\"missing\"}".to_string()), "spans: {:?}", spans);
+
+    // Only the failed call records its error.
+    let events = subscriber.events.lock().unwrap();
+    assert_eq!(events.len(), 1, "events: {:?}", events);
+    assert!(events[0].starts_with("ERROR error=") && events[0].contains("missing"), "events: {:?}", events);
+}
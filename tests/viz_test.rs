@@ -0,0 +1,37 @@
+// File: tests/viz_test.rs
+mod common;
+
+use common::setup_graph;
+use odgi_ffi::GraphRegion;
+use odgi_ffi::viz::{self, VizOptions};
+
+#[test]
+fn test_render_region_svg() {
+    let (graph, _temp_file) = setup_graph();
+
+    // The end of 'x' covers nodes 2 and 4; one edge of context adds nodes 1 and 3.
+    let svg = viz::render_region_svg(&graph, &GraphRegion::new("x", 7, 11), &VizOptions::default()).unwrap();
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    for node_id in 1..=4 {
+        assert!(svg.contains(&format!("<title>node {} (", node_id)));
+    }
+    // All three paths get a ribbon, starting with the region's path.
+    let x = svg.find("<g><title>x</title>").unwrap();
+    let y = svg.find("<g><title>y</title>").unwrap();
+    assert!(x < y && svg.contains("<g><title>z</title>"));
+
+    // Without context, only the path's own nodes are drawn.
+    let options = VizOptions { context: 0, show_labels: false, ..Default::default() };
+    let svg = viz::render_region_svg(&graph, &GraphRegion::new("x", 7, 11), &options).unwrap();
+    assert!(!svg.contains("<title>node 1 (") && !svg.contains("<title>node 3 ("));
+    assert!(!svg.contains("<text"));
+
+    let html = viz::render_region_html(&graph, &GraphRegion::new("x", 0, 11), &VizOptions::default()).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>") && html.contains("<svg "));
+    assert!(html.contains("<title>x:1-11</title>"));
+
+    assert!(viz::render_region_svg(&graph, &GraphRegion::new("missing", 0, 1), &VizOptions::default()).is_err());
+    assert!(viz::render_region_svg(&graph, &GraphRegion::new("x", 5, 5), &VizOptions::default()).is_err());
+    assert!(viz::render_region_svg(&graph, &GraphRegion::new("x", 11, 20), &VizOptions::default()).is_err());
+}
@@ -0,0 +1,49 @@
+// File: tests/wasm_test.rs
+// Run with `cargo test --features wasm --test wasm_test`.
+#![cfg(feature = "wasm")]
+mod common;
+
+use common::setup_memory_graph;
+use odgi_ffi::{ErrorKind, Graph};
+
+#[test]
+fn test_wasm_queries() {
+    let graph = setup_memory_graph();
+
+    assert_eq!(graph.node_count().unwrap(), 4);
+    assert_eq!(graph.get_path_names().unwrap(), vec!["x", "y", "z"]);
+    assert_eq!(graph.get_node_sequence(1).unwrap(), "GATTACA");
+    assert_eq!(graph.get_path_length("x").unwrap(), 11);
+    assert!(matches!(graph.get_path_length("missing").unwrap_err().kind(), ErrorKind::PathNotFound(_)));
+
+    let position = graph.project("x", 7).unwrap();
+    assert_eq!((position.node_id, position.offset, position.is_forward), (2, 0, true));
+    assert!(matches!(graph.project("x", 11).unwrap_err().kind(), ErrorKind::InvalidArgument(_)));
+
+    let successors: Vec<u64> = graph.get_successors(1).unwrap().iter().map(|e| e.to_node).collect();
+    assert_eq!(successors, vec![2, 3]);
+    let mut edges = Vec::new();
+    graph.get_predecessors_into(2, &mut edges).unwrap();
+    assert_eq!(edges.iter().map(|e| (e.to_node, e.from_orientation, e.to_orientation)).collect::<Vec<_>>(), vec![(1, true, true), (4, false, false)]);
+    let mut sequence = String::new();
+    graph.get_node_sequence_into(4, &mut sequence).unwrap();
+    assert_eq!(sequence, "GTC");
+    assert_eq!(graph.get_node_sequence_bytes(4).unwrap(), b"GTC");
+    let predecessors = graph.get_predecessors(4).unwrap();
+    assert!(predecessors.iter().all(|e| e.from_orientation && e.to_orientation));
+    assert_eq!(predecessors.len(), 2);
+
+    assert_eq!(graph.get_paths_on_node(2).unwrap(), vec!["x", "z"]);
+    assert_eq!(graph.get_paths_on_edge(1, true, 2, true).unwrap(), vec!["x", "z"]);
+    assert!(graph.get_paths_on_edge(2, true, 1, true).unwrap().is_empty());
+    assert_eq!(graph.get_next_node_on_path(1, "y").unwrap(), Some(3));
+    assert_eq!(graph.node_sort_offset(3).unwrap(), 8);
+    assert_eq!(graph.node_at_sort_offset(9).unwrap(), (4, 0));
+}
+
+#[test]
+fn test_wasm_from_gfa_bytes_errors() {
+    let error = Graph::from_gfa_bytes(b"S\t1\tA\nL\t1\t+\t2\t+\t0M\n").err().unwrap();
+    assert!(error.to_string().contains("line 2"));
+    assert!(Graph::load("graph.og").is_err());
+}